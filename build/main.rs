@@ -58,16 +58,22 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
             "GL_ARB_occlusion_query",
             "GL_ARB_pixel_buffer_object",
             "GL_ARB_robustness",
+            "GL_ARB_sample_locations",
+            "GL_ARB_separate_shader_objects",
             "GL_ARB_shader_image_load_store",
             "GL_ARB_shader_objects",
+            "GL_ARB_shading_language_include",
             "GL_ARB_texture_buffer_object",
             "GL_ARB_texture_float",
             "GL_ARB_texture_multisample",
             "GL_ARB_texture_rg",
             "GL_ARB_texture_rgb10_a2ui",
+            "GL_ARB_transform_feedback2",
             "GL_ARB_transform_feedback3",
+            "GL_ARB_vertex_attrib_binding",
             "GL_ARB_vertex_buffer_object",
             "GL_ARB_vertex_shader",
+            "GL_ARB_viewport_array",
             "GL_ATI_draw_buffers",
             "GL_ATI_meminfo",
             "GL_EXT_debug_marker",
@@ -105,6 +111,7 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
             "GL_APPLE_sync",
             "GL_ARM_rgba8",
             "GL_EXT_buffer_storage",
+            "GL_EXT_clip_cull_distance",
             "GL_EXT_disjoint_timer_query",
             "GL_EXT_multi_draw_indirect",
             "GL_EXT_multisampled_render_to_texture",
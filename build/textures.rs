@@ -309,13 +309,14 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             use std::borrow::Cow;
 
             use texture::any::{{self, TextureAny, TextureAnyLayer, TextureAnyMipmap}};
-            use texture::any::{{TextureAnyLayerMipmap, TextureAnyImage, Dimensions}};
+            use texture::any::{{TextureAnyLayerMipmap, TextureAnyImage, Dimensions, ExportedTexture}};
             use texture::bindless::{{ResidentTexture, BindlessTexturesNotSupportedError}};
             use texture::get_format::{{InternalFormat, InternalFormatType, GetFormatError}};
             use texture::pixel_buffer::PixelBuffer;
             use texture::{{TextureCreationError, Texture1dDataSource, Texture2dDataSource}};
             use texture::{{Texture3dDataSource, Texture2dDataSink, MipmapsOption, CompressedMipmapsOption}};
             use texture::{{RawImage1d, RawImage2d, RawImage3d, CubeLayer}};
+            use texture::{{PixelValue, ClearError}};
 
             use image_format::{{ClientFormatAny, TextureFormatRequest}};
             use image_format::{{UncompressedFloatFormat, UncompressedIntFormat}};
@@ -795,6 +796,28 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                     let format = TextureFormatRequest::Specific(format);
                     {name}(any::from_id(facade, format, id, owned, mipmap, ty))
                 }}
+
+                /// Detaches the texture from its context, so that it can be sent to another
+                /// thread and adopted by a context whose objects are shared with this one.
+                ///
+                /// See the documentation of `ExportedTexture` for more infos.
+                #[inline]
+                pub fn export(self) -> ExportedTexture<{name}> {{
+                    any::export(self.0)
+                }}
+
+                /// Adopts a texture that was detached from another context with `export`.
+                ///
+                /// # Unsafety
+                ///
+                /// The context of `facade` must share its objects with the context that exported
+                /// the texture.
+                #[inline]
+                pub unsafe fn from_exported<F: Facade>(facade: &F, texture: ExportedTexture<{name}>)
+                                                       -> {name}
+                {{
+                    {name}(any::import(facade, texture))
+                }}
         ", format = relevant_format, name = name)).unwrap();
 
     // dimensions getters
@@ -823,6 +846,27 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             ")).unwrap();
     }
 
+    // writing the `sample_positions` function
+    if dimensions == TextureDimensions::Texture2dMultisample {
+        match ty {
+            TextureType::Regular | TextureType::Srgb | TextureType::Integral | TextureType::Unsigned => {
+                (write!(dest, "
+                        /// Returns the position of each sample within a pixel, with each
+                        /// coordinate between `0.0` and `1.0`.
+                        ///
+                        /// Returns `None` if querying the positions is not supported by the
+                        /// backend, or if the texture can't be attached to a framebuffer.
+                        #[inline]
+                        pub fn sample_positions(&self) -> Option<Vec<[f32; 2]>> {{
+                            framebuffer::SimpleFrameBuffer::new(self.0.get_context(), self).ok()
+                                .and_then(|fb| fb.get_sample_positions())
+                        }}
+                    ")).unwrap();
+            },
+            _ => ()
+        }
+    }
+
     // writing the `get_mipmap_levels` function
     (write!(dest, "
             /// Returns the number of mipmap levels of the texture.
@@ -881,7 +925,10 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 /// operations (for example, while you're drawing).
                 ///
                 /// Returns the compressed format of the texture and the compressed data, gives
-                /// `None` when the internal compression format is generic or unknown.
+                /// `None` when the internal compression format is generic or unknown, or when the
+                /// backend can't read back compressed data (OpenGL ES).
+                ///
+                /// Use `mipmap(level).read_compressed_data()` to read another mipmap level.
                 #[inline]
                 pub fn read_compressed_data(&self) -> Option<({format}, Vec<u8>)> {{
                     self.main_level().read_compressed_data()
@@ -916,14 +963,62 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 ///
                 /// ## Panic
                 ///
-                /// Panics if the the dimensions of `data` don't match the `Rect`.
+                /// Panics if the the dimensions of `data` don't match the `Rect`, or if the client
+                /// format of `data` is not supported or not compatible with the texture's format.
                 {compressed_restrictions}
                 #[inline]
                 pub fn write<'a, T>(&self, rect: Rect, data: T) where T: {data_source_trait}<'a> {{
                     self.main_level().write(rect, data)
                 }}
+
+                /// Uploads some data in the texture. Each row of `data` is `row_length` pixels
+                /// long, but only the first `rect.width` pixels of each row are uploaded.
+                ///
+                /// See the documentation of `write` for more infos.
+                #[inline]
+                pub fn write_with_row_length<'a, T>(&self, rect: Rect, data: T, row_length: u32)
+                                                    where T: {data_source_trait}<'a>
+                {{
+                    self.main_level().write_with_row_length(rect, data, row_length)
+                }}
             "#, data_source_trait = data_source_trait,
                 compressed_restrictions = compressed_restrictions)).unwrap();
+
+        if !is_compressed {
+            (write!(dest, r#"
+                /// Fills a mipmap level of the texture with a single value.
+                ///
+                /// See the documentation of `TextureAnyMipmap::raw_clear` for more infos.
+                ///
+                /// ## Panic
+                ///
+                /// Panics if the mipmap level doesn't exist.
+                #[inline]
+                pub fn clear<P>(&self, level: u32, value: P) -> Result<(), ClearError>
+                                where P: PixelValue
+                {{
+                    self.mipmap(level).expect("Mipmap level doesn't exist").raw_clear(value)
+                }}
+
+                /// Fills a rectangle of a mipmap level of the texture with a single value.
+                ///
+                /// See the documentation of `TextureAnyMipmap::raw_clear` for more infos.
+                ///
+                /// ## Panic
+                ///
+                /// Panics if the mipmap level doesn't exist or if the rectangle is outside of
+                /// the texture.
+                #[inline]
+                pub fn clear_region<P>(&self, level: u32, rect: &Rect, value: P)
+                                       -> Result<(), ClearError> where P: PixelValue
+                {{
+                    let mipmap = self.mipmap(level).expect("Mipmap level doesn't exist");
+                    mipmap.raw_clear_region(rect.left .. rect.left + rect.width,
+                                            rect.bottom .. rect.bottom + rect.height, 0 .. 1,
+                                            value)
+                }}
+            "#)).unwrap();
+        }
     }
 
     // writing the `write_compressed_data` function
@@ -1095,6 +1190,23 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
         // dimensions getters
         write_dimensions_getters(dest, dimensions, "self.0", true);
 
+        // writing the `as_surface` function for mipmaps
+        if dimensions == TextureDimensions::Texture2d && ty == TextureType::Regular {
+            (write!(dest, "
+                    /// Starts drawing on this mipmap level of the texture.
+                    ///
+                    /// The dimensions of the framebuffer are the dimensions of this level. This
+                    /// allows generating the mipmaps yourself, for example by rendering each
+                    /// level from the previous one.
+                    ///
+                    /// See `{name}::as_surface`.
+                    #[inline]
+                    pub fn as_surface(&self) -> framebuffer::SimpleFrameBuffer<'t> {{
+                        framebuffer::SimpleFrameBuffer::new((self.1).0.get_context(), *self).unwrap()
+                    }}
+                ", name = name)).unwrap();
+        }
+
         // writing the `write` function for mipmaps.
         // TODO: implement for other types too
         if dimensions == TextureDimensions::Texture2d &&
@@ -1120,7 +1232,8 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                     ///
                     /// ## Panic
                     ///
-                    /// Panics if the the dimensions of `data` don't match the `Rect`.
+                    /// Panics if the the dimensions of `data` don't match the `Rect`, or if the client
+                    /// format of `data` is not supported or not compatible with the texture's format.
                     {compressed_restrictions}
                     pub fn write<'a, T>(&self, rect: Rect, data: T) where T: {data_source_trait}<'a> {{
                         let RawImage2d {{ data, width, height, format: client_format }} =
@@ -1132,10 +1245,41 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                         let client_format = ClientFormatAny::ClientFormat(client_format);
 
                         self.0.upload_texture(rect.left, rect.bottom, 0, (client_format, data),
-                                              width, Some(height), None, true).unwrap()
+                                              width, Some(height), None, None, None, true).unwrap()
+                    }}
+
+                    /// Uploads some data in the texture level. Each row of `data` is `row_length`
+                    /// pixels long, but only the first `rect.width` pixels of each row are
+                    /// uploaded.
+                    ///
+                    /// This allows you to upload a part of a larger image without copying it to
+                    /// a temporary buffer first.
+                    ///
+                    /// Note that this may cause a synchronization if you use the texture right before
+                    /// or right after this call.
+                    ///
+                    /// ## Panic
+                    ///
+                    /// Panics if the the dimensions of `data` don't match the `Rect`, if `data`
+                    /// is too small, or if the backend doesn't support custom row lengths.
+                    pub fn write_with_row_length<'a, T>(&self, rect: Rect, data: T, row_length: u32)
+                                                        where T: {data_source_trait}<'a>
+                    {{
+                        let RawImage2d {{ data, width, height, format: client_format }} =
+                                                data.into_raw();
+
+                        assert_eq!(width, rect.width);
+                        assert_eq!(height, rect.height);
+
+                        let client_format = ClientFormatAny::ClientFormat(client_format);
+
+                        self.0.upload_texture(rect.left, rect.bottom, 0, (client_format, data),
+                                              width, Some(height), None, Some(row_length),
+                                              None, true).unwrap()
                     }}
                 "#, data_source_trait = data_source_trait,
                     compressed_restrictions = compressed_restrictions)).unwrap();
+
         }
 
         // writing the `write_compressed_data` function for mipmaps.
@@ -1171,7 +1315,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                         let client_format = {client_format_any}(format);
 
                         self.0.upload_texture(rect.left, rect.bottom, 0, (client_format, data),
-                                              width, Some(height), None, false)
+                                              width, Some(height), None, None, None, false)
                     }}
                 "#, format = relevant_format, client_format_any = client_format_any_ty)).unwrap();
         }
@@ -1185,7 +1329,8 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                     /// operations (for example, while you're drawing).
                     ///
                     /// Returns the compressed format of the texture and the compressed data, gives
-                    /// `None` when the internal compression format is generic or unknown.
+                    /// `None` when the internal compression format is generic or unknown, or when
+                    /// the backend can't read back compressed data (OpenGL ES).
                     #[inline]
                     pub fn read_compressed_data(&self) -> Option<({format}, Vec<u8>)> {{
                         match self.0.download_compressed_data() {{
@@ -1264,6 +1409,37 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
         // dimensions getters
         write_dimensions_getters(dest, dimensions, "self.0", false);
 
+        // writing the `write` function for layers of 2D arrays
+        if dimensions == TextureDimensions::Texture2dArray &&
+                (ty == TextureType::Regular || ty == TextureType::Srgb)
+        {
+            (write!(dest, r#"
+                    /// Uploads some data in this layer of the texture level.
+                    ///
+                    /// Note that this may cause a synchronization if you use the texture right before
+                    /// or right after this call.
+                    ///
+                    /// ## Panic
+                    ///
+                    /// Panics if the the dimensions of `data` don't match the `Rect`.
+                    pub fn write<'a, T>(&self, rect: Rect, data: T) where T: {data_source_trait}<'a> {{
+                        let RawImage2d {{ data, width, height, format: client_format }} =
+                                                data.into_raw();
+
+                        assert_eq!(width, rect.width);
+                        assert_eq!(height, rect.height);
+
+                        let client_format = ClientFormatAny::ClientFormat(client_format);
+
+                        let level = self.0.get_level();
+                        let mipmap = self.0.get_texture().mipmap(level).unwrap();
+                        mipmap.upload_texture(rect.left, rect.bottom, self.0.get_layer(),
+                                              (client_format, data), width, Some(height), Some(1),
+                                              None, None, level == 0).unwrap()
+                    }}
+                "#, data_source_trait = data_source_trait)).unwrap();
+        }
+
         // to the image struct
         if dimensions.is_cube() {
             writeln!(dest,
@@ -119,6 +119,40 @@ impl GlutinFacade {
         Option::as_ref(&self.backend).map(|w| WinRef(w.borrow()))
     }
 
+    /// Builds a new window whose OpenGL context shares its objects with the one of this
+    /// display.
+    ///
+    /// Buffers, textures and programs created with one of the two displays can be used with
+    /// the other one, from the same thread. See `Context::new_shared` for more details.
+    ///
+    /// Returns `glutin::CreationError::NotSupported` if this display uses a headless context.
+    ///
+    /// # Panic
+    ///
+    /// Panics if this display has been built with `build_glium_unchecked`.
+    pub fn build_shared(&self, builder: glutin::WindowBuilder)
+                        -> Result<GlutinFacade, GliumCreationError<glutin::CreationError>>
+    {
+        let backend = {
+            let window = match Option::as_ref(&self.backend) {
+                Some(window) => window.borrow(),
+                None => return Err(GliumCreationError::BackendCreationError(
+                                                        glutin::CreationError::NotSupported)),
+            };
+            let builder = builder.with_shared_lists(window.get_window());
+            Rc::new(try!(GlutinWindowBackend::new(builder)))
+        };
+
+        let context = try!(unsafe {
+            context::Context::new_shared(backend.clone(), true, Default::default(), &self.context)
+        });
+
+        Ok(GlutinFacade {
+            context: context,
+            backend: Rc::new(Some(RefCell::new(backend))),
+        })
+    }
+
     /// Start drawing on the backbuffer.
     ///
     /// This function returns a `Frame`, which can be used to draw on it. When the `Frame` is
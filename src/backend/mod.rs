@@ -25,6 +25,10 @@ use version::Version;
 
 pub use context::Context;
 pub use context::ReleaseBehavior;
+pub use context::{HintTarget, HintMode, ColorClampTarget, ColorClampMode};
+pub use context::MemoryUsage;
+pub use context::ResetStatus;
+pub use context::ErrorCheckingMode;
 
 #[cfg(feature = "glutin")]
 pub mod glutin_backend;
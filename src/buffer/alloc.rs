@@ -28,6 +28,9 @@ pub enum ReadError {
 
     /// The context has been lost. Reading from the buffer would return garbage data.
     ContextLost,
+
+    /// The requested element is outside of the buffer.
+    OutOfBounds,
 }
 
 impl fmt::Display for ReadError {
@@ -42,6 +45,7 @@ impl Error for ReadError {
         match *self {
             NotSupported => "The backend doesn't support reading from a buffer",
             ContextLost => "The context has been lost. Reading from the buffer would return garbage data",
+            OutOfBounds => "The requested element is outside of the buffer",
         }
     }
 }
@@ -84,6 +88,9 @@ pub struct Alloc {
     /// A pointer to the persistent mapping of this buffer in memory, if there is one.
     persistent_mapping: Option<*mut raw::c_void>,
 
+    /// True if the persistent mapping was created with `GL_MAP_COHERENT_BIT`.
+    coherent_mapping: bool,
+
     /// If true, then this buffer can only be modified by calls to `glCopyBufferSubData` or through
     /// the persistent mapping.
     immutable: bool,
@@ -118,12 +125,17 @@ impl Alloc {
             create_buffer(&mut ctxt, size, Some(data), ty, mode)
         });
 
+        let allocated = ctxt.allocated_memory.buffers.get();
+        ctxt.allocated_memory.buffers.set(allocated + size);
+
         Ok(Alloc {
             context: facade.get_context().clone(),
             id: id,
             ty: ty,
             size: size,
             persistent_mapping: persistent_mapping,
+            // persistent mappings are always created with `GL_MAP_FLUSH_EXPLICIT_BIT` instead
+            coherent_mapping: false,
             immutable: immutable,
             created_with_buffer_storage: created_with_buffer_storage,
             creation_mode: mode,
@@ -142,12 +154,17 @@ impl Alloc {
             create_buffer::<()>(&mut ctxt, size, None, ty, mode)
         });
 
+        let allocated = ctxt.allocated_memory.buffers.get();
+        ctxt.allocated_memory.buffers.set(allocated + size);
+
         Ok(Alloc {
             context: facade.get_context().clone(),
             id: id,
             ty: ty,
             size: size,
             persistent_mapping: persistent_mapping,
+            // persistent mappings are always created with `GL_MAP_FLUSH_EXPLICIT_BIT` instead
+            coherent_mapping: false,
             immutable: immutable,
             created_with_buffer_storage: created_with_buffer_storage,
             creation_mode: mode,
@@ -156,6 +173,43 @@ impl Alloc {
         })
     }
 
+    /// Builds a new buffer of the given size whose content is all zeroes.
+    ///
+    /// Uses `glClearBufferData` if it is supported, and uploads zeroes otherwise.
+    pub fn empty_zeroed<F>(facade: &F, ty: BufferType, size: usize, mode: BufferMode)
+                           -> Result<Alloc, BufferCreationError> where F: Facade
+    {
+        let supports_clear = {
+            let ctxt = facade.get_context();
+            ctxt.get_version() >= &Version(Api::Gl, 4, 3) ||
+            ctxt.get_extensions().gl_arb_clear_buffer_object
+        };
+
+        if !supports_clear {
+            return Alloc::new(facade, &vec![0u8; size][..], ty, mode);
+        }
+
+        let alloc = try!(Alloc::empty(facade, ty, size, mode));
+
+        {
+            let mut ctxt = alloc.context.make_current();
+
+            // a null data pointer fills the buffer with zeroes
+            unsafe {
+                if ctxt.capabilities.direct_state_access {
+                    ctxt.gl.ClearNamedBufferData(alloc.id, gl::R8UI, gl::RED_INTEGER,
+                                                 gl::UNSIGNED_BYTE, ptr::null());
+                } else {
+                    let bind = bind_buffer(&mut ctxt, alloc.id, alloc.ty);
+                    ctxt.gl.ClearBufferData(bind, gl::R8UI, gl::RED_INTEGER,
+                                            gl::UNSIGNED_BYTE, ptr::null());
+                }
+            }
+        }
+
+        Ok(alloc)
+    }
+
     /// Returns the context corresponding to this buffer.
     #[inline]
     pub fn get_context(&self) -> &Rc<Context> {
@@ -174,6 +228,37 @@ impl Alloc {
         self.persistent_mapping.is_some()
     }
 
+    /// Returns true if the buffer is persistently mapped with `GL_MAP_COHERENT_BIT`.
+    ///
+    /// Buffers that are not persistently mapped are only mapped temporarily and never
+    /// coherently.
+    #[inline]
+    pub fn is_mapping_coherent(&self) -> bool {
+        self.persistent_mapping.is_some() && self.coherent_mapping
+    }
+
+    /// Flushes a range of the persistent mapping of the buffer, so that the writes done in this
+    /// range become visible to the GPU. Does nothing if the mapping is coherent or if the
+    /// buffer is not persistently mapped.
+    ///
+    /// `range` is in bytes.
+    ///
+    /// # Panic
+    ///
+    /// Panics if out of range.
+    ///
+    pub fn flush_if_needed(&self, range: Range<usize>) {
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.size);
+
+        if self.persistent_mapping.is_none() || self.is_mapping_coherent() {
+            return;
+        }
+
+        let mut ctxt = self.context.make_current();
+        unsafe { flush_range(&mut ctxt, self.id, self.ty, range) };
+    }
+
     /// Changes the type of the buffer. Returns `Err` if this is forbidden.
     pub fn set_type(mut self, ty: BufferType) -> Result<Alloc, Alloc> {
         // FIXME: return Err for GLES2
@@ -204,6 +289,17 @@ impl Alloc {
         }
     }
 
+    /// Calls `glMemoryBarrier(GL_CLIENT_MAPPED_BUFFER_BARRIER_BIT)` if necessary.
+    ///
+    /// Persistent mappings are not coherent, so this is required before the CPU can see the
+    /// data written by shaders through the persistent mapping.
+    fn barrier_for_client_mapped_buffer(&self, ctxt: &mut CommandContext) {
+        if self.latest_shader_write.get() >= ctxt.state.latest_memory_barrier_client_mapped_buffer {
+            unsafe { ctxt.gl.MemoryBarrier(gl::CLIENT_MAPPED_BUFFER_BARRIER_BIT); }
+            ctxt.state.latest_memory_barrier_client_mapped_buffer = ctxt.state.next_draw_call_id;
+        }
+    }
+
     /// Calls `glMemoryBarrier(GL_VERTEX_ATTRIB_ARRAY_BARRIER_BIT)` if necessary.
     pub fn prepare_for_vertex_attrib_array(&self, ctxt: &mut CommandContext) {
         self.assert_unmapped(ctxt);
@@ -458,7 +554,7 @@ impl Alloc {
                 ctxt.gl.InvalidateBufferData(self.id);
             }
 
-            if ctxt.version >= &Version(Api::Gl, 4, 5) {
+            if ctxt.capabilities.direct_state_access {
                 ctxt.gl.NamedBufferSubData(self.id, offset_bytes as gl::types::GLintptr,
                                            mem::size_of_val(data) as gl::types::GLsizeiptr,
                                            data.to_void_ptr() as *const _)
@@ -580,7 +676,7 @@ impl Alloc {
         if let Some(existing_mapping) = self.persistent_mapping.clone() {
             // TODO: optimize so that it's not always necessary to make the context current
             let mut ctxt = self.context.make_current();
-            self.barrier_for_buffer_update(&mut ctxt);
+            self.barrier_for_client_mapped_buffer(&mut ctxt);
 
             let data = (existing_mapping as *mut u8).offset(bytes_range.start as isize);
             let data = Content::ref_from_ptr(data as *mut (),
@@ -795,7 +891,7 @@ impl Alloc {
             self.barrier_for_buffer_update(&mut ctxt);
 
             <D as Content>::read(size_to_read, |output| {
-                if ctxt.version >= &Version(Api::Gl, 4, 5) {
+                if ctxt.capabilities.direct_state_access {
                     ctxt.gl.GetNamedBufferSubData(self.id, range.start as gl::types::GLintptr,
                                                   size_to_read as gl::types::GLsizeiptr,
                                                   output as *mut _ as *mut _);
@@ -865,7 +961,18 @@ impl Drop for Alloc {
             self.assert_not_transform_feedback(&mut ctxt);
             VertexAttributesSystem::purge_buffer(&mut ctxt, self.id);
             destroy_buffer(&mut ctxt, self.id);
+
+            let allocated = ctxt.allocated_memory.buffers.get();
+            ctxt.allocated_memory.buffers.set(allocated - self.size);
         }
+
+        // the ID may be reused by a future buffer, so the contexts that share this buffer
+        // must forget about it too
+        let id = self.id;
+        self.context.purge_shared_contexts(move |ctxt| {
+            VertexAttributesSystem::purge_buffer(ctxt, id);
+            forget_buffer(ctxt, id);
+        });
     }
 }
 
@@ -1125,7 +1232,7 @@ unsafe fn create_buffer<D: ?Sized>(mut ctxt: &mut CommandContext, size: usize, d
     // creating the id of the buffer
     let id = {
         let mut id: gl::types::GLuint = mem::uninitialized();
-        if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
+        if ctxt.capabilities.direct_state_access {
             ctxt.gl.CreateBuffers(1, &mut id);
         } else if ctxt.version >= &Version(Api::Gl, 1, 5) ||
             ctxt.version >= &Version(Api::GlEs, 2, 0)
@@ -1188,7 +1295,7 @@ unsafe fn create_buffer<D: ?Sized>(mut ctxt: &mut CommandContext, size: usize, d
     // whether the buffer was created with `glBufferStorage`
     let created_with_buffer_storage: bool;
 
-    if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
+    if ctxt.capabilities.direct_state_access {
         ctxt.gl.NamedBufferStorage(id, size as gl::types::GLsizeiptr,
                                    data_ptr as *const _,
                                    immutable_storage_flags);
@@ -1264,7 +1371,7 @@ unsafe fn create_buffer<D: ?Sized>(mut ctxt: &mut CommandContext, size: usize, d
 
     let persistent_mapping = if let BufferMode::Persistent = mode {
         if immutable {
-            let ptr = if ctxt.version >= &Version(Api::Gl, 4, 5) {
+            let ptr = if ctxt.capabilities.direct_state_access {
                 ctxt.gl.MapNamedBufferRange(id, 0, size as gl::types::GLsizeiptr,
                                             gl::MAP_READ_BIT | gl::MAP_WRITE_BIT |
                                             gl::MAP_PERSISTENT_BIT | gl::MAP_FLUSH_EXPLICIT_BIT)
@@ -1517,7 +1624,7 @@ unsafe fn copy_buffer(ctxt: &mut CommandContext, source: gl::types::GLuint,
                       source_offset: usize, dest: gl::types::GLuint, dest_offset: usize,
                       size: usize) -> Result<(), CopyError>
 {
-    if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
+    if ctxt.capabilities.direct_state_access {
         ctxt.gl.CopyNamedBufferSubData(source, dest, source_offset as gl::types::GLintptr,
                                        dest_offset as gl::types::GLintptr,
                                        size as gl::types::GLsizeiptr);
@@ -1596,6 +1703,21 @@ unsafe fn destroy_buffer(mut ctxt: &mut CommandContext, id: gl::types::GLuint) {
     // FIXME: uncomment this and move it from Buffer's destructor
     //self.context.vertex_array_objects.purge_buffer(&mut ctxt, id);
 
+    forget_buffer(ctxt, id);
+
+    if ctxt.version >= &Version(Api::Gl, 1, 5) ||
+        ctxt.version >= &Version(Api::GlEs, 2, 0)
+    {
+        ctxt.gl.DeleteBuffers(1, [id].as_ptr());
+    } else if ctxt.extensions.gl_arb_vertex_buffer_object {
+        ctxt.gl.DeleteBuffersARB(1, [id].as_ptr());
+    } else {
+        unreachable!();
+    }
+}
+
+/// Removes a buffer from the bindings of the state cache.
+fn forget_buffer(ctxt: &mut CommandContext, id: gl::types::GLuint) {
     if ctxt.state.array_buffer_binding == id {
         ctxt.state.array_buffer_binding = 0;
     }
@@ -1668,23 +1790,13 @@ unsafe fn destroy_buffer(mut ctxt: &mut CommandContext, id: gl::types::GLuint) {
             point.buffer = 0;
         }
     }
-
-    if ctxt.version >= &Version(Api::Gl, 1, 5) ||
-        ctxt.version >= &Version(Api::GlEs, 2, 0)
-    {
-        ctxt.gl.DeleteBuffers(1, [id].as_ptr());
-    } else if ctxt.extensions.gl_arb_vertex_buffer_object {
-        ctxt.gl.DeleteBuffersARB(1, [id].as_ptr());
-    } else {
-        unreachable!();
-    }
 }
 
 /// Flushes a range of a mapped buffer.
 unsafe fn flush_range(mut ctxt: &mut CommandContext, id: gl::types::GLuint, ty: BufferType,
                       range: Range<usize>)
 {
-    if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
+    if ctxt.capabilities.direct_state_access {
         ctxt.gl.FlushMappedNamedBufferRange(id, range.start as gl::types::GLintptr,
                                             (range.end - range.start) as gl::types::GLsizeiptr);
 
@@ -1718,7 +1830,7 @@ unsafe fn map_buffer(mut ctxt: &mut CommandContext, id: gl::types::GLuint, ty: B
         (false, false) => 0,
     };
 
-    if ctxt.version >= &Version(Api::Gl, 4, 5) {
+    if ctxt.capabilities.direct_state_access {
         Some(ctxt.gl.MapNamedBufferRange(id, range.start as gl::types::GLintptr,
                                          (range.end - range.start) as gl::types::GLsizeiptr,
                                          flags) as *mut ())
@@ -1743,7 +1855,7 @@ unsafe fn map_buffer(mut ctxt: &mut CommandContext, id: gl::types::GLuint, ty: B
 ///
 /// Assumes that the buffer exists, that it is of the right type, and that it is already mapped.
 unsafe fn unmap_buffer(mut ctxt: &mut CommandContext, id: gl::types::GLuint, ty: BufferType) {
-    if ctxt.version >= &Version(Api::Gl, 4, 5) {
+    if ctxt.capabilities.direct_state_access {
         ctxt.gl.UnmapNamedBuffer(id);
 
     } else if ctxt.version >= &Version(Api::Gl, 1, 5) ||
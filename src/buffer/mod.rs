@@ -49,6 +49,7 @@ pub use self::view::{BufferSlice, BufferAnySlice};
 pub use self::alloc::{Mapping, WriteMapping, ReadMapping, ReadError, CopyError};
 pub use self::alloc::{is_buffer_read_supported};
 pub use self::fences::Inserter;
+pub use self::reduce::{BufferReducer, ReduceOp};
 
 /// DEPRECATED. Only here for backward compatibility.
 pub use self::view::Buffer as BufferView;
@@ -69,6 +70,7 @@ use std::slice;
 
 mod alloc;
 mod fences;
+mod reduce;
 mod view;
 
 /// Trait for types of data that can be put inside buffers.
@@ -250,6 +252,11 @@ pub enum BufferMode {
     /// Tries to use `glBufferStorage` with `GL_MAP_PERSISTENT_BIT`. Sync fences are automatically
     /// managed by glium.
     ///
+    /// The buffer is mapped without `GL_MAP_COHERENT_BIT` and with `GL_MAP_FLUSH_EXPLICIT_BIT`.
+    /// Glium calls `glFlushMappedBufferRange` on the modified range when a mapping is released,
+    /// and `glMemoryBarrier(GL_CLIENT_MAPPED_BUFFER_BARRIER_BIT)` before a mapping is created
+    /// if shaders have written to the buffer.
+    ///
     /// If this function is not available, falls back to `glBufferData` with `GL_DYNAMIC_DRAW`.
     ///
     Persistent,
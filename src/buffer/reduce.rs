@@ -0,0 +1,231 @@
+//! Reduces the content of a buffer of floats to a single value on the GPU.
+
+use std::cmp;
+
+use backend::Facade;
+use buffer::{Buffer, BufferSlice, BufferAnySlice, BufferType, BufferMode, BufferCreationError};
+use program::{ComputeShader, ProgramCreationError};
+use uniforms::{Uniforms, UniformValue};
+use utils::layout::any_layout;
+
+/// The operation to apply in order to reduce the values of a buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReduceOp {
+    /// Returns the smallest value.
+    Min,
+    /// Returns the largest value.
+    Max,
+    /// Returns the sum of all the values.
+    Sum,
+    /// Returns the sum of all the values divided by their number.
+    Average,
+}
+
+impl ReduceOp {
+    /// Returns the value of the `operation` uniform of the shader.
+    #[inline]
+    fn to_shader_value(&self) -> u32 {
+        match *self {
+            ReduceOp::Min => 0,
+            ReduceOp::Max => 1,
+            ReduceOp::Sum => 2,
+            ReduceOp::Average => 3,
+        }
+    }
+}
+
+/// Reduces a buffer of floats to a single value with a compute shader, for example in order
+/// to compute the average luminance of an image or the bounds of a depth buffer.
+///
+/// The result stays in video memory, which means that it can be used by other shaders without
+/// waiting for the GPU. If you need to read it, you may want to insert a `SyncFence` after the
+/// reduction and wait for it in a later frame.
+///
+/// All the operations are handled by the same shader, so a single reducer can be used for
+/// every `ReduceOp` and every buffer.
+///
+/// The reduction is done in two passes. Up to 256 work groups first reduce separate parts of
+/// the buffer to a temporary buffer, then a single work group reduces these partial results.
+///
+/// ```no_run
+/// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+/// # let luminances: glium::buffer::Buffer<[f32]> = unsafe { std::mem::uninitialized() };
+/// use glium::buffer::{BufferReducer, ReduceOp};
+///
+/// let reducer = BufferReducer::new(&display).unwrap();
+/// let average = reducer.reduce(luminances.as_slice(), ReduceOp::Average).unwrap();
+/// ```
+pub struct BufferReducer {
+    program: ComputeShader,
+}
+
+impl BufferReducer {
+    /// Compiles the reduction shader.
+    ///
+    /// Returns `ShaderTypeNotSupported` if the backend doesn't support compute shaders.
+    pub fn new<F>(facade: &F) -> Result<BufferReducer, ProgramCreationError>
+                  where F: Facade
+    {
+        if !ComputeShader::is_supported(facade) {
+            return Err(ProgramCreationError::ShaderTypeNotSupported);
+        }
+
+        let program = try!(ComputeShader::from_source(facade, r#"
+            #version 430
+            layout(local_size_x = 256) in;
+
+            layout(std430) buffer Input {
+                float values[];
+            };
+
+            layout(std430) buffer Output {
+                float results[];
+            };
+
+            uniform uint values_count;
+            uniform uint operation;
+            uniform float divisor;
+
+            shared float partial[256];
+
+            float combine(float a, float b) {
+                if (operation == 0) {
+                    return min(a, b);
+                } else if (operation == 1) {
+                    return max(a, b);
+                } else {
+                    return a + b;
+                }
+            }
+
+            void main() {
+                uint id = gl_LocalInvocationID.x;
+                uint group = gl_WorkGroupID.x;
+                uint stride = 256 * gl_NumWorkGroups.x;
+
+                // each invocation first reduces a strided part of the buffer
+                float value = operation < 2 ? values[0] : 0.0;
+                for (uint i = group * 256 + id; i < values_count; i += stride) {
+                    value = combine(value, values[i]);
+                }
+
+                partial[id] = value;
+                memoryBarrierShared();
+                barrier();
+
+                // then the partial results are reduced in shared memory
+                for (uint step = 128; step > 0; step /= 2) {
+                    if (id < step) {
+                        partial[id] = combine(partial[id], partial[id + step]);
+                    }
+
+                    memoryBarrierShared();
+                    barrier();
+                }
+
+                if (id == 0) {
+                    results[group] = partial[0] / divisor;
+                }
+            }
+        "#));
+
+        Ok(BufferReducer {
+            program: program,
+        })
+    }
+
+    /// Reduces the values of `input` and returns a buffer containing the result.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `input` is empty.
+    pub fn reduce(&self, input: BufferSlice<[f32]>, op: ReduceOp)
+                  -> Result<Buffer<f32>, BufferCreationError>
+    {
+        assert!(input.len() >= 1);
+
+        let mut output: Buffer<f32> = try!(Buffer::empty(input.get_context(),
+                                                         BufferType::ShaderStorageBuffer,
+                                                         BufferMode::Default));
+
+        try!(self.reduce_into(input, op, &mut output));
+        Ok(output)
+    }
+
+    /// Reduces the values of `input` and writes the result in `output`.
+    ///
+    /// This is the same as `reduce`, except that it doesn't need to create the output buffer.
+    /// A temporary buffer is still created for the partial results if `input` contains more
+    /// than 256 values.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `input` is empty.
+    pub fn reduce_into(&self, input: BufferSlice<[f32]>, op: ReduceOp, output: &mut Buffer<f32>)
+                       -> Result<(), BufferCreationError>
+    {
+        let values_count = input.len();
+        assert!(values_count >= 1);
+
+        // the average is a sum that is divided at the end of the last pass
+        let divisor = match op {
+            ReduceOp::Average => values_count as f32,
+            _ => 1.0,
+        };
+
+        let groups = cmp::min((values_count + 255) / 256, 256);
+
+        if groups == 1 {
+            self.program.execute(ReduceUniforms {
+                input: input.as_slice_any(),
+                output: output.as_slice_any(),
+                values_count: values_count as u32,
+                operation: op.to_shader_value(),
+                divisor: divisor,
+            }, 1, 1, 1);
+
+            return Ok(());
+        }
+
+        let partials: Buffer<[f32]> = try!(Buffer::empty_array(input.get_context(),
+                                                               BufferType::ShaderStorageBuffer,
+                                                               groups, BufferMode::Default));
+
+        self.program.execute(ReduceUniforms {
+            input: input.as_slice_any(),
+            output: partials.as_slice_any(),
+            values_count: values_count as u32,
+            operation: op.to_shader_value(),
+            divisor: 1.0,
+        }, groups as u32, 1, 1);
+
+        self.program.execute(ReduceUniforms {
+            input: partials.as_slice_any(),
+            output: output.as_slice_any(),
+            values_count: groups as u32,
+            operation: op.to_shader_value(),
+            divisor: divisor,
+        }, 1, 1, 1);
+
+        Ok(())
+    }
+}
+
+/// The uniforms passed to the reduction shader.
+struct ReduceUniforms<'b> {
+    input: BufferAnySlice<'b>,
+    output: BufferAnySlice<'b>,
+    values_count: u32,
+    operation: u32,
+    divisor: f32,
+}
+
+impl<'b> Uniforms for ReduceUniforms<'b> {
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+        output("Input", UniformValue::Block(self.input, any_layout));
+        output("Output", UniformValue::Block(self.output, any_layout));
+        output("values_count", UniformValue::UnsignedInt(self.values_count));
+        output("operation", UniformValue::UnsignedInt(self.operation));
+        output("divisor", UniformValue::Float(self.divisor));
+    }
+}
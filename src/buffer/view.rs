@@ -1,6 +1,7 @@
 use std::fmt;
 use std::mem;
 use std::borrow::Cow;
+use std::ops::Range;
 use utils::range::RangeArgument;
 use std::marker::PhantomData;
 
@@ -99,6 +100,32 @@ impl<T: ?Sized> Buffer<T> where T: Content {
         self.alloc.as_ref().unwrap().uses_persistent_mapping()
     }
 
+    /// Returns true if the writes done through a mapping of this buffer are visible to the
+    /// GPU without being flushed.
+    ///
+    /// Glium always maps buffers with `GL_MAP_FLUSH_EXPLICIT_BIT`, so this currently always
+    /// returns false.
+    #[inline]
+    pub fn is_mapping_coherent(&self) -> bool {
+        self.alloc.as_ref().unwrap().is_mapping_coherent()
+    }
+
+    /// Makes the writes done in a range of the buffer visible to the GPU, by calling
+    /// `glFlushMappedBufferRange` if the buffer is persistently mapped and the mapping is not
+    /// coherent. Does nothing otherwise.
+    ///
+    /// `bytes_range` is in bytes. The mappings returned by `map` and `map_write` are already
+    /// flushed when they are released, so this is only needed to make the writes visible
+    /// before that.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the range is out of the bounds of the buffer.
+    #[inline]
+    pub fn flush_if_needed(&self, bytes_range: Range<usize>) {
+        self.alloc.as_ref().unwrap().flush_if_needed(bytes_range)
+    }
+
     /// Uploads some data in this buffer.
     ///
     /// # Implementation
@@ -354,6 +381,20 @@ impl<T> Buffer<[T]> where [T]: Content, T: Copy {
             })
     }
 
+    /// Builds a new buffer of the given size whose content is all zeroes.
+    pub fn empty_array_zeroed<F>(facade: &F, ty: BufferType, len: usize, mode: BufferMode)
+                                 -> Result<Buffer<[T]>, BufferCreationError> where F: Facade
+    {
+        Alloc::empty_zeroed(facade, ty, len * mem::size_of::<T>(), mode)
+            .map(|buffer| {
+                Buffer {
+                    alloc: Some(buffer),
+                    fence: Some(Fences::new()),
+                    marker: PhantomData,
+                }
+            })
+    }
+
     /// Returns the number of elements in this buffer.
     #[inline]
     pub fn len(&self) -> usize {
@@ -377,6 +418,42 @@ impl<T> Buffer<[T]> where [T]: Content, T: Copy {
     pub fn slice_mut<R: RangeArgument<usize>>(&mut self, range: R) -> Option<BufferMutSlice<[T]>> {
         self.as_mut_slice().slice(range)
     }
+
+    /// Reads the elements of a range of the buffer, without reading the rest of it.
+    ///
+    /// Returns `ReadError::OutOfBounds` if the range is outside of the buffer.
+    ///
+    /// This is the same as calling `slice(range)` then `read()` on the slice.
+    #[inline]
+    pub fn read_range<R: RangeArgument<usize>>(&self, range: R) -> Result<Vec<T>, ReadError> {
+        match self.slice(range) {
+            Some(slice) => slice.read(),
+            None => Err(ReadError::OutOfBounds),
+        }
+    }
+
+    /// Turns this buffer into a buffer of elements of a different type. No actual OpenGL
+    /// operation is performed and the content of the buffer is left untouched.
+    ///
+    /// The number of elements is adjusted to the size of `U`. Returns the buffer unchanged
+    /// in an `Err` if the size of the buffer is not a multiple of the size of `U`.
+    ///
+    /// Both `T` and `U` should be `#[repr(C)]`, otherwise the layout of their fields in memory
+    /// is unspecified.
+    pub fn reinterpret<U>(mut self) -> Result<Buffer<[U]>, Buffer<[T]>>
+                          where [U]: Content, U: Copy
+    {
+        let size = self.get_size();
+        if mem::size_of::<U>() == 0 || size % mem::size_of::<U>() != 0 {
+            return Err(self);
+        }
+
+        Ok(Buffer {
+            alloc: self.alloc.take(),
+            fence: self.fence.take(),
+            marker: PhantomData,
+        })
+    }
 }
 
 impl<T> Buffer<[T]> where T: PixelValue {
@@ -793,7 +870,7 @@ impl<'a, T: ?Sized> BufferExt for BufferSlice<'a, T> where T: Content {
 
     #[inline]
     fn prepare_and_bind_for_uniform(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
-        self.alloc.prepare_and_bind_for_uniform(ctxt, index, 0 .. self.alloc.get_size());
+        self.alloc.prepare_and_bind_for_uniform(ctxt, index, self.bytes_start .. self.bytes_end);
     }
 
     #[inline]
@@ -1385,7 +1462,7 @@ impl<'a> BufferExt for BufferAnySlice<'a> {
 
     #[inline]
     fn prepare_and_bind_for_uniform(&self, ctxt: &mut CommandContext, index: gl::types::GLuint) {
-        self.alloc.prepare_and_bind_for_uniform(ctxt, index, 0 .. self.alloc.get_size());
+        self.alloc.prepare_and_bind_for_uniform(ctxt, index, self.bytes_start .. self.bytes_end);
     }
 
     #[inline]
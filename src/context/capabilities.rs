@@ -56,7 +56,15 @@ pub struct Capabilities {
     /// will be supported.
     pub forward_compatible: bool,
 
-    /// True if out-of-bound access on the GPU side can't result in crashes.
+    /// True if objects can be created and modified without being bound, either because the
+    /// backend is OpenGL 4.5 or because it supports `GL_ARB_direct_state_access`.
+    pub direct_state_access: bool,
+
+    /// True if out-of-bound access on the GPU side can't result in crashes, which happens when
+    /// the context has been created with `GL_CONTEXT_FLAG_ROBUST_ACCESS_BIT`.
+    ///
+    /// Glium never requests this flag itself. It must be requested when building the context,
+    /// for example with the `with_gl_robustness` method of glutin's builders.
     pub robustness: bool,
 
     /// True if it is possible for the OpenGL context to be lost.
@@ -88,9 +96,11 @@ pub struct Capabilities {
     /// `glActiveTexture` must be between `GL_TEXTURE0` and `GL_TEXTURE0` + this value - 1.
     pub max_combined_texture_image_units: gl::types::GLint,
 
-    /// Maximum value for `GL_TEXTURE_MAX_ANISOTROPY_EXT​`.
+    /// Maximum value for `GL_TEXTURE_MAX_ANISOTROPY`.
     ///
-    /// `None` if the extension is not supported by the hardware.
+    /// `None` if anisotropic filtering is not supported by the hardware. Anisotropic filtering
+    /// is core since OpenGL 4.6, and is otherwise available through
+    /// `GL_ARB_texture_filter_anisotropic` or `GL_EXT_texture_filter_anisotropic`.
     pub max_texture_max_anisotropy: Option<gl::types::GLfloat>,
 
     /// Maximum size of a buffer texture. `None` if this is not supported.
@@ -99,9 +109,23 @@ pub struct Capabilities {
     /// Maximum width and height of `glViewport`.
     pub max_viewport_dims: (gl::types::GLint, gl::types::GLint),
 
+    /// Maximum number of viewports that can be used at the same time. `1` if viewport arrays
+    /// are not supported.
+    pub max_viewports: gl::types::GLint,
+
     /// Maximum number of elements that can be passed with `glDrawBuffers`.
     pub max_draw_buffers: gl::types::GLint,
 
+    /// Maximum number of `gl_ClipDistance` outputs that can be enabled. `0` if not supported.
+    pub max_clip_distances: gl::types::GLint,
+
+    /// Minimum and maximum width of lines when smoothing is disabled.
+    pub aliased_line_width_range: (gl::types::GLfloat, gl::types::GLfloat),
+
+    /// Minimum and maximum width of lines when smoothing is enabled. `None` if smoothing is
+    /// not supported.
+    pub smooth_line_width_range: Option<(gl::types::GLfloat, gl::types::GLfloat)>,
+
     /// Maximum number of vertices per patch. `None` if tessellation is not supported.
     pub max_patch_vertices: Option<gl::types::GLint>,
 
@@ -117,6 +141,47 @@ pub struct Capabilities {
     /// Number of available buffer bind points for `GL_UNIFORM_BUFFER`.
     pub max_indexed_uniform_buffer: gl::types::GLint,
 
+    /// Number of vertex streams that a geometry shader can output to. `1` if multiple streams
+    /// are not supported.
+    pub max_vertex_streams: gl::types::GLint,
+
+    /// Maximum number of vertices that a geometry shader can output. `None` if geometry shaders
+    /// are not supported.
+    ///
+    /// Programs whose geometry shader declares more output vertices fail to compile or to link.
+    pub max_geometry_output_vertices: Option<gl::types::GLint>,
+
+    /// Maximum number of invocations of a geometry shader per primitive. `None` if geometry
+    /// shaders are not supported, and `Some(1)` if they are supported but not instancing.
+    pub max_geometry_shader_invocations: Option<gl::types::GLint>,
+
+    /// Alignment in bytes that the offset of a range of a buffer binded to `GL_UNIFORM_BUFFER`
+    /// must respect.
+    pub uniform_buffer_offset_alignment: gl::types::GLint,
+
+    /// True if multiple draw commands stored in a buffer can be submitted at once, either
+    /// because the backend is OpenGL 4.3 or because it supports `GL_ARB_multi_draw_indirect`
+    /// or `GL_EXT_multi_draw_indirect`.
+    pub multi_draw_indirect: bool,
+
+    /// True if the number of draw commands can be read from a buffer, either because the
+    /// backend is OpenGL 4.6 or because it supports `GL_ARB_indirect_parameters`.
+    pub indirect_parameters: bool,
+
+    /// Maximum number of commands that can be submitted with a single multidraw call. `None` if
+    /// multidraw is not supported.
+    ///
+    /// This is not queried from the backend. OpenGL doesn't have any state for this limit, and
+    /// the only constraint put by the specifications is that the count fits in a `GLsizei`.
+    pub max_draw_indirect_count: Option<gl::types::GLint>,
+
+    /// Alignment in bytes that the offset of the commands in a buffer binded to
+    /// `GL_DRAW_INDIRECT_BUFFER` must respect.
+    ///
+    /// This is not queried from the backend. The specifications require the offset to be a
+    /// multiple of the size of a `GLuint`, so this is always `4`.
+    pub draw_indirect_offset_alignment: gl::types::GLint,
+
     /// Number of work groups for compute shaders.
     pub max_compute_work_group_count: (gl::types::GLint, gl::types::GLint, gl::types::GLint),
 
@@ -134,6 +199,22 @@ pub struct Capabilities {
 
     /// Maximum samples of an empty framebuffer. `None` if not supported.
     pub max_framebuffer_samples: Option<gl::types::GLint>,
+
+    /// Maximum number of samples of a multisample texture with a color format. `None` if
+    /// multisample textures are not supported.
+    pub max_color_texture_samples: Option<gl::types::GLint>,
+
+    /// Maximum number of samples of a multisample texture with a depth or stencil format.
+    /// `None` if multisample textures are not supported.
+    pub max_depth_texture_samples: Option<gl::types::GLint>,
+
+    /// Maximum number of samples of a multisample texture with an integral format. `None` if
+    /// multisample textures are not supported.
+    pub max_integer_samples: Option<gl::types::GLint>,
+
+    /// Maximum number of samples of a multisampled renderbuffer or of a texture that is
+    /// rendered to with implicit multisampling. `None` if multisampling is not supported.
+    pub max_samples: Option<gl::types::GLint>,
 }
 
 /// Information about an internal format.
@@ -141,6 +222,23 @@ pub struct Capabilities {
 pub struct FormatInfos {
     /// Possible values for multisampling. `None` if unknown.
     pub multisamples: Option<Vec<gl::types::GLint>>,
+
+    /// Whether the format can be used as a color attachment. `None` if unknown.
+    pub color_renderable: Option<bool>,
+
+    /// Whether the format supports linear filtering. `None` if unknown.
+    pub filterable: Option<bool>,
+
+    /// Whether blending is supported when rendering to this format. `None` if unknown.
+    pub blendable: Option<bool>,
+}
+
+impl FormatInfos {
+    /// Returns the maximum number of samples supported by this format. `None` if unknown.
+    #[inline]
+    pub fn get_max_samples(&self) -> Option<u32> {
+        self.multisamples.as_ref().map(|s| s.iter().cloned().max().unwrap_or(0) as u32)
+    }
 }
 
 /// Defines what happens when you change the current context.
@@ -224,6 +322,9 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
 
         forward_compatible: forward_compatible,
 
+        direct_state_access: version >= &Version(Api::Gl, 4, 5) ||
+                             extensions.gl_arb_direct_state_access,
+
         robustness: if version >= &Version(Api::Gl, 4, 5) || version >= &Version(Api::GlEs, 3, 2) ||
                        (version >= &Version(Api::Gl, 3, 0) && extensions.gl_arb_robustness)
         {
@@ -391,7 +492,10 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             val
         },
 
-        max_texture_max_anisotropy: if !extensions.gl_ext_texture_filter_anisotropic {
+        max_texture_max_anisotropy: if !(version >= &Version(Api::Gl, 4, 6)) &&
+                                       !extensions.gl_arb_texture_filter_anisotropic &&
+                                       !extensions.gl_ext_texture_filter_anisotropic
+        {
             None
 
         } else {
@@ -424,6 +528,16 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             (val[0], val[1])
         },
 
+        max_viewports: {
+            if version >= &Version(Api::Gl, 4, 1) || extensions.gl_arb_viewport_array {
+                let mut val = 1;
+                gl.GetIntegerv(gl::MAX_VIEWPORTS, &mut val);
+                val
+            } else {
+                1
+            }
+        },
+
         max_draw_buffers: {
             if version >= &Version(Api::Gl, 2, 0) ||
                 version >= &Version(Api::GlEs, 3, 0) ||
@@ -437,6 +551,32 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        max_clip_distances: {
+            if version >= &Version(Api::Gl, 1, 0) || extensions.gl_ext_clip_cull_distance {
+                let mut val = 0;
+                gl.GetIntegerv(gl::MAX_CLIP_DISTANCES, &mut val);
+                val
+            } else {
+                0
+            }
+        },
+
+        aliased_line_width_range: {
+            let mut val: [gl::types::GLfloat; 2] = [1.0, 1.0];
+            gl.GetFloatv(gl::ALIASED_LINE_WIDTH_RANGE, val.as_mut_ptr());
+            (val[0], val[1])
+        },
+
+        smooth_line_width_range: {
+            if version >= &Version(Api::Gl, 1, 0) {
+                let mut val: [gl::types::GLfloat; 2] = [1.0, 1.0];
+                gl.GetFloatv(gl::SMOOTH_LINE_WIDTH_RANGE, val.as_mut_ptr());
+                Some((val[0], val[1]))
+            } else {
+                None
+            }
+        },
+
         max_patch_vertices: if version >= &Version(Api::Gl, 4, 0) ||
             extensions.gl_arb_tessellation_shader
         {
@@ -482,6 +622,46 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        max_vertex_streams: {
+            if version >= &Version(Api::Gl, 4, 0) || extensions.gl_arb_transform_feedback3 {
+                let mut val = mem::uninitialized();
+                gl.GetIntegerv(gl::MAX_VERTEX_STREAMS, &mut val);
+                val
+            } else {
+                1
+            }
+        },
+
+        max_geometry_output_vertices: {
+            if version >= &Version(Api::Gl, 3, 2) || version >= &Version(Api::GlEs, 3, 2) ||
+               extensions.gl_arb_geometry_shader4 || extensions.gl_ext_geometry_shader4 ||
+               extensions.gl_ext_geometry_shader || extensions.gl_oes_geometry_shader
+            {
+                let mut val = mem::uninitialized();
+                gl.GetIntegerv(gl::MAX_GEOMETRY_OUTPUT_VERTICES, &mut val);
+                Some(val)
+            } else {
+                None
+            }
+        },
+
+        max_geometry_shader_invocations: {
+            if version >= &Version(Api::Gl, 4, 0) || version >= &Version(Api::GlEs, 3, 2) ||
+               extensions.gl_arb_gpu_shader5 || extensions.gl_ext_geometry_shader ||
+               extensions.gl_oes_geometry_shader
+            {
+                let mut val = mem::uninitialized();
+                gl.GetIntegerv(gl::MAX_GEOMETRY_SHADER_INVOCATIONS, &mut val);
+                Some(val)
+            } else if version >= &Version(Api::Gl, 3, 2) || extensions.gl_arb_geometry_shader4 ||
+                      extensions.gl_ext_geometry_shader4
+            {
+                Some(1)
+            } else {
+                None
+            }
+        },
+
         max_indexed_uniform_buffer: {
             if version >= &Version(Api::Gl, 3, 1) || extensions.gl_arb_uniform_buffer_object {      // TODO: GLES
                 let mut val = mem::uninitialized();
@@ -492,6 +672,39 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        uniform_buffer_offset_alignment: {
+            if version >= &Version(Api::Gl, 3, 1) || version >= &Version(Api::GlEs, 3, 0) ||
+               extensions.gl_arb_uniform_buffer_object
+            {
+                let mut val = mem::uninitialized();
+                gl.GetIntegerv(gl::UNIFORM_BUFFER_OFFSET_ALIGNMENT, &mut val);
+                val
+            } else {
+                1
+            }
+        },
+
+        multi_draw_indirect: version >= &Version(Api::Gl, 4, 3) ||
+                             extensions.gl_arb_multi_draw_indirect ||
+                             extensions.gl_ext_multi_draw_indirect,
+
+        indirect_parameters: version >= &Version(Api::Gl, 4, 6) ||
+                             extensions.gl_arb_indirect_parameters,
+
+        max_draw_indirect_count: if version >= &Version(Api::Gl, 4, 3) ||
+                                    extensions.gl_arb_multi_draw_indirect ||
+                                    extensions.gl_ext_multi_draw_indirect
+        {
+            // there is no `GL_MAX_*` constant to query, the count is only limited by its type
+            Some(gl::types::GLint::max_value())
+        } else {
+            None
+        },
+
+        // constant defined by the specifications: the offset must be a multiple of the size of
+        // a `GLuint`, and there is no `GL_*_ALIGNMENT` constant to query
+        draw_indirect_offset_alignment: mem::size_of::<gl::types::GLuint>() as gl::types::GLint,
+
         max_compute_work_group_count: if version >= &Version(Api::Gl, 4, 3) ||
                                          version >= &Version(Api::GlEs, 3, 1) ||
                                          extensions.gl_arb_compute_shader
@@ -576,6 +789,59 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        max_color_texture_samples: {
+            if version >= &Version(Api::Gl, 3, 2) || version >= &Version(Api::GlEs, 3, 1) ||
+               extensions.gl_arb_texture_multisample
+            {
+                let mut val = 0;
+                gl.GetIntegerv(gl::MAX_COLOR_TEXTURE_SAMPLES, &mut val);
+                Some(val)
+
+            } else {
+                None
+            }
+        },
+
+        max_depth_texture_samples: {
+            if version >= &Version(Api::Gl, 3, 2) || version >= &Version(Api::GlEs, 3, 1) ||
+               extensions.gl_arb_texture_multisample
+            {
+                let mut val = 0;
+                gl.GetIntegerv(gl::MAX_DEPTH_TEXTURE_SAMPLES, &mut val);
+                Some(val)
+
+            } else {
+                None
+            }
+        },
+
+        max_integer_samples: {
+            if version >= &Version(Api::Gl, 3, 2) || version >= &Version(Api::GlEs, 3, 1) ||
+               extensions.gl_arb_texture_multisample
+            {
+                let mut val = 0;
+                gl.GetIntegerv(gl::MAX_INTEGER_SAMPLES, &mut val);
+                Some(val)
+
+            } else {
+                None
+            }
+        },
+
+        max_samples: {
+            if version >= &Version(Api::Gl, 3, 0) || version >= &Version(Api::GlEs, 3, 0) ||
+               extensions.gl_arb_framebuffer_object || extensions.gl_ext_framebuffer_multisample ||
+               extensions.gl_ext_multisampled_render_to_texture
+            {
+                let mut val = 0;
+                gl.GetIntegerv(gl::MAX_SAMPLES, &mut val);
+                Some(val)
+
+            } else {
+                None
+            }
+        },
+
         renderer: renderer,
     }
 }
@@ -736,8 +1002,25 @@ pub fn get_internal_format(gl: &gl::Gl, version: &Version, extensions: &Extensio
             None
         };
 
+        // querying the other properties requires `GL_ARB_internalformat_query2`
+        let query2 = |pname| {
+            if version >= &Version(Api::Gl, 4, 3) || extensions.gl_arb_internalformat_query2 {
+                let target = if renderbuffer { gl::RENDERBUFFER } else { gl::TEXTURE_2D };
+                let mut value = 0;
+                gl.GetInternalformativ(target, format.to_glenum(), pname, 1, &mut value);
+                Some(value as gl::types::GLenum)
+            } else {
+                None
+            }
+        };
+
         FormatInfos {
             multisamples: samples,
+            color_renderable: query2(gl::COLOR_RENDERABLE).map(|v| v == gl::TRUE as gl::types::GLenum),
+            filterable: query2(gl::FILTER).map(|v| v == gl::FULL_SUPPORT ||
+                                                   v == gl::CAVEAT_SUPPORT),
+            blendable: query2(gl::FRAMEBUFFER_BLEND).map(|v| v == gl::FULL_SUPPORT ||
+                                                            v == gl::CAVEAT_SUPPORT),
         }
     }
 }
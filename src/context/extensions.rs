@@ -54,8 +54,12 @@ extensions! {
     "GL_APPLE_framebuffer_multisample" => gl_apple_framebuffer_multisample,
     "GL_APPLE_sync" => gl_apple_sync,
     "GL_APPLE_vertex_array_object" => gl_apple_vertex_array_object,
+    "GL_ARB_base_instance" => gl_arb_base_instance,
     "GL_ARB_bindless_texture" => gl_arb_bindless_texture,
     "GL_ARB_buffer_storage" => gl_arb_buffer_storage,
+    "GL_ARB_clear_buffer_object" => gl_arb_clear_buffer_object,
+    "GL_ARB_clear_texture" => gl_arb_clear_texture,
+    "GL_ARB_clip_control" => gl_arb_clip_control,
     "GL_ARB_compute_shader" => gl_arb_compute_shader,
     "GL_ARB_copy_buffer" => gl_arb_copy_buffer,
     "GL_ARB_debug_output" => gl_arb_debug_output,
@@ -75,10 +79,13 @@ extensions! {
     "GL_ARB_framebuffer_sRGB" => gl_arb_framebuffer_srgb,
     "GL_ARB_geometry_shader4" => gl_arb_geometry_shader4,
     "GL_ARB_get_program_binary" => gl_arb_get_programy_binary,
+    "GL_ARB_gpu_shader5" => gl_arb_gpu_shader5,
     "GL_ARB_gpu_shader_fp64" => gl_arb_gpu_shader_fp64,
     "GL_ARB_gpu_shader_int64" => gl_arb_gpu_shader_int64,
+    "GL_ARB_indirect_parameters" => gl_arb_indirect_parameters,
     "GL_ARB_instanced_arrays" => gl_arb_instanced_arrays,
     "GL_ARB_internalformat_query" => gl_arb_internalformat_query,
+    "GL_ARB_internalformat_query2" => gl_arb_internalformat_query2,
     "GL_ARB_invalidate_subdata" => gl_arb_invalidate_subdata,
     "GL_ARB_occlusion_query" => gl_arb_occlusion_query,
     "GL_ARB_occlusion_query2" => gl_arb_occlusion_query2,
@@ -90,11 +97,16 @@ extensions! {
     "GL_ARB_provoking_vertex" => gl_arb_provoking_vertex,
     "GL_ARB_robustness" => gl_arb_robustness,
     "GL_ARB_robust_buffer_access_behavior" => gl_arb_robust_buffer_access_behavior,
+    "GL_ARB_sample_locations" => gl_arb_sample_locations,
     "GL_ARB_sampler_objects" => gl_arb_sampler_objects,
+    "GL_ARB_seamless_cube_map" => gl_arb_seamless_cube_map,
+    "GL_ARB_separate_shader_objects" => gl_arb_separate_shader_objects,
+    "GL_ARB_shader_draw_parameters" => gl_arb_shader_draw_parameters,
     "GL_ARB_shader_image_load_store" => gl_arb_shader_image_load_store,
     "GL_ARB_shader_objects" => gl_arb_shader_objects,
     "GL_ARB_shader_storage_buffer_object" => gl_arb_shader_storage_buffer_object,
     "GL_ARB_shader_subroutine" => gl_arb_shader_subroutine,
+    "GL_ARB_shading_language_include" => gl_arb_shading_language_include,
     "GL_ARB_sync" => gl_arb_sync,
     "GL_ARB_tessellation_shader" => gl_arb_tessellation_shader,
     "GL_ARB_texture_buffer_object" => gl_arb_texture_buffer_object,
@@ -102,6 +114,7 @@ extensions! {
     "GL_ARB_texture_compression_bptc" => gl_arb_texture_compression_bptc,
     "GL_ARB_texture_cube_map" => gl_arb_texture_cube_map,
     "GL_ARB_texture_cube_map_array" => gl_arb_texture_cube_map_array,
+    "GL_ARB_texture_filter_anisotropic" => gl_arb_texture_filter_anisotropic,
     "GL_ARB_texture_float" => gl_arb_texture_float,
     "GL_ARB_texture_multisample" => gl_arb_texture_multisample,
     "GL_ARB_texture_non_power_of_two" => gl_arb_texture_non_power_of_two,
@@ -109,22 +122,30 @@ extensions! {
     "GL_ARB_texture_rgb10_a2ui" => gl_arb_texture_rgb10_a2ui,
     "GL_ARB_texture_stencil8" => gl_arb_texture_stencil8,
     "GL_ARB_texture_storage" => gl_arb_texture_storage,
+    "GL_ARB_texture_swizzle" => gl_arb_texture_swizzle,
     "GL_ARB_timer_query" => gl_arb_timer_query,
+    "GL_ARB_transform_feedback2" => gl_arb_transform_feedback2,
     "GL_ARB_transform_feedback3" => gl_arb_transform_feedback3,
+    "GL_ARB_transform_feedback_instanced" => gl_arb_transform_feedback_instanced,
     "GL_ARB_uniform_buffer_object" => gl_arb_uniform_buffer_object,
     "GL_ARB_vertex_array_object" => gl_arb_vertex_array_object,
+    "GL_ARB_vertex_attrib_binding" => gl_arb_vertex_attrib_binding,
     "GL_ARB_vertex_buffer_object" => gl_arb_vertex_buffer_object,
     "GL_ARB_vertex_half_float" => gl_arb_vertex_half_float,
     "GL_ARB_vertex_shader" => gl_arb_vertex_shader,
     "GL_ARB_vertex_type_10f_11f_11f_rev" => gl_arb_vertex_type_10f_11f_11f_rev,
     "GL_ARB_vertex_type_2_10_10_10_rev" => gl_arb_vertex_type_2_10_10_10_rev,
+    "GL_ARB_viewport_array" => gl_arb_viewport_array,
     "GL_ARM_rgba8" => gl_arm_rgba8,
     "GL_ATI_meminfo" => gl_ati_meminfo,
     "GL_ATI_draw_buffers" => gl_ati_draw_buffers,
     "GL_ATI_texture_float" => gl_ati_texture_float,
+    "GL_EXT_base_instance" => gl_ext_base_instance,
     "GL_EXT_blend_minmax" => gl_ext_blend_minmax,
     "GL_EXT_buffer_storage" => gl_ext_buffer_storage,
+    "GL_EXT_clip_cull_distance" => gl_ext_clip_cull_distance,
     "GL_EXT_debug_marker" => gl_ext_debug_marker,
+    "GL_EXT_depth_clamp" => gl_ext_depth_clamp,
     "GL_EXT_direct_state_access" => gl_ext_direct_state_access,
     "GL_EXT_disjoint_timer_query" => gl_ext_disjoint_timer_query,
     "GL_EXT_framebuffer_blit" => gl_ext_framebuffer_blit,
@@ -155,7 +176,9 @@ extensions! {
     "GL_EXT_texture_shared_exponent" => gl_ext_texture_shared_exponent,
     "GL_EXT_texture_snorm" => gl_ext_texture_snorm,
     "GL_EXT_texture_sRGB" => gl_ext_texture_srgb,
+    "GL_EXT_texture_swizzle" => gl_ext_texture_swizzle,
     "GL_EXT_transform_feedback" => gl_ext_transform_feedback,
+    "GL_EXT_unpack_subimage" => gl_ext_unpack_subimage,
     "GL_GREMEDY_string_marker" => gl_gremedy_string_marker,
     "GL_KHR_debug" => gl_khr_debug,
     "GL_KHR_context_flush_control" => gl_khr_context_flush_control,
@@ -168,6 +191,7 @@ extensions! {
     "GL_NV_framebuffer_multisample" => gl_nv_framebuffer_multisample,
     "GL_NV_half_float" => gl_nv_half_float,
     "GL_NV_internalformat_sample_query" => gl_nv_internalformat_sample_query,
+    "GL_NV_pack_subimage" => gl_nv_pack_subimage,
     "GL_NV_pixel_buffer_object" => gl_nv_pixel_buffer_object,
     "GL_NV_read_depth" => gl_nv_read_depth,
     "GL_NV_read_stencil" => gl_nv_read_stencil,
@@ -183,6 +207,7 @@ extensions! {
     "GL_OES_packed_depth_stencil" => gl_oes_packed_depth_stencil,
     "GL_OES_primitive_bounding_box" => gl_oes_primitive_bounding_box,
     "GL_OES_rgb8_rgba8" => gl_oes_rgb8_rgba8,
+    "GL_OES_standard_derivatives" => gl_oes_standard_derivatives,
     "GL_OES_stencil1" => gl_oes_stencil1,
     "GL_OES_stencil4" => gl_oes_stencil4,
     "GL_OES_tessellation_shader" => gl_oes_tessellation_shader,
@@ -10,27 +10,31 @@ use std::collections::HashMap;
 use std::cell::{Cell, RefCell, RefMut};
 use std::marker::PhantomData;
 use std::ffi::CStr;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 use std::os::raw;
 
 use GliumCreationError;
 use SwapBuffersError;
 use CapabilitiesSource;
 use ContextExt;
+use ToGlEnum;
 use backend::Backend;
 use version;
 use version::Api;
 use version::Version;
 
 use debug;
+use index;
+use image_format::TextureFormat;
 use fbo;
 use ops;
 use sampler_object;
 use texture;
 use uniforms;
 use vertex_array_object;
+use vertex::VertexBuffer;
 
-pub use self::capabilities::{ReleaseBehavior, Capabilities, Profile};
+pub use self::capabilities::{ReleaseBehavior, Capabilities, FormatInfos, Profile};
 pub use self::extensions::ExtensionsList;
 pub use self::state::GlState;
 
@@ -74,6 +78,9 @@ pub struct Context {
     /// be set to `false` in some situations, like compiling/linking shaders.
     report_debug_output_errors: Cell<bool>,
 
+    /// The checks configured with `set_error_checking_mode`.
+    error_checking_mode: Cell<ErrorCheckingMode>,
+
     /// We maintain a cache of FBOs.
     /// The `Option` is here in order to destroy the container. It must be filled at all time
     /// is a normal situation.
@@ -92,6 +99,109 @@ pub struct Context {
     /// List of images handles that are resident. We need to call `MakeImageHandleResidentARB`
     /// when rebuilding the context.
     resident_image_handles: RefCell<Vec<(gl::types::GLuint64, gl::types::GLenum)>>,
+
+    /// The buffer returned by `index::fullscreen_triangle`. This is a weak reference, because
+    /// the buffer itself holds a strong reference to the context.
+    fullscreen_triangle: RefCell<Option<Weak<VertexBuffer<index::FullscreenVertex>>>>,
+
+    /// Amount of memory allocated by the buffers, textures and render buffers of this context.
+    allocated_memory: AllocatedMemory,
+
+    /// The strings registered with `add_shader_include`, in case the backend doesn't support
+    /// `GL_ARB_shading_language_include`.
+    shader_includes: RefCell<HashMap<String, String>>,
+
+    /// The callback registered with `set_context_lost_callback`.
+    context_lost_callback: RefCell<Option<Box<Fn(ResetStatus)>>>,
+
+    /// The other contexts that share their objects with this one. When an object is destroyed,
+    /// the caches of these contexts must be purged as well.
+    shared_contexts: RefCell<Vec<Weak<Context>>>,
+
+    /// Functions registered by the other contexts of `shared_contexts` with
+    /// `purge_shared_contexts`. They are called the next time this context is made current.
+    pending_purges: RefCell<Vec<Rc<Fn(&mut CommandContext)>>>,
+}
+
+/// Amount of memory used by the objects of a context.
+///
+/// See `Context::memory_usage`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Total size in bytes of the buffers.
+    pub buffers: usize,
+
+    /// Estimated total size in bytes of the textures, including their mipmaps.
+    pub textures: usize,
+
+    /// Estimated total size in bytes of the render buffers.
+    pub render_buffers: usize,
+
+    /// Total amount of video memory in bytes, if known.
+    ///
+    /// Requires `GL_NVX_gpu_memory_info`.
+    pub total_video_memory: Option<usize>,
+
+    /// Amount of video memory in bytes that is still available, if known.
+    ///
+    /// Requires `GL_NVX_gpu_memory_info` or `GL_ATI_meminfo`.
+    pub available_video_memory: Option<usize>,
+}
+
+impl MemoryUsage {
+    /// Returns the total size in bytes of the objects allocated by glium.
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.buffers + self.textures + self.render_buffers
+    }
+}
+
+/// Status of the context regarding graphics resets.
+///
+/// See `Context::reset_status`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResetStatus {
+    /// The context has not been lost.
+    NoError,
+
+    /// The context has been lost because of a command submitted by this context.
+    GuiltyContextReset,
+
+    /// The context has been lost because of a command that was not submitted by this context.
+    InnocentContextReset,
+
+    /// The context has been lost, but the cause is unknown.
+    UnknownContextReset,
+}
+
+/// Describes which error checks glium performs implicitly.
+///
+/// See `Context::set_error_checking_mode`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ErrorCheckingMode {
+    /// If true, the debug output is synchronous so that errors are reported when the faulty
+    /// command is called. Has no effect if no debug callback is installed.
+    ///
+    /// The default value depends on the `DebugCallbackBehavior` passed when creating the context.
+    pub per_call: bool,
+
+    /// If true, the reset status of the context is queried after swapping buffers in order to
+    /// detect context losses. Has no effect if the context can't be lost.
+    ///
+    /// This query can add a synchronization point at frame boundaries on some drivers. The
+    /// default value is `true`.
+    pub on_swap: bool,
+}
+
+/// Tracks the memory allocated by the objects of a context, in bytes.
+#[derive(Debug, Default)]
+pub struct AllocatedMemory {
+    /// Memory used by buffers.
+    pub buffers: Cell<usize>,
+    /// Memory used by textures that are owned by glium.
+    pub textures: Cell<usize>,
+    /// Memory used by render buffers.
+    pub render_buffers: Cell<usize>,
 }
 
 /// This struct is a guard that is returned when you want to access the OpenGL backend.
@@ -122,6 +232,9 @@ pub struct CommandContext<'a> {
     /// The list of framebuffer objects.
     pub framebuffer_objects: &'a fbo::FramebuffersContainer,
 
+    /// Amount of memory allocated by the objects of this context.
+    pub allocated_memory: &'a AllocatedMemory,
+
     /// The list of samplers.
     pub samplers: RefMut<'a, HashMap<uniforms::SamplerBehavior, sampler_object::SamplerObject>>,
 
@@ -194,6 +307,10 @@ impl Context {
             capabilities: capabilities,
             debug_callback: debug_callback,
             report_debug_output_errors: report_debug_output_errors,
+            error_checking_mode: Cell::new(ErrorCheckingMode {
+                per_call: synchronous,
+                on_swap: true,
+            }),
             backend: RefCell::new(Box::new(backend)),
             check_current_context: check_current_context,
             framebuffer_objects: Some(framebuffer_objects),
@@ -201,6 +318,12 @@ impl Context {
             samplers: samplers,
             resident_texture_handles: resident_texture_handles,
             resident_image_handles: resident_image_handles,
+            fullscreen_triangle: RefCell::new(None),
+            allocated_memory: Default::default(),
+            shader_includes: RefCell::new(HashMap::new()),
+            context_lost_callback: RefCell::new(None),
+            shared_contexts: RefCell::new(Vec::new()),
+            pending_purges: RefCell::new(Vec::new()),
         });
 
         if context.debug_callback.is_some() {
@@ -222,6 +345,69 @@ impl Context {
         Ok(context)
     }
 
+    /// Builds a new context whose objects are shared with an existing context.
+    ///
+    /// This is the same as `new`, except that glium keeps track of the contexts that share
+    /// their objects. Buffers, textures and programs created with one of them can be used with
+    /// all the others, and destroying them purges the caches of all the contexts.
+    ///
+    /// The backend's OpenGL context **must** have been created with lists shared with the one
+    /// of `shared_with`.
+    ///
+    /// Modifications made to an object with one context are only guaranteed to be visible by
+    /// the other contexts after they have been executed by the GPU. Use `SyncFence::wait_in`
+    /// to hand the objects over from one context to another.
+    ///
+    /// ## Threads
+    ///
+    /// Glium's objects can't be sent between threads, and all the contexts created with
+    /// `new_shared` must be used from the same thread, for example to draw on several windows.
+    ///
+    /// To upload textures from a worker thread, create a separate context on that thread with
+    /// `Context::new`, using a backend whose OpenGL context shares its lists with the one of
+    /// the main thread. Textures created with it can be detached with `export`, sent to the
+    /// main thread as an `ExportedTexture`, and adopted with `from_exported`. See
+    /// `texture::ExportedTexture`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `check_current_context` is `false`, or if `shared_with` was created with
+    /// `check_current_context` set to `false`. Glium needs to switch between the contexts,
+    /// which isn't possible if it assumes that one of them is always current.
+    pub unsafe fn new_shared<B, E>(backend: B, check_current_context: bool,
+                                   callback_behavior: DebugCallbackBehavior,
+                                   shared_with: &Rc<Context>)
+                                   -> Result<Rc<Context>, GliumCreationError<E>>
+                                   where B: Backend + 'static
+    {
+        assert!(check_current_context && shared_with.check_current_context,
+                "Shared contexts must check the current context");
+
+        let context = try!(Context::new(backend, check_current_context, callback_behavior));
+
+        let mut group: Vec<Rc<Context>> = shared_with.shared_contexts.borrow().iter()
+                                                     .filter_map(|c| c.upgrade()).collect();
+        group.push(shared_with.clone());
+
+        for other in group.iter() {
+            other.shared_contexts.borrow_mut().push(Rc::downgrade(&context));
+            context.shared_contexts.borrow_mut().push(Rc::downgrade(other));
+        }
+
+        Ok(context)
+    }
+
+    /// Returns true if the objects of this context can be used with `other`, in other words if
+    /// `other` is this context or if it has been created with `new_shared`.
+    pub fn is_shared_with(&self, other: &Context) -> bool {
+        if self as *const Context == other as *const Context {
+            return true;
+        }
+
+        self.shared_contexts.borrow().iter().filter_map(|c| c.upgrade())
+                            .any(|c| &*c as *const Context == other as *const Context)
+    }
+
     /// Calls `get_framebuffer_dimensions` on the backend object stored by this context.
     #[inline]
     pub fn get_framebuffer_dimensions(&self) -> (u32, u32) {
@@ -265,6 +451,10 @@ impl Context {
     }
 
     /// Swaps the buffers in the backend.
+    ///
+    /// Glium doesn't call `glGetError` when swapping buffers. If the context can be lost and
+    /// `ErrorCheckingMode::on_swap` is enabled, its reset status is queried after the swap in
+    /// order to detect context losses. See `set_error_checking_mode`.
     pub fn swap_buffers(&self) -> Result<(), SwapBuffersError> {
         if self.state.borrow().lost_context {
             return Err(SwapBuffersError::ContextLost);
@@ -296,19 +486,69 @@ impl Context {
             }
         }
 
-        let backend = self.backend.borrow();
-        if self.check_current_context {
-            if !backend.is_current() {
-                unsafe { backend.make_current() };
+        // swapping
+        let err = {
+            let backend = self.backend.borrow();
+            if self.check_current_context {
+                if !backend.is_current() {
+                    unsafe { backend.make_current() };
+                }
             }
+
+            backend.swap_buffers()
+        };
+
+        match err {
+            Err(SwapBuffersError::ContextLost) => {
+                self.report_context_lost(ResetStatus::UnknownContextReset);
+                err
+            },
+            Ok(()) if self.capabilities().can_lose_context &&
+                      self.error_checking_mode.get().on_swap =>
+            {
+                // some backends don't report context losses when swapping buffers
+                if self.reset_status() != ResetStatus::NoError {
+                    Err(SwapBuffersError::ContextLost)
+                } else {
+                    Ok(())
+                }
+            },
+            err => err,
         }
+    }
 
-        // swapping
-        let err = backend.swap_buffers();
-        if let Err(SwapBuffersError::ContextLost) = err {
-            self.state.borrow_mut().lost_context = true;
+    /// Returns the error checks that glium currently performs implicitly.
+    #[inline]
+    pub fn get_error_checking_mode(&self) -> ErrorCheckingMode {
+        self.error_checking_mode.get()
+    }
+
+    /// Changes the error checks that glium performs implicitly.
+    ///
+    /// The checks done when swapping buffers are configured separately from the per-call
+    /// checks of the debug output, so that you can for example keep the latter in debug mode
+    /// while removing the synchronization point at frame boundaries.
+    pub fn set_error_checking_mode(&self, mode: ErrorCheckingMode) {
+        let previous = self.error_checking_mode.get();
+        self.error_checking_mode.set(mode);
+
+        if previous.per_call == mode.per_call || self.debug_callback.is_none() {
+            return;
+        }
+
+        let mut ctxt = self.make_current();
+
+        if ctxt.version >= &Version(Api::Gl, 4,5) || ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+           ctxt.extensions.gl_khr_debug || ctxt.extensions.gl_arb_debug_output
+        {
+            if mode.per_call && ctxt.state.enabled_debug_output_synchronous != true {
+                unsafe { ctxt.gl.Enable(gl::DEBUG_OUTPUT_SYNCHRONOUS); }
+                ctxt.state.enabled_debug_output_synchronous = true;
+            } else if !mode.per_call && ctxt.state.enabled_debug_output_synchronous != false {
+                unsafe { ctxt.gl.Disable(gl::DEBUG_OUTPUT_SYNCHRONOUS); }
+                ctxt.state.enabled_debug_output_synchronous = false;
+            }
         }
-        err
     }
 
     /// DEPRECATED. Use `get_opengl_version` instead.
@@ -383,7 +623,13 @@ impl Context {
     /// Returns true if out-of-bound buffer access from the GPU side (inside a program) cannot
     /// result in a crash.
     ///
-    /// You should take extra care if `is_robust` returns false.
+    /// You should take extra care if `is_robust` returns false. Glium doesn't request a robust
+    /// context by itself. A robust context can be requested when building the display, by
+    /// calling `with_gl_robustness` on `glutin::WindowBuilder` or on
+    /// `glutin::HeadlessRendererBuilder` before `build_glium`.
+    ///
+    /// Regardless of this value, glium uses `glReadnPixels` and `glGetnUniform` when they are
+    /// available, so that reading from OpenGL can never write outside of glium's buffers.
     #[inline]
     pub fn is_robust(&self) -> bool {
         self.capabilities().robustness
@@ -407,23 +653,80 @@ impl Context {
             return true;
         }
 
-        let mut ctxt = self.make_current();
+        self.reset_status() != ResetStatus::NoError
+    }
 
-        let lost = if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-                      ctxt.version >= &Version(Api::GlEs, 3, 2) ||
-                      ctxt.extensions.gl_khr_robustness
-        {
-            unsafe { ctxt.gl.GetGraphicsResetStatus() != gl::NO_ERROR }
-        } else if ctxt.extensions.gl_ext_robustness {
-            unsafe { ctxt.gl.GetGraphicsResetStatusEXT() != gl::NO_ERROR }
-        } else if ctxt.extensions.gl_arb_robustness {
-            unsafe { ctxt.gl.GetGraphicsResetStatusARB() != gl::NO_ERROR }
-        } else {
-            false
+    /// Queries whether the context has been lost and why.
+    ///
+    /// Once a context loss has been detected, this function never returns `NoError` until the
+    /// context is rebuilt. The callback registered with `set_context_lost_callback` is called
+    /// the first time a loss is detected.
+    ///
+    /// # Implementation
+    ///
+    /// Calls `glGetGraphicsResetStatus`, which requires OpenGL 4.5, OpenGL ES 3.2,
+    /// `GL_KHR_robustness`, `GL_ARB_robustness` or `GL_EXT_robustness`. If this function is not
+    /// available, only context losses reported by the backend when swapping buffers are
+    /// detected.
+    pub fn reset_status(&self) -> ResetStatus {
+        let status = {
+            let mut ctxt = self.make_current();
+
+            let status = if ctxt.version >= &Version(Api::Gl, 4, 5) ||
+                            ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+                            ctxt.extensions.gl_khr_robustness
+            {
+                unsafe { ctxt.gl.GetGraphicsResetStatus() }
+            } else if ctxt.extensions.gl_ext_robustness {
+                unsafe { ctxt.gl.GetGraphicsResetStatusEXT() }
+            } else if ctxt.extensions.gl_arb_robustness {
+                unsafe { ctxt.gl.GetGraphicsResetStatusARB() }
+            } else {
+                gl::NO_ERROR
+            };
+
+            match status {
+                gl::GUILTY_CONTEXT_RESET => ResetStatus::GuiltyContextReset,
+                gl::INNOCENT_CONTEXT_RESET => ResetStatus::InnocentContextReset,
+                gl::UNKNOWN_CONTEXT_RESET => ResetStatus::UnknownContextReset,
+                _ if ctxt.state.lost_context => ResetStatus::UnknownContextReset,
+                _ => ResetStatus::NoError,
+            }
         };
 
-        if lost { ctxt.state.lost_context = true; }
-        lost
+        if status != ResetStatus::NoError {
+            self.report_context_lost(status);
+        }
+
+        status
+    }
+
+    /// Registers a function that is called when a context loss is detected.
+    ///
+    /// Context losses are detected when swapping buffers (unless disabled with
+    /// `set_error_checking_mode`), or when calling `reset_status` or
+    /// `is_context_lost`. Draw commands don't query the reset status themselves, in order to
+    /// avoid a round-trip per draw. After a loss, they return `DrawError::ContextLost` and all
+    /// the objects of the context need to be recreated, which the callback can for example
+    /// schedule. The callback must not call `set_context_lost_callback`.
+    pub fn set_context_lost_callback<F>(&self, callback: F) where F: Fn(ResetStatus) + 'static {
+        *self.context_lost_callback.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Marks the context as lost and calls the callback if this is the first time that the loss
+    /// is detected.
+    fn report_context_lost(&self, status: ResetStatus) {
+        {
+            let mut state = self.state.borrow_mut();
+            if state.lost_context {
+                return;
+            }
+            state.lost_context = true;
+        }
+
+        if let Some(ref callback) = *self.context_lost_callback.borrow() {
+            callback(status);
+        }
     }
 
     /// Returns the behavior when the current OpenGL context is changed.
@@ -442,6 +745,22 @@ impl Context {
         self.capabilities().max_texture_max_anisotropy.map(|v| v as u16)
     }
 
+    /// Returns informations about a format when it is used to create textures.
+    ///
+    /// Returns `None` if the format isn't supported for textures.
+    #[inline]
+    pub fn get_texture_format_infos(&self, format: TextureFormat) -> Option<&FormatInfos> {
+        self.capabilities().internal_formats_textures.get(&format)
+    }
+
+    /// Returns informations about a format when it is used to create renderbuffers.
+    ///
+    /// Returns `None` if the format isn't supported for renderbuffers.
+    #[inline]
+    pub fn get_renderbuffer_format_infos(&self, format: TextureFormat) -> Option<&FormatInfos> {
+        self.capabilities().internal_formats_renderbuffers.get(&format)
+    }
+
     /// Returns the maximum dimensions of the viewport.
     ///
     /// Glium will panic if you request a larger viewport than this when drawing.
@@ -468,6 +787,143 @@ impl Context {
         }
     }
 
+    /// Registers a string that can be included in the source code of shaders with
+    /// `#include "path"`.
+    ///
+    /// The path must be absolute, in other words it must start with `/`. Registering a path
+    /// a second time replaces the previous string.
+    ///
+    /// Includes are only resolved in programs whose `uses_shader_includes` is true. If the
+    /// backend supports `GL_ARB_shading_language_include`, the string is passed to
+    /// `glNamedStringARB` and the shaders must enable the extension with
+    /// `#extension GL_ARB_shading_language_include : require`. Otherwise the `#include`
+    /// directives are replaced by glium before compiling the shaders and this `#extension`
+    /// directive is removed.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the path doesn't start with `/`.
+    ///
+    pub fn add_shader_include(&self, path: &str, source: &str) {
+        assert!(path.starts_with('/'));
+
+        let ctxt = self.make_current();
+
+        if ctxt.extensions.gl_arb_shading_language_include {
+            unsafe {
+                ctxt.gl.NamedStringARB(gl::SHADER_INCLUDE_ARB, path.len() as gl::types::GLint,
+                                       path.as_ptr() as *const _,
+                                       source.len() as gl::types::GLint,
+                                       source.as_ptr() as *const _);
+            }
+        }
+
+        self.shader_includes.borrow_mut().insert(path.to_owned(), source.to_owned());
+    }
+
+    /// Sets the value of a hint for the given target.
+    ///
+    /// Hints allow you to choose between quality and speed for some operations whose
+    /// implementation is up to the driver. Note that this is just a hint and the driver may
+    /// disregard it.
+    ///
+    /// Line and polygon smoothing hints are not handled here, but with the `smooth` member
+    /// of the draw parameters.
+    ///
+    /// Returns `Err` if the backend doesn't support this hint target. You can choose whether
+    /// to call `.unwrap()` if you want to make sure that it works, or `.ok()` if you don't care.
+    pub fn set_hint(&self, target: HintTarget, mode: HintMode) -> Result<(), ()> {
+        let mut ctxt = self.make_current();
+        let mode = mode.to_glenum();
+
+        match target {
+            HintTarget::FragmentShaderDerivative => {
+                if !(ctxt.version >= &Version(Api::Gl, 2, 0)) &&
+                   !(ctxt.version >= &Version(Api::GlEs, 3, 0)) &&
+                   !ctxt.extensions.gl_oes_standard_derivatives
+                {
+                    return Err(());
+                }
+
+                if ctxt.state.fragment_shader_derivative_hint != mode {
+                    unsafe { ctxt.gl.Hint(gl::FRAGMENT_SHADER_DERIVATIVE_HINT, mode) };
+                    ctxt.state.fragment_shader_derivative_hint = mode;
+                }
+            },
+
+            HintTarget::GenerateMipmap => {
+                // removed from the core profile
+                let supported = if ctxt.version >= &Version(Api::GlEs, 2, 0) {
+                    true
+                } else if ctxt.version >= &Version(Api::Gl, 1, 4) {
+                    ctxt.capabilities.profile != Some(Profile::Core) &&
+                    !ctxt.capabilities.forward_compatible
+                } else {
+                    false
+                };
+
+                if !supported {
+                    return Err(());
+                }
+
+                if ctxt.state.generate_mipmap_hint != mode {
+                    unsafe { ctxt.gl.Hint(gl::GENERATE_MIPMAP_HINT, mode) };
+                    ctxt.state.generate_mipmap_hint = mode;
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Sets whether the colors are clamped between `0.0` and `1.0` before being written.
+    ///
+    /// By default the vertex colors are clamped, and the fragment colors are only clamped when
+    /// writing to a framebuffer that doesn't use floating-point values. Floating-point
+    /// framebuffers therefore receive the values written by the fragment shader unchanged.
+    ///
+    /// The clamping of the colors that are read back (`GL_CLAMP_READ_COLOR`) can't be set with
+    /// this function. Glium sets it before each call to `glReadPixels` (when reading from a
+    /// framebuffer or from a texture through a framebuffer) so that the values are returned
+    /// unchanged, which would overwrite any value chosen here. It keeps its default value of
+    /// `GL_FIXED_ONLY` until the first read, and is left untouched before OpenGL 3.0.
+    ///
+    /// Clamping the vertex and fragment colors only exists in the compatibility profile of
+    /// OpenGL 3.0 and above. Returns `Err` if this is not supported by the backend, in other
+    /// words with OpenGL ES, with a core profile or with a forward-compatible context.
+    pub fn set_color_clamp(&self, target: ColorClampTarget, mode: ColorClampMode)
+                           -> Result<(), ()>
+    {
+        let mut ctxt = self.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 3, 0)) ||
+           ctxt.capabilities.profile == Some(Profile::Core) ||
+           ctxt.capabilities.forward_compatible
+        {
+            return Err(());
+        }
+
+        let mode = mode.to_glenum();
+
+        match target {
+            ColorClampTarget::Vertex => {
+                if ctxt.state.clamp_vertex_color != mode {
+                    unsafe { ctxt.gl.ClampColor(gl::CLAMP_VERTEX_COLOR, mode) };
+                    ctxt.state.clamp_vertex_color = mode;
+                }
+            },
+
+            ColorClampTarget::Fragment => {
+                if ctxt.state.clamp_fragment_color != mode {
+                    unsafe { ctxt.gl.ClampColor(gl::CLAMP_FRAGMENT_COLOR, mode) };
+                    ctxt.state.clamp_fragment_color = mode;
+                }
+            },
+        }
+
+        Ok(())
+    }
+
     /// Returns an estimate of the amount of video memory available in bytes.
     ///
     /// Returns `None` if no estimate is available.
@@ -492,6 +948,44 @@ impl Context {
         }
     }
 
+    /// Returns the amount of memory that glium has allocated for the objects of this context,
+    /// and the amount of video memory of the graphics card if it is known.
+    ///
+    /// The sizes of the textures and render buffers are estimated from their dimensions and
+    /// from their format as reported by the backend. The actual usage of the driver can be
+    /// higher because of padding, alignment or extra storage.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let (total, available) = unsafe {
+            let ctxt = self.make_current();
+
+            let mut total = mem::uninitialized();
+            let mut available = mem::uninitialized();
+
+            if ctxt.extensions.gl_nvx_gpu_memory_info {
+                ctxt.gl.GetIntegerv(gl::GPU_MEMORY_INFO_TOTAL_AVAILABLE_MEMORY_NVX, &mut total);
+                ctxt.gl.GetIntegerv(gl::GPU_MEMORY_INFO_CURRENT_AVAILABLE_VIDMEM_NVX,
+                                    &mut available);
+                (Some(total as usize * 1024), Some(available as usize * 1024))
+
+            } else if ctxt.extensions.gl_ati_meminfo {
+                let mut value: [gl::types::GLint; 4] = mem::uninitialized();
+                ctxt.gl.GetIntegerv(gl::TEXTURE_FREE_MEMORY_ATI, &mut value[0]);
+                (None, Some(value[0] as usize * 1024))
+
+            } else {
+                (None, None)
+            }
+        };
+
+        MemoryUsage {
+            buffers: self.allocated_memory.buffers.get(),
+            textures: self.allocated_memory.textures.get(),
+            render_buffers: self.allocated_memory.render_buffers.get(),
+            total_video_memory: total,
+            available_video_memory: available,
+        }
+    }
+
     /// Reads the content of the front buffer.
     ///
     /// You will only see the data that has finished being drawn.
@@ -517,7 +1011,7 @@ impl Context {
 
         let mut data = Vec::with_capacity(0);
         ops::read(&mut ctxt, ops::Source::DefaultFramebuffer(gl::FRONT_LEFT), &rect, &mut data,
-                  false);
+                  None, false);
         T::from_raw(Cow::Owned(data), dimensions.0, dimensions.1)
     }
 
@@ -620,6 +1114,89 @@ impl Context {
             Ok(())
         }
     }
+
+    /// Opens a named debug group in the commands queue. The group is closed when the returned
+    /// `DebugGroup` is destroyed.
+    ///
+    /// OpenGL debuggers show the commands that are executed while the group is alive as nested
+    /// under its name. Groups can be nested.
+    ///
+    /// Returns `Err` if the backend doesn't support this functionnality.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+    /// {
+    ///     let _group = display.push_debug_group("shadow pass").ok();
+    ///     // draw the shadows here
+    /// }
+    /// ```
+    pub fn push_debug_group(&self, name: &str) -> Result<DebugGroup, ()> {
+        let ctxt = self.make_current();
+        let name = name.as_bytes();
+
+        unsafe {
+            if ctxt.version >= &Version(Api::Gl, 4, 3) ||
+               ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+               (ctxt.version >= &Version(Api::Gl, 1, 0) && ctxt.extensions.gl_khr_debug)
+            {
+                ctxt.gl.PushDebugGroup(gl::DEBUG_SOURCE_APPLICATION, 0,
+                                       name.len() as gl::types::GLsizei,
+                                       name.as_ptr() as *const _);
+
+            } else if ctxt.version >= &Version(Api::GlEs, 2, 0) &&
+                      ctxt.extensions.gl_khr_debug
+            {
+                ctxt.gl.PushDebugGroupKHR(gl::DEBUG_SOURCE_APPLICATION, 0,
+                                          name.len() as gl::types::GLsizei,
+                                          name.as_ptr() as *const _);
+
+            } else if ctxt.extensions.gl_ext_debug_marker {
+                ctxt.gl.PushGroupMarkerEXT(name.len() as gl::types::GLsizei,
+                                           name.as_ptr() as *const _);
+
+            } else {
+                return Err(());
+            }
+        }
+
+        Ok(DebugGroup {
+            context: self,
+        })
+    }
+}
+
+/// A debug group opened with `Context::push_debug_group`. The group is closed when this object
+/// is destroyed.
+pub struct DebugGroup<'a> {
+    context: &'a Context,
+}
+
+impl<'a> Drop for DebugGroup<'a> {
+    fn drop(&mut self) {
+        let ctxt = self.context.make_current();
+
+        unsafe {
+            if ctxt.version >= &Version(Api::Gl, 4, 3) ||
+               ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+               (ctxt.version >= &Version(Api::Gl, 1, 0) && ctxt.extensions.gl_khr_debug)
+            {
+                ctxt.gl.PopDebugGroup();
+
+            } else if ctxt.version >= &Version(Api::GlEs, 2, 0) &&
+                      ctxt.extensions.gl_khr_debug
+            {
+                ctxt.gl.PopDebugGroupKHR();
+
+            } else if ctxt.extensions.gl_ext_debug_marker {
+                ctxt.gl.PopGroupMarkerEXT();
+
+            } else {
+                unreachable!();
+            }
+        }
+    }
 }
 
 impl ContextExt for Context {
@@ -637,7 +1214,7 @@ impl ContextExt for Context {
             }
         }
 
-        CommandContext {
+        let mut ctxt = CommandContext {
             gl: &self.gl,
             state: self.state.borrow_mut(),
             version: &self.version,
@@ -646,17 +1223,53 @@ impl ContextExt for Context {
             report_debug_output_errors: &self.report_debug_output_errors,
             vertex_array_objects: &self.vertex_array_objects,
             framebuffer_objects: self.framebuffer_objects.as_ref().unwrap(),
+            allocated_memory: &self.allocated_memory,
             samplers: self.samplers.borrow_mut(),
             resident_texture_handles: self.resident_texture_handles.borrow_mut(),
             resident_image_handles: self.resident_image_handles.borrow_mut(),
             marker: PhantomData,
+        };
+
+        // purging the objects that have been destroyed by the contexts that share with this one
+        if !self.pending_purges.borrow().is_empty() {
+            let purges = mem::replace(&mut *self.pending_purges.borrow_mut(), Vec::new());
+            for purge in purges.iter() {
+                purge(&mut ctxt);
+            }
         }
+
+        ctxt
     }
 
     #[inline]
     fn capabilities(&self) -> &Capabilities {
         &self.capabilities
     }
+
+    fn purge_shared_contexts<F>(&self, f: F) where F: Fn(&mut CommandContext) + 'static {
+        let contexts = self.shared_contexts.borrow();
+        if contexts.is_empty() {
+            return;
+        }
+
+        // the purges are deferred so that destroying an object doesn't switch between contexts
+        let f = Rc::new(f) as Rc<Fn(&mut CommandContext)>;
+        for context in contexts.iter().filter_map(|c| c.upgrade()) {
+            context.pending_purges.borrow_mut().push(f.clone());
+        }
+    }
+
+    #[inline]
+    fn fullscreen_triangle(&self)
+                           -> &RefCell<Option<Weak<VertexBuffer<index::FullscreenVertex>>>>
+    {
+        &self.fullscreen_triangle
+    }
+
+    #[inline]
+    fn shader_includes(&self) -> &RefCell<HashMap<String, String>> {
+        &self.shader_includes
+    }
 }
 
 impl CapabilitiesSource for Context {
@@ -697,6 +1310,7 @@ impl Drop for Context {
                 report_debug_output_errors: &self.report_debug_output_errors,
                 vertex_array_objects: &self.vertex_array_objects,
                 framebuffer_objects: self.framebuffer_objects.as_ref().unwrap(),
+                allocated_memory: &self.allocated_memory,
                 samplers: self.samplers.borrow_mut(),
                 resident_texture_handles: self.resident_texture_handles.borrow_mut(),
                 resident_image_handles: self.resident_image_handles.borrow_mut(),
@@ -783,6 +1397,76 @@ fn check_gl_compatibility<T>(version: &Version, extensions: &ExtensionsList)
     }
 }
 
+/// Target of a call to `set_hint`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HintTarget {
+    /// Accuracy of the derivative built-in functions (`dFdx`, `dFdy` and `fwidth`) in
+    /// fragment shaders.
+    FragmentShaderDerivative,
+
+    /// Quality of the mipmaps generated by the implementation.
+    GenerateMipmap,
+}
+
+/// Behavior requested with `set_hint`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HintMode {
+    /// The most efficient option should be chosen.
+    Fastest,
+
+    /// The most correct, or highest quality, option should be chosen.
+    Nicest,
+
+    /// No preference. This is the default value.
+    DontCare,
+}
+
+impl ToGlEnum for HintMode {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            HintMode::Fastest => gl::FASTEST,
+            HintMode::Nicest => gl::NICEST,
+            HintMode::DontCare => gl::DONT_CARE,
+        }
+    }
+}
+
+/// Target of a call to `set_color_clamp`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ColorClampTarget {
+    /// The colors written by the vertex shader to `gl_FrontColor` and similar outputs.
+    Vertex,
+
+    /// The colors written by the fragment shader.
+    Fragment,
+}
+
+/// Behavior requested with `set_color_clamp`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ColorClampMode {
+    /// The colors are always clamped.
+    Clamp,
+
+    /// The colors are never clamped.
+    NoClamp,
+
+    /// The colors are only clamped when written to a framebuffer that doesn't use
+    /// floating-point values.
+    FixedOnly,
+}
+
+impl ToGlEnum for ColorClampMode {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            ColorClampMode::Clamp => gl::TRUE as gl::types::GLenum,
+            ColorClampMode::NoClamp => gl::FALSE as gl::types::GLenum,
+            ColorClampMode::FixedOnly => gl::FIXED_ONLY,
+        }
+    }
+}
+
 /// Describes the behavior that the debug output should have.
 pub enum DebugCallbackBehavior {
     /// Don't do anything. This is the default behavior in release.
@@ -790,6 +1474,10 @@ pub enum DebugCallbackBehavior {
 
     /// Print a message on stdout on error, except in some circumstances like when compiling
     /// shaders. This is the default behavior in debug mode.
+    ///
+    /// The debug output is synchronous in this mode, so that the message is printed when the
+    /// faulty command is called. This can slow down the driver. Use `Ignore`, `Custom` with
+    /// `synchronous: false`, or `Context::set_error_checking_mode` when measuring performance.
     DebugMessageOnError,
 
     /// Print every single output received by the driver.
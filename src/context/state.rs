@@ -13,6 +13,10 @@ pub struct GlState {
     pub lost_context: bool,
 
     /// Whether GL_BLEND is enabled
+    ///
+    /// Glium never calls the indexed `glEnablei`, `glBlendFunci` or `glColorMaski` functions.
+    /// The non-indexed versions modify the state of all the draw buffers at once, so this
+    /// field and the other blending and color mask fields are valid for all of them.
     pub enabled_blend: bool,
 
     /// Whether GL_CULL_FACE is enabled
@@ -51,12 +55,18 @@ pub struct GlState {
     /// Whether GL_SAMPLE_ALPHA_TO_COVERAGE is enabled
     pub enabled_sample_alpha_to_coverage: bool,
 
+    /// Whether GL_SAMPLE_ALPHA_TO_ONE is enabled
+    pub enabled_sample_alpha_to_one: bool,
+
     /// Whether GL_SAMPLE_COVERAGE is enabled
     pub enabled_sample_coverage: bool,
 
     /// Whether GL_SCISSOR_TEST is enabled
     pub enabled_scissor_test: bool,
 
+    /// Whether GL_TEXTURE_CUBE_MAP_SEAMLESS is enabled
+    pub enabled_texture_cube_map_seamless: bool,
+
     /// Whether GL_STENCIL_TEST is enabled
     pub enabled_stencil_test: bool,
 
@@ -69,6 +79,9 @@ pub struct GlState {
     /// Whether GL_PROGRAM_POINT_SIZE is enabled
     pub enabled_program_point_size: bool,
 
+    /// Bitmask of the GL_CLIP_DISTANCEi that are enabled
+    pub enabled_clip_planes: u32,
+
     /// The latest value passed to `glUseProgram`.
     pub program: Handle,
 
@@ -89,6 +102,9 @@ pub struct GlState {
     pub color_mask: (gl::types::GLboolean, gl::types::GLboolean,
                      gl::types::GLboolean, gl::types::GLboolean),
 
+    /// The latest values passed to `glSampleCoverage`.
+    pub sample_coverage: (f32, bool),
+
     /// The latest buffer bound to `GL_ARRAY_BUFFER`.
     pub array_buffer_binding: gl::types::GLuint,
 
@@ -192,10 +208,22 @@ pub struct GlState {
     pub viewport: Option<(gl::types::GLint, gl::types::GLint,
                           gl::types::GLsizei, gl::types::GLsizei)>,
 
+    /// The latest values passed to `glViewportIndexedf` for the viewport 0. `None` means
+    /// unknown or that `glViewport` has been used instead.
+    pub viewport_indexed_f: Option<[gl::types::GLfloat; 4]>,
+
+    /// The latest values passed to `glViewportArrayv`, starting at the viewport 0. Empty means
+    /// unknown or that `glViewport` or `glViewportIndexedf` has been used instead.
+    pub viewport_array: Vec<[gl::types::GLfloat; 4]>,
+
     /// The latest values passed to `glScissor`. `None` means unknown.
     pub scissor: Option<(gl::types::GLint, gl::types::GLint,
                          gl::types::GLsizei, gl::types::GLsizei)>,
 
+    /// The latest values passed to `glScissorArrayv`, starting at the viewport 0. Empty means
+    /// unknown or that `glScissor` has been used instead.
+    pub scissor_array: Vec<[gl::types::GLint; 4]>,
+
     /// The latest value passed to `glLineWidth`.
     pub line_width: gl::types::GLfloat,
 
@@ -211,21 +239,51 @@ pub struct GlState {
     /// The latest value passed to `glHint` for smoothing.
     pub smooth: (gl::types::GLenum, gl::types::GLenum),
 
+    /// The latest value passed to `glHint` with `GL_FRAGMENT_SHADER_DERIVATIVE_HINT`.
+    pub fragment_shader_derivative_hint: gl::types::GLenum,
+
+    /// The latest value passed to `glHint` with `GL_GENERATE_MIPMAP_HINT`.
+    pub generate_mipmap_hint: gl::types::GLenum,
+
     /// The latest value passed to `glProvokingVertex`.
     pub provoking_vertex: gl::types::GLenum,
 
+    /// The latest values passed to `glClipControl`, in the form `(origin, depth)`.
+    pub clip_control: (gl::types::GLenum, gl::types::GLenum),
+
     /// The latest value passed to `glPixelStore` with `GL_UNPACK_ALIGNMENT`.
     pub pixel_store_unpack_alignment: gl::types::GLint,
 
     /// The latest value passed to `glPixelStore` with `GL_PACK_ALIGNMENT`.
     pub pixel_store_pack_alignment: gl::types::GLint,
 
+    /// The latest value passed to `glPixelStore` with `GL_UNPACK_ROW_LENGTH`.
+    pub pixel_store_unpack_row_length: gl::types::GLint,
+
+    /// The latest value passed to `glPixelStore` with `GL_UNPACK_IMAGE_HEIGHT`.
+    pub pixel_store_unpack_image_height: gl::types::GLint,
+
+    /// The latest value passed to `glPixelStore` with `GL_PACK_ROW_LENGTH`.
+    pub pixel_store_pack_row_length: gl::types::GLint,
+
     /// The latest value passed to `glClampColor`.
     pub clamp_color: gl::types::GLenum,
 
+    /// The latest value passed to `glClampColor` with `GL_CLAMP_VERTEX_COLOR`.
+    pub clamp_vertex_color: gl::types::GLenum,
+
+    /// The latest value passed to `glClampColor` with `GL_CLAMP_FRAGMENT_COLOR`.
+    pub clamp_fragment_color: gl::types::GLenum,
+
     /// The latest value passed to `glPatchParameter` with `GL_PATCH_VERTICES`.
     pub patch_patch_vertices: gl::types::GLint,
 
+    /// The latest value passed to `glPatchParameter` with `GL_PATCH_DEFAULT_OUTER_LEVEL`.
+    pub patch_default_outer_level: [gl::types::GLfloat; 4],
+
+    /// The latest value passed to `glPatchParameter` with `GL_PATCH_DEFAULT_INNER_LEVEL`.
+    pub patch_default_inner_level: [gl::types::GLfloat; 2],
+
     /// The id of the active texture unit.
     /// IMPORTANT: this is a raw number (0, 1, 2, ...), not an
     ///            enumeration (GL_TEXTURE0, GL_TEXTURE1, ...).
@@ -249,6 +307,11 @@ pub struct GlState {
     /// Current query being used for GL_TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN​.
     pub transform_feedback_primitives_written_query: gl::types::GLuint,
 
+    /// Current query being used for GL_TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN with
+    /// `glBeginQueryIndexed`, for each vertex stream. The first element corresponds to the
+    /// stream 1, as the stream 0 uses `transform_feedback_primitives_written_query`.
+    pub indexed_transform_feedback_primitives_written_queries: SmallVec<[gl::types::GLuint; 4]>,
+
     /// Current query being used for GL_TIME_ELAPSED​.
     pub time_elapsed_query: gl::types::GLuint,
 
@@ -263,6 +326,9 @@ pub struct GlState {
     // TODO: move this inside transform feedback objects
     pub transform_feedback_paused: bool,
 
+    /// The latest value passed to `glBindTransformFeedback`.
+    pub transform_feedback_object: gl::types::GLuint,
+
     /// The latest value passed to `glPrimitiveBoundingBox`.
     pub primitive_bounding_box: (f32, f32, f32, f32, f32, f32, f32, f32),
 
@@ -325,6 +391,10 @@ pub struct GlState {
     /// The draw call ID of the latest call to `glMemoryBarrier` with
     /// `GL_QUERY_BUFFER_BARRIER_BIT`.
     pub latest_memory_barrier_query_buffer: u64,
+
+    /// The draw call ID of the latest call to `glMemoryBarrier` with
+    /// `GL_CLIENT_MAPPED_BUFFER_BARRIER_BIT`.
+    pub latest_memory_barrier_client_mapped_buffer: u64,
 }
 
 /// State of a texture unit (the one designated by `glActiveTexture`).
@@ -375,12 +445,15 @@ impl Default for GlState {
             enabled_polygon_offset_fill: false,
             enabled_rasterizer_discard: false,
             enabled_sample_alpha_to_coverage: false,
+            enabled_sample_alpha_to_one: false,
             enabled_sample_coverage: false,
             enabled_scissor_test: false,
+            enabled_texture_cube_map_seamless: false,
             enabled_stencil_test: false,
             enabled_line_smooth: false,
             enabled_polygon_smooth: false,
             enabled_program_point_size: false,
+            enabled_clip_planes: 0,
 
             program: Handle::Id(0),
             vertex_array: 0,
@@ -388,6 +461,7 @@ impl Default for GlState {
             clear_depth: 1.0,
             clear_stencil: 0,
             color_mask: (1, 1, 1, 1),
+            sample_coverage: (1.0, false),
             array_buffer_binding: 0,
             pixel_pack_buffer_binding: 0,
             pixel_unpack_buffer_binding: 0,
@@ -421,17 +495,30 @@ impl Default for GlState {
             blend_func: (gl::ONE, gl::ZERO, gl::ONE, gl::ZERO),
             blend_color: (0.0, 0.0, 0.0, 0.0),
             viewport: None,
+            viewport_indexed_f: None,
+            viewport_array: Vec::new(),
             scissor: None,
+            scissor_array: Vec::new(),
             line_width: 1.0,
             point_size: 1.0,
             cull_face: gl::BACK,
             polygon_mode: gl::FILL,
             smooth: (gl::DONT_CARE, gl::DONT_CARE),
+            fragment_shader_derivative_hint: gl::DONT_CARE,
+            generate_mipmap_hint: gl::DONT_CARE,
             provoking_vertex: gl::LAST_VERTEX_CONVENTION,
+            clip_control: (gl::LOWER_LEFT, gl::NEGATIVE_ONE_TO_ONE),
             pixel_store_unpack_alignment: 4,
             pixel_store_pack_alignment: 4,
+            pixel_store_unpack_row_length: 0,
+            pixel_store_unpack_image_height: 0,
+            pixel_store_pack_row_length: 0,
             clamp_color: gl::FIXED_ONLY,
+            clamp_vertex_color: gl::TRUE as gl::types::GLenum,
+            clamp_fragment_color: gl::FIXED_ONLY,
             patch_patch_vertices: 3,
+            patch_default_outer_level: [1.0, 1.0, 1.0, 1.0],
+            patch_default_inner_level: [1.0, 1.0],
             active_texture: 0,
             texture_units: small_vec_one(),
             samples_passed_query: 0,
@@ -439,10 +526,12 @@ impl Default for GlState {
             any_samples_passed_conservative_query: 0,
             primitives_generated_query: 0,
             transform_feedback_primitives_written_query: 0,
+            indexed_transform_feedback_primitives_written_queries: SmallVec::new(),
             time_elapsed_query: 0,
             conditional_render: None,
             transform_feedback_enabled: None,
             transform_feedback_paused: false,
+            transform_feedback_object: 0,
             primitive_bounding_box: (-1.0, -1.0, -1.0, -1.0, 1.0, 1.0, 1.0, 1.0),
 
             next_draw_call_id: 1,
@@ -460,6 +549,7 @@ impl Default for GlState {
             latest_memory_barrier_atomic_counter: 1,
             latest_memory_barrier_shader_storage: 1,
             latest_memory_barrier_query_buffer: 1,
+            latest_memory_barrier_client_mapped_buffer: 1,
         }
     }
 }
@@ -13,6 +13,7 @@ use std::rc::Rc;
 use std::mem;
 
 pub use context::DebugCallbackBehavior;
+pub use context::DebugGroup;
 
 /// Represents a callback that can be used for the debug output feature of OpenGL.
 ///
@@ -20,6 +20,9 @@ pub struct Blend {
     /// The blending function for alpha channels.
     pub alpha: BlendingFunction,
     /// A constant color that can be used in the blending functions.
+    ///
+    /// This value is only used if one of the factors is `ConstantColor`, `ConstantAlpha` or
+    /// one of their `OneMinus` equivalents.
     pub constant_value: (f32, f32, f32, f32),
 }
 
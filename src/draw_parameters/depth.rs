@@ -37,6 +37,21 @@ pub struct Depth {
     /// The first value of the tuple must be the "near" value, where `-1.0` will be mapped.
     /// The second value must be the "far" value, where `1.0` will be mapped.
     /// It is possible for the "near" value to be greater than the "far" value.
+    ///
+    /// # Reversed depth
+    ///
+    /// Passing `(1.0, 0.0)` here maps distant objects near `0.0` instead of `1.0`. When doing
+    /// so, the depth buffer must be cleared with `0.0` instead of `1.0` and the depth test must
+    /// be reversed as well, for example with `IfMore` instead of `IfLess`. Combined with
+    /// `DepthClamp::Clamp`, this allows using a projection matrix with an infinite far plane.
+    ///
+    /// Floating-point depth buffers have much more precision near `0.0` than near `1.0`, but
+    /// reversing the range alone doesn't improve the precision. With the default
+    /// `DepthClipMode::NegativeOneToOne`, the Z coordinate is remapped from `[-1.0, 1.0]` with
+    /// `z * 0.5 + 0.5`, which loses the precision of the values near `0.0` before they reach the
+    /// depth buffer. To get the benefit, also set `depth_clip_mode` to
+    /// `DepthClipMode::ZeroToOne` in the draw parameters and use a projection matrix that
+    /// outputs Z coordinates between `0.0` and `1.0`.
     pub range: (f32, f32),
 
     /// Sets whether the depth values of samples should be clamped to `0.0` and `1.0`.
@@ -149,7 +164,8 @@ pub enum DepthClamp {
 
     /// Clamp the depth values. All samples will always be drawn.
     ///
-    /// This value is only supported on OpenGL.
+    /// This value is supported on OpenGL 3.2 and above, and on OpenGL ES with the
+    /// `GL_EXT_depth_clamp` extension.
     Clamp,
 
     /// Depth values inferior to `0.0` will be clamped to `0.0`.
@@ -163,6 +179,13 @@ pub enum DepthClamp {
     ClampFar,
 }
 
+/// Returns true if `GL_DEPTH_CLAMP` can be enabled.
+#[inline]
+fn is_depth_clamp_supported(ctxt: &CommandContext) -> bool {
+    ctxt.version >= &Version(Api::Gl, 3, 2) || ctxt.extensions.gl_arb_depth_clamp ||
+    ctxt.extensions.gl_nv_depth_clamp || ctxt.extensions.gl_ext_depth_clamp
+}
+
 pub fn sync_depth(ctxt: &mut CommandContext, depth: &Depth) -> Result<(), DrawError> {
     // depth clamp
     {
@@ -174,9 +197,7 @@ pub fn sync_depth(ctxt: &mut CommandContext, depth: &Depth) -> Result<(), DrawEr
             (DepthClamp::Clamp, &mut true, &mut true) => (),
 
             (DepthClamp::NoClamp, near, far) => {
-                if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_depth_clamp ||
-                   ctxt.extensions.gl_nv_depth_clamp
-                {
+                if is_depth_clamp_supported(ctxt) {
                     unsafe { ctxt.gl.Disable(gl::DEPTH_CLAMP) };
                     *near = false;
                     *far = false;
@@ -186,9 +207,7 @@ pub fn sync_depth(ctxt: &mut CommandContext, depth: &Depth) -> Result<(), DrawEr
             },
 
             (DepthClamp::Clamp, near, far) => {
-                if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_depth_clamp ||
-                   ctxt.extensions.gl_nv_depth_clamp
-                {
+                if is_depth_clamp_supported(ctxt) {
                     unsafe { ctxt.gl.Enable(gl::DEPTH_CLAMP) };
                     *near = true;
                     *far = true;
@@ -244,16 +263,17 @@ pub fn sync_depth(ctxt: &mut CommandContext, depth: &Depth) -> Result<(), DrawEr
     }
 
     // depth range
-    if depth.range.0 < 0.0 || depth.range.0 > 1.0 ||
-       depth.range.1 < 0.0 || depth.range.1 > 1.0
-    {
-        return Err(DrawError::InvalidDepthRange);
-    }
-
     if depth.range != ctxt.state.depth_range {
         // TODO: WebGL requires depth.range.1 > depth.range.0
         unsafe {
-            ctxt.gl.DepthRange(depth.range.0 as f64, depth.range.1 as f64);
+            if ctxt.version >= &Version(Api::Gl, 4, 1) ||
+               ctxt.version >= &Version(Api::GlEs, 2, 0) ||
+               ctxt.extensions.gl_arb_es2_compatibility
+            {
+                ctxt.gl.DepthRangef(depth.range.0, depth.range.1);
+            } else {
+                ctxt.gl.DepthRange(depth.range.0 as f64, depth.range.1 as f64);
+            }
         }
         ctxt.state.depth_range = depth.range;
     }
@@ -51,7 +51,18 @@
 //! ```
 //!
 //! If you use conditional rendering, glium will submit the draw command but the GPU will execute
-//! it only if the query contains a value different from 0.
+//! it only if the query contains a value different from 0. This allows for example to draw the
+//! bounding box of an object with an `AnySamplesPassedQuery` and with the `color_mask` set to
+//! `false`, then to draw the object itself only if its bounding box was visible, without
+//! waiting for the result on the CPU side.
+//!
+//! There is no function that wraps several draws between `glBeginConditionalRender` and
+//! `glEndConditionalRender`, because glium applies the state of the `DrawParameters` for each
+//! draw command. Instead, pass the same `condition` to each draw that depends on the query.
+//! Consecutive draws with the same condition don't call `glBeginConditionalRender` again. The
+//! `wait` and `per_region` fields select the mode, as documented in `ConditionalRendering`.
+//!
+//! A query that has never been used contains 0, and drawing with it as a condition does nothing.
 //!
 //! ## WrongQueryOperation errors
 //!
@@ -63,7 +74,6 @@
 //!
 use gl;
 use context;
-use context::Context;
 use version::Version;
 use version::Api;
 
@@ -80,6 +90,8 @@ use std::ops::Range;
 
 pub use self::blend::{Blend, BlendingFunction, LinearBlendingFactor};
 pub use self::depth::{Depth, DepthTest, DepthClamp};
+pub use self::multisample::MultisampleState;
+pub use self::pipeline::Pipeline;
 pub use self::query::{QueryCreationError};
 pub use self::query::{SamplesPassedQuery, TimeElapsedQuery, PrimitivesGeneratedQuery};
 pub use self::query::{AnySamplesPassedQuery, TransformFeedbackPrimitivesWrittenQuery};
@@ -87,6 +99,8 @@ pub use self::stencil::{StencilTest, StencilOperation, Stencil};
 
 mod blend;
 mod depth;
+mod multisample;
+mod pipeline;
 mod query;
 mod stencil;
 
@@ -227,6 +241,58 @@ pub enum ProvokingVertex {
     FirstVertex,
 }
 
+/// The origin of the window coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SurfaceOrigin {
+    /// The point `(-1.0, -1.0)` in normalized device coordinates corresponds to the lower-left
+    /// corner of the surface. This is the default in OpenGL.
+    LowerLeft,
+
+    /// The point `(-1.0, -1.0)` in normalized device coordinates corresponds to the upper-left
+    /// corner of the surface, like in Direct3D.
+    ///
+    /// Note that this also inverts the winding order of the triangles on the screen, which
+    /// matters for backface culling.
+    UpperLeft,
+}
+
+impl ToGlEnum for SurfaceOrigin {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            SurfaceOrigin::LowerLeft => gl::LOWER_LEFT,
+            SurfaceOrigin::UpperLeft => gl::UPPER_LEFT,
+        }
+    }
+}
+
+/// The range of the Z coordinate in normalized device coordinates that is mapped to the
+/// depth range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepthClipMode {
+    /// Z values between `-1.0` and `1.0` are mapped to the depth range. This is the default in
+    /// OpenGL.
+    NegativeOneToOne,
+
+    /// Z values between `0.0` and `1.0` are mapped to the depth range, like in Direct3D and
+    /// Vulkan.
+    ///
+    /// Combined with a reversed depth test, a floating-point depth buffer and a projection
+    /// matrix that maps the near plane to `1.0` and the far plane to `0.0`, this gives a much
+    /// better precision of the depth buffer. See the documentation of `Depth::range`.
+    ZeroToOne,
+}
+
+impl ToGlEnum for DepthClipMode {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            DepthClipMode::NegativeOneToOne => gl::NEGATIVE_ONE_TO_ONE,
+            DepthClipMode::ZeroToOne => gl::ZERO_TO_ONE,
+        }
+    }
+}
+
 /// Represents the parameters to use when drawing.
 ///
 /// Example:
@@ -267,13 +333,31 @@ pub struct DrawParameters<'a> {
     /// Width in pixels of the lines to draw when drawing lines.
     ///
     /// `None` means "don't care". Use this when you don't draw lines.
+    ///
+    /// The supported widths are given by the `aliased_line_width_range` and
+    /// `smooth_line_width_range` capabilities. Many drivers only support a width of `1.0`, and
+    /// forward-compatible contexts don't support wide lines at all. Drawing returns
+    /// `LineWidthNotSupported` if the width is not supported.
     pub line_width: Option<f32>,
 
     /// Diameter in pixels of the points to draw when drawing points.
     ///
     /// `None` means "don't care". Use this when you don't draw points.
+    ///
+    /// This value is ignored if the program was created with `uses_point_size` set to `true`,
+    /// in which case the size is the value written to `gl_PointSize` by the shader.
     pub point_size: Option<f32>,
 
+    /// Bitmask of the clip planes to enable. The default value is `0`.
+    ///
+    /// If the bit `i` is set, then `GL_CLIP_DISTANCEi` is enabled and the value written to
+    /// `gl_ClipDistance[i]` by the shader is used to clip the primitives. Otherwise the
+    /// value written to `gl_ClipDistance[i]` is ignored.
+    ///
+    /// Drawing returns `ClipPlaneIndexOutOfBounds` if one of the bits is above the
+    /// `max_clip_distances` capability.
+    pub clip_planes_bitmask: u32,
+
     /// Whether or not the GPU should filter out some faces.
     ///
     /// After the vertex shader stage, the GPU will try to remove the faces that aren't facing
@@ -287,12 +371,20 @@ pub struct DrawParameters<'a> {
     /// See the documentation of `PolygonMode` for more infos.
     pub polygon_mode: PolygonMode,
 
-    /// Whether multisample antialiasing (MSAA) should be used. Default value is `true`.
+    /// Multisampling parameters: whether multisample antialiasing is used and how the coverage
+    /// of the fragments is computed.
+    ///
+    /// See the documentation of `MultisampleState` for more infos.
+    pub multisampling: MultisampleState,
+
+    /// If `true`, sampling a cubemap near the edge of a face blends the texels of the
+    /// neighbouring faces, which removes the visible seams between the faces. The default value
+    /// is `false`.
     ///
-    /// Note that you will need to set the appropriate option when creating the window.
-    /// The recommended way to do is to leave this to `true`, and adjust the option when
-    /// creating the window.
-    pub multisampling: bool,
+    /// Requires OpenGL 3.2 or `GL_ARB_seamless_cube_map`. Drawing returns
+    /// `SeamlessCubemapsNotSupported` if this is `true` and the backend doesn't support it.
+    /// Cubemap filtering is always seamless on OpenGL ES 3.0 and above.
+    pub seamless_cubemaps: bool,
 
     /// Whether dithering is activated. Default value is `true`.
     ///
@@ -310,12 +402,46 @@ pub struct DrawParameters<'a> {
     /// `None` means "use the whole surface".
     pub viewport: Option<Rect>,
 
+    /// Offset in pixels, which can be a fraction of a pixel, to add to the origin of the
+    /// viewport. The default value is `(0.0, 0.0)`.
+    ///
+    /// This is useful to jitter the image by a fraction of a pixel between frames, for example
+    /// for temporal anti-aliasing, without modifying the projection matrix.
+    ///
+    /// Sub-pixel precision requires OpenGL 4.1 or `GL_ARB_viewport_array`. If it is not
+    /// supported, the offset is rounded to the nearest pixel.
+    pub viewport_subpixel_offset: (f32, f32),
+
+    /// A list of viewports, that replaces `viewport` if it is not empty. The default value is
+    /// an empty list.
+    ///
+    /// The geometry shader chooses the viewport of each primitive by writing to
+    /// `gl_ViewportIndex`. This allows rendering several views in a single draw call, for
+    /// example the two eyes of a stereo image. Primitives use the first viewport if the
+    /// shader doesn't write to `gl_ViewportIndex`.
+    ///
+    /// Requires OpenGL 4.1 or `GL_ARB_viewport_array`, and a program with a geometry shader.
+    /// The number of viewports must not exceed `max_viewports` in the capabilities.
+    /// `viewport_subpixel_offset` is ignored when this list is used.
+    pub viewports: Vec<Rect>,
+
     /// If specified, only pixels in this rect will be displayed. Default is `None`.
     ///
     /// This is different from a viewport. The image will stretch to fill the viewport, but
     /// not the scissor box.
     pub scissor: Option<Rect>,
 
+    /// A list of scissor boxes, one for each element of `viewports`, that replaces `scissor` if
+    /// it is not empty. The default value is an empty list.
+    ///
+    /// The scissor box of each index only applies to the primitives that are drawn in the
+    /// viewport of the same index. This allows confining each view of a single draw call to its
+    /// own region.
+    ///
+    /// Requires the same support as `viewports`. Drawing returns `ScissorsCountMismatch` if the
+    /// number of scissor boxes is different from the number of viewports.
+    pub scissors: Vec<Rect>,
+
     /// If `false`, the pipeline will stop after the primitives generation stage. The default
     /// value is `true`.
     ///
@@ -343,6 +469,15 @@ pub struct DrawParameters<'a> {
     pub transform_feedback_primitives_written_query:
                                     Option<&'a TransformFeedbackPrimitivesWrittenQuery>,
 
+    /// Additional queries that count the number of vertices written by transform feedback,
+    /// usually one for each vertex stream that the geometry shader outputs to.
+    ///
+    /// Two queries active at the same time must count different streams, including the query
+    /// of `transform_feedback_primitives_written_query`. Otherwise drawing returns
+    /// `WrongQueryOperation`.
+    pub transform_feedback_primitives_written_queries:
+                                    &'a [&'a TransformFeedbackPrimitivesWrittenQuery],
+
     /// If set, the commands will only be executed if the specified query contains `true` or
     /// a number different than 0.
     pub condition: Option<ConditionalRendering<'a>>,
@@ -378,6 +513,30 @@ pub struct DrawParameters<'a> {
     /// Since this is purely an optimization, this parameter is ignored if the backend doesn't
     /// support it.
     pub primitive_bounding_box: (Range<f32>, Range<f32>, Range<f32>, Range<f32>),
+
+    /// The outer and inner tessellation levels to use when drawing patches with a program that
+    /// has a tessellation evaluation shader but no tessellation control shader.
+    ///
+    /// The default value is `([1.0, 1.0, 1.0, 1.0], [1.0, 1.0])`, which is the default value
+    /// in OpenGL. This parameter is ignored on OpenGL ES, where a tessellation control shader
+    /// is always required.
+    pub default_tessellation_levels: ([f32; 4], [f32; 2]),
+
+    /// The origin of the window coordinates.
+    ///
+    /// The default value is `LowerLeft`, as this is the default in OpenGL. `UpperLeft` requires
+    /// OpenGL 4.5 or the `GL_ARB_clip_control` extension, otherwise a
+    /// `ClipControlNotSupported` error is returned. If it isn't available, you can instead
+    /// invert the Y coordinate in your projection matrix.
+    pub origin: SurfaceOrigin,
+
+    /// The range of the Z coordinate in normalized device coordinates that is mapped to the
+    /// depth range.
+    ///
+    /// The default value is `NegativeOneToOne`, as this is the default in OpenGL. `ZeroToOne`
+    /// requires OpenGL 4.5 or the `GL_ARB_clip_control` extension, otherwise a
+    /// `ClipControlNotSupported` error is returned.
+    pub depth_clip_mode: DepthClipMode,
 }
 
 /// Condition whether to render or not.
@@ -388,10 +547,14 @@ pub struct ConditionalRendering<'a> {
 
     /// If true, the GPU will wait until the query result has been obtained. If false, the GPU
     /// is free to ignore the query and draw anyway.
+    ///
+    /// Corresponds to `GL_QUERY_WAIT` (true) or `GL_QUERY_NO_WAIT` (false).
     pub wait: bool,
 
     /// If true, only samples that match those that were written with the query active will
     /// be drawn.
+    ///
+    /// Corresponds to the `GL_QUERY_BY_REGION_*` variants of the modes.
     pub per_region: bool,
 }
 
@@ -427,43 +590,153 @@ impl<'a> Default for DrawParameters<'a> {
             color_mask: (true, true, true, true),
             line_width: None,
             point_size: None,
+            clip_planes_bitmask: 0,
             backface_culling: BackfaceCullingMode::CullingDisabled,
             polygon_mode: PolygonMode::Fill,
-            multisampling: true,
+            multisampling: Default::default(),
+            seamless_cubemaps: false,
             dithering: true,
             viewport: None,
+            viewport_subpixel_offset: (0.0, 0.0),
+            viewports: Vec::new(),
             scissor: None,
+            scissors: Vec::new(),
             draw_primitives: true,
             samples_passed_query: None,
             time_elapsed_query: None,
             primitives_generated_query: None,
             transform_feedback_primitives_written_query: None,
+            transform_feedback_primitives_written_queries: &[],
             condition: None,
             transform_feedback: None,
             smooth: None,
             provoking_vertex: ProvokingVertex::LastVertex,
             primitive_bounding_box: (-1.0 .. 1.0, -1.0 .. 1.0, -1.0 .. 1.0, -1.0 .. 1.0),
+            default_tessellation_levels: ([1.0, 1.0, 1.0, 1.0], [1.0, 1.0]),
+            origin: SurfaceOrigin::LowerLeft,
+            depth_clip_mode: DepthClipMode::NegativeOneToOne,
         }
     }
 }
 
-/// DEPRECATED. Checks parameters and returns an error if something is wrong.
-pub fn validate(context: &Context, params: &DrawParameters) -> Result<(), DrawError> {
+/// Checks that the parameters are valid and supported by the backend, and returns an error if
+/// something is wrong.
+///
+/// This is done once when building a `Pipeline`, and before each draw command otherwise.
+pub fn validate<C: ?Sized>(context: &C, params: &DrawParameters) -> Result<(), DrawError>
+                           where C: CapabilitiesSource
+{
+    let caps = context.get_capabilities();
+
     if params.depth.range.0 < 0.0 || params.depth.range.0 > 1.0 ||
        params.depth.range.1 < 0.0 || params.depth.range.1 > 1.0
     {
         return Err(DrawError::InvalidDepthRange);
     }
 
-    if !params.draw_primitives && context.get_version() < &Version(Api::Gl, 3, 0) &&
+    if !params.draw_primitives && !(context.get_version() >= &Version(Api::Gl, 3, 0)) &&
         !context.get_extensions().gl_ext_transform_feedback
     {
         return Err(DrawError::RasterizerDiscardNotSupported);
     }
 
+    if !params.viewports.is_empty() {
+        if !(context.get_version() >= &Version(Api::Gl, 4, 1)) &&
+           !context.get_extensions().gl_arb_viewport_array
+        {
+            return Err(DrawError::ViewportArrayNotSupported);
+        }
+
+        if params.viewports.len() > caps.max_viewports as usize {
+            return Err(DrawError::TooManyViewports);
+        }
+    }
+
+    for viewport in params.viewport.iter().chain(params.viewports.iter()) {
+        if viewport.width > caps.max_viewport_dims.0 as u32 ||
+           viewport.height > caps.max_viewport_dims.1 as u32
+        {
+            return Err(DrawError::ViewportTooLarge);
+        }
+    }
+
+    if !params.scissors.is_empty() && params.scissors.len() != params.viewports.len() {
+        return Err(DrawError::ScissorsCountMismatch);
+    }
+
+    if let Some(line_width) = params.line_width {
+        // wide lines generate an error in forward-compatible contexts
+        if line_width > 1.0 && caps.forward_compatible {
+            return Err(DrawError::LineWidthNotSupported);
+        }
+
+        let range = if params.smooth.is_some() {
+            caps.smooth_line_width_range.unwrap_or(caps.aliased_line_width_range)
+        } else {
+            caps.aliased_line_width_range
+        };
+
+        if line_width <= 0.0 || line_width > range.1 {
+            return Err(DrawError::LineWidthNotSupported);
+        }
+    }
+
+    let max_clip_distances = caps.max_clip_distances as u32;
+    if max_clip_distances < 32 && (params.clip_planes_bitmask >> max_clip_distances) != 0 {
+        return Err(DrawError::ClipPlaneIndexOutOfBounds);
+    }
+
+    // smoothing isn't supported on OpenGL ES
+    if params.smooth.is_some() && !(context.get_version() >= &Version(Api::Gl, 1, 0)) {
+        return Err(DrawError::SmoothingNotSupported);
+    }
+
+    if params.multisampling.alpha_to_one && !(context.get_version() >= &Version(Api::Gl, 1, 3)) {
+        return Err(DrawError::AlphaToOneNotSupported);
+    }
+
+    // always enabled on OpenGL ES 3
+    if params.seamless_cubemaps && !(context.get_version() >= &Version(Api::Gl, 3, 2)) &&
+       !(context.get_version() >= &Version(Api::GlEs, 3, 0)) &&
+       !context.get_extensions().gl_arb_seamless_cube_map
+    {
+        return Err(DrawError::SeamlessCubemapsNotSupported);
+    }
+
+    if params.provoking_vertex != ProvokingVertex::LastVertex &&
+       !(context.get_version() >= &Version(Api::Gl, 3, 2)) &&
+       !context.get_extensions().gl_arb_provoking_vertex &&
+       !context.get_extensions().gl_ext_provoking_vertex
+    {
+        return Err(DrawError::ProvokingVertexNotSupported);
+    }
+
+    if (params.origin != SurfaceOrigin::LowerLeft ||
+        params.depth_clip_mode != DepthClipMode::NegativeOneToOne) &&
+       !(context.get_version() >= &Version(Api::Gl, 4, 5)) &&
+       !context.get_extensions().gl_arb_clip_control
+    {
+        return Err(DrawError::ClipControlNotSupported);
+    }
+
+    {
+        let queries = params.transform_feedback_primitives_written_queries;
+        let streams = params.transform_feedback_primitives_written_query.iter()
+                            .chain(queries.iter()).map(|q| q.get_stream()).collect::<Vec<_>>();
+
+        for (n, stream) in streams.iter().enumerate() {
+            if streams[n + 1 ..].contains(stream) {
+                return Err(DrawError::WrongQueryOperation);
+            }
+        }
+    }
+
     Ok(())
 }
 
+/// Applies the draw parameters to the state of the context.
+///
+/// The parameters must have been checked with `validate` beforehand.
 #[doc(hidden)]
 pub fn sync(ctxt: &mut context::CommandContext, draw_parameters: &DrawParameters,
             dimensions: (u32, u32), primitives_types: PrimitiveType) -> Result<(), DrawError>
@@ -474,20 +747,33 @@ pub fn sync(ctxt: &mut context::CommandContext, draw_parameters: &DrawParameters
     sync_color_mask(ctxt, draw_parameters.color_mask);
     sync_line_width(ctxt, draw_parameters.line_width);
     sync_point_size(ctxt, draw_parameters.point_size);
+    sync_clip_planes(ctxt, draw_parameters.clip_planes_bitmask);
     sync_polygon_mode(ctxt, draw_parameters.backface_culling, draw_parameters.polygon_mode);
-    sync_multisampling(ctxt, draw_parameters.multisampling);
+    multisample::sync_multisampling(ctxt, &draw_parameters.multisampling);
+    sync_seamless_cubemaps(ctxt, draw_parameters.seamless_cubemaps);
     sync_dithering(ctxt, draw_parameters.dithering);
-    sync_viewport_scissor(ctxt, draw_parameters.viewport, draw_parameters.scissor,
-                          dimensions);
-    try!(sync_rasterizer_discard(ctxt, draw_parameters.draw_primitives));
+    if draw_parameters.viewports.is_empty() {
+        sync_viewport(ctxt, draw_parameters.viewport, draw_parameters.viewport_subpixel_offset,
+                      dimensions);
+    } else {
+        sync_viewport_array(ctxt, &draw_parameters.viewports);
+    }
+    if draw_parameters.scissors.is_empty() {
+        sync_scissor(ctxt, draw_parameters.scissor);
+    } else {
+        sync_scissor_array(ctxt, &draw_parameters.scissors);
+    }
+    sync_rasterizer_discard(ctxt, draw_parameters.draw_primitives);
     try!(sync_queries(ctxt, draw_parameters.samples_passed_query,
                       draw_parameters.time_elapsed_query,
                       draw_parameters.primitives_generated_query,
-                      draw_parameters.transform_feedback_primitives_written_query));
+                      draw_parameters.transform_feedback_primitives_written_query,
+                      draw_parameters.transform_feedback_primitives_written_queries));
     sync_conditional_render(ctxt, draw_parameters.condition);
     try!(sync_smooth(ctxt, draw_parameters.smooth, primitives_types));
-    try!(sync_provoking_vertex(ctxt, draw_parameters.provoking_vertex));
+    sync_provoking_vertex(ctxt, draw_parameters.provoking_vertex);
     sync_primitive_bounding_box(ctxt, &draw_parameters.primitive_bounding_box);
+    sync_clip_control(ctxt, draw_parameters.origin, draw_parameters.depth_clip_mode);
 
     Ok(())
 }
@@ -531,6 +817,31 @@ fn sync_point_size(ctxt: &mut context::CommandContext, point_size: Option<f32>)
     }
 }
 
+fn sync_clip_planes(ctxt: &mut context::CommandContext, bitmask: u32) {
+    if ctxt.state.enabled_clip_planes == bitmask {
+        return;
+    }
+
+    let max = ctxt.capabilities.max_clip_distances as u32;
+
+    for i in 0 .. max.min(32) {
+        let mask = 1 << i;
+        if (ctxt.state.enabled_clip_planes & mask) == (bitmask & mask) {
+            continue;
+        }
+
+        unsafe {
+            if (bitmask & mask) != 0 {
+                ctxt.gl.Enable(gl::CLIP_DISTANCE0 + i);
+            } else {
+                ctxt.gl.Disable(gl::CLIP_DISTANCE0 + i);
+            }
+        }
+    }
+
+    ctxt.state.enabled_clip_planes = bitmask;
+}
+
 fn sync_polygon_mode(ctxt: &mut context::CommandContext, backface_culling: BackfaceCullingMode,
                      polygon_mode: PolygonMode)
 {
@@ -576,17 +887,21 @@ fn sync_polygon_mode(ctxt: &mut context::CommandContext, backface_culling: Backf
     }
 }
 
-fn sync_multisampling(ctxt: &mut context::CommandContext, multisampling: bool) {
-    if ctxt.state.enabled_multisample != multisampling {
+fn sync_seamless_cubemaps(ctxt: &mut context::CommandContext, seamless: bool) {
+    // always enabled on OpenGL ES 3
+    if ctxt.version >= &Version(Api::GlEs, 3, 0) {
+        return;
+    }
+
+    if ctxt.state.enabled_texture_cube_map_seamless != seamless {
         unsafe {
-            if multisampling {
-                ctxt.gl.Enable(gl::MULTISAMPLE);
-                ctxt.state.enabled_multisample = true;
+            if seamless {
+                ctxt.gl.Enable(gl::TEXTURE_CUBE_MAP_SEAMLESS);
             } else {
-                ctxt.gl.Disable(gl::MULTISAMPLE);
-                ctxt.state.enabled_multisample = false;
+                ctxt.gl.Disable(gl::TEXTURE_CUBE_MAP_SEAMLESS);
             }
         }
+        ctxt.state.enabled_texture_cube_map_seamless = seamless;
     }
 }
 
@@ -604,24 +919,17 @@ fn sync_dithering(ctxt: &mut context::CommandContext, dithering: bool) {
     }
 }
 
-fn sync_viewport_scissor(ctxt: &mut context::CommandContext, viewport: Option<Rect>,
-                         scissor: Option<Rect>, surface_dimensions: (u32, u32))
+fn sync_viewport(ctxt: &mut context::CommandContext, viewport: Option<Rect>,
+                 subpixel_offset: (f32, f32), surface_dimensions: (u32, u32))
 {
-    // viewport
-    if let Some(viewport) = viewport {
+    let viewport = if let Some(viewport) = viewport {
         assert!(viewport.width <= ctxt.capabilities.max_viewport_dims.0 as u32,
                 "Viewport dimensions are too large");
         assert!(viewport.height <= ctxt.capabilities.max_viewport_dims.1 as u32,
                 "Viewport dimensions are too large");
 
-        let viewport = (viewport.left as gl::types::GLint, viewport.bottom as gl::types::GLint,
-                        viewport.width as gl::types::GLsizei,
-                        viewport.height as gl::types::GLsizei);
-
-        if ctxt.state.viewport != Some(viewport) {
-            unsafe { ctxt.gl.Viewport(viewport.0, viewport.1, viewport.2, viewport.3); }
-            ctxt.state.viewport = Some(viewport);
-        }
+        (viewport.left as gl::types::GLint, viewport.bottom as gl::types::GLint,
+         viewport.width as gl::types::GLsizei, viewport.height as gl::types::GLsizei)
 
     } else {
         assert!(surface_dimensions.0 <= ctxt.capabilities.max_viewport_dims.0 as u32,
@@ -629,16 +937,60 @@ fn sync_viewport_scissor(ctxt: &mut context::CommandContext, viewport: Option<Re
         assert!(surface_dimensions.1 <= ctxt.capabilities.max_viewport_dims.1 as u32,
                 "Viewport dimensions are too large");
 
-        let viewport = (0, 0, surface_dimensions.0 as gl::types::GLsizei,
-                        surface_dimensions.1 as gl::types::GLsizei);
+        (0, 0, surface_dimensions.0 as gl::types::GLsizei,
+         surface_dimensions.1 as gl::types::GLsizei)
+    };
+
+    let subpixel = subpixel_offset.0.fract() != 0.0 || subpixel_offset.1.fract() != 0.0;
+
+    if subpixel && (ctxt.version >= &Version(Api::Gl, 4, 1) ||
+                    ctxt.extensions.gl_arb_viewport_array)
+    {
+        let viewport = [viewport.0 as f32 + subpixel_offset.0,
+                        viewport.1 as f32 + subpixel_offset.1,
+                        viewport.2 as f32, viewport.3 as f32];
+
+        if ctxt.state.viewport_indexed_f != Some(viewport) {
+            unsafe { ctxt.gl.ViewportIndexedf(0, viewport[0], viewport[1],
+                                              viewport[2], viewport[3]); }
+            ctxt.state.viewport_indexed_f = Some(viewport);
+            ctxt.state.viewport = None;
+            ctxt.state.viewport_array.clear();
+        }
+
+    } else {
+        let viewport = (viewport.0 + subpixel_offset.0.round() as gl::types::GLint,
+                        viewport.1 + subpixel_offset.1.round() as gl::types::GLint,
+                        viewport.2, viewport.3);
 
         if ctxt.state.viewport != Some(viewport) {
             unsafe { ctxt.gl.Viewport(viewport.0, viewport.1, viewport.2, viewport.3); }
             ctxt.state.viewport = Some(viewport);
+            ctxt.state.viewport_indexed_f = None;
+            ctxt.state.viewport_array.clear();
         }
     }
+}
+
+fn sync_viewport_array(ctxt: &mut context::CommandContext, viewports: &[Rect]) {
+    let viewports = viewports.iter().map(|viewport| {
+        [viewport.left as gl::types::GLfloat, viewport.bottom as gl::types::GLfloat,
+         viewport.width as gl::types::GLfloat, viewport.height as gl::types::GLfloat]
+    }).collect::<Vec<_>>();
+
+    if ctxt.state.viewport_array != viewports {
+        unsafe {
+            ctxt.gl.ViewportArrayv(0, viewports.len() as gl::types::GLsizei,
+                                   viewports.as_ptr() as *const _);
+        }
 
-    // scissor
+        ctxt.state.viewport = None;
+        ctxt.state.viewport_indexed_f = None;
+        ctxt.state.viewport_array = viewports;
+    }
+}
+
+fn sync_scissor(ctxt: &mut context::CommandContext, scissor: Option<Rect>) {
     if let Some(scissor) = scissor {
         let scissor = (scissor.left as gl::types::GLint, scissor.bottom as gl::types::GLint,
                        scissor.width as gl::types::GLsizei,
@@ -648,6 +1000,7 @@ fn sync_viewport_scissor(ctxt: &mut context::CommandContext, viewport: Option<Re
             if ctxt.state.scissor != Some(scissor) {
                 ctxt.gl.Scissor(scissor.0, scissor.1, scissor.2, scissor.3);
                 ctxt.state.scissor = Some(scissor);
+                ctxt.state.scissor_array.clear();
             }
 
             if !ctxt.state.enabled_scissor_test {
@@ -665,9 +1018,29 @@ fn sync_viewport_scissor(ctxt: &mut context::CommandContext, viewport: Option<Re
     }
 }
 
-fn sync_rasterizer_discard(ctxt: &mut context::CommandContext, draw_primitives: bool)
-                           -> Result<(), DrawError>
-{
+fn sync_scissor_array(ctxt: &mut context::CommandContext, scissors: &[Rect]) {
+    let scissors = scissors.iter().map(|scissor| {
+        [scissor.left as gl::types::GLint, scissor.bottom as gl::types::GLint,
+         scissor.width as gl::types::GLint, scissor.height as gl::types::GLint]
+    }).collect::<Vec<_>>();
+
+    unsafe {
+        if ctxt.state.scissor_array != scissors {
+            ctxt.gl.ScissorArrayv(0, scissors.len() as gl::types::GLsizei,
+                                  scissors.as_ptr() as *const _);
+
+            ctxt.state.scissor = None;
+            ctxt.state.scissor_array = scissors;
+        }
+
+        if !ctxt.state.enabled_scissor_test {
+            ctxt.gl.Enable(gl::SCISSOR_TEST);
+            ctxt.state.enabled_scissor_test = true;
+        }
+    }
+}
+
+fn sync_rasterizer_discard(ctxt: &mut context::CommandContext, draw_primitives: bool) {
     if ctxt.state.enabled_rasterizer_discard == draw_primitives {
         if ctxt.version >= &Version(Api::Gl, 3, 0) {
             if draw_primitives {
@@ -686,13 +1059,8 @@ fn sync_rasterizer_discard(ctxt: &mut context::CommandContext, draw_primitives:
                 unsafe { ctxt.gl.Enable(gl::RASTERIZER_DISCARD_EXT); }
                 ctxt.state.enabled_rasterizer_discard = true;
             }
-
-        } else {
-            return Err(DrawError::RasterizerDiscardNotSupported);
         }
     }
-
-    Ok(())
 }
 
 fn sync_queries(ctxt: &mut context::CommandContext,
@@ -700,7 +1068,9 @@ fn sync_queries(ctxt: &mut context::CommandContext,
                 time_elapsed_query: Option<&TimeElapsedQuery>,
                 primitives_generated_query: Option<&PrimitivesGeneratedQuery>,
                 transform_feedback_primitives_written_query:
-                                            Option<&TransformFeedbackPrimitivesWrittenQuery>)
+                                            Option<&TransformFeedbackPrimitivesWrittenQuery>,
+                transform_feedback_primitives_written_queries:
+                                            &[&TransformFeedbackPrimitivesWrittenQuery])
                 -> Result<(), DrawError>
 {
     if let Some(SamplesQueryParam::SamplesPassedQuery(q)) = samples_passed_query {
@@ -723,10 +1093,24 @@ fn sync_queries(ctxt: &mut context::CommandContext,
         TimeElapsedQuery::end_primitives_generated_query(ctxt);
     }
 
+    // ending the queries of the streams that are no longer counted
+    let num_streams = ctxt.state.indexed_transform_feedback_primitives_written_queries.len() + 1;
+    for stream in 0 .. num_streams as u32 {
+        let used = transform_feedback_primitives_written_query.iter()
+                        .chain(transform_feedback_primitives_written_queries.iter())
+                        .any(|q| q.get_stream() == stream);
+
+        if !used {
+            TimeElapsedQuery::end_transform_feedback_stream_primitives_written_query(ctxt, stream);
+        }
+    }
+
     if let Some(tfq) = transform_feedback_primitives_written_query {
         try!(tfq.begin_query(ctxt));
-    } else {
-        TimeElapsedQuery::end_transform_feedback_primitives_written_query(ctxt);
+    }
+
+    for tfq in transform_feedback_primitives_written_queries {
+        try!(tfq.begin_query(ctxt));
     }
 
     Ok(())
@@ -755,11 +1139,6 @@ fn sync_smooth(ctxt: &mut context::CommandContext,
                primitive_type: PrimitiveType) -> Result<(), DrawError> {
 
     if let Some(smooth) = smooth {
-        // check if smoothing is supported, it isn't on OpenGL ES
-        if !(ctxt.version >= &Version(Api::Gl, 1, 0)) {
-            return Err(DrawError::SmoothingNotSupported);
-        }
-
         let hint = smooth.to_glenum();
 
         match primitive_type {
@@ -824,16 +1203,14 @@ fn sync_smooth(ctxt: &mut context::CommandContext,
     Ok(())
 }
 
-fn sync_provoking_vertex(ctxt: &mut context::CommandContext, value: ProvokingVertex)
-                         -> Result<(), DrawError>
-{
+fn sync_provoking_vertex(ctxt: &mut context::CommandContext, value: ProvokingVertex) {
     let value = match value {
         ProvokingVertex::LastVertex => gl::LAST_VERTEX_CONVENTION,
         ProvokingVertex::FirstVertex => gl::FIRST_VERTEX_CONVENTION,
     };
 
     if ctxt.state.provoking_vertex == value {
-        return Ok(());
+        return;
     }
 
     if ctxt.version >= &Version(Api::Gl, 3, 2) || ctxt.extensions.gl_arb_provoking_vertex {
@@ -843,12 +1220,20 @@ fn sync_provoking_vertex(ctxt: &mut context::CommandContext, value: ProvokingVer
     } else if ctxt.extensions.gl_ext_provoking_vertex {
         unsafe { ctxt.gl.ProvokingVertexEXT(value); }
         ctxt.state.provoking_vertex = value;
+    }
+}
 
-    } else {
-        return Err(DrawError::ProvokingVertexNotSupported);
+fn sync_clip_control(ctxt: &mut context::CommandContext, origin: SurfaceOrigin,
+                     depth_mode: DepthClipMode)
+{
+    let value = (origin.to_glenum(), depth_mode.to_glenum());
+
+    if ctxt.state.clip_control == value {
+        return;
     }
 
-    Ok(())
+    unsafe { ctxt.gl.ClipControl(value.0, value.1); }
+    ctxt.state.clip_control = value;
 }
 
 fn sync_primitive_bounding_box(ctxt: &mut context::CommandContext,
@@ -0,0 +1,96 @@
+use context::CommandContext;
+
+use gl;
+
+/// Represents the multisampling parameters of a draw command.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MultisampleState {
+    /// Whether multisample antialiasing (MSAA) should be used. Default value is `true`.
+    ///
+    /// Note that you will need to set the appropriate option when creating the window.
+    /// The recommended way to do is to leave this to `true`, and adjust the option when
+    /// creating the window.
+    pub enabled: bool,
+
+    /// If `true`, the alpha value of each fragment is used to determine which samples of the
+    /// pixel are covered by the fragment. The default value is `false`.
+    ///
+    /// This is the usual way to render alpha-tested geometry, like foliage, with smooth edges
+    /// when multisampling is enabled. Has no effect if the target doesn't have multisampling.
+    pub alpha_to_coverage: bool,
+
+    /// If `true`, the alpha value of each fragment is replaced by 1.0 after the coverage has
+    /// been computed. The default value is `false`.
+    ///
+    /// This is usually used together with `alpha_to_coverage`. Drawing returns
+    /// `AlphaToOneNotSupported` if this is `true` on OpenGL ES.
+    pub alpha_to_one: bool,
+
+    /// If set, the coverage of each fragment is combined with a mask that covers the given
+    /// proportion of the samples, between `0.0` and `1.0`. If the boolean is `true`, the mask
+    /// is inverted. The default value is `None`.
+    pub sample_coverage: Option<(f32, bool)>,
+}
+
+impl Default for MultisampleState {
+    #[inline]
+    fn default() -> MultisampleState {
+        MultisampleState {
+            enabled: true,
+            alpha_to_coverage: false,
+            alpha_to_one: false,
+            sample_coverage: None,
+        }
+    }
+}
+
+pub fn sync_multisampling(ctxt: &mut CommandContext, params: &MultisampleState) {
+    if ctxt.state.enabled_multisample != params.enabled {
+        unsafe {
+            if params.enabled {
+                ctxt.gl.Enable(gl::MULTISAMPLE);
+            } else {
+                ctxt.gl.Disable(gl::MULTISAMPLE);
+            }
+        }
+        ctxt.state.enabled_multisample = params.enabled;
+    }
+
+    if ctxt.state.enabled_sample_alpha_to_coverage != params.alpha_to_coverage {
+        unsafe {
+            if params.alpha_to_coverage {
+                ctxt.gl.Enable(gl::SAMPLE_ALPHA_TO_COVERAGE);
+            } else {
+                ctxt.gl.Disable(gl::SAMPLE_ALPHA_TO_COVERAGE);
+            }
+        }
+        ctxt.state.enabled_sample_alpha_to_coverage = params.alpha_to_coverage;
+    }
+
+    if ctxt.state.enabled_sample_alpha_to_one != params.alpha_to_one {
+        unsafe {
+            if params.alpha_to_one {
+                ctxt.gl.Enable(gl::SAMPLE_ALPHA_TO_ONE);
+            } else {
+                ctxt.gl.Disable(gl::SAMPLE_ALPHA_TO_ONE);
+            }
+        }
+        ctxt.state.enabled_sample_alpha_to_one = params.alpha_to_one;
+    }
+
+    if let Some((value, invert)) = params.sample_coverage {
+        if !ctxt.state.enabled_sample_coverage {
+            unsafe { ctxt.gl.Enable(gl::SAMPLE_COVERAGE); }
+            ctxt.state.enabled_sample_coverage = true;
+        }
+
+        if ctxt.state.sample_coverage != (value, invert) {
+            unsafe { ctxt.gl.SampleCoverage(value, if invert { 1 } else { 0 }); }
+            ctxt.state.sample_coverage = (value, invert);
+        }
+
+    } else if ctxt.state.enabled_sample_coverage {
+        unsafe { ctxt.gl.Disable(gl::SAMPLE_COVERAGE); }
+        ctxt.state.enabled_sample_coverage = false;
+    }
+}
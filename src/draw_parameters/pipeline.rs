@@ -0,0 +1,119 @@
+use std::borrow::Borrow;
+
+use backend::Facade;
+
+use DrawError;
+use Program;
+
+use draw_parameters::{self, DrawParameters};
+use vertex::VertexFormat;
+
+/// A program, some draw parameters and the formats of the vertices sources that have been
+/// checked against each other once and for all.
+///
+/// Creating a `Pipeline` performs ahead of time some checks that would otherwise be done each
+/// time you draw, and some that are not done at all when drawing, like checking that the vertex
+/// formats provide all the attributes of the program. Once it has been created, a pipeline can
+/// be passed to `Surface::draw_with_pipeline` as many times as you want, which skips the checks
+/// that have already been done.
+///
+/// The vertices sources passed when drawing must have the same formats as the ones that were
+/// passed at creation, in the same order.
+///
+/// ## External state changes
+///
+/// Glium assumes that it is the only one to modify the OpenGL state. Modifying the state
+/// without restoring it (for example with `exec_in_context`) invalidates the assumptions that
+/// were made when the pipeline was created.
+///
+/// ## Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate glium;
+/// # fn main() {
+/// # use glium::Surface;
+/// # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+/// # let program: glium::Program = unsafe { ::std::mem::uninitialized() };
+/// # let vertex_buffer: glium::VertexBuffer<u8> = unsafe { ::std::mem::uninitialized() };
+/// # let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+/// let pipeline = glium::draw_parameters::Pipeline::new(&display, &program, Default::default(),
+///                                                      &[vertex_buffer.get_bindings()]).unwrap();
+///
+/// let mut target = display.draw();
+/// target.draw_with_pipeline(&pipeline, &vertex_buffer, &indices,
+///                           &glium::uniforms::EmptyUniforms).unwrap();
+/// target.finish().unwrap();
+/// # }
+/// ```
+pub struct Pipeline<'a> {
+    program: &'a Program,
+    draw_parameters: DrawParameters<'a>,
+}
+
+impl<'a> Pipeline<'a> {
+    /// Builds a new pipeline and checks that its components are compatible with each other and
+    /// with the backend.
+    pub fn new<F>(facade: &F, program: &'a Program, draw_parameters: DrawParameters<'a>,
+                  vertex_formats: &[&VertexFormat]) -> Result<Pipeline<'a>, DrawError>
+                  where F: Facade
+    {
+        try!(draw_parameters::validate(facade, &draw_parameters));
+        try!(check_vertex_formats(program, vertex_formats));
+
+        if !draw_parameters.viewports.is_empty() && !program.has_geometry_shader() {
+            return Err(DrawError::ViewportArrayWithoutGeometryShader);
+        }
+
+        Ok(Pipeline {
+            program: program,
+            draw_parameters: draw_parameters,
+        })
+    }
+
+    /// Returns the program of this pipeline.
+    #[inline]
+    pub fn get_program(&self) -> &'a Program {
+        self.program
+    }
+
+    /// Returns the draw parameters of this pipeline.
+    #[inline]
+    pub fn get_draw_parameters(&self) -> &DrawParameters<'a> {
+        &self.draw_parameters
+    }
+}
+
+/// Checks that each attribute of the program is provided by exactly one of the vertex formats,
+/// with the right type.
+fn check_vertex_formats(program: &Program, vertex_formats: &[&VertexFormat])
+                        -> Result<(), DrawError>
+{
+    for format in vertex_formats {
+        for &(ref name, _, ty) in format.iter() {
+            let attribute = match program.get_attribute(Borrow::<str>::borrow(name)) {
+                Some(a) => a,
+                None => continue
+            };
+
+            if ty.get_num_components() != attribute.ty.get_num_components() ||
+               attribute.size != 1
+            {
+                return Err(DrawError::AttributeTypeMismatch);
+            }
+        }
+    }
+
+    for (name, _) in program.attributes() {
+        let sources = vertex_formats.iter().filter(|format| {
+            format.iter().any(|&(ref n, _, _)| n == name)
+        }).count();
+
+        if sources == 0 {
+            return Err(DrawError::AttributeMissing);
+        } else if sources > 1 {
+            return Err(DrawError::AttributeInMultipleSources);
+        }
+    }
+
+    Ok(())
+}
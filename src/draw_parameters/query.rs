@@ -40,6 +40,8 @@ pub enum QueryType {
     Timestamp,
     PrimitivesGenerated,
     TransformFeedbackPrimitivesWritten,
+    // the stream is always superior to 0
+    TransformFeedbackStreamPrimitivesWritten(u32),
 }
 
 impl ToGlEnum for QueryType {
@@ -55,6 +57,9 @@ impl ToGlEnum for QueryType {
             QueryType::TransformFeedbackPrimitivesWritten => {
                 gl::TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN
             },
+            QueryType::TransformFeedbackStreamPrimitivesWritten(_) => {
+                gl::TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN
+            },
         }
     }
 }
@@ -113,6 +118,18 @@ impl RawQuery {
 
         // FIXME: handle Timestamp separately
 
+        if let QueryType::TransformFeedbackStreamPrimitivesWritten(stream) = ty {
+            if !(ctxt.version >= &Version(Api::Gl, 4, 0)) &&
+               !ctxt.extensions.gl_arb_transform_feedback3
+            {
+                return Err(QueryCreationError::NotSupported);
+            }
+
+            if stream >= ctxt.capabilities.max_vertex_streams as u32 {
+                return Err(QueryCreationError::NotSupported);
+            }
+        }
+
         let id = unsafe {
             let mut id = mem::uninitialized();
 
@@ -120,7 +137,8 @@ impl RawQuery {
                 match ty {
                     QueryType::AnySamplesPassed | QueryType::SamplesPassed |
                     QueryType::PrimitivesGenerated | QueryType::TimeElapsed |
-                    QueryType::TransformFeedbackPrimitivesWritten => (),
+                    QueryType::TransformFeedbackPrimitivesWritten |
+                    QueryType::TransformFeedbackStreamPrimitivesWritten(_) => (),
                     QueryType::AnySamplesPassedConservative if
                             ctxt.extensions.gl_arb_es3_compatibility ||
                             ctxt.version >= &Version(Api:: Gl, 4, 3) => (),
@@ -138,7 +156,8 @@ impl RawQuery {
             } else if ctxt.version >= &Version(Api::Gl, 3, 0) {
                 match ty {
                     QueryType::SamplesPassed | QueryType::PrimitivesGenerated |
-                    QueryType::TransformFeedbackPrimitivesWritten => (),
+                    QueryType::TransformFeedbackPrimitivesWritten |
+                    QueryType::TransformFeedbackStreamPrimitivesWritten(_) => (),
                     QueryType::AnySamplesPassed if ctxt.extensions.gl_arb_occlusion_query2 => (),
                     QueryType::AnySamplesPassedConservative if ctxt.extensions.gl_arb_es3_compatibility => (),
                     QueryType::TimeElapsed if ctxt.extensions.gl_arb_timer_query => (),
@@ -255,6 +274,8 @@ impl RawQuery {
     }
 
     /// Writes the value of the query to a buffer.
+    ///
+    /// If the query has never been used, writes `0` like `get_u32` would return.
     pub fn write_u32_to_buffer(&self, target: BufferSlice<u32>) -> Result<(), ToBufferError> {
         let mut ctxt = self.context.make_current();
 
@@ -267,7 +288,9 @@ impl RawQuery {
         self.deactivate(&mut ctxt);
 
         if !self.has_been_used.get() {
-            panic!();
+            drop(ctxt);
+            target.write(&0);
+            return Ok(());
         }
 
         assert!(target.get_offset_bytes() % 4 == 0);
@@ -371,6 +394,16 @@ impl RawQuery {
             ctxt.state.transform_feedback_primitives_written_query = 0;
         }
 
+        for index in 0 .. ctxt.state.indexed_transform_feedback_primitives_written_queries.len() {
+            if ctxt.state.indexed_transform_feedback_primitives_written_queries[index] == self.id {
+                unsafe {
+                    raw_end_query_indexed(ctxt, gl::TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN,
+                                          index as gl::types::GLuint + 1);
+                }
+                ctxt.state.indexed_transform_feedback_primitives_written_queries[index] = 0;
+            }
+        }
+
         if ctxt.state.time_elapsed_query == self.id {
             unsafe { raw_end_query(ctxt, gl::TIME_ELAPSED) };
             ctxt.state.time_elapsed_query = 0;
@@ -550,6 +583,33 @@ impl QueryExt for RawQuery {
                     ctxt.state.transform_feedback_primitives_written_query = self.id;
                 }
             },
+
+            QueryType::TransformFeedbackStreamPrimitivesWritten(stream) => {
+                let index = stream as usize - 1;
+                while ctxt.state.indexed_transform_feedback_primitives_written_queries.len() <= index {
+                    ctxt.state.indexed_transform_feedback_primitives_written_queries.push(0);
+                }
+
+                let current = ctxt.state.indexed_transform_feedback_primitives_written_queries[index];
+
+                if current != self.id {
+                    if self.has_been_used.get() {
+                        return Err(DrawError::WrongQueryOperation);
+                    }
+
+                    unsafe {
+                        if current != 0 {
+                            raw_end_query_indexed(ctxt, gl::TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN,
+                                                  stream);
+                        }
+                        raw_begin_query_indexed(ctxt, gl::TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN,
+                                                stream, self.id);
+                    }
+
+                    self.has_been_used.set(true);
+                    ctxt.state.indexed_transform_feedback_primitives_written_queries[index] = self.id;
+                }
+            },
         };
 
         Ok(())
@@ -596,6 +656,26 @@ impl QueryExt for RawQuery {
         }
     }
 
+    fn end_transform_feedback_stream_primitives_written_query(ctxt: &mut CommandContext,
+                                                              stream: u32)
+    {
+        if stream == 0 {
+            return RawQuery::end_transform_feedback_primitives_written_query(ctxt);
+        }
+
+        let index = stream as usize - 1;
+        if index >= ctxt.state.indexed_transform_feedback_primitives_written_queries.len() {
+            return;
+        }
+
+        if ctxt.state.indexed_transform_feedback_primitives_written_queries[index] != 0 {
+            ctxt.state.indexed_transform_feedback_primitives_written_queries[index] = 0;
+            unsafe {
+                raw_end_query_indexed(ctxt, gl::TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN, stream);
+            }
+        }
+    }
+
     fn begin_conditional_render(&self, ctxt: &mut CommandContext, wait: bool, per_region: bool) {
         let new_mode = match (wait, per_region) {
             (true, true) => gl::QUERY_BY_REGION_WAIT,
@@ -721,6 +801,38 @@ unsafe fn raw_end_query(ctxt: &mut CommandContext, ty: gl::types::GLenum) {
     }
 }
 
+/// Calls `glBeginQueryIndexed`.
+///
+/// # Unsafe
+///
+/// The type of query must be guaranteed to be supported by the backend.
+/// The id of the query must be valid.
+///
+unsafe fn raw_begin_query_indexed(ctxt: &mut CommandContext, ty: gl::types::GLenum,
+                                  index: gl::types::GLuint, id: gl::types::GLuint)
+{
+    if ctxt.version >= &Version(Api::Gl, 4, 0) || ctxt.extensions.gl_arb_transform_feedback3 {
+        ctxt.gl.BeginQueryIndexed(ty, index, id);
+    } else {
+        unreachable!();
+    }
+}
+
+/// Calls `glEndQueryIndexed`.
+///
+/// # Unsafe
+///
+/// The type of query must be guaranteed to be supported by the backend.
+unsafe fn raw_end_query_indexed(ctxt: &mut CommandContext, ty: gl::types::GLenum,
+                                index: gl::types::GLuint)
+{
+    if ctxt.version >= &Version(Api::Gl, 4, 0) || ctxt.extensions.gl_arb_transform_feedback3 {
+        ctxt.gl.EndQueryIndexed(ty, index);
+    } else {
+        unreachable!();
+    }
+}
+
 macro_rules! impl_helper {
     ($name:ident, $ret:ty, $get_fn:ident) => {
         impl $name {
@@ -749,7 +861,11 @@ macro_rules! impl_helper {
             /// This function doesn't block. Instead it submits a commands to the GPU's commands
             /// queue and orders the GPU to write the result of the query to a buffer.
             ///
-            /// This operation is not necessarly supported everywhere.
+            /// This operation is not necessarly supported everywhere. If supported, the result
+            /// can be consumed by the GPU (for example by a compute shader that generates draw
+            /// commands) without ever being read back by the CPU.
+            ///
+            /// If the query has never been used, `0` is written.
             #[inline]
             pub fn to_buffer_u32(&self, target: BufferSlice<u32>)
                                  -> Result<(), ToBufferError>
@@ -793,6 +909,13 @@ macro_rules! impl_helper {
                 RawQuery::end_transform_feedback_primitives_written_query(ctxt)
             }
 
+            #[inline]
+            fn end_transform_feedback_stream_primitives_written_query(ctxt: &mut CommandContext,
+                                                                      stream: u32)
+            {
+                RawQuery::end_transform_feedback_stream_primitives_written_query(ctxt, stream)
+            }
+
             #[inline]
             fn begin_conditional_render(&self, ctxt: &mut CommandContext, wait: bool, per_region: bool) {
                 self.query.begin_conditional_render(ctxt, wait, per_region)
@@ -914,19 +1037,51 @@ impl PrimitivesGeneratedQuery {
 impl_helper!(PrimitivesGeneratedQuery, u32, get_u32);
 
 /// Query that allows you to know the number of primitives generated by transform feedback.
+///
+/// Each query counts the primitives written to one vertex stream. Geometry shaders can emit
+/// vertices to multiple streams with `EmitStreamVertex`, in which case you can use one query per
+/// stream with the `transform_feedback_primitives_written_queries` draw parameter.
 #[derive(Debug)]
 pub struct TransformFeedbackPrimitivesWrittenQuery {
     query: RawQuery,
+    stream: u32,
 }
 
 impl TransformFeedbackPrimitivesWrittenQuery {
-    /// Builds a new query.
+    /// Builds a new query that counts the primitives written to the vertex stream 0.
     #[inline]
     pub fn new<F>(facade: &F) -> Result<TransformFeedbackPrimitivesWrittenQuery, QueryCreationError>
                   where F: Facade
     {
-        RawQuery::new(facade, QueryType::TransformFeedbackPrimitivesWritten)
-                                     .map(|q| TransformFeedbackPrimitivesWrittenQuery { query: q })
+        TransformFeedbackPrimitivesWrittenQuery::new_for_stream(facade, 0)
+    }
+
+    /// Builds a new query that counts the primitives written to a specific vertex stream.
+    ///
+    /// Using a stream other than `0` requires OpenGL 4.0 or `GL_ARB_transform_feedback3`, and
+    /// the stream must be inferior to the `max_vertex_streams` capability.
+    pub fn new_for_stream<F>(facade: &F, stream: u32)
+                             -> Result<TransformFeedbackPrimitivesWrittenQuery, QueryCreationError>
+                             where F: Facade
+    {
+        let ty = if stream == 0 {
+            QueryType::TransformFeedbackPrimitivesWritten
+        } else {
+            QueryType::TransformFeedbackStreamPrimitivesWritten(stream)
+        };
+
+        RawQuery::new(facade, ty).map(|q| {
+            TransformFeedbackPrimitivesWrittenQuery {
+                query: q,
+                stream: stream,
+            }
+        })
+    }
+
+    /// Returns the vertex stream whose primitives are counted by this query.
+    #[inline]
+    pub fn get_stream(&self) -> u32 {
+        self.stream
     }
 }
 
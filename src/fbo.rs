@@ -110,6 +110,10 @@ pub enum RegularAttachment<'a> {
     Texture(TextureAnyImage<'a>),
     /// A renderbuffer.
     RenderBuffer(&'a RenderBufferAny),
+    /// A single-sample 2D texture that is rendered to with the given number of samples, through
+    /// `GL_EXT_multisampled_render_to_texture`. The samples are resolved implicitly. Can only be
+    /// used as a color attachment.
+    MultisampledTexture(TextureAnyImage<'a>, u32),
 }
 
 impl<'a> RegularAttachment<'a> {
@@ -119,10 +123,20 @@ impl<'a> RegularAttachment<'a> {
         match self {
             &RegularAttachment::Texture(t) => t.get_texture().kind(),
             &RegularAttachment::RenderBuffer(rb) => rb.kind(),
+            &RegularAttachment::MultisampledTexture(t, _) => t.get_texture().kind(),
         }
     }
 }
 
+/// Returns true if the backend supports rendering to a single-sample texture with implicit
+/// multisampling (`GL_EXT_multisampled_render_to_texture`).
+#[inline]
+pub fn is_multisampled_render_to_texture_supported<C>(context: &C) -> bool
+    where C: CapabilitiesSource
+{
+    context.get_extensions().gl_ext_multisampled_render_to_texture
+}
+
 /// Describes a single layered framebuffer attachment.
 #[derive(Copy, Clone)]
 pub struct LayeredAttachment<'a>(TextureAnyMipmap<'a>);
@@ -204,6 +218,7 @@ impl<'a> FramebufferAttachments<'a> {
                         layers: layers,
                         depth_buffer_bits: None,
                         stencil_buffer_bits: None,
+                        first_color_kind: None,
                         marker: PhantomData,
                     })
 
@@ -340,6 +355,9 @@ impl<'a> FramebufferAttachments<'a> {
             layers: None,       // FIXME: count layers
             depth_buffer_bits: depth_bits,
             stencil_buffer_bits: stencil_bits,
+            first_color_kind: colors.first().map(|&(_, LayeredAttachment(ref a))| {
+                a.get_texture().kind()
+            }),
             marker: PhantomData,
         })
     }
@@ -444,11 +462,81 @@ impl<'a> FramebufferAttachments<'a> {
             });
         }
 
+        macro_rules! handle_ms_tex {
+            // only color attachments can be multisampled textures, so there are no bits to count
+            ($tex:ident, $tex_samples:ident, $dim:ident, $samples:ident, $num_bits:ident) => ({
+                handle_ms_tex!($tex, $tex_samples, $dim, $samples)
+            });
+
+            ($tex:ident, $tex_samples:ident, $dim:ident, $samples:ident) => ({
+                let context = $tex.get_texture().get_context();
+
+                if !is_multisampled_render_to_texture_supported(context) {
+                    return Err(ValidationError::MultisampledRenderToTextureNotSupported);
+                }
+
+                if $tex.get_texture().get_bind_point() != gl::TEXTURE_2D {
+                    return Err(ValidationError::MultisampledRenderToTextureNotSupported);
+                }
+
+                match $tex.get_texture().kind() {
+                    TextureKind::Depth | TextureKind::Stencil | TextureKind::DepthStencil => {
+                        return Err(ValidationError::MultisampledRenderToTextureNotSupported);
+                    },
+                    _ => ()
+                }
+
+                match context.get_capabilities().max_samples {
+                    Some(max) if $tex_samples != 0 && $tex_samples <= max as u32 => (),
+                    _ => return Err(ValidationError::SamplesCountNotSupported),
+                }
+
+                match &mut $samples {
+                    &mut Some(samples) => {
+                        if samples != $tex_samples {
+                            return Err(ValidationError::SamplesCountMismatch);
+                        }
+                    },
+                    s @ &mut None => {
+                        *s = Some($tex_samples);
+                    }
+                }
+
+                match &mut $dim {
+                    &mut Some((ref mut w, ref mut h)) => {
+                        let height = $tex.get_height().unwrap_or(1);
+                        if *w != $tex.get_width() || *h != height {
+                            *w = cmp::min(*w, $tex.get_width());
+                            *h = cmp::min(*h, height);
+
+                            // checking that multiple different sizes is supported by the backend
+                            if !is_dimensions_mismatch_supported(context) {
+                                return Err(ValidationError::DimensionsMismatchNotSupported);
+                            }
+                        }
+                    },
+
+                    dim @ &mut None => {
+                        *dim = Some(($tex.get_width(), $tex.get_height().unwrap_or(1)));
+                    },
+                }
+
+                RawAttachment::MultisampledTexture {
+                    texture: $tex.get_texture().get_id(),
+                    level: $tex.get_level(),
+                    samples: $tex_samples,
+                }
+            });
+        }
+
         macro_rules! handle_atch {
             ($atch:ident, $($t:tt)*) => (
                 match $atch {
                     &RegularAttachment::Texture(ref tex) => handle_tex!(tex, $($t)*),
                     &RegularAttachment::RenderBuffer(ref rb) => handle_rb!(rb, $($t)*),
+                    &RegularAttachment::MultisampledTexture(ref tex, tex_samples) => {
+                        handle_ms_tex!(tex, tex_samples, $($t)*)
+                    },
                 }
             );
         }
@@ -519,6 +607,11 @@ impl<'a> FramebufferAttachments<'a> {
             layers: None,
             depth_buffer_bits: depth_bits,
             stencil_buffer_bits: stencil_bits,
+            first_color_kind: colors.first().map(|&(_, ref a)| match a {
+                &RegularAttachment::Texture(ref tex) => tex.get_texture().kind(),
+                &RegularAttachment::RenderBuffer(ref rb) => rb.kind(),
+                &RegularAttachment::MultisampledTexture(ref tex, _) => tex.get_texture().kind(),
+            }),
             marker: PhantomData,
         })
     }
@@ -532,6 +625,8 @@ pub struct ValidatedAttachments<'a> {
     layers: Option<u32>,
     depth_buffer_bits: Option<u16>,
     stencil_buffer_bits: Option<u16>,
+    // kind of the attachment bound to `GL_COLOR_ATTACHMENT0`
+    first_color_kind: Option<TextureKind>,
     marker: PhantomData<&'a ()>,
 }
 
@@ -542,6 +637,34 @@ impl<'a> ValidatedAttachments<'a> {
         self.layers.is_some()
     }
 
+    /// Returns the kind of the first color attachment, or `None` if there is no color
+    /// attachment.
+    #[inline]
+    pub fn get_first_color_attachment_kind(&self) -> Option<TextureKind> {
+        self.first_color_kind
+    }
+
+    /// Returns `true` if one of the attachments is a level of the texture with the given ID
+    /// between `levels.0` and `levels.1` included.
+    pub fn has_texture_attachment(&self, id: gl::types::GLuint, levels: (u32, u32)) -> bool {
+        let is_texture = |attachment: &RawAttachment| {
+            match *attachment {
+                RawAttachment::Texture { texture, level, .. } => {
+                    texture == id && level >= levels.0 && level <= levels.1
+                },
+                RawAttachment::MultisampledTexture { texture, level, .. } => {
+                    texture == id && level >= levels.0 && level <= levels.1
+                },
+                RawAttachment::RenderBuffer(_) => false,
+            }
+        };
+
+        self.raw.color.iter().any(|&(_, ref a)| is_texture(a)) ||
+        self.raw.depth.as_ref().map_or(false, |a| is_texture(a)) ||
+        self.raw.stencil.as_ref().map_or(false, |a| is_texture(a)) ||
+        self.raw.depth_stencil.as_ref().map_or(false, |a| is_texture(a))
+    }
+
     /// Returns the dimensions that the framebuffer will have if you use these attachments.
     #[inline]
     pub fn get_dimensions(&self) -> (u32, u32) {
@@ -561,6 +684,24 @@ impl<'a> ValidatedAttachments<'a> {
     pub fn get_stencil_buffer_bits(&self) -> Option<u16> {
         self.stencil_buffer_bits
     }
+
+    /// Changes the lifetime of the attachments.
+    ///
+    /// # Unsafety
+    ///
+    /// The objects that are attached must outlive the returned value.
+    #[inline]
+    pub unsafe fn extend_lifetime<'b>(self) -> ValidatedAttachments<'b> {
+        ValidatedAttachments {
+            raw: self.raw,
+            dimensions: self.dimensions,
+            layers: self.layers,
+            depth_buffer_bits: self.depth_buffer_bits,
+            stencil_buffer_bits: self.stencil_buffer_bits,
+            first_color_kind: self.first_color_kind,
+            marker: PhantomData,
+        }
+    }
 }
 
 /// An error that can happen while validating attachments.
@@ -581,6 +722,14 @@ pub enum ValidationError {
     /// All attachments must have the same number of samples.
     SamplesCountMismatch,
 
+    /// Rendering to a texture with implicit multisampling is not supported by the backend, or
+    /// the texture is not a two-dimensional color texture.
+    MultisampledRenderToTextureNotSupported,
+
+    /// The number of samples of a texture rendered to with implicit multisampling is zero or
+    /// greater than the maximum supported by the backend.
+    SamplesCountNotSupported,
+
     /// Backends only support a certain number of color attachments.
     TooManyColorAttachments {
         /// Maximum number of attachments.
@@ -588,6 +737,13 @@ pub enum ValidationError {
         /// Number of attachments that were given.
         obtained: usize,
     },
+
+    /// The backend reported that the framebuffer object is incomplete, for example because
+    /// the combination of formats of the attachments is not supported.
+    FramebufferIncomplete {
+        /// Description of the status returned by `glCheckFramebufferStatus`.
+        status: &'static str,
+    },
 }
 
 impl fmt::Display for ValidationError {
@@ -596,6 +752,8 @@ impl fmt::Display for ValidationError {
         match *self {
             TooManyColorAttachments{ ref maximum, ref obtained } =>
                 write!(fmt, "{}: found {}, maximum: {}", self.description(), obtained, maximum),
+            FramebufferIncomplete { status } =>
+                write!(fmt, "{}: {}", self.description(), status),
             _ =>
                 write!(fmt, "{}", self.description()),
         }
@@ -614,8 +772,14 @@ impl Error for ValidationError {
                 "The backend doesn't support attachments with various dimensions",
             SamplesCountMismatch =>
                 "All attachments must have the same number of samples",
+            MultisampledRenderToTextureNotSupported =>
+                "Rendering to a texture with implicit multisampling is not supported",
+            SamplesCountNotSupported =>
+                "The number of samples is not supported by the backend",
             TooManyColorAttachments {..} =>
                 "Backends only support a certain number of color attachments",
+            FramebufferIncomplete {..} =>
+                "The framebuffer object is incomplete",
         }
     }
 }
@@ -659,6 +823,16 @@ enum RawAttachment {
 
     /// A renderbuffer with its ID.
     RenderBuffer(gl::types::GLuint),
+
+    /// A 2D texture rendered to with `glFramebufferTexture2DMultisampleEXT`.
+    MultisampledTexture {
+        // id of the texture
+        texture: gl::types::GLuint,
+        // mipmap level
+        level: u32,
+        // number of samples used while rendering
+        samples: u32,
+    },
 }
 
 /// Data to pass to the `clear_buffer` function.
@@ -731,6 +905,7 @@ impl FramebuffersContainer {
         FramebuffersContainer::purge_if(ctxt, |a| {
             match a {
                 &RawAttachment::Texture { texture: id, .. } if id == texture => true,
+                &RawAttachment::MultisampledTexture { texture: id, .. } if id == texture => true,
                 _ => false
             }
         });
@@ -806,7 +981,7 @@ impl FramebuffersContainer {
                                        -> gl::types::GLuint
     {
         if let Some(attachments) = attachments {
-            FramebuffersContainer::get_framebuffer(ctxt, attachments)
+            FramebuffersContainer::get_framebuffer(ctxt, attachments).0
         } else {
             0
         }
@@ -845,9 +1020,92 @@ impl FramebuffersContainer {
             depth_stencil: DepthStencilAttachments::None,
         }).validate(ctxt).unwrap();
 
-        let framebuffer = FramebuffersContainer::get_framebuffer_for_drawing(ctxt, Some(&attachments));
+        FramebuffersContainer::bind_framebuffer_color_for_reading(ctxt, &attachments, 0);
+    }
+
+    /// Binds a framebuffer to `GL_READ_FRAMEBUFFER` or `GL_FRAMEBUFFER` and selects its color
+    /// attachment number `index` with `glReadBuffer`, so that it becomes the source of
+    /// `glReadPixels`, `glCopyTexImage2D`, etc.
+    ///
+    /// # Unsafety
+    ///
+    /// After calling this function, you **must** make sure to call `purge_texture`
+    /// and/or `purge_renderbuffer` when one of the attachment is destroyed.
+    pub unsafe fn bind_framebuffer_color_for_reading(ctxt: &mut CommandContext,
+                                                     attachments: &ValidatedAttachments,
+                                                     index: u32)
+    {
+        let framebuffer = FramebuffersContainer::get_framebuffer_for_drawing(ctxt, Some(attachments));
+        bind_framebuffer(ctxt, framebuffer, false, true);
+        FramebuffersContainer::set_read_buffer(ctxt, attachments, gl::COLOR_ATTACHMENT0 + index);
+    }
+
+    /// Returns the framebuffer object corresponding to the attachments, or 0 for the default
+    /// framebuffer, after making sure that its read buffer is its first color attachment so
+    /// that it can be used as the source of `glBlitFramebuffer`.
+    ///
+    /// Unless direct state access is supported, the framebuffer object is bound to
+    /// `GL_READ_FRAMEBUFFER`.
+    ///
+    /// # Unsafety
+    ///
+    /// After calling this function, you **must** make sure to call `purge_texture`
+    /// and/or `purge_renderbuffer` when one of the attachment is destroyed.
+    pub unsafe fn get_framebuffer_for_blitting(ctxt: &mut CommandContext,
+                                               attachments: Option<&ValidatedAttachments>)
+                                               -> gl::types::GLuint
+    {
+        let attachments = match attachments {
+            Some(a) => a,
+            None => return 0,
+        };
+
+        let framebuffer = FramebuffersContainer::get_framebuffer_for_drawing(ctxt, Some(attachments));
+        if !ctxt.capabilities.direct_state_access {
+            bind_framebuffer(ctxt, framebuffer, false, true);
+        }
+        FramebuffersContainer::set_read_buffer(ctxt, attachments, gl::COLOR_ATTACHMENT0);
+        framebuffer
+    }
+
+    /// Calls `glReadBuffer` on the framebuffer object corresponding to the attachments, unless
+    /// `read_buffer` is already its read buffer.
+    ///
+    /// The framebuffer object must already exist and, unless direct state access is supported,
+    /// be bound to `GL_READ_FRAMEBUFFER`.
+    unsafe fn set_read_buffer(ctxt: &mut CommandContext, attachments: &ValidatedAttachments,
+                              read_buffer: gl::types::GLenum)
+    {
+        let id = {
+            let mut framebuffers = ctxt.framebuffer_objects.framebuffers.borrow_mut();
+            let fbo = framebuffers.get_mut(&attachments.raw).unwrap();
+            if fbo.current_read_buffer == read_buffer {
+                return;
+            }
+
+            fbo.current_read_buffer = read_buffer;
+            fbo.id
+        };
+
+        if ctxt.capabilities.direct_state_access {
+            ctxt.gl.NamedFramebufferReadBuffer(id, read_buffer);
+        } else {
+            ctxt.gl.ReadBuffer(read_buffer);
+        }
+    }
+
+    /// Binds a framebuffer, or the default framebuffer if `None`, so that its depth buffer can
+    /// be read with `glReadPixels`.
+    ///
+    /// # Unsafety
+    ///
+    /// After calling this function, you **must** make sure to call `purge_texture`
+    /// and/or `purge_renderbuffer` when one of the attachment is destroyed.
+    pub unsafe fn bind_framebuffer_depth_for_reading(ctxt: &mut CommandContext,
+                                                     attachments: Option<&ValidatedAttachments>)
+    {
+        let framebuffer = FramebuffersContainer::get_framebuffer_for_drawing(ctxt, attachments);
         bind_framebuffer(ctxt, framebuffer, false, true);
-        ctxt.gl.ReadBuffer(gl::COLOR_ATTACHMENT0);     // TODO: cache
     }
 
     /// Calls `glClearBuffer` on a framebuffer that contains the attachment.
@@ -904,24 +1162,49 @@ impl FramebuffersContainer {
         }
     }
 
+    /// Builds the framebuffer object corresponding to the attachments if it doesn't exist yet,
+    /// and returns an error if the backend reported it as incomplete.
+    ///
+    /// Framebuffers must call this function when they are created, so that an incomplete
+    /// framebuffer is reported as an error instead of generating OpenGL errors when drawing.
+    /// The status of a framebuffer object is only queried once, when it is built.
+    ///
+    /// The framebuffer object is kept in the container afterwards, so you **must** make sure to
+    /// call `purge_texture` and/or `purge_renderbuffer` when one of the attachment is destroyed.
+    pub fn check_completeness(ctxt: &mut CommandContext, attachments: &ValidatedAttachments)
+                              -> Result<(), ValidationError>
+    {
+        let (_, status) = FramebuffersContainer::get_framebuffer(ctxt, attachments);
+
+        // the read buffer is only set when reading, so an incomplete read buffer is fine here
+        if status != gl::FRAMEBUFFER_COMPLETE && status != gl::FRAMEBUFFER_INCOMPLETE_READ_BUFFER {
+            return Err(ValidationError::FramebufferIncomplete { status: status_to_str(status) });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the id and the status of the framebuffer object corresponding to the
+    /// attachments, building it if it doesn't exist yet.
     ///
     /// # Unsafety
     ///
     /// After calling this function, you **must** make sure to call `purge_texture`
     /// and/or `purge_renderbuffer` when one of the attachment is destroyed.
     fn get_framebuffer(ctxt: &mut CommandContext, attachments: &ValidatedAttachments)
-                       -> gl::types::GLuint
+                       -> (gl::types::GLuint, gl::types::GLenum)
     {
         // TODO: use entries API
         let mut framebuffers = ctxt.framebuffer_objects.framebuffers.borrow_mut();
         if let Some(value) = framebuffers.get(&attachments.raw) {
-            return value.id;
+            return (value.id, value.status);
         }
 
         let new_fbo = FrameBufferObject::new(ctxt, &attachments.raw);
         let new_fbo_id = new_fbo.id.clone();
+        let new_fbo_status = new_fbo.status;
         framebuffers.insert(attachments.raw.clone(), new_fbo);
-        new_fbo_id
+        (new_fbo_id, new_fbo_status)
     }
 }
 
@@ -938,6 +1221,8 @@ impl Drop for FramebuffersContainer {
 struct FrameBufferObject {
     id: gl::types::GLuint,
     current_read_buffer: gl::types::GLenum,
+    /// Value returned by `glCheckFramebufferStatus` when the FBO was built.
+    status: gl::types::GLenum,
 }
 
 impl FrameBufferObject {
@@ -957,9 +1242,7 @@ impl FrameBufferObject {
         let id = unsafe {
             let mut id = mem::uninitialized();
 
-            if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-                ctxt.extensions.gl_arb_direct_state_access
-            {
+            if ctxt.capabilities.direct_state_access {
                 ctxt.gl.CreateFramebuffers(1, &mut id);
 
             } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
@@ -1073,9 +1356,7 @@ impl FrameBufferObject {
 
         // calling `glDrawBuffers` if necessary
         if raw_attachments != &[gl::COLOR_ATTACHMENT0] {
-            if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-               ctxt.extensions.gl_arb_direct_state_access
-            {
+            if ctxt.capabilities.direct_state_access {
                 unsafe {
                     ctxt.gl.NamedFramebufferDrawBuffers(id, raw_attachments.len()
                                                         as gl::types::GLsizei,
@@ -1105,16 +1386,20 @@ impl FrameBufferObject {
                                            raw_attachments.as_ptr());
                 }
 
-            } else {
+            } else if !raw_attachments.is_empty() {
                 // OpenGL ES 2 and OpenGL 1 don't support calling `glDrawBuffers`
+                // (framebuffers without any color attachment, for example depth-only
+                // framebuffers, don't need it)
                 panic!("Using more than one attachment is not supported by the backend");
             }
         }
 
+        let status = unsafe { check_status(&mut ctxt, id) };
 
         FrameBufferObject {
             id: id,
-            current_read_buffer: gl::BACK,
+            current_read_buffer: gl::COLOR_ATTACHMENT0,
+            status: status,
         }
     }
 
@@ -1143,6 +1428,46 @@ impl FrameBufferObject {
     }
 }
 
+/// Calls `glCheckFramebufferStatus` on the framebuffer.
+unsafe fn check_status(mut ctxt: &mut CommandContext, id: gl::types::GLuint) -> gl::types::GLenum {
+    if ctxt.capabilities.direct_state_access {
+        ctxt.gl.CheckNamedFramebufferStatus(id, gl::DRAW_FRAMEBUFFER)
+
+    } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+              ctxt.version >= &Version(Api::GlEs, 2, 0) ||
+              ctxt.extensions.gl_arb_framebuffer_object
+    {
+        bind_framebuffer(&mut ctxt, id, true, false);
+        ctxt.gl.CheckFramebufferStatus(gl::DRAW_FRAMEBUFFER)
+
+    } else if ctxt.extensions.gl_ext_framebuffer_object {
+        bind_framebuffer(&mut ctxt, id, true, false);
+        ctxt.gl.CheckFramebufferStatusEXT(gl::FRAMEBUFFER_EXT)
+
+    } else {
+        unreachable!();
+    }
+}
+
+/// Returns a human-readable description of the value returned by `glCheckFramebufferStatus`.
+fn status_to_str(status: gl::types::GLenum) -> &'static str {
+    match status {
+        gl::FRAMEBUFFER_UNDEFINED => "GL_FRAMEBUFFER_UNDEFINED",
+        gl::FRAMEBUFFER_INCOMPLETE_ATTACHMENT => "GL_FRAMEBUFFER_INCOMPLETE_ATTACHMENT \
+                                                  (an attachment is not attachment complete)",
+        gl::FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT => "GL_FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT \
+                                                          (no attachment and no default size)",
+        gl::FRAMEBUFFER_INCOMPLETE_DRAW_BUFFER => "GL_FRAMEBUFFER_INCOMPLETE_DRAW_BUFFER",
+        gl::FRAMEBUFFER_UNSUPPORTED => "GL_FRAMEBUFFER_UNSUPPORTED \
+                                        (the combination of formats is not supported)",
+        gl::FRAMEBUFFER_INCOMPLETE_MULTISAMPLE => "GL_FRAMEBUFFER_INCOMPLETE_MULTISAMPLE \
+                                                   (the attachments don't have the same number \
+                                                   of samples)",
+        gl::FRAMEBUFFER_INCOMPLETE_LAYER_TARGETS => "GL_FRAMEBUFFER_INCOMPLETE_LAYER_TARGETS",
+        _ => "unknown status",
+    }
+}
+
 impl GlObject for FrameBufferObject {
     type Id = gl::types::GLuint;
 
@@ -1248,9 +1573,7 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
                     assert_eq!(layer, Some(0));
                     debug_assert!(cubemap_layer.is_none());
 
-                    if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-                       ctxt.extensions.gl_arb_direct_state_access
-                    {
+                    if ctxt.capabilities.direct_state_access {
                         ctxt.gl.NamedFramebufferTexture(id, slot, tex_id,
                                                         level as gl::types::GLint);
 
@@ -1324,9 +1647,7 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
                         layer.unwrap()
                     };
 
-                    if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-                       ctxt.extensions.gl_arb_direct_state_access
-                    {
+                    if ctxt.capabilities.direct_state_access {
                         ctxt.gl.NamedFramebufferTextureLayer(id, slot, tex_id,
                                                              level as gl::types::GLint,
                                                              layer as gl::types::GLint);
@@ -1392,9 +1713,7 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
                 gl::TEXTURE_1D_ARRAY | gl::TEXTURE_2D_ARRAY | gl::TEXTURE_2D_MULTISAMPLE_ARRAY |
                 gl::TEXTURE_3D | gl::TEXTURE_CUBE_MAP_ARRAY if layer.is_none() =>
                 {
-                    if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-                       ctxt.extensions.gl_arb_direct_state_access
-                    {
+                    if ctxt.capabilities.direct_state_access {
                         ctxt.gl.NamedFramebufferTexture(id, slot, tex_id,
                                                         level as gl::types::GLint);
 
@@ -1447,9 +1766,7 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
 
                 // layered cubemaps
                 gl::TEXTURE_CUBE_MAP if layer.is_none() => {
-                    if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-                       ctxt.extensions.gl_arb_direct_state_access
-                    {
+                    if ctxt.capabilities.direct_state_access {
                         ctxt.gl.NamedFramebufferTexture(id, slot, tex_id,
                                                         level as gl::types::GLint);
 
@@ -1474,11 +1791,22 @@ unsafe fn attach(ctxt: &mut CommandContext, slot: gl::types::GLenum,
             }
         },
 
+        RawAttachment::MultisampledTexture { texture: tex_id, level, samples } => {
+            if ctxt.extensions.gl_ext_multisampled_render_to_texture {
+                bind_framebuffer(ctxt, id, true, true);
+                ctxt.gl.FramebufferTexture2DMultisampleEXT(gl::FRAMEBUFFER, slot, gl::TEXTURE_2D,
+                                                           tex_id, level as gl::types::GLint,
+                                                           samples as gl::types::GLsizei);
+
+            } else {
+                // note that this should have been detected earlier
+                panic!("Multisampled render to texture is not supported");
+            }
+        },
+
         // renderbuffers are straight-forward
         RawAttachment::RenderBuffer(renderbuffer) => {
-            if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-               ctxt.extensions.gl_arb_direct_state_access
-            {
+            if ctxt.capabilities.direct_state_access {
                 ctxt.gl.NamedFramebufferRenderbuffer(id, slot, gl::RENDERBUFFER, renderbuffer);
 
             } else if ctxt.extensions.gl_ext_direct_state_access &&
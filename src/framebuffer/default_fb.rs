@@ -17,6 +17,7 @@ use uniforms;
 
 use {Program, Surface};
 use DrawError;
+use ReadError;
 
 use {fbo, gl};
 use framebuffer;
@@ -75,34 +76,31 @@ impl Surface for DefaultFramebuffer {
         self.context.capabilities().stencil_bits
     }
 
+    fn read_depth_rect(&self, rect: &Rect) -> Result<Vec<f32>, ReadError> {
+        ops::read_depth(&self.context, None, rect)
+    }
+
     fn draw<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
                          index_buffer: I, program: &Program, uniforms: &U,
                          draw_parameters: &DrawParameters) -> Result<(), DrawError>
                          where I: Into<index::IndicesSource<'a>>, U: uniforms::Uniforms,
                          V: vertex::MultiVerticesSource<'b>
     {
-        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
-                draw_parameters.depth.write)
-        {
-            return Err(DrawError::NoDepthBuffer);
-        }
-
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-        }
-
         // TODO: wrong attachment
-        ops::draw(&self.context, None, vertex_buffer, index_buffer.into(), program,
-                  uniforms, draw_parameters, self.get_dimensions())
+        ops::draw_on_surface(self, &self.context, None,
+                             vertex_buffer, index_buffer, program, uniforms, draw_parameters, false)
+    }
+
+    fn draw_with_pipeline<'i, 'v, V, I, U>(&mut self, pipeline: &::draw_parameters::Pipeline,
+                                           vertices: V, indices: I, uniforms: &U)
+                                           -> Result<(), DrawError>
+                                           where V: ::vertex::MultiVerticesSource<'v>,
+                                                 I: Into<::index::IndicesSource<'i>>,
+                                                 U: ::uniforms::Uniforms
+    {
+        ops::draw_on_surface(self, &self.context, None,
+                             vertices, indices, pipeline.get_program(), uniforms,
+                             pipeline.get_draw_parameters(), true)
     }
 
     #[inline]
@@ -0,0 +1,149 @@
+/*!
+Floating-point render targets for high dynamic range rendering.
+
+An `HdrRenderTarget` owns a floating-point color texture and an optional depth buffer. If the
+requested precision can't be rendered to, the closest precision that can is used instead.
+
+```no_run
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+use glium::framebuffer::{HdrRenderTarget, HdrPrecision};
+
+let target = HdrRenderTarget::new(&display, HdrPrecision::F16, 1024, 768, None).unwrap();
+if target.get_precision() != HdrPrecision::F16 {
+    println!("Fell back to {:?}", target.get_precision());
+}
+
+let mut framebuffer = target.as_surface();
+// framebuffer.draw(...);
+// then sample `target.get_color()` when tonemapping
+```
+
+*/
+use backend::Facade;
+use CapabilitiesSource;
+use TextureExt;
+
+use texture::{Texture2d, DepthFormat, MipmapsOption, TextureFormat, UncompressedFloatFormat};
+use texture::TextureCreationError;
+
+use super::{DepthRenderBuffer, SimpleFrameBuffer};
+
+/// Precision of the components of an `HdrRenderTarget`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HdrPrecision {
+    /// Four 16 bits floats per pixel.
+    F16,
+
+    /// Four 32 bits floats per pixel.
+    F32,
+}
+
+impl HdrPrecision {
+    #[inline]
+    fn to_format(&self) -> UncompressedFloatFormat {
+        match *self {
+            HdrPrecision::F16 => UncompressedFloatFormat::F16F16F16F16,
+            HdrPrecision::F32 => UncompressedFloatFormat::F32F32F32F32,
+        }
+    }
+
+    /// Returns the list of precisions to try, in order of preference.
+    #[inline]
+    fn candidates(&self) -> [HdrPrecision; 2] {
+        match *self {
+            HdrPrecision::F16 => [HdrPrecision::F16, HdrPrecision::F32],
+            HdrPrecision::F32 => [HdrPrecision::F32, HdrPrecision::F16],
+        }
+    }
+
+    /// Returns true if the backend can render to a texture of this precision.
+    pub fn is_renderable<C>(&self, context: &C) -> bool where C: CapabilitiesSource {
+        let format = self.to_format();
+
+        if !format.is_color_renderable(context) {
+            return false;
+        }
+
+        // `GL_ARB_internalformat_query2` gives a more precise answer, if available
+        match context.get_capabilities().internal_formats_textures
+                     .get(&TextureFormat::UncompressedFloat(format))
+        {
+            Some(infos) => infos.color_renderable != Some(false),
+            None => false,
+        }
+    }
+}
+
+/// A floating-point color texture and an optional depth buffer.
+///
+/// See the documentation of the module for more infos.
+pub struct HdrRenderTarget {
+    color: Texture2d,
+    depth: Option<DepthRenderBuffer>,
+    precision: HdrPrecision,
+}
+
+impl HdrRenderTarget {
+    /// Builds a new render target.
+    ///
+    /// If `precision` can't be rendered to, the other precision is used instead. Returns
+    /// `FormatNotSupported` if the backend can't render to any floating-point format.
+    pub fn new<F>(facade: &F, precision: HdrPrecision, width: u32, height: u32,
+                  depth: Option<DepthFormat>) -> Result<HdrRenderTarget, TextureCreationError>
+                  where F: Facade
+    {
+        let precision = match precision.candidates().iter()
+                                       .find(|p| p.is_renderable(&**facade.get_context()))
+        {
+            Some(p) => *p,
+            None => return Err(TextureCreationError::FormatNotSupported),
+        };
+
+        let color = try!(Texture2d::empty_with_format(facade, precision.to_format(),
+                                                      MipmapsOption::NoMipmap, width, height));
+
+        let depth = match depth {
+            Some(format) => {
+                match DepthRenderBuffer::new(facade, format, width, height) {
+                    Ok(d) => Some(d),
+                    Err(_) => return Err(TextureCreationError::FormatNotSupported),
+                }
+            },
+            None => None,
+        };
+
+        Ok(HdrRenderTarget {
+            color: color,
+            depth: depth,
+            precision: precision,
+        })
+    }
+
+    /// Returns the precision that was chosen when creating the target.
+    #[inline]
+    pub fn get_precision(&self) -> HdrPrecision {
+        self.precision
+    }
+
+    /// Returns the color texture.
+    #[inline]
+    pub fn get_color(&self) -> &Texture2d {
+        &self.color
+    }
+
+    /// Returns the depth buffer, if any.
+    #[inline]
+    pub fn get_depth(&self) -> Option<&DepthRenderBuffer> {
+        self.depth.as_ref()
+    }
+
+    /// Returns a framebuffer that draws on the color texture and the depth buffer.
+    pub fn as_surface(&self) -> SimpleFrameBuffer {
+        let context = self.color.get_context();
+
+        match self.depth {
+            Some(ref depth) => SimpleFrameBuffer::with_depth_buffer(context, &self.color, depth),
+            None => SimpleFrameBuffer::new(context, &self.color),
+        }.unwrap()      // the formats were checked when creating the target
+    }
+}
@@ -61,15 +61,28 @@ Modern OpenGL implementations support empty framebuffers. This is handled by gli
 
 You can check whether they are supported by calling `EmptyFrameBuffer::is_supported(&display)`.
 
+# Multisampled rendering to a texture
+
+If you want to draw with multisampling on a regular texture, use a `MultisampleFrameBuffer`.
+When the backend supports it, the samples are resolved implicitly by the hardware, which is
+much faster on tiled GPUs. Otherwise they are stored in a renderbuffer and must be resolved
+by calling `resolve()`.
+
+# High dynamic range
+
+An `HdrRenderTarget` creates a floating-point texture that can be rendered to, and falls back
+to another precision if the requested one isn't supported.
+
 # Layered framebuffers
 
 Not yet supported
 
 */
+use std::borrow::Cow;
 use std::rc::Rc;
 use smallvec::SmallVec;
 
-use texture::TextureAnyImage;
+use texture::{PixelValue, Texture2dDataSink, TextureAnyImage};
 use TextureExt;
 
 use backend::Facade;
@@ -88,6 +101,7 @@ use uniforms;
 
 use {Program, Surface};
 use DrawError;
+use ReadError;
 
 use {fbo, gl};
 
@@ -95,10 +109,16 @@ pub use self::default_fb::{DefaultFramebufferAttachment, DefaultFramebuffer};
 pub use self::render_buffer::{RenderBuffer, RenderBufferAny, DepthRenderBuffer};
 pub use self::render_buffer::{StencilRenderBuffer, DepthStencilRenderBuffer};
 pub use self::render_buffer::CreationError as RenderBufferCreationError;
+pub use self::multisample::{MultisampleFrameBuffer, MultisampleFrameBufferCreationError};
+pub use self::hdr::{HdrRenderTarget, HdrPrecision};
 pub use fbo::is_dimensions_mismatch_supported;
 pub use fbo::ValidationError;
+pub use ops::{Attachment, AttachmentInfo, AttachmentKind, AttachmentComponentType};
+pub use ops::SampleLocationsError;
 
 mod default_fb;
+mod hdr;
+mod multisample;
 mod render_buffer;
 
 /// A framebuffer which has only one color attachment.
@@ -129,8 +149,10 @@ impl<'a> SimpleFrameBuffer<'a> {
                                     Some(depth.to_depth_attachment()), None, None)
     }
 
-    /// Creates a `SimpleFrameBuffer` with a single color attachment and no depth
-    /// nor stencil buffer.
+    /// Creates a `SimpleFrameBuffer` with a depth attachment and no color attachment.
+    ///
+    /// The dimensions of the framebuffer are the dimensions of the depth attachment. This is
+    /// typically used for shadow maps.
     #[inline]
     pub fn depth_only<F, D>(facade: &F, depth: D)
                             -> Result<SimpleFrameBuffer<'a>, ValidationError>
@@ -258,11 +280,55 @@ impl<'a> SimpleFrameBuffer<'a> {
 
         let attachments = try!(attachments.validate(facade));
 
+        {
+            let mut ctxt = facade.get_context().make_current();
+            try!(fbo::FramebuffersContainer::check_completeness(&mut ctxt, &attachments));
+        }
+
         Ok(SimpleFrameBuffer {
             context: facade.get_context().clone(),
             attachments: attachments,
         })
     }
+
+    /// Returns the position of each sample of the framebuffer within a pixel, with each
+    /// coordinate between `0.0` and `1.0`.
+    ///
+    /// A framebuffer without multisampling has a single sample at `[0.5, 0.5]`. Returns `None`
+    /// if querying the positions is not supported by the backend.
+    #[inline]
+    pub fn get_sample_positions(&self) -> Option<Vec<[f32; 2]>> {
+        ops::get_sample_positions(&self.context, Some(&self.attachments))
+    }
+
+    /// Sets the position of the samples of the framebuffer, with each coordinate between `0.0`
+    /// and `1.0`. Passing an empty slice restores the default positions.
+    ///
+    /// There must be exactly one location per sample of the framebuffer, otherwise
+    /// `SamplesCountMismatch` is returned. This requires the `GL_ARB_sample_locations`
+    /// extension.
+    ///
+    /// The positions are stored in the OpenGL framebuffer object, which glium shares between
+    /// all the framebuffers that have the same attachments.
+    #[inline]
+    pub fn set_sample_locations(&self, locations: &[[f32; 2]])
+                                -> Result<(), SampleLocationsError>
+    {
+        ops::set_sample_locations(&self.context, Some(&self.attachments), locations)
+    }
+
+    /// Returns informations about one of the attachments of the framebuffer, as reported by
+    /// the backend with `glGetFramebufferAttachmentParameteriv`.
+    ///
+    /// This allows checking the actual format and number of samples that the backend has
+    /// chosen for the attachment, which can differ from the requested one.
+    ///
+    /// Returns `None` if nothing is attached at this point, or if the backend doesn't support
+    /// this query.
+    #[inline]
+    pub fn attachment_info(&self, attachment: Attachment) -> Option<AttachmentInfo> {
+        ops::get_attachment_info(&self.context, &self.attachments, attachment)
+    }
 }
 
 impl<'a> Surface for SimpleFrameBuffer<'a> {
@@ -288,32 +354,30 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
         self.attachments.get_stencil_buffer_bits()
     }
 
+    #[inline]
+    fn read_depth_rect(&self, rect: &Rect) -> Result<Vec<f32>, ReadError> {
+        ops::read_depth(&self.context, Some(&self.attachments), rect)
+    }
+
     fn draw<'b, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &::Program,
         uniforms: &U, draw_parameters: &::DrawParameters) -> Result<(), DrawError>
         where I: Into<::index::IndicesSource<'b>>, U: ::uniforms::Uniforms,
         V: ::vertex::MultiVerticesSource<'v>
     {
-        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
-                        draw_parameters.depth.write)
-        {
-            return Err(DrawError::NoDepthBuffer);
-        }
-
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-        }
+        ops::draw_on_surface(self, &self.context, Some(&self.attachments),
+                             vb, ib, program, uniforms, draw_parameters, false)
+    }
 
-        ops::draw(&self.context, Some(&self.attachments), vb,
-                  ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
+    fn draw_with_pipeline<'i, 'v, V, I, U>(&mut self, pipeline: &::draw_parameters::Pipeline,
+                                           vertices: V, indices: I, uniforms: &U)
+                                           -> Result<(), DrawError>
+                                           where V: ::vertex::MultiVerticesSource<'v>,
+                                                 I: Into<::index::IndicesSource<'i>>,
+                                                 U: ::uniforms::Uniforms
+    {
+        ops::draw_on_surface(self, &self.context, Some(&self.attachments),
+                             vertices, indices, pipeline.get_program(), uniforms,
+                             pipeline.get_draw_parameters(), true)
     }
 
     #[inline]
@@ -505,6 +569,11 @@ impl<'a> MultiOutputFrameBuffer<'a> {
             depth_stencil: depth_stencil_attachments,
         }).validate(facade));
 
+        {
+            let mut ctxt = facade.get_context().make_current();
+            try!(fbo::FramebuffersContainer::check_completeness(&mut ctxt, &example_attachments));
+        }
+
         Ok(MultiOutputFrameBuffer {
             context: facade.get_context().clone(),
             example_attachments: example_attachments,
@@ -513,6 +582,56 @@ impl<'a> MultiOutputFrameBuffer<'a> {
         })
     }
 
+    /// Returns the position of each sample of the framebuffer within a pixel, with each
+    /// coordinate between `0.0` and `1.0`.
+    ///
+    /// See `SimpleFrameBuffer::get_sample_positions`.
+    #[inline]
+    pub fn get_sample_positions(&self) -> Option<Vec<[f32; 2]>> {
+        ops::get_sample_positions(&self.context, Some(&self.example_attachments))
+    }
+
+    /// Sets the position of the samples of the framebuffer.
+    ///
+    /// See `SimpleFrameBuffer::set_sample_locations`.
+    #[inline]
+    pub fn set_sample_locations(&self, locations: &[[f32; 2]])
+                                -> Result<(), SampleLocationsError>
+    {
+        ops::set_sample_locations(&self.context, Some(&self.example_attachments), locations)
+    }
+
+    /// Reads the content of one of the color attachments of the framebuffer.
+    ///
+    /// `index` is the position of the attachment in the list that was passed when creating the
+    /// framebuffer. The attachment is selected with `glReadBuffer`, which means that attachments
+    /// other than the first one can be read without building a new framebuffer.
+    ///
+    /// # Panic
+    ///
+    /// Panicks if `index` or the rect is out of range.
+    ///
+    pub fn read_color_attachment<T, P>(&self, index: usize, rect: &Rect) -> Result<T, ReadError>
+        where T: Texture2dDataSink<P>, P: PixelValue
+    {
+        let attachment = match self.color_attachments.get(index) {
+            Some(&(_, ref attachment)) => attachment,
+            None => panic!("The color attachment {} doesn't exist", index),
+        };
+
+        let (width, height) = self.example_attachments.get_dimensions();
+        assert!(rect.left + rect.width <= width);
+        assert!(rect.bottom + rect.height <= height);
+
+        let mut ctxt = self.context.make_current();
+
+        let mut data = Vec::new();
+        let source = ops::Source::ColorAttachment(&self.example_attachments, index as u32,
+                                                  attachment);
+        try!(ops::read(&mut ctxt, source, rect, &mut data, None, false));
+        Ok(T::from_raw(Cow::Owned(data), rect.width, rect.height))
+    }
+
     fn build_attachments(&self, program: &Program) -> fbo::ValidatedAttachments {
         let mut colors = SmallVec::new();
 
@@ -556,32 +675,31 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
         self.example_attachments.get_stencil_buffer_bits()
     }
 
+    #[inline]
+    fn read_depth_rect(&self, rect: &Rect) -> Result<Vec<f32>, ReadError> {
+        ops::read_depth(&self.context, Some(&self.example_attachments), rect)
+    }
+
     fn draw<'i, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &::Program,
         uniforms: &U, draw_parameters: &::DrawParameters) -> Result<(), DrawError>
         where I: Into<::index::IndicesSource<'i>>,
         U: ::uniforms::Uniforms, V: ::vertex::MultiVerticesSource<'v>
     {
-        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
-                draw_parameters.depth.write)
-        {
-            return Err(DrawError::NoDepthBuffer);
-        }
-
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-        }
+        ops::draw_on_surface(self, &self.context, Some(&self.build_attachments(program)),
+                             vb, ib, program, uniforms, draw_parameters, false)
+    }
 
-        ops::draw(&self.context, Some(&self.build_attachments(program)), vb,
-                  ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
+    fn draw_with_pipeline<'i, 'v, V, I, U>(&mut self, pipeline: &::draw_parameters::Pipeline,
+                                           vertices: V, indices: I, uniforms: &U)
+                                           -> Result<(), DrawError>
+                                           where V: ::vertex::MultiVerticesSource<'v>,
+                                                 I: Into<::index::IndicesSource<'i>>,
+                                                 U: ::uniforms::Uniforms
+    {
+        let program = pipeline.get_program();
+        ops::draw_on_surface(self, &self.context, Some(&self.build_attachments(program)),
+                             vertices, indices, program, uniforms,
+                             pipeline.get_draw_parameters(), true)
     }
 
     #[inline]
@@ -695,6 +813,11 @@ impl<'a> EmptyFrameBuffer {
 
         let attachments = try!(attachments.validate(context));
 
+        {
+            let mut ctxt = context.make_current();
+            try!(fbo::FramebuffersContainer::check_completeness(&mut ctxt, &attachments));
+        }
+
         Ok(EmptyFrameBuffer {
             context: context.clone(),
             attachments: attachments,
@@ -725,32 +848,30 @@ impl Surface for EmptyFrameBuffer {
         None
     }
 
+    #[inline]
+    fn read_depth_rect(&self, rect: &Rect) -> Result<Vec<f32>, ReadError> {
+        ops::read_depth(&self.context, Some(&self.attachments), rect)
+    }
+
     fn draw<'b, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &::Program,
         uniforms: &U, draw_parameters: &::DrawParameters) -> Result<(), DrawError>
         where I: Into<::index::IndicesSource<'b>>, U: ::uniforms::Uniforms,
         V: ::vertex::MultiVerticesSource<'v>
     {
-        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
-                        draw_parameters.depth.write)
-        {
-            return Err(DrawError::NoDepthBuffer);
-        }
-
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-        }
+        ops::draw_on_surface(self, &self.context, Some(&self.attachments),
+                             vb, ib, program, uniforms, draw_parameters, false)
+    }
 
-        ops::draw(&self.context, Some(&self.attachments), vb,
-                  ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
+    fn draw_with_pipeline<'i, 'v, V, I, U>(&mut self, pipeline: &::draw_parameters::Pipeline,
+                                           vertices: V, indices: I, uniforms: &U)
+                                           -> Result<(), DrawError>
+                                           where V: ::vertex::MultiVerticesSource<'v>,
+                                                 I: Into<::index::IndicesSource<'i>>,
+                                                 U: ::uniforms::Uniforms
+    {
+        ops::draw_on_surface(self, &self.context, Some(&self.attachments),
+                             vertices, indices, pipeline.get_program(), uniforms,
+                             pipeline.get_draw_parameters(), true)
     }
 
     #[inline]
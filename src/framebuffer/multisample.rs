@@ -0,0 +1,396 @@
+/*!
+Multisampled rendering into a regular, single-sample texture.
+
+Many OpenGL ES implementations running on tiled GPUs support the
+`GL_EXT_multisampled_render_to_texture` extension. With this extension the multisampled data
+only lives in on-chip memory and is resolved implicitly when the tile is written back to the
+texture, which is a lot cheaper than drawing to a multisampled renderbuffer and blitting it
+afterwards.
+
+A `MultisampleFrameBuffer` uses this extension when it is available. Otherwise it falls back
+to drawing to a multisampled renderbuffer that is resolved into the texture when you call
+`resolve()`.
+
+```no_run
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+# let texture: glium::texture::Texture2d = unsafe { ::std::mem::uninitialized() };
+let mut framebuffer = glium::framebuffer::MultisampleFrameBuffer::new(&display, &texture, 4)
+                                                                     .unwrap();
+// framebuffer.draw(...);
+framebuffer.resolve();      // `texture` now contains the result
+```
+
+*/
+use std::rc::Rc;
+use std::fmt;
+use std::error::Error;
+use smallvec::SmallVec;
+
+use texture::{Texture2d, DepthFormat, UncompressedFloatFormat};
+use image_format::{TextureFormatRequest, TextureFormat};
+
+use backend::Facade;
+use context::Context;
+use CapabilitiesSource;
+
+use FboAttachments;
+use Rect;
+use BlitTarget;
+use ToGlEnum;
+use ContextExt;
+use ops;
+use uniforms;
+
+use Surface;
+use DrawError;
+use ReadError;
+
+use {fbo, gl};
+
+use super::{ColorAttachment, ToColorAttachment, SimpleFrameBuffer, MultiOutputFrameBuffer};
+use super::{RenderBuffer, DepthRenderBuffer, RenderBufferCreationError, ValidationError};
+
+/// Error that can happen while creating a `MultisampleFrameBuffer`.
+#[derive(Copy, Clone, Debug)]
+pub enum MultisampleFrameBufferCreationError {
+    /// One of the multisampled render buffers couldn't be created.
+    RenderBufferCreationError(RenderBufferCreationError),
+
+    /// The attachments couldn't be validated.
+    ValidationError(ValidationError),
+
+    /// Multisampling requires an explicit resolve, and the format of the texture can't be used
+    /// for a multisampled render buffer.
+    FormatNotSupported,
+}
+
+impl fmt::Display for MultisampleFrameBufferCreationError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::MultisampleFrameBufferCreationError::*;
+        match *self {
+            RenderBufferCreationError(ref err) => write!(fmt, "{}: {}", self.description(), err),
+            ValidationError(ref err) => write!(fmt, "{}: {}", self.description(), err),
+            FormatNotSupported => write!(fmt, "{}", self.description()),
+        }
+    }
+}
+
+impl Error for MultisampleFrameBufferCreationError {
+    fn description(&self) -> &str {
+        use self::MultisampleFrameBufferCreationError::*;
+        match *self {
+            RenderBufferCreationError(_) =>
+                "Error while creating the multisampled render buffers",
+            ValidationError(_) =>
+                "Error while validating the attachments",
+            FormatNotSupported =>
+                "The format of the texture can't be used for a multisampled render buffer",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        use self::MultisampleFrameBufferCreationError::*;
+        match *self {
+            RenderBufferCreationError(ref err) => Some(err),
+            ValidationError(ref err) => Some(err),
+            FormatNotSupported => None,
+        }
+    }
+}
+
+impl From<RenderBufferCreationError> for MultisampleFrameBufferCreationError {
+    #[inline]
+    fn from(err: RenderBufferCreationError) -> MultisampleFrameBufferCreationError {
+        MultisampleFrameBufferCreationError::RenderBufferCreationError(err)
+    }
+}
+
+impl From<ValidationError> for MultisampleFrameBufferCreationError {
+    #[inline]
+    fn from(err: ValidationError) -> MultisampleFrameBufferCreationError {
+        MultisampleFrameBufferCreationError::ValidationError(err)
+    }
+}
+
+/// Returns the format of a multisampled render buffer whose samples can be resolved into
+/// `texture`, or `None` if the texture doesn't have a floating-point format.
+fn get_render_buffer_format(texture: &Texture2d) -> Option<UncompressedFloatFormat> {
+    use texture::InternalFormat::*;
+    use texture::InternalFormatType::UnsignedNormalized as Un;
+
+    match texture.get_requested_format() {
+        TextureFormatRequest::Specific(TextureFormat::UncompressedFloat(f)) => Some(f),
+
+        TextureFormatRequest::AnyFloatingPoint => match texture.get_internal_format() {
+            Ok(OneComponent { ty1: Un, bits1: 8 }) => Some(UncompressedFloatFormat::U8),
+            Ok(TwoComponents { ty1: Un, bits1: 8, ty2: Un, bits2: 8 }) => {
+                Some(UncompressedFloatFormat::U8U8)
+            },
+            Ok(ThreeComponents { ty1: Un, bits1: 8, ty2: Un, bits2: 8, ty3: Un, bits3: 8 }) => {
+                Some(UncompressedFloatFormat::U8U8U8)
+            },
+            Ok(FourComponents { ty1: Un, bits1: 8, ty2: Un, bits2: 8, ty3: Un, bits3: 8,
+                                ty4: Un, bits4: 8 }) => Some(UncompressedFloatFormat::U8U8U8U8),
+            Ok(_) => None,
+            // the backend can't tell us the format, but `AnyFloatingPoint` textures created
+            // without data are always `RGBA8`
+            Err(_) => Some(UncompressedFloatFormat::U8U8U8U8),
+        },
+
+        _ => None,
+    }
+}
+
+/// A framebuffer that draws with multisampling on a single-sample `Texture2d`.
+///
+/// See the documentation of the module for more infos.
+pub struct MultisampleFrameBuffer<'a> {
+    context: Rc<Context>,
+    attachments: fbo::ValidatedAttachments<'a>,
+    color: &'a Texture2d,
+    // contains the texture as a single-sample attachment if a resolve is needed
+    resolve_target: Option<fbo::ValidatedAttachments<'a>>,
+    // `attachments` may point to these buffers, so they must be boxed and must never be moved
+    #[allow(dead_code)]
+    color_buffer: Option<Box<RenderBuffer>>,
+    #[allow(dead_code)]
+    depth_buffer: Option<Box<DepthRenderBuffer>>,
+}
+
+impl<'a> MultisampleFrameBuffer<'a> {
+    /// Returns true if the backend can render to a single-sample texture with implicit
+    /// multisampling.
+    ///
+    /// If this returns false, a `MultisampleFrameBuffer` can still be created but requires an
+    /// explicit resolve.
+    #[inline]
+    pub fn is_implicit_resolve_supported<C>(context: &C) -> bool where C: CapabilitiesSource {
+        fbo::is_multisampled_render_to_texture_supported(context)
+    }
+
+    /// Creates a `MultisampleFrameBuffer` that draws on `color` with the given number of
+    /// samples, and without any depth buffer.
+    #[inline]
+    pub fn new<F>(facade: &F, color: &'a Texture2d, samples: u32)
+                  -> Result<MultisampleFrameBuffer<'a>, MultisampleFrameBufferCreationError>
+                  where F: Facade
+    {
+        MultisampleFrameBuffer::new_impl(facade, color, None, samples)
+    }
+
+    /// Creates a `MultisampleFrameBuffer` that draws on `color` with the given number of
+    /// samples, and with a multisampled depth buffer of the given format.
+    #[inline]
+    pub fn with_depth_buffer<F>(facade: &F, color: &'a Texture2d, depth: DepthFormat,
+                                samples: u32)
+                                -> Result<MultisampleFrameBuffer<'a>,
+                                          MultisampleFrameBufferCreationError>
+                                where F: Facade
+    {
+        MultisampleFrameBuffer::new_impl(facade, color, Some(depth), samples)
+    }
+
+    fn new_impl<F>(facade: &F, color: &'a Texture2d, depth: Option<DepthFormat>, samples: u32)
+                   -> Result<MultisampleFrameBuffer<'a>, MultisampleFrameBufferCreationError>
+                   where F: Facade
+    {
+        let image = match color.to_color_attachment() {
+            ColorAttachment::Texture(image) => image,
+            _ => unreachable!()
+        };
+
+        let (width, height) = (image.get_width(), image.get_height().unwrap_or(1));
+
+        match facade.get_context().get_capabilities().max_samples {
+            Some(max) if samples != 0 && samples <= max as u32 => (),
+            _ => return Err(ValidationError::SamplesCountNotSupported.into()),
+        }
+
+        let depth_buffer = match depth {
+            Some(format) => {
+                Some(Box::new(try!(DepthRenderBuffer::new_multisample(facade, format, width,
+                                                                      height, samples))))
+            },
+            None => None,
+        };
+
+        let implicit = MultisampleFrameBuffer::is_implicit_resolve_supported(facade);
+
+        let color_buffer = if implicit {
+            None
+        } else {
+            let format = match get_render_buffer_format(color) {
+                Some(f) => f,
+                None => return Err(MultisampleFrameBufferCreationError::FormatNotSupported),
+            };
+
+            Some(Box::new(try!(RenderBuffer::new_multisample(facade, format, width, height,
+                                                             samples))))
+        };
+
+        // the renderbuffers are boxed and owned by the framebuffer, so they live as long as
+        // the attachments
+        let attachments = {
+            let color_attachment = match color_buffer {
+                Some(ref buffer) => fbo::RegularAttachment::RenderBuffer(&***buffer),
+                None => fbo::RegularAttachment::MultisampledTexture(image, samples),
+            };
+
+            let depth_attachment = match depth_buffer {
+                Some(ref depth) => fbo::DepthStencilAttachments::DepthAttachment(
+                                                fbo::RegularAttachment::RenderBuffer(&***depth)),
+                None => fbo::DepthStencilAttachments::None,
+            };
+
+            let attachments = fbo::FramebufferAttachments::Regular(fbo::FramebufferSpecificAttachments {
+                colors: { let mut v = SmallVec::new(); v.push((0, color_attachment)); v },
+                depth_stencil: depth_attachment,
+            });
+
+            unsafe { try!(attachments.validate(facade)).extend_lifetime() }
+        };
+
+        let resolve_target = if color_buffer.is_some() {
+            let attachments = fbo::FramebufferAttachments::Regular(fbo::FramebufferSpecificAttachments {
+                colors: { let mut v = SmallVec::new();
+                          v.push((0, fbo::RegularAttachment::Texture(image))); v },
+                depth_stencil: fbo::DepthStencilAttachments::None,
+            });
+
+            Some(try!(attachments.validate(facade)))
+        } else {
+            None
+        };
+
+        {
+            let mut ctxt = facade.get_context().make_current();
+            try!(fbo::FramebuffersContainer::check_completeness(&mut ctxt, &attachments));
+            if let Some(ref resolve_target) = resolve_target {
+                try!(fbo::FramebuffersContainer::check_completeness(&mut ctxt, resolve_target));
+            }
+        }
+
+        Ok(MultisampleFrameBuffer {
+            context: facade.get_context().clone(),
+            attachments: attachments,
+            color: color,
+            resolve_target: resolve_target,
+            color_buffer: color_buffer,
+            depth_buffer: depth_buffer,
+        })
+    }
+
+    /// Returns true if the samples are resolved implicitly by the backend.
+    #[inline]
+    pub fn has_implicit_resolve(&self) -> bool {
+        self.resolve_target.is_none()
+    }
+
+    /// Writes the result of the drawing operations to the texture.
+    ///
+    /// Does nothing if the samples are resolved implicitly by the backend.
+    pub fn resolve(&self) {
+        if let Some(ref target) = self.resolve_target {
+            let (width, height) = self.get_dimensions();
+            let rect = Rect { left: 0, bottom: 0, width: width, height: height };
+            let target_rect = BlitTarget { left: 0, bottom: 0, width: width as i32,
+                                           height: height as i32 };
+
+            ops::blit(&self.context, Some(&self.attachments), Some(target),
+                      gl::COLOR_BUFFER_BIT, &rect, &target_rect, gl::NEAREST);
+        }
+    }
+}
+
+impl<'a> Surface for MultisampleFrameBuffer<'a> {
+    #[inline]
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, color_srgb: bool,
+             depth: Option<f32>, stencil: Option<i32>)
+    {
+        ops::clear(&self.context, Some(&self.attachments), rect, color, color_srgb, depth, stencil);
+    }
+
+    #[inline]
+    fn get_dimensions(&self) -> (u32, u32) {
+        self.attachments.get_dimensions()
+    }
+
+    #[inline]
+    fn get_depth_buffer_bits(&self) -> Option<u16> {
+        self.attachments.get_depth_buffer_bits()
+    }
+
+    #[inline]
+    fn get_stencil_buffer_bits(&self) -> Option<u16> {
+        self.attachments.get_stencil_buffer_bits()
+    }
+
+    #[inline]
+    fn read_depth_rect(&self, rect: &Rect) -> Result<Vec<f32>, ReadError> {
+        ops::read_depth(&self.context, Some(&self.attachments), rect)
+    }
+
+    fn draw<'b, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &::Program,
+        uniforms: &U, draw_parameters: &::DrawParameters) -> Result<(), DrawError>
+        where I: Into<::index::IndicesSource<'b>>, U: ::uniforms::Uniforms,
+        V: ::vertex::MultiVerticesSource<'v>
+    {
+        ops::draw_on_surface(self, &self.context, Some(&self.attachments),
+                             vb, ib, program, uniforms, draw_parameters, false)
+    }
+
+    fn draw_with_pipeline<'i, 'v, V, I, U>(&mut self, pipeline: &::draw_parameters::Pipeline,
+                                           vertices: V, indices: I, uniforms: &U)
+                                           -> Result<(), DrawError>
+                                           where V: ::vertex::MultiVerticesSource<'v>,
+                                                 I: Into<::index::IndicesSource<'i>>,
+                                                 U: ::uniforms::Uniforms
+    {
+        ops::draw_on_surface(self, &self.context, Some(&self.attachments),
+                             vertices, indices, pipeline.get_program(), uniforms,
+                             pipeline.get_draw_parameters(), true)
+    }
+
+    #[inline]
+    fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
+                     filter: uniforms::MagnifySamplerFilter) where S: Surface
+    {
+        // multisampled sources can't be scaled, so we blit from the resolved texture instead
+        self.resolve();
+        let source = SimpleFrameBuffer::new(&self.context, self.color).unwrap();
+        target.blit_from_simple_framebuffer(&source, source_rect, target_rect, filter)
+    }
+
+    #[inline]
+    fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
+                       filter: uniforms::MagnifySamplerFilter)
+    {
+        ops::blit(&self.context, None, self.get_attachments(),
+                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_from_simple_framebuffer(&self, source: &SimpleFrameBuffer,
+                                    source_rect: &Rect, target_rect: &BlitTarget,
+                                    filter: uniforms::MagnifySamplerFilter)
+    {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_from_multioutput_framebuffer(&self, source: &MultiOutputFrameBuffer,
+                                         source_rect: &Rect, target_rect: &BlitTarget,
+                                         filter: uniforms::MagnifySamplerFilter)
+    {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+    }
+}
+
+impl<'a> FboAttachments for MultisampleFrameBuffer<'a> {
+    #[inline]
+    fn get_attachments(&self) -> Option<&fbo::ValidatedAttachments> {
+        Some(&self.attachments)
+    }
+}
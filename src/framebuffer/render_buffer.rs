@@ -76,6 +76,20 @@ impl RenderBuffer {
             buffer: RenderBufferAny::new(facade, format, TextureKind::Float, width, height, None)
         })
     }
+
+    /// Builds a new multisampled render buffer.
+    pub fn new_multisample<F>(facade: &F, format: UncompressedFloatFormat, width: u32,
+                              height: u32, samples: u32)
+                              -> Result<RenderBuffer, CreationError> where F: Facade
+    {
+        let format = image_format::TextureFormatRequest::Specific(image_format::TextureFormat::UncompressedFloat(format));
+        let format = try!(image_format::format_request_to_glenum(&facade.get_context(), format, image_format::RequestType::Renderbuffer));
+
+        Ok(RenderBuffer {
+            buffer: RenderBufferAny::new(facade, format, TextureKind::Float, width, height,
+                                         Some(samples))
+        })
+    }
 }
 
 impl<'a> ToColorAttachment<'a> for &'a RenderBuffer {
@@ -129,6 +143,20 @@ impl DepthRenderBuffer {
             buffer: RenderBufferAny::new(facade, format, TextureKind::Depth, width, height, None)
         })
     }
+
+    /// Builds a new multisampled render buffer.
+    pub fn new_multisample<F>(facade: &F, format: DepthFormat, width: u32, height: u32,
+                              samples: u32)
+                              -> Result<DepthRenderBuffer, CreationError> where F: Facade
+    {
+        let format = image_format::TextureFormatRequest::Specific(image_format::TextureFormat::DepthFormat(format));
+        let format = try!(image_format::format_request_to_glenum(&facade.get_context(), format, image_format::RequestType::Renderbuffer));
+
+        Ok(DepthRenderBuffer {
+            buffer: RenderBufferAny::new(facade, format, TextureKind::Depth, width, height,
+                                         Some(samples))
+        })
+    }
 }
 
 impl<'a> ToDepthAttachment<'a> for &'a DepthRenderBuffer {
@@ -276,6 +304,8 @@ pub struct RenderBufferAny {
     height: u32,
     samples: Option<u32>,
     kind: TextureKind,
+    /// Estimated size in bytes, as registered in the context's memory usage.
+    memory_size: usize,
 }
 
 impl RenderBufferAny {
@@ -290,9 +320,7 @@ impl RenderBufferAny {
             let mut ctxt = facade.get_context().make_current();
             let mut id = mem::uninitialized();
 
-            if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-               ctxt.extensions.gl_arb_direct_state_access
-            {
+            if ctxt.capabilities.direct_state_access {
                 ctxt.gl.CreateRenderbuffers(1, &mut id);
                 if let Some(samples) = samples {
                     ctxt.gl.NamedRenderbufferStorageMultisample(id, samples as gl::types::GLsizei,
@@ -393,6 +421,12 @@ impl RenderBufferAny {
                 unreachable!();
             }
 
+            let bits = image_format::get_internal_format_bits(format).unwrap_or(0);
+            let memory_size = bits * width as usize * height as usize *
+                              samples.unwrap_or(1) as usize / 8;
+            let allocated = ctxt.allocated_memory.render_buffers.get();
+            ctxt.allocated_memory.render_buffers.set(allocated + memory_size);
+
             RenderBufferAny {
                 context: facade.get_context().clone(),
                 id: id,
@@ -400,6 +434,7 @@ impl RenderBufferAny {
                 height: height,
                 samples: samples,
                 kind: kind,
+                memory_size: memory_size,
             }
         }
     }
@@ -457,7 +492,20 @@ impl Drop for RenderBufferAny {
             } else {
                 unreachable!();
             }
+
+            let allocated = ctxt.allocated_memory.render_buffers.get();
+            ctxt.allocated_memory.render_buffers.set(allocated - self.memory_size);
         }
+
+        // the contexts that share this render buffer must forget about it too
+        let id = self.id;
+        self.context.purge_shared_contexts(move |ctxt| {
+            FramebuffersContainer::purge_renderbuffer(ctxt, id);
+
+            if ctxt.state.renderbuffer == id {
+                ctxt.state.renderbuffer = 0;
+            }
+        });
     }
 }
 
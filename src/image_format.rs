@@ -109,6 +109,15 @@ pub enum ClientFormat {
     F32F32,
     F32F32F32,
     F32F32F32F32,
+    /// Three unsigned normalized 8-bits components stored in the blue, green, red order.
+    ///
+    /// Not supported by OpenGL ES.
+    B8G8R8,
+    /// Four unsigned normalized 8-bits components stored in the blue, green, red, alpha order.
+    ///
+    /// This is the layout that most image decoders and windowing systems produce. Not supported
+    /// by OpenGL ES.
+    B8G8R8A8,
 }
 
 impl ClientFormat {
@@ -154,6 +163,8 @@ impl ClientFormat {
             ClientFormat::F32F32 => 2 * mem::size_of::<f32>(),
             ClientFormat::F32F32F32 => 3 * mem::size_of::<f32>(),
             ClientFormat::F32F32F32F32 => 4 * mem::size_of::<f32>(),
+            ClientFormat::B8G8R8 => 3 * mem::size_of::<u8>(),
+            ClientFormat::B8G8R8A8 => 4 * mem::size_of::<u8>(),
         }
     }
 
@@ -197,6 +208,8 @@ impl ClientFormat {
             ClientFormat::F32F32 => 2,
             ClientFormat::F32F32F32 => 3,
             ClientFormat::F32F32F32F32 => 4,
+            ClientFormat::B8G8R8 => 3,
+            ClientFormat::B8G8R8A8 => 4,
         }
     }
 }
@@ -2061,7 +2074,70 @@ pub fn format_request_to_glenum(context: &Context, format: TextureFormatRequest,
     })
 }
 
-/// Checks that the client texture format is supported.
+/// Returns the number of bits per texel of an internal format returned by
+/// `format_request_to_glenum`, in order to estimate the memory used by a texture or a render
+/// buffer without querying the backend.
+///
+/// For unsized and generic compressed formats, this is the size that drivers usually choose.
+/// Returns `None` if the format is unknown.
+pub fn get_internal_format_bits(format: gl::types::GLenum) -> Option<usize> {
+    Some(match format {
+        // the legacy formats that are the number of components
+        1 => 8,
+        2 => 16,
+        3 => 24,
+        4 => 32,
+
+        gl::RED | gl::R8 | gl::R8I | gl::R8UI | gl::R8_SNORM | gl::R3_G3_B2 => 8,
+        gl::RG | gl::RG8 | gl::RG8I | gl::RG8UI | gl::RG8_SNORM => 16,
+        gl::R16 | gl::R16F | gl::R16I | gl::R16UI | gl::R16_SNORM => 16,
+        gl::RGB4 => 12,
+        gl::RGB5 => 15,
+        gl::RGB565 | gl::RGB5_A1 | gl::RGBA4 => 16,
+        gl::RGBA2 => 8,
+        gl::RGB | gl::RGB8 | gl::RGB8I | gl::RGB8UI | gl::RGB8_SNORM | gl::SRGB8 => 24,
+        gl::RGB10 => 30,
+        gl::RGB12 => 36,
+        gl::RGBA | gl::RGBA8 | gl::RGBA8I | gl::RGBA8UI | gl::RGBA8_SNORM |
+        gl::SRGB8_ALPHA8 | gl::RGB10_A2 | gl::RGB10_A2UI | gl::R11F_G11F_B10F |
+        gl::RGB9_E5 => 32,
+        gl::RG16 | gl::RG16F | gl::RG16I | gl::RG16UI | gl::RG16_SNORM => 32,
+        gl::R32F | gl::R32I | gl::R32UI => 32,
+        gl::RGBA12 => 48,
+        gl::RGB16 | gl::RGB16F | gl::RGB16I | gl::RGB16UI | gl::RGB16_SNORM => 48,
+        gl::RGBA16 | gl::RGBA16F | gl::RGBA16I | gl::RGBA16UI | gl::RGBA16_SNORM => 64,
+        gl::RG32F | gl::RG32I | gl::RG32UI => 64,
+        gl::RGB32F | gl::RGB32I | gl::RGB32UI => 96,
+        gl::RGBA32F | gl::RGBA32I | gl::RGBA32UI => 128,
+
+        gl::COMPRESSED_RED | gl::COMPRESSED_RGB | gl::COMPRESSED_SRGB => 4,
+        gl::COMPRESSED_RG | gl::COMPRESSED_RGBA | gl::COMPRESSED_SRGB_ALPHA => 8,
+        gl::COMPRESSED_RED_RGTC1 | gl::COMPRESSED_SIGNED_RED_RGTC1 => 4,
+        gl::COMPRESSED_RG_RGTC2 | gl::COMPRESSED_SIGNED_RG_RGTC2 => 8,
+        gl::COMPRESSED_RGB_S3TC_DXT1_EXT | gl::COMPRESSED_RGBA_S3TC_DXT1_EXT |
+        gl::COMPRESSED_SRGB_S3TC_DXT1_EXT | gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT => 4,
+        gl::COMPRESSED_RGBA_S3TC_DXT3_EXT | gl::COMPRESSED_RGBA_S3TC_DXT5_EXT |
+        gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT | gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT => 8,
+        gl::COMPRESSED_RGBA_BPTC_UNORM | gl::COMPRESSED_SRGB_ALPHA_BPTC_UNORM |
+        gl::COMPRESSED_RGB_BPTC_SIGNED_FLOAT | gl::COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT => 8,
+
+        gl::DEPTH_COMPONENT16 => 16,
+        gl::DEPTH_COMPONENT | gl::DEPTH_COMPONENT24 => 24,
+        gl::DEPTH_COMPONENT32 | gl::DEPTH_COMPONENT32F => 32,
+        gl::DEPTH_STENCIL | gl::DEPTH24_STENCIL8 => 32,
+        gl::DEPTH32F_STENCIL8 => 40,
+
+        gl::STENCIL_INDEX1 => 1,
+        gl::STENCIL_INDEX4 => 4,
+        gl::STENCIL_INDEX | gl::STENCIL_INDEX8 => 8,
+        gl::STENCIL_INDEX16 => 16,
+
+        _ => return None
+    })
+}
+
+/// Checks that the client texture format is supported and that it is compatible with the
+/// format of the texture.
 ///
 /// If `inverted` is true, returns a format where the R, G and B components are flipped.
 ///
@@ -2127,6 +2203,32 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
         TextureFormatRequest::Specific(TextureFormat::CompressedFormat(_)) |
         TextureFormatRequest::Specific(TextureFormat::CompressedSrgbFormat(_)) =>
         {
+            // OpenGL ES doesn't convert 16 and 32 bits integers to normalized or floating-point
+            // values
+            if context.get_version() >= &Version(Api::GlEs, 2, 0) {
+                match client {
+                    ClientFormatAny::ClientFormat(ClientFormat::U16) |
+                    ClientFormatAny::ClientFormat(ClientFormat::U16U16) |
+                    ClientFormatAny::ClientFormat(ClientFormat::U16U16U16) |
+                    ClientFormatAny::ClientFormat(ClientFormat::U16U16U16U16) |
+                    ClientFormatAny::ClientFormat(ClientFormat::I16) |
+                    ClientFormatAny::ClientFormat(ClientFormat::I16I16) |
+                    ClientFormatAny::ClientFormat(ClientFormat::I16I16I16) |
+                    ClientFormatAny::ClientFormat(ClientFormat::I16I16I16I16) |
+                    ClientFormatAny::ClientFormat(ClientFormat::U32) |
+                    ClientFormatAny::ClientFormat(ClientFormat::U32U32) |
+                    ClientFormatAny::ClientFormat(ClientFormat::U32U32U32) |
+                    ClientFormatAny::ClientFormat(ClientFormat::U32U32U32U32) |
+                    ClientFormatAny::ClientFormat(ClientFormat::I32) |
+                    ClientFormatAny::ClientFormat(ClientFormat::I32I32) |
+                    ClientFormatAny::ClientFormat(ClientFormat::I32I32I32) |
+                    ClientFormatAny::ClientFormat(ClientFormat::I32I32I32I32) => {
+                        return Err(FormatNotSupportedError);
+                    },
+                    _ => ()
+                }
+            }
+
             match client {
                 ClientFormatAny::ClientFormat(ClientFormat::U8) => Ok((gl::RED, gl::UNSIGNED_BYTE)),
                 ClientFormatAny::ClientFormat(ClientFormat::U8U8) => Ok((gl::RG, gl::UNSIGNED_BYTE)),
@@ -2165,6 +2267,8 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
                 ClientFormatAny::ClientFormat(ClientFormat::F32F32) => Ok((gl::RG, gl::FLOAT)),
                 ClientFormatAny::ClientFormat(ClientFormat::F32F32F32) => Ok((gl::RGB, gl::FLOAT)),
                 ClientFormatAny::ClientFormat(ClientFormat::F32F32F32F32) => Ok((gl::RGBA, gl::FLOAT)),
+                ClientFormatAny::ClientFormat(ClientFormat::B8G8R8) => Ok((gl::BGR, gl::UNSIGNED_BYTE)),
+                ClientFormatAny::ClientFormat(ClientFormat::B8G8R8A8) => Ok((gl::BGRA, gl::UNSIGNED_BYTE)),
 
                 // this kind of situation shouldn't happen, it should have a special handling when
                 // client is compressed.
@@ -2177,6 +2281,21 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
         TextureFormatRequest::Specific(TextureFormat::UncompressedIntegral(_)) |
         TextureFormatRequest::Specific(TextureFormat::UncompressedUnsigned(_)) =>
         {
+            // integral textures can't be filled with floating-point values
+            match client {
+                ClientFormatAny::ClientFormat(ClientFormat::F16) |
+                ClientFormatAny::ClientFormat(ClientFormat::F16F16) |
+                ClientFormatAny::ClientFormat(ClientFormat::F16F16F16) |
+                ClientFormatAny::ClientFormat(ClientFormat::F16F16F16F16) |
+                ClientFormatAny::ClientFormat(ClientFormat::F32) |
+                ClientFormatAny::ClientFormat(ClientFormat::F32F32) |
+                ClientFormatAny::ClientFormat(ClientFormat::F32F32F32) |
+                ClientFormatAny::ClientFormat(ClientFormat::F32F32F32F32) => {
+                    return Err(FormatNotSupportedError);
+                },
+                _ => ()
+            }
+
             match client {
                 ClientFormatAny::ClientFormat(ClientFormat::U8) => Ok((gl::RED_INTEGER, gl::UNSIGNED_BYTE)),
                 ClientFormatAny::ClientFormat(ClientFormat::U8U8) => Ok((gl::RG_INTEGER, gl::UNSIGNED_BYTE)),
@@ -2215,6 +2334,8 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
                 ClientFormatAny::ClientFormat(ClientFormat::F32F32) => Ok((gl::RG_INTEGER, gl::FLOAT)),
                 ClientFormatAny::ClientFormat(ClientFormat::F32F32F32) => Ok((gl::RGB_INTEGER, gl::FLOAT)),
                 ClientFormatAny::ClientFormat(ClientFormat::F32F32F32F32) => Ok((gl::RGBA_INTEGER, gl::FLOAT)),
+                ClientFormatAny::ClientFormat(ClientFormat::B8G8R8) => Ok((gl::BGR_INTEGER, gl::UNSIGNED_BYTE)),
+                ClientFormatAny::ClientFormat(ClientFormat::B8G8R8A8) => Ok((gl::BGRA_INTEGER, gl::UNSIGNED_BYTE)),
 
                 // this kind of situation shouldn't happen, it should have a special handling when
                 // client is compressed.
@@ -2226,8 +2347,9 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
         TextureFormatRequest::AnyDepth |
         TextureFormatRequest::Specific(TextureFormat::DepthFormat(_)) =>
         {
+            // only `F32` can be used to upload on a depth texture
             if client != ClientFormatAny::ClientFormat(ClientFormat::F32) {
-                panic!("Only ClientFormatAny::ClientFormat(ClientFormat::F32) can be used to upload on a depth texture");
+                return Err(FormatNotSupportedError);
             }
 
             Ok((gl::DEPTH_COMPONENT, gl::FLOAT))
@@ -2245,7 +2367,9 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
                 ClientFormatAny::ClientFormat(ClientFormat::I32) => Ok((gl::RED_INTEGER, gl::INT)),
                 ClientFormatAny::ClientFormat(ClientFormat::F16) => Ok((gl::RED_INTEGER, gl::HALF_FLOAT)),
                 ClientFormatAny::ClientFormat(ClientFormat::F32) => Ok((gl::RED_INTEGER, gl::FLOAT)),
-                _ => panic!("Can't upload to a stencil texture with more than one channel")
+
+                // stencil textures only have one channel
+                _ => return Err(FormatNotSupportedError)
             }
         }
 
@@ -2256,17 +2380,33 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
         },
     };
 
-    if inverted {
+    let value = if inverted {
         value.and_then(|(format, ty)| {
             let format = match format {
                 gl::RGB => gl::BGR,
                 gl::RGBA => gl::BGRA,
-                f => return Err(FormatNotSupportedError)
+                gl::BGR => gl::RGB,
+                gl::BGRA => gl::RGBA,
+                _ => return Err(FormatNotSupportedError)
             };
 
             Ok((format, ty))
         })
     } else {
         value
-    }
+    };
+
+    // OpenGL ES only accepts `GL_BGRA_EXT` data for textures whose internal format is
+    // `GL_BGRA_EXT`, and glium never creates such textures
+    value.and_then(|(format, ty)| {
+        match format {
+            gl::BGR | gl::BGRA if !(context.get_version() >= &Version(Api::Gl, 1, 2)) => {
+                Err(FormatNotSupportedError)
+            },
+            gl::BGR_INTEGER | gl::BGRA_INTEGER if !(context.get_version() >= &Version(Api::Gl, 3, 0)) => {
+                Err(FormatNotSupportedError)
+            },
+            _ => Ok((format, ty))
+        }
+    })
 }
@@ -182,6 +182,34 @@ impl<T> IndexBuffer<T> where T: Index {
         })
     }
 
+    /// Builds an index buffer from an existing buffer, without copying its content.
+    ///
+    /// This allows using indices that were written by the GPU, for example in a shader storage
+    /// buffer written by a compute shader. The buffer doesn't need to have been created with
+    /// `BufferType::ElementArrayBuffer`, and the required memory barriers are inserted before
+    /// the indices are used by a draw command.
+    #[inline]
+    pub fn from_buffer(buffer: Buffer<[T]>, prim: PrimitiveType)
+                       -> Result<IndexBuffer<T>, CreationError>
+    {
+        {
+            let context = &**buffer.get_context();
+
+            if !prim.is_supported(context) {
+                return Err(CreationError::PrimitiveTypeNotSupported);
+            }
+
+            if !T::is_supported(context) {
+                return Err(CreationError::IndexTypeNotSupported);
+            }
+        }
+
+        Ok(IndexBuffer {
+            buffer: buffer,
+            primitives: prim,
+        })
+    }
+
     /// Returns the type of primitives associated with this index buffer.
     #[inline]
     pub fn get_primitives_type(&self) -> PrimitiveType {
@@ -0,0 +1,327 @@
+//! Removes the invisible draw commands from a buffer of commands on the GPU, so that a
+//! multidraw command only issues the visible ones.
+
+use std::cmp;
+
+use backend::Facade;
+use buffer::{Buffer, BufferSlice, BufferAnySlice, BufferType, BufferMode, BufferCreationError};
+use program::{ComputeShader, ProgramCreationError};
+use uniforms::{Uniforms, UniformValue};
+use utils::layout::any_layout;
+
+use index::DrawCommandsIndicesBuffer;
+
+/// Compacts a buffer of draw commands according to per-command visibility flags.
+///
+/// The compaction is done by compute shaders, which compute a prefix sum of the visibility
+/// flags and then scatter the visible commands to the destination buffer. The order of the
+/// visible commands is preserved, and their number is written to a separate buffer. The
+/// commands of the destination that come after the visible ones are disabled by setting their
+/// number of instances to `0`, so that the whole destination buffer can be passed to a
+/// multidraw command without having to read the number of visible commands back.
+///
+/// The commands are split between up to 256 work groups. A first pass counts the visible
+/// commands of each group, a second pass computes the offset of each group in the destination,
+/// and a third pass scatters the visible commands.
+///
+/// The source, the visibility flags and the destination are passed to each call to `compact`,
+/// so a single compactor can be used for all the buffers of commands of a scene.
+///
+/// ```no_run
+/// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+/// # let commands: glium::index::DrawCommandsIndicesBuffer = unsafe { std::mem::uninitialized() };
+/// # let mut visible_commands: glium::index::DrawCommandsIndicesBuffer = unsafe { std::mem::uninitialized() };
+/// # let visibility: glium::buffer::Buffer<[u32]> = unsafe { std::mem::uninitialized() };
+/// # let mut count: glium::buffer::Buffer<u32> = unsafe { std::mem::uninitialized() };
+/// let compactor = glium::index::CommandsCompactor::new(&display).unwrap();
+/// compactor.compact(&commands, visibility.as_slice(), &mut visible_commands, &mut count)
+///          .unwrap();
+/// ```
+pub struct CommandsCompactor {
+    count_program: ComputeShader,
+    scan_program: ComputeShader,
+    scatter_program: ComputeShader,
+}
+
+impl CommandsCompactor {
+    /// Compiles the compaction shaders.
+    ///
+    /// Returns `ShaderTypeNotSupported` if the backend doesn't support compute shaders, as
+    /// the compaction can't be done on the GPU without them.
+    pub fn new<F>(facade: &F) -> Result<CommandsCompactor, ProgramCreationError>
+                  where F: Facade
+    {
+        if !ComputeShader::is_supported(facade) {
+            return Err(ProgramCreationError::ShaderTypeNotSupported);
+        }
+
+        // counts the visible commands of the block of each work group
+        let count_program = try!(ComputeShader::from_source(facade, r#"
+            #version 430
+            layout(local_size_x = 64) in;
+
+            layout(std430) buffer Visibility {
+                uint visibility[];
+            };
+
+            layout(std430) buffer Offsets {
+                uint offsets[];
+            };
+
+            uniform uint commands_count;
+            uniform uint block_size;
+
+            shared uint visible_count;
+
+            void main() {
+                uint id = gl_LocalInvocationID.x;
+                uint group = gl_WorkGroupID.x;
+
+                if (id == 0) {
+                    visible_count = 0;
+                }
+
+                memoryBarrierShared();
+                barrier();
+
+                uint end = min((group + 1) * block_size, commands_count);
+                for (uint index = group * block_size + id; index < end; index += 64) {
+                    if (visibility[index] != 0) {
+                        atomicAdd(visible_count, 1);
+                    }
+                }
+
+                memoryBarrierShared();
+                barrier();
+
+                if (id == 0) {
+                    offsets[group] = visible_count;
+                }
+            }
+        "#));
+
+        // turns the number of visible commands of each block into an exclusive prefix sum
+        let scan_program = try!(ComputeShader::from_source(facade, r#"
+            #version 430
+            layout(local_size_x = 1) in;
+
+            layout(std430) buffer Offsets {
+                uint offsets[];
+            };
+
+            layout(std430) buffer Count {
+                uint count;
+            };
+
+            uniform uint groups_count;
+
+            void main() {
+                uint total = 0;
+
+                for (uint group = 0; group < groups_count; ++group) {
+                    uint visible = offsets[group];
+                    offsets[group] = total;
+                    total += visible;
+                }
+
+                count = total;
+            }
+        "#));
+
+        // each command is made of 5 integers
+        let scatter_program = try!(ComputeShader::from_source(facade, r#"
+            #version 430
+            layout(local_size_x = 64) in;
+
+            layout(std430) buffer Source {
+                uint source[];
+            };
+
+            layout(std430) buffer Visibility {
+                uint visibility[];
+            };
+
+            layout(std430) buffer Destination {
+                uint destination[];
+            };
+
+            layout(std430) buffer Offsets {
+                uint offsets[];
+            };
+
+            layout(std430) buffer Count {
+                uint count;
+            };
+
+            uniform uint commands_count;
+            uniform uint destination_count;
+            uniform uint block_size;
+
+            shared uint chunk_offsets[64];
+
+            void main() {
+                uint id = gl_LocalInvocationID.x;
+                uint group = gl_WorkGroupID.x;
+                uint total = offsets[group];
+
+                uint end = min((group + 1) * block_size, commands_count);
+                for (uint base = group * block_size; base < end; base += 64) {
+                    uint index = base + id;
+                    uint visible = (index < end && visibility[index] != 0) ? 1 : 0;
+                    chunk_offsets[id] = visible;
+
+                    memoryBarrierShared();
+                    barrier();
+
+                    // inclusive prefix sum of the visibility flags of this chunk
+                    for (uint step = 1; step < 64; step *= 2) {
+                        uint value = id >= step ? chunk_offsets[id - step] : 0;
+                        memoryBarrierShared();
+                        barrier();
+                        chunk_offsets[id] += value;
+                        memoryBarrierShared();
+                        barrier();
+                    }
+
+                    if (visible != 0) {
+                        uint target = total + chunk_offsets[id] - 1;
+                        for (uint i = 0; i < 5; ++i) {
+                            destination[target * 5 + i] = source[index * 5 + i];
+                        }
+                    }
+
+                    total += chunk_offsets[63];
+
+                    memoryBarrierShared();
+                    barrier();
+                }
+
+                // disabling the commands that come after the visible ones
+                uint stride = 64 * gl_NumWorkGroups.x;
+                for (uint index = count + group * 64 + id; index < destination_count;
+                     index += stride)
+                {
+                    destination[index * 5 + 1] = 0;
+                }
+            }
+        "#));
+
+        Ok(CommandsCompactor {
+            count_program: count_program,
+            scan_program: scan_program,
+            scatter_program: scatter_program,
+        })
+    }
+
+    /// Copies the commands of `source` whose visibility flag is not `0` at the beginning of
+    /// `destination`, and writes their number to `count`.
+    ///
+    /// The commands of `destination` that come after the visible ones keep their other
+    /// parameters, but their number of instances is set to `0` so that they don't draw anything.
+    ///
+    /// Returns an error if the temporary buffer that holds the offset of each work group
+    /// can't be created.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `visibility` or `destination` contain less elements than `source`.
+    pub fn compact(&self, source: &DrawCommandsIndicesBuffer, visibility: BufferSlice<[u32]>,
+                   destination: &mut DrawCommandsIndicesBuffer, count: &mut Buffer<u32>)
+                   -> Result<(), BufferCreationError>
+    {
+        assert!(visibility.len() >= source.len());
+        assert!(destination.len() >= source.len());
+
+        // each work group handles a block of commands whose size is a multiple of 64
+        let chunks = cmp::max((source.len() + 63) / 64, 1);
+        let groups = cmp::min(chunks, 256);
+        let block_size = ((chunks + groups - 1) / groups) * 64;
+
+        let offsets: Buffer<[u32]> = try!(Buffer::empty_array(source.get_context(),
+                                                              BufferType::ShaderStorageBuffer,
+                                                              groups, BufferMode::Default));
+
+        self.count_program.execute(CountUniforms {
+            visibility: visibility.as_slice_any(),
+            offsets: offsets.as_slice_any(),
+            commands_count: source.len() as u32,
+            block_size: block_size as u32,
+        }, groups as u32, 1, 1);
+
+        self.scan_program.execute(ScanUniforms {
+            offsets: offsets.as_slice_any(),
+            count: count.as_slice_any(),
+            groups_count: groups as u32,
+        }, 1, 1, 1);
+
+        self.scatter_program.execute(ScatterUniforms {
+            source: source.as_slice_any(),
+            visibility: visibility.as_slice_any(),
+            destination: destination.as_slice_any(),
+            offsets: offsets.as_slice_any(),
+            count: count.as_slice_any(),
+            commands_count: source.len() as u32,
+            destination_count: destination.len() as u32,
+            block_size: block_size as u32,
+        }, groups as u32, 1, 1);
+
+        Ok(())
+    }
+}
+
+/// The uniforms passed to the shader that counts the visible commands of each work group.
+struct CountUniforms<'b> {
+    visibility: BufferAnySlice<'b>,
+    offsets: BufferAnySlice<'b>,
+    commands_count: u32,
+    block_size: u32,
+}
+
+impl<'b> Uniforms for CountUniforms<'b> {
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+        output("Visibility", UniformValue::Block(self.visibility, any_layout));
+        output("Offsets", UniformValue::Block(self.offsets, any_layout));
+        output("commands_count", UniformValue::UnsignedInt(self.commands_count));
+        output("block_size", UniformValue::UnsignedInt(self.block_size));
+    }
+}
+
+/// The uniforms passed to the shader that computes the offset of each work group.
+struct ScanUniforms<'b> {
+    offsets: BufferAnySlice<'b>,
+    count: BufferAnySlice<'b>,
+    groups_count: u32,
+}
+
+impl<'b> Uniforms for ScanUniforms<'b> {
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+        output("Offsets", UniformValue::Block(self.offsets, any_layout));
+        output("Count", UniformValue::Block(self.count, any_layout));
+        output("groups_count", UniformValue::UnsignedInt(self.groups_count));
+    }
+}
+
+/// The uniforms passed to the shader that scatters the visible commands.
+struct ScatterUniforms<'b> {
+    source: BufferAnySlice<'b>,
+    visibility: BufferAnySlice<'b>,
+    destination: BufferAnySlice<'b>,
+    offsets: BufferAnySlice<'b>,
+    count: BufferAnySlice<'b>,
+    commands_count: u32,
+    destination_count: u32,
+    block_size: u32,
+}
+
+impl<'b> Uniforms for ScatterUniforms<'b> {
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+        output("Source", UniformValue::Block(self.source, any_layout));
+        output("Visibility", UniformValue::Block(self.visibility, any_layout));
+        output("Destination", UniformValue::Block(self.destination, any_layout));
+        output("Offsets", UniformValue::Block(self.offsets, any_layout));
+        output("Count", UniformValue::Block(self.count, any_layout));
+        output("commands_count", UniformValue::UnsignedInt(self.commands_count));
+        output("destination_count", UniformValue::UnsignedInt(self.destination_count));
+        output("block_size", UniformValue::UnsignedInt(self.block_size));
+    }
+}
@@ -0,0 +1,69 @@
+//! Converts strips and fans of triangles into lists of triangles.
+//!
+
+/// Turns the indices of a `TriangleStrip` into the indices of an equivalent `TrianglesList`.
+///
+/// Every other triangle of a strip has its vertices in the reverse order. The winding order
+/// is corrected so that all the triangles of the list face the same direction as in the strip.
+///
+/// Degenerate triangles, which strips use to join several strips together, are removed.
+///
+/// ## Example
+///
+/// ```
+/// let list = glium::index::triangle_strip_to_list(&[0u16, 1, 2, 3, 4]);
+/// assert_eq!(list, vec![0, 1, 2, 2, 1, 3, 2, 3, 4]);
+/// ```
+pub fn triangle_strip_to_list<T>(indices: &[T]) -> Vec<T> where T: Copy + PartialEq {
+    let mut list = Vec::with_capacity(indices.len().saturating_sub(2) * 3);
+
+    for (n, triangle) in indices.windows(3).enumerate() {
+        let (a, b, c) = if n % 2 == 0 {
+            (triangle[0], triangle[1], triangle[2])
+        } else {
+            (triangle[1], triangle[0], triangle[2])
+        };
+
+        if a == b || b == c || a == c {
+            continue;
+        }
+
+        list.push(a);
+        list.push(b);
+        list.push(c);
+    }
+
+    list
+}
+
+/// Turns the indices of a `TriangleFan` into the indices of an equivalent `TrianglesList`.
+///
+/// Degenerate triangles are removed.
+///
+/// ## Example
+///
+/// ```
+/// let list = glium::index::triangle_fan_to_list(&[0u16, 1, 2, 3]);
+/// assert_eq!(list, vec![0, 1, 2, 0, 2, 3]);
+/// ```
+pub fn triangle_fan_to_list<T>(indices: &[T]) -> Vec<T> where T: Copy + PartialEq {
+    let mut list = Vec::with_capacity(indices.len().saturating_sub(2) * 3);
+
+    if indices.len() < 3 {
+        return list;
+    }
+
+    let center = indices[0];
+
+    for edge in indices[1..].windows(2) {
+        if edge[0] == edge[1] || edge[0] == center || edge[1] == center {
+            continue;
+        }
+
+        list.push(center);
+        list.push(edge[0]);
+        list.push(edge[1]);
+    }
+
+    list
+}
@@ -0,0 +1,114 @@
+//! Builds indexed meshes from lists of vertices that contain duplicates.
+//!
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Removes the duplicates from a list of vertices, and returns the list of unique vertices and
+/// the indices that rebuild the original list from them.
+///
+/// This is useful when importing meshes whose vertices are stored as a flat list, for example
+/// a triangle soup. The order of the unique vertices is the order of their first occurence.
+///
+/// ## Example
+///
+/// ```
+/// let vertices = [0u8, 1, 2, 2, 1, 3];
+/// let (unique, indices) = glium::index::build_indexed(&vertices);
+///
+/// assert_eq!(unique, vec![0, 1, 2, 3]);
+/// assert_eq!(indices, vec![0, 1, 2, 2, 1, 3]);
+/// ```
+pub fn build_indexed<V>(vertices: &[V]) -> (Vec<V>, Vec<u32>) where V: Clone + Eq + Hash {
+    let mut unique = Vec::new();
+    let mut indices = Vec::with_capacity(vertices.len());
+    let mut known: HashMap<&V, u32> = HashMap::with_capacity(vertices.len());
+
+    for vertex in vertices {
+        let index = *known.entry(vertex).or_insert_with(|| {
+            unique.push(vertex.clone());
+            (unique.len() - 1) as u32
+        });
+
+        indices.push(index);
+    }
+
+    (unique, indices)
+}
+
+/// Same as `build_indexed`, but two vertices are considered identical if the distance between
+/// their positions is at most `epsilon` on each axis.
+///
+/// The `position` function returns the position of a vertex. The other attributes are not
+/// compared, and the first vertex of each group of identical vertices is kept.
+///
+/// # Panic
+///
+/// Panics if `epsilon` is not strictly positive.
+///
+/// ## Example
+///
+/// ```
+/// let vertices = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.00001, 0.0, 0.0]];
+/// let (unique, indices) = glium::index::build_indexed_with_epsilon(&vertices, 0.001, |v| *v);
+///
+/// assert_eq!(unique.len(), 2);
+/// assert_eq!(indices, vec![0, 1, 0]);
+/// ```
+pub fn build_indexed_with_epsilon<V, F>(vertices: &[V], epsilon: f32, position: F)
+                                        -> (Vec<V>, Vec<u32>)
+                                        where V: Clone, F: Fn(&V) -> [f32; 3]
+{
+    assert!(epsilon > 0.0);
+
+    let mut unique: Vec<V> = Vec::new();
+    let mut indices = Vec::with_capacity(vertices.len());
+
+    // the vertices are sorted in cells whose size is `epsilon`, so that identical vertices are
+    // either in the same cell or in neighbouring cells
+    let mut grid: HashMap<(i64, i64, i64), Vec<u32>> = HashMap::new();
+
+    for vertex in vertices {
+        let pos = position(vertex);
+        let cell = ((pos[0] / epsilon).floor() as i64, (pos[1] / epsilon).floor() as i64,
+                    (pos[2] / epsilon).floor() as i64);
+
+        let mut found = None;
+
+        'search: for dx in -1 .. 2 {
+            for dy in -1 .. 2 {
+                for dz in -1 .. 2 {
+                    let neighbour = match grid.get(&(cell.0 + dx, cell.1 + dy, cell.2 + dz)) {
+                        Some(n) => n,
+                        None => continue
+                    };
+
+                    for &index in neighbour.iter() {
+                        let other = position(&unique[index as usize]);
+
+                        if (other[0] - pos[0]).abs() <= epsilon &&
+                           (other[1] - pos[1]).abs() <= epsilon &&
+                           (other[2] - pos[2]).abs() <= epsilon
+                        {
+                            found = Some(index);
+                            break 'search;
+                        }
+                    }
+                }
+            }
+        }
+
+        let index = match found {
+            Some(index) => index,
+            None => {
+                let index = unique.len() as u32;
+                unique.push(vertex.clone());
+                grid.entry(cell).or_insert_with(Vec::new).push(index);
+                index
+            }
+        };
+
+        indices.push(index);
+    }
+
+    (unique, indices)
+}
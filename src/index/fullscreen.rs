@@ -0,0 +1,63 @@
+use std::rc::Rc;
+
+use backend::Facade;
+use ContextExt;
+
+use vertex::VertexBuffer;
+use vertex::BufferCreationError;
+
+use super::{NoIndices, PrimitiveType};
+
+/// A vertex of the triangle returned by `fullscreen_triangle`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FullscreenVertex {
+    /// Position of the vertex in normalized device coordinates.
+    pub position: [f32; 2],
+
+    /// Texture coordinates. They go from `0.0` to `1.0` inside of the viewport.
+    pub tex_coords: [f32; 2],
+}
+
+implement_vertex!(FullscreenVertex, position, tex_coords);
+
+/// Returns a vertex buffer and the corresponding indices source that draw a single triangle
+/// covering the whole viewport.
+///
+/// The attributes of the vertices are named `position` and `tex_coords`.
+///
+/// The buffer is shared between all the calls made with the same context, and is only created
+/// once as long as one of the returned `Rc`s is alive. The context can't own the buffer itself,
+/// as the buffer keeps the context alive. You should therefore store the returned buffer
+/// alongside your program instead of calling this function every frame.
+///
+/// ```no_run
+/// # use glium::Surface;
+/// # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+/// # let program: glium::Program = unsafe { ::std::mem::uninitialized() };
+/// let (vertices, indices) = glium::index::fullscreen_triangle(&display).unwrap();
+///
+/// let mut target = display.draw();
+/// target.draw(&*vertices, &indices, &program, &uniform!{}, &Default::default()).unwrap();
+/// target.finish().unwrap();
+/// ```
+pub fn fullscreen_triangle<F>(facade: &F)
+                              -> Result<(Rc<VertexBuffer<FullscreenVertex>>, NoIndices),
+                                        BufferCreationError>
+                              where F: Facade
+{
+    let indices = NoIndices(PrimitiveType::TrianglesList);
+
+    let mut cache = facade.get_context().fullscreen_triangle().borrow_mut();
+    if let Some(buffer) = cache.as_ref().and_then(|b| b.upgrade()) {
+        return Ok((buffer, indices));
+    }
+
+    let buffer = Rc::new(try!(VertexBuffer::immutable(facade, &[
+        FullscreenVertex { position: [-1.0, -1.0], tex_coords: [0.0, 0.0] },
+        FullscreenVertex { position: [ 3.0, -1.0], tex_coords: [2.0, 0.0] },
+        FullscreenVertex { position: [-1.0,  3.0], tex_coords: [0.0, 2.0] },
+    ])));
+
+    *cache = Some(Rc::downgrade(&buffer));
+    Ok((buffer, indices))
+}
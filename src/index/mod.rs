@@ -22,12 +22,20 @@ There are two ways to specify the indices that must be used:
  - `NoIndices`, in which case the vertices will be used in the order in which they are in the
    vertex buffer.
 
+## Full-screen triangle
+
+`fullscreen_triangle` returns a vertex buffer containing a single triangle that covers the
+whole viewport, which is what most post-processing passes need.
+
 ## Multidraw indirect
 
 In addition to indices, you can also use **multidraw indirect** rendering.
 
 The idea is to put a list of things to render in a buffer, and pass that buffer to OpenGL.
 
+A `MultiMeshBuilder` can be used to pack multiple meshes into the same vertex and index buffers
+and to generate the corresponding draw commands.
+
 */
 use gl;
 use ToGlEnum;
@@ -38,14 +46,27 @@ use version::Version;
 use std::mem;
 
 use buffer::BufferAnySlice;
+use vertex::TransformFeedbackSession;
 
 pub use self::buffer::{IndexBuffer, IndexBufferSlice, IndexBufferAny};
 pub use self::buffer::CreationError as BufferCreationError;
+pub use self::compaction::CommandsCompactor;
+pub use self::convert::{triangle_strip_to_list, triangle_fan_to_list};
+pub use self::dedup::{build_indexed, build_indexed_with_epsilon};
+pub use self::fullscreen::{fullscreen_triangle, FullscreenVertex};
 pub use self::multidraw::{DrawCommandsNoIndicesBuffer, DrawCommandNoIndices};
 pub use self::multidraw::{DrawCommandsIndicesBuffer, DrawCommandIndices};
+pub use self::multidraw::{is_base_instance_supported, is_shader_draw_parameters_supported};
+pub use self::multidraw::coalesce_commands;
+pub use self::multimesh::{MultiMeshBuilder, MultiMesh, MultiMeshCreationError};
 
 mod buffer;
+mod compaction;
+mod convert;
+mod dedup;
+mod fullscreen;
 mod multidraw;
+mod multimesh;
 
 /// Describes a source of indices used for drawing.
 #[derive(Clone)]
@@ -86,6 +107,17 @@ pub enum IndicesSource<'a> {
         /// Type of primitives contained in the vertex source.
         primitives: PrimitiveType,
     },
+
+    /// Draw the vertices captured by the latest draw command of a transform feedback session,
+    /// without knowing their number in advance.
+    TransformFeedback {
+        /// The session that has captured the vertices.
+        session: &'a TransformFeedbackSession<'a>,
+        /// The vertex stream whose vertices are drawn.
+        stream: u32,
+        /// Type of primitives contained in the vertex source.
+        primitives: PrimitiveType,
+    },
 }
 
 impl<'a> IndicesSource<'a> {
@@ -97,6 +129,7 @@ impl<'a> IndicesSource<'a> {
             &IndicesSource::MultidrawArray { primitives, .. } => primitives,
             &IndicesSource::MultidrawElement { primitives, .. } => primitives,
             &IndicesSource::NoIndices { primitives } => primitives,
+            &IndicesSource::TransformFeedback { primitives, .. } => primitives,
         }
     }
 }
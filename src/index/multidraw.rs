@@ -1,11 +1,21 @@
 //! Allows one to draw multiple geometry located in the same buffer.
 //!
+//! The commands are always submitted with a single call to `glMultiDrawArraysIndirect` or
+//! `glMultiDrawElementsIndirect`, and never emulated with multiple draw calls. This means that
+//! in the shaders `gl_DrawIDARB` always contains the index of the command in the buffer, and
+//! `gl_BaseVertexARB` and `gl_BaseInstanceARB` contain the values of the command. These
+//! variables require OpenGL 4.6 or `GL_ARB_shader_draw_parameters`, see
+//! `is_shader_draw_parameters_supported`.
+//!
 use std::ops::Deref;
 use std::ops::DerefMut;
 use std::os::raw;
 
 use backend::Facade;
-use buffer::{BufferCreationError, BufferType, BufferMode, Buffer};
+use CapabilitiesSource;
+use version::Api;
+use version::Version;
+use buffer::{BufferCreationError, BufferType, BufferMode, Buffer, ReadError};
 use buffer::{BufferSlice, BufferMutSlice};
 use index::{IndicesSource, PrimitiveType, IndexBuffer, Index};
 
@@ -20,6 +30,9 @@ pub struct DrawCommandNoIndices {
     /// First vertex to draw in the vertices source.
     pub first_index: raw::c_uint,
     /// Numero of the first instance to draw.
+    ///
+    /// Per-instance attributes are read starting from this element. Must be `0` if
+    /// `is_base_instance_supported` returns false.
     pub base_instance: raw::c_uint,
 }
 
@@ -39,12 +52,61 @@ pub struct DrawCommandIndices {
     /// Value to add to each index.
     pub base_vertex: raw::c_uint,
     /// Numero of the first instance to draw.
+    ///
+    /// Per-instance attributes are read starting from this element, which allows each command
+    /// to use a different part of the same per-instance buffer. Note that `gl_InstanceID`
+    /// doesn't include this value. Must be `0` if `is_base_instance_supported` returns false.
     pub base_instance: raw::c_uint,
 }
 
 implement_uniform_block!(DrawCommandIndices, count, instance_count, first_index,
                          base_vertex, base_instance);
 
+/// Returns true if the backend supports a non-zero `base_instance` in draw commands.
+#[inline]
+pub fn is_base_instance_supported<C>(caps: &C) -> bool where C: CapabilitiesSource {
+    caps.get_version() >= &Version(Api::Gl, 4, 2) ||
+    caps.get_extensions().gl_arb_base_instance || caps.get_extensions().gl_ext_base_instance
+}
+
+/// Returns true if the backend supports the `gl_DrawIDARB`, `gl_BaseVertexARB` and
+/// `gl_BaseInstanceARB` shader variables.
+#[inline]
+pub fn is_shader_draw_parameters_supported<C>(caps: &C) -> bool where C: CapabilitiesSource {
+    caps.get_version() >= &Version(Api::Gl, 4, 6) ||
+    caps.get_extensions().gl_arb_shader_draw_parameters
+}
+
+/// Merges the consecutive commands of a list that draw the same geometry into single instanced
+/// commands.
+///
+/// Two consecutive commands are merged if they have the same `count`, `first_index` and
+/// `base_vertex`, and if the instances of the second one immediately follow the instances of
+/// the first one. The result draws exactly the same things, but with less commands. Note
+/// however that `gl_DrawIDARB` is no longer the index of the command in the original list.
+pub fn coalesce_commands(commands: &[DrawCommandIndices]) -> Vec<DrawCommandIndices> {
+    let mut result: Vec<DrawCommandIndices> = Vec::with_capacity(commands.len());
+
+    for command in commands.iter() {
+        if let Some(last) = result.last_mut() {
+            let next_instance = last.base_instance.checked_add(last.instance_count);
+            let instance_count = last.instance_count.checked_add(command.instance_count);
+
+            if last.count == command.count && last.first_index == command.first_index &&
+               last.base_vertex == command.base_vertex &&
+               next_instance == Some(command.base_instance) && instance_count.is_some()
+            {
+                last.instance_count = instance_count.unwrap();
+                continue;
+            }
+        }
+
+        result.push(*command);
+    }
+
+    result
+}
+
 /// A buffer containing a list of draw commands.
 pub struct DrawCommandsNoIndicesBuffer {
     buffer: Buffer<[DrawCommandNoIndices]>,
@@ -64,6 +126,22 @@ impl DrawCommandsNoIndicesBuffer {
         Ok(DrawCommandsNoIndicesBuffer { buffer: buf })
     }
 
+    /// Builds a buffer whose commands are all zero.
+    ///
+    /// Contrary to `empty`, the content of the buffer is not undefined. Since the
+    /// `instance_count` of each command is `0`, the commands that you don't fill draw nothing.
+    ///
+    /// The parameter indicates the number of elements.
+    #[inline]
+    pub fn empty_zeroed<F>(facade: &F, elements: usize)
+                           -> Result<DrawCommandsNoIndicesBuffer, BufferCreationError>
+                           where F: Facade
+    {
+        let buf = try!(Buffer::empty_array_zeroed(facade, BufferType::DrawIndirectBuffer,
+                                                  elements, BufferMode::Default));
+        Ok(DrawCommandsNoIndicesBuffer { buffer: buf })
+    }
+
     /// Builds an empty buffer.
     ///
     /// The parameter indicates the number of elements.
@@ -112,6 +190,45 @@ impl DrawCommandsNoIndicesBuffer {
             primitives: primitives,
         }
     }
+
+    /// Builds an indices source that only uses `count` commands of this buffer, starting at the
+    /// command `offset`.
+    ///
+    /// This allows storing the commands of multiple draw calls in the same buffer. Returns
+    /// `None` if the range of commands is outside of the buffer.
+    #[inline]
+    pub fn with_primitive_type_at(&self, primitives: PrimitiveType, offset: usize, count: usize)
+                                  -> Option<IndicesSource>
+    {
+        let end = match offset.checked_add(count) {
+            Some(e) => e,
+            None => return None
+        };
+
+        let slice = match self.buffer.slice(offset .. end) {
+            Some(s) => s,
+            None => return None
+        };
+
+        Some(IndicesSource::MultidrawArray {
+            buffer: slice.as_slice_any(),
+            primitives: primitives,
+        })
+    }
+
+    /// Reads a single command from the buffer.
+    ///
+    /// Contrary to `read()` or `map()`, only the requested command is transferred. Returns
+    /// `OutOfBounds` if `index` is not smaller than the number of commands.
+    #[inline]
+    pub fn read_command(&self, index: usize) -> Result<DrawCommandNoIndices, ReadError> {
+        if index >= self.buffer.len() {
+            return Err(ReadError::OutOfBounds);
+        }
+
+        let slice = self.buffer.slice(index .. index + 1).unwrap();
+        slice.read().map(|mut cmds| cmds.remove(0))
+    }
 }
 
 impl Deref for DrawCommandsNoIndicesBuffer {
@@ -165,6 +282,22 @@ impl DrawCommandsIndicesBuffer {
         Ok(DrawCommandsIndicesBuffer { buffer: buf })
     }
 
+    /// Builds a buffer whose commands are all zero.
+    ///
+    /// Contrary to `empty`, the content of the buffer is not undefined. Since the
+    /// `instance_count` of each command is `0`, the commands that you don't fill draw nothing.
+    ///
+    /// The parameter indicates the number of elements.
+    #[inline]
+    pub fn empty_zeroed<F>(facade: &F, elements: usize)
+                           -> Result<DrawCommandsIndicesBuffer, BufferCreationError>
+                           where F: Facade
+    {
+        let buf = try!(Buffer::empty_array_zeroed(facade, BufferType::DrawIndirectBuffer,
+                                                  elements, BufferMode::Default));
+        Ok(DrawCommandsIndicesBuffer { buffer: buf })
+    }
+
     /// Builds an empty buffer.
     ///
     /// The parameter indicates the number of elements.
@@ -217,6 +350,72 @@ impl DrawCommandsIndicesBuffer {
             primitives: index_buffer.get_primitives_type(),
         }
     }
+
+    /// Builds an indices source that only uses `count` commands of this buffer, starting at the
+    /// command `offset`.
+    ///
+    /// This allows storing the commands of multiple draw calls in the same buffer. Returns
+    /// `None` if the range of commands is outside of the buffer.
+    #[inline]
+    pub fn with_index_buffer_at<'a, T>(&'a self, index_buffer: &'a IndexBuffer<T>,
+                                       offset: usize, count: usize)
+                                       -> Option<IndicesSource<'a>> where T: Index
+    {
+        let end = match offset.checked_add(count) {
+            Some(e) => e,
+            None => return None
+        };
+
+        let slice = match self.buffer.slice(offset .. end) {
+            Some(s) => s,
+            None => return None
+        };
+
+        Some(IndicesSource::MultidrawElement {
+            commands: slice.as_slice_any(),
+            indices: index_buffer.as_slice_any(),
+            data_type: index_buffer.get_indices_type(),
+            primitives: index_buffer.get_primitives_type(),
+        })
+    }
+
+    /// Merges the consecutive commands of the buffer that draw the same geometry. See
+    /// `coalesce_commands`.
+    ///
+    /// The merged commands are written at the beginning of the buffer, and the rest of the
+    /// buffer is filled with commands that don't draw anything. Returns the number of merged
+    /// commands, which can be passed to `with_index_buffer_at` in order to skip the empty ones.
+    pub fn coalesce(&mut self) -> Result<usize, ReadError> {
+        let commands = try!(self.buffer.read());
+
+        let mut merged = coalesce_commands(&commands);
+        let len = merged.len();
+
+        merged.resize(commands.len(), DrawCommandIndices {
+            count: 0,
+            instance_count: 0,
+            first_index: 0,
+            base_vertex: 0,
+            base_instance: 0,
+        });
+
+        self.buffer.write(&merged);
+        Ok(len)
+    }
+
+    /// Reads a single command from the buffer.
+    ///
+    /// Contrary to `read()` or `map()`, only the requested command is transferred. Returns
+    /// `OutOfBounds` if `index` is not smaller than the number of commands.
+    #[inline]
+    pub fn read_command(&self, index: usize) -> Result<DrawCommandIndices, ReadError> {
+        if index >= self.buffer.len() {
+            return Err(ReadError::OutOfBounds);
+        }
+
+        let slice = self.buffer.slice(index .. index + 1).unwrap();
+        slice.read().map(|mut cmds| cmds.remove(0))
+    }
 }
 
 impl Deref for DrawCommandsIndicesBuffer {
@@ -250,3 +449,56 @@ impl<'a> From<&'a mut DrawCommandsIndicesBuffer> for BufferMutSlice<'a, [DrawCom
         b.as_mut_slice()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{coalesce_commands, DrawCommandIndices};
+
+    fn command(count: u32, first_index: u32, base_vertex: u32, instance_count: u32,
+               base_instance: u32) -> DrawCommandIndices
+    {
+        DrawCommandIndices {
+            count: count,
+            instance_count: instance_count,
+            first_index: first_index,
+            base_vertex: base_vertex,
+            base_instance: base_instance,
+        }
+    }
+
+    #[test]
+    fn coalesce_consecutive() {
+        let result = coalesce_commands(&[
+            command(6, 0, 0, 2, 0),
+            command(6, 0, 0, 3, 2),
+            command(6, 0, 0, 1, 6),     // not consecutive
+            command(3, 6, 0, 1, 7),     // different geometry
+        ]);
+
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].instance_count, 5);
+        assert_eq!(result[0].base_instance, 0);
+        assert_eq!(result[1].base_instance, 6);
+        assert_eq!(result[2].count, 3);
+    }
+
+    #[test]
+    fn coalesce_empty() {
+        assert!(coalesce_commands(&[]).is_empty());
+    }
+
+    #[test]
+    fn coalesce_overflow() {
+        let result = coalesce_commands(&[
+            command(6, 0, 0, 2, 0xffffffff),
+            command(6, 0, 0, 1, 1),
+        ]);
+        assert_eq!(result.len(), 2);
+
+        let result = coalesce_commands(&[
+            command(6, 0, 0, 0xffffffff, 0),
+            command(6, 0, 0, 1, 0xffffffff),
+        ]);
+        assert_eq!(result.len(), 2);
+    }
+}
@@ -0,0 +1,188 @@
+//! Packs multiple meshes in the same buffers, in order to draw them with a single multidraw
+//! command.
+//!
+use std::fmt;
+use std::error::Error;
+use std::os::raw;
+
+use backend::Facade;
+use buffer::BufferCreationError;
+use vertex::{Vertex, VertexBuffer};
+use vertex::BufferCreationError as VertexBufferCreationError;
+
+use index::{Index, IndexBuffer, PrimitiveType};
+use index::BufferCreationError as IndexBufferCreationError;
+use index::{DrawCommandIndices, DrawCommandsIndicesBuffer};
+
+/// Error that can happen when building a `MultiMesh`.
+#[derive(Copy, Clone, Debug)]
+pub enum MultiMeshCreationError {
+    /// Error while creating the vertex buffer.
+    VertexBufferCreationError(VertexBufferCreationError),
+
+    /// Error while creating the index buffer.
+    IndexBufferCreationError(IndexBufferCreationError),
+
+    /// Error while creating the buffer of draw commands.
+    CommandsBufferCreationError(BufferCreationError),
+
+    /// No mesh has been added to the builder.
+    Empty,
+}
+
+impl fmt::Display for MultiMeshCreationError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.description())
+    }
+}
+
+impl Error for MultiMeshCreationError {
+    fn description(&self) -> &str {
+        use self::MultiMeshCreationError::*;
+        match *self {
+            VertexBufferCreationError(_) =>
+                "Error while creating the vertex buffer",
+            IndexBufferCreationError(_) =>
+                "Error while creating the index buffer",
+            CommandsBufferCreationError(_) =>
+                "Error while creating the buffer of draw commands",
+            Empty =>
+                "No mesh has been added to the builder",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        use self::MultiMeshCreationError::*;
+        match *self {
+            VertexBufferCreationError(ref err) => Some(err),
+            IndexBufferCreationError(ref err) => Some(err),
+            CommandsBufferCreationError(ref err) => Some(err),
+            Empty => None,
+        }
+    }
+}
+
+/// Concatenates multiple meshes into a single vertex buffer, a single index buffer and a list
+/// of draw commands.
+///
+/// Each mesh keeps its own indices, which are relative to the first vertex of the mesh. The
+/// builder takes care of computing the `first_index` and `base_vertex` of each draw command.
+///
+/// ```no_run
+/// # #[macro_use] extern crate glium;
+/// # fn main() {
+/// # use glium::Surface;
+/// # #[derive(Copy, Clone)] struct Vertex { position: [f32; 2] }
+/// # implement_vertex!(Vertex, position);
+/// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+/// # let program: glium::Program = unsafe { std::mem::uninitialized() };
+/// # let (triangle_vertices, triangle_indices): (Vec<Vertex>, Vec<u16>) = (vec![], vec![]);
+/// # let (quad_vertices, quad_indices): (Vec<Vertex>, Vec<u16>) = (vec![], vec![]);
+/// use glium::index::{MultiMeshBuilder, PrimitiveType};
+///
+/// let mut builder = MultiMeshBuilder::new(PrimitiveType::TrianglesList);
+/// let triangle = builder.add(&triangle_vertices, &triangle_indices);
+/// let quad = builder.add(&quad_vertices, &quad_indices);
+/// let mut mesh = builder.build(&display).unwrap();
+///
+/// // `triangle` and `quad` are the indices of the corresponding commands, which can be
+/// // modified in order to change the number of instances of each mesh
+/// let mut commands = mesh.commands.map();
+/// commands[quad].instance_count = 3;
+/// # drop(commands);
+///
+/// let mut target = display.draw();
+/// target.draw(&mesh.vertices, mesh.commands.with_index_buffer(&mesh.indices), &program,
+///             &uniform!{}, &Default::default()).unwrap();
+/// target.finish().unwrap();
+/// # }
+/// ```
+pub struct MultiMeshBuilder<V, I> {
+    primitives: PrimitiveType,
+    vertices: Vec<V>,
+    indices: Vec<I>,
+    commands: Vec<DrawCommandIndices>,
+}
+
+impl<V, I> MultiMeshBuilder<V, I> where V: Vertex, I: Index {
+    /// Builds a new empty builder. All the meshes must use the same type of primitives.
+    #[inline]
+    pub fn new(primitives: PrimitiveType) -> MultiMeshBuilder<V, I> {
+        MultiMeshBuilder {
+            primitives: primitives,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            commands: Vec::new(),
+        }
+    }
+
+    /// Adds a mesh. The indices are relative to the first element of `vertices`.
+    ///
+    /// Returns the index of the draw command corresponding to this mesh. The command draws
+    /// a single instance of the mesh.
+    pub fn add(&mut self, vertices: &[V], indices: &[I]) -> usize {
+        self.commands.push(DrawCommandIndices {
+            count: indices.len() as raw::c_uint,
+            instance_count: 1,
+            first_index: self.indices.len() as raw::c_uint,
+            base_vertex: self.vertices.len() as raw::c_uint,
+            base_instance: 0,
+        });
+
+        self.vertices.extend_from_slice(vertices);
+        self.indices.extend_from_slice(indices);
+
+        self.commands.len() - 1
+    }
+
+    /// Returns the number of meshes that have been added.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns true if no mesh has been added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Uploads the meshes to video memory.
+    ///
+    /// Returns `Empty` if no mesh has been added.
+    pub fn build<F>(self, facade: &F) -> Result<MultiMesh<V, I>, MultiMeshCreationError>
+                    where F: Facade
+    {
+        if self.is_empty() {
+            return Err(MultiMeshCreationError::Empty);
+        }
+
+        let vertices = try!(VertexBuffer::new(facade, &self.vertices)
+                                .map_err(MultiMeshCreationError::VertexBufferCreationError));
+
+        let indices = try!(IndexBuffer::new(facade, self.primitives, &self.indices)
+                               .map_err(MultiMeshCreationError::IndexBufferCreationError));
+
+        let commands = try!(DrawCommandsIndicesBuffer::empty(facade, self.commands.len())
+                                .map_err(MultiMeshCreationError::CommandsBufferCreationError));
+        commands.write(&self.commands);
+
+        Ok(MultiMesh {
+            vertices: vertices,
+            indices: indices,
+            commands: commands,
+        })
+    }
+}
+
+/// Multiple meshes packed in the same buffers. Built with a `MultiMeshBuilder`.
+pub struct MultiMesh<V, I> where V: Copy, I: Index {
+    /// The vertices of all the meshes.
+    pub vertices: VertexBuffer<V>,
+
+    /// The indices of all the meshes.
+    pub indices: IndexBuffer<I>,
+
+    /// One draw command per mesh, in the order in which they were added to the builder.
+    pub commands: DrawCommandsIndicesBuffer,
+}
@@ -96,22 +96,27 @@ extern crate lazy_static;
 extern crate backtrace;
 extern crate smallvec;
 
+#[cfg(feature = "image")]
+extern crate image;
+
 #[cfg(feature = "glutin")]
 pub use backend::glutin_backend::glutin;
 pub use context::Profile;
 pub use draw_parameters::{Blend, BlendingFunction, LinearBlendingFactor, BackfaceCullingMode};
 pub use draw_parameters::{Depth, DepthTest, PolygonMode, DrawParameters, StencilTest, StencilOperation};
-pub use draw_parameters::{Smooth};
+pub use draw_parameters::{Smooth, ProvokingVertex, SurfaceOrigin, DepthClipMode};
 pub use index::IndexBuffer;
+pub use ops::ReadError;
 pub use vertex::{VertexBuffer, Vertex, VertexFormat};
 pub use program::{Program, ProgramCreationError};
 pub use program::ProgramCreationError::{CompilationError, LinkingError, ShaderTypeNotSupported};
-pub use sync::{LinearSyncFence, SyncFence};
+pub use sync::{FrameLimiter, LinearSyncFence, SyncFence};
 pub use texture::Texture2d;
 pub use version::{Api, Version, get_supported_glsl_version};
 
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
 use std::thread;
 use std::error::Error;
 use std::fmt;
@@ -169,6 +174,11 @@ pub trait CapabilitiesSource {
 }
 
 /// Trait for objects that are OpenGL objects.
+///
+/// The id can be passed to other libraries or to raw OpenGL calls, for example for interop
+/// with CUDA or OpenCL. The object is still owned by glium: you must not delete it, and you
+/// must not modify it in a way that invalidates the state that glium keeps about it (for
+/// example by reallocating the storage of a texture or of a buffer).
 pub trait GlObject {
     /// The type of identifier for this object.
     type Id;
@@ -269,6 +279,18 @@ trait ContextExt {
 
     /// Returns the capabilities of the backend.
     fn capabilities(&self) -> &context::Capabilities;
+
+    /// Queues a function that will be called with each of the other contexts that share their
+    /// objects with this one, the next time they are made current. Must be called when an
+    /// object is destroyed, so that these contexts purge their caches before being used again.
+    fn purge_shared_contexts<F>(&self, F) where F: Fn(&mut context::CommandContext) + 'static;
+
+    /// Returns the weak reference to the buffer returned by `index::fullscreen_triangle`.
+    fn fullscreen_triangle(&self)
+                           -> &RefCell<Option<Weak<VertexBuffer<index::FullscreenVertex>>>>;
+
+    /// Returns the strings registered with `add_shader_include`.
+    fn shader_includes(&self) -> &RefCell<HashMap<String, String>>;
 }
 
 /// Internal trait for programs.
@@ -281,6 +303,10 @@ trait ProgramExt {
     fn set_uniform(&self, ctxt: &mut context::CommandContext, uniform_location: gl::types::GLint,
                    value: &RawUniformValue);
 
+    /// Changes the value of a uniform array of the program, starting at its first element.
+    fn set_uniform_array(&self, ctxt: &mut context::CommandContext,
+                         uniform_location: gl::types::GLint, value: &RawUniformArrayValue);
+
     /// Changes the uniform block binding of the program.
     fn set_uniform_block_binding(&self, ctxt: &mut context::CommandContext,
                                  block_location: gl::types::GLuint, value: gl::types::GLuint);
@@ -316,6 +342,10 @@ trait QueryExt {
 
     fn end_transform_feedback_primitives_written_query(ctxt: &mut CommandContext);
 
+    /// Ends the `GL_TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN` query of a vertex stream.
+    fn end_transform_feedback_stream_primitives_written_query(ctxt: &mut CommandContext,
+                                                              stream: u32);
+
     fn begin_conditional_render(&self, ctxt: &mut CommandContext, wait: bool, per_region: bool);
 
     fn end_conditional_render(ctxt: &mut CommandContext);
@@ -338,6 +368,13 @@ trait TextureExt {
     /// Makes sure that the texture is binded to the current texture unit and returns the
     /// bind point to use to access the texture (eg. `GL_TEXTURE_2D`, `GL_TEXTURE_3D`, etc.).
     fn bind_to_current(&self, &mut CommandContext) -> gl::types::GLenum;
+
+    /// Returns the first and last mipmap levels that can be sampled.
+    fn get_sampled_levels(&self) -> (u32, u32);
+
+    /// Changes the mipmap levels that can be sampled with `GL_TEXTURE_BASE_LEVEL` and
+    /// `GL_TEXTURE_MAX_LEVEL`.
+    fn set_sampled_levels(&self, &mut CommandContext, base: u32, max: u32);
 }
 
 /// Internal trait for textures.
@@ -346,6 +383,7 @@ trait TextureMipmapExt {
     fn upload_texture<'a, P>(&self, x_offset: u32, y_offset: u32, z_offset: u32,
                              (image_format::ClientFormatAny, std::borrow::Cow<'a, [P]>), width: u32,
                              height: Option<u32>, depth: Option<u32>,
+                             row_length: Option<u32>, image_height: Option<u32>,
                              regen_mipmaps: bool)
                              -> Result<(), ()>   // TODO return a better Result!?
                              where P: Send + Copy + Clone + 'a;
@@ -374,6 +412,11 @@ trait UniformsExt {
     /// Will replace texture and buffer bind points.
     fn bind_uniforms<'a, P>(&'a self, &mut CommandContext, &P, &mut Vec<buffer::Inserter<'a>>)
                             -> Result<(), DrawError> where P: ProgramExt;
+
+    /// Returns the name of the first uniform used by the program whose texture is attached to
+    /// the framebuffer.
+    fn find_feedback_loop<P>(&self, &P, &fbo::ValidatedAttachments) -> Option<String>
+                             where P: ProgramExt;
 }
 
 
@@ -420,6 +463,21 @@ enum RawUniformValue {
     UnsignedInt64Vec4([gl::types::GLuint64; 4]),
 }
 
+/// A raw value of an array of uniforms. Passed directly with `glUniform*v`.
+#[derive(Copy, Clone, Debug)]
+enum RawUniformArrayValue<'a> {
+    Float(&'a [gl::types::GLfloat]),
+    /// 2x2 column-major matrices.
+    Mat2(&'a [[[gl::types::GLfloat; 2]; 2]]),
+    /// 3x3 column-major matrices.
+    Mat3(&'a [[[gl::types::GLfloat; 3]; 3]]),
+    /// 4x4 column-major matrices.
+    Mat4(&'a [[[gl::types::GLfloat; 4]; 4]]),
+    Vec2(&'a [[gl::types::GLfloat; 2]]),
+    Vec3(&'a [[gl::types::GLfloat; 3]]),
+    Vec4(&'a [[gl::types::GLfloat; 4]]),
+}
+
 /// Area of a surface in pixels.
 ///
 /// In the OpenGL ecosystem, the (0,0) coordinate is at the bottom-left hand corner of the images.
@@ -504,7 +562,7 @@ pub struct BlitTarget {
 ///
 /// *`viewport_left`, `viewport_width`, `viewport_bottom` and `viewport_height` correspond to
 /// the `viewport` member of the draw parameters, and `depth_near` and `depth_far` correspond
-/// to the `depth_range` member*.
+/// to the `depth.range` member*.
 ///
 /// This means that if `x / w`, `y / w` or `z / w` are equal to `-1.0`, then the result will be
 /// `viewport_left`, `viewport_bottom` or `depth_near`. If they are equal to `1.0`, the result
@@ -572,8 +630,8 @@ pub struct BlitTarget {
 ///
 /// If the target framebuffer has multisampling enabled, then each pixel of the target image is
 /// in turn split into four subpixels. The output of the fragment shader is copied and written into
-/// each subpixel. If `multisampling` in the draw parameters is `true` (its default value), only
-/// subpixels that belong to the triangle are written.
+/// each subpixel. If `multisampling.enabled` in the draw parameters is `true` (its default value),
+/// only subpixels that belong to the triangle are written.
 ///
 /// If a query has been specified through `samples_passed_query`, then its value is updated.
 ///
@@ -746,6 +804,33 @@ pub trait Surface {
         self.get_stencil_buffer_bits().is_some()
     }
 
+    /// Reads the content of a rectangle of the depth buffer.
+    ///
+    /// The values are between `0.0` and `1.0` and are returned row by row, starting from the
+    /// bottom-left corner of the rectangle.
+    ///
+    /// Returns `ReadError::NoDepthBuffer` if the surface doesn't have a depth buffer, and
+    /// `ReadError::MultisampledSource` if it is multisampled.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the rectangle is outside of the surface.
+    fn read_depth_rect(&self, rect: &Rect) -> Result<Vec<f32>, ReadError>;
+
+    /// Reads the content of the whole depth buffer. See `read_depth_rect`.
+    #[inline]
+    fn read_depth(&self) -> Result<Vec<f32>, ReadError> {
+        let (width, height) = self.get_dimensions();
+        self.read_depth_rect(&Rect { left: 0, bottom: 0, width: width, height: height })
+    }
+
+    /// Reads the value of a single pixel of the depth buffer. See `read_depth_rect`.
+    #[inline]
+    fn read_depth_pixel(&self, x: u32, y: u32) -> Result<f32, ReadError> {
+        self.read_depth_rect(&Rect { left: x, bottom: y, width: 1, height: 1 })
+            .map(|data| data[0])
+    }
+
     /// Draws.
     ///
     /// This is probably the most complex function of glium. Check out the rest of the
@@ -758,6 +843,120 @@ pub trait Surface {
         V: vertex::MultiVerticesSource<'b>, I: Into<index::IndicesSource<'a>>,
         U: uniforms::Uniforms;
 
+    /// Draws with a `Pipeline` that has been created ahead of time.
+    ///
+    /// This is the same as calling `draw` with the program and the draw parameters of the
+    /// pipeline, except that the checks that were performed when the pipeline was created
+    /// (whether the backend supports the draw parameters and whether the program can write
+    /// `gl_ViewportIndex`) are not done again.
+    ///
+    /// The default implementation simply calls `draw` and performs all the checks. The
+    /// surfaces provided by glium override it.
+    #[inline]
+    fn draw_with_pipeline<'a, 'b, V, I, U>(&mut self, pipeline: &draw_parameters::Pipeline,
+                                           vertices: V, indices: I, uniforms: &U)
+                                           -> Result<(), DrawError>
+                                           where V: vertex::MultiVerticesSource<'b>,
+                                                 I: Into<index::IndicesSource<'a>>,
+                                                 U: uniforms::Uniforms, Self: Sized
+    {
+        self.draw(vertices, indices, pipeline.get_program(), uniforms,
+                  pipeline.get_draw_parameters())
+    }
+
+    /// Draws a single triangle that covers the whole viewport.
+    ///
+    /// This is meant to be used for post-processing passes, like tonemapping an HDR
+    /// render target before presenting it. No vertex buffer is involved: the program must
+    /// not have any vertex attribute and must generate the vertices itself from
+    /// `gl_VertexID`. The `program::FULLSCREEN_VERTEX_SHADER_140` and
+    /// `program::FULLSCREEN_VERTEX_SHADER_300_ES` vertex shaders do exactly this and can be
+    /// combined with any fragment shader.
+    ///
+    /// ```no_run
+    /// # use glium::Surface;
+    /// # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+    /// # let fragment_shader_src = "";
+    /// # let hdr_texture: glium::texture::Texture2d = unsafe { ::std::mem::uninitialized() };
+    /// let program = glium::Program::from_source(&display,
+    ///                                           glium::program::FULLSCREEN_VERTEX_SHADER_140,
+    ///                                           fragment_shader_src, None).unwrap();
+    ///
+    /// let mut target = display.draw();
+    /// target.fullscreen_pass(&program, &uniform! { tex: &hdr_texture },
+    ///                        &Default::default()).unwrap();
+    /// target.finish().unwrap();
+    /// ```
+    #[inline]
+    fn fullscreen_pass<U>(&mut self, program: &Program, uniforms: &U,
+                          draw_parameters: &DrawParameters) -> Result<(), DrawError>
+                          where U: uniforms::Uniforms, Self: Sized
+    {
+        self.draw(vertex::EmptyVertexAttributes { len: 3 },
+                  index::NoIndices(index::PrimitiveType::TrianglesList), program, uniforms,
+                  draw_parameters)
+    }
+
+    /// Draws `instances` instances of the vertices, without any per-instance attribute.
+    ///
+    /// This is the same as passing `(vertices, vertex::EmptyInstanceAttributes { len: instances })`
+    /// to `draw`. The per-instance data must be computed by the program from `gl_InstanceID`.
+    #[inline]
+    fn draw_instanced<'a, 'b, V, I, U>(&mut self, vertices: V, indices: I, program: &Program,
+                                       uniforms: &U, draw_parameters: &DrawParameters,
+                                       instances: usize) -> Result<(), DrawError>
+                                       where V: vertex::IntoVerticesSource<'b>,
+                                             I: Into<index::IndicesSource<'a>>,
+                                             U: uniforms::Uniforms, Self: Sized
+    {
+        self.draw((vertices, vertex::EmptyInstanceAttributes { len: instances }), indices,
+                  program, uniforms, draw_parameters)
+    }
+
+    /// Draws the vertices that have been captured by the latest draw command of a transform
+    /// feedback session, without reading back their number.
+    ///
+    /// The buffers of the session are used as the vertices sources. If the session hasn't
+    /// captured anything yet, nothing is drawn.
+    ///
+    /// This requires OpenGL 4.0 or `GL_ARB_transform_feedback2`, otherwise
+    /// `TransformFeedbackDrawNotSupported` is returned. The draw parameters must not use the
+    /// same session to capture this draw command.
+    #[inline]
+    fn draw_transform_feedback<U>(&mut self, session: &vertex::TransformFeedbackSession,
+                                  program: &Program, uniforms: &U,
+                                  draw_parameters: &DrawParameters) -> Result<(), DrawError>
+                                  where U: uniforms::Uniforms, Self: Sized
+    {
+        self.draw_transform_feedback_stream(session, 0, program, uniforms, draw_parameters)
+    }
+
+    /// Same as `draw_transform_feedback`, but draws the vertices that have been captured for
+    /// a specific vertex stream.
+    ///
+    /// Only the program attributes that belong to the buffers of this stream should be used.
+    /// Using a stream other than `0` requires OpenGL 4.0 or `GL_ARB_transform_feedback3`.
+    ///
+    /// Drawing several instances, by calling `draw` with `IndicesSource::TransformFeedback` and
+    /// per-instance vertex sources, requires OpenGL 4.2 or `GL_ARB_transform_feedback_instanced`.
+    fn draw_transform_feedback_stream<U>(&mut self, session: &vertex::TransformFeedbackSession,
+                                         stream: u32, program: &Program, uniforms: &U,
+                                         draw_parameters: &DrawParameters)
+                                         -> Result<(), DrawError>
+                                         where U: uniforms::Uniforms, Self: Sized
+    {
+        let primitives = match session.get_captured_primitives() {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+
+        self.draw(session, index::IndicesSource::TransformFeedback {
+            session: session,
+            stream: stream,
+            primitives: primitives,
+        }, program, uniforms, draw_parameters)
+    }
+
     /// Blits from the default framebuffer.
     fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
                        filter: uniforms::MagnifySamplerFilter);
@@ -830,6 +1029,11 @@ pub enum DrawError {
     /// not used by the program.
     AttributeMissing,
 
+    /// One of the attributes required by the program is provided by multiple vertex formats.
+    ///
+    /// This is only returned by `Pipeline::new`. Drawing with such vertices sources panics.
+    AttributeInMultipleSources,
+
     /// The viewport's dimensions are not supported by the backend.
     ViewportTooLarge,
 
@@ -856,6 +1060,13 @@ pub enum DrawError {
         name: String,
     },
 
+    /// The offset of the slice of buffer binded to a uniform block is not a multiple of
+    /// `uniform_buffer_offset_alignment`.
+    UniformBufferOffsetNotAligned {
+        /// Name of the block you are trying to bind.
+        name: String,
+    },
+
     /// The layout of the content of the uniform buffer does not match the layout of the block.
     UniformBlockLayoutMismatch {
         /// Name of the block you are trying to bind.
@@ -910,6 +1121,14 @@ pub enum DrawError {
     /// You requested not to draw primitives, but this is not supported by the backend.
     TransformFeedbackNotSupported,
 
+    /// Drawing the vertices captured by a transform feedback session is not supported by the
+    /// backend.
+    TransformFeedbackDrawNotSupported,
+
+    /// Tried to draw the vertices captured by a transform feedback session while capturing the
+    /// same draw command with this session.
+    TransformFeedbackSessionInUse,
+
     /// See the documentation of the `draw_parameters` module for infos.
     WrongQueryOperation,
 
@@ -927,6 +1146,45 @@ pub enum DrawError {
 
     /// One of the blending parameters is not supported by the backend.
     BlendingParameterNotSupported,
+
+    /// One of the requested clip planes is above the maximum number of clip distances
+    /// supported by the backend.
+    ClipPlaneIndexOutOfBounds,
+
+    /// The requested line width is not supported by the backend.
+    LineWidthNotSupported,
+
+    /// Using several viewports is not supported by the backend.
+    ViewportArrayNotSupported,
+
+    /// The number of viewports is above the maximum supported by the backend.
+    TooManyViewports,
+
+    /// Using several viewports requires a geometry shader that writes `gl_ViewportIndex`.
+    ViewportArrayWithoutGeometryShader,
+
+    /// The number of scissor boxes is different from the number of viewports.
+    ScissorsCountMismatch,
+
+    /// Replacing the alpha value of the fragments by 1.0 is not supported by the backend.
+    AlphaToOneNotSupported,
+
+    /// Seamless cubemap filtering is not supported by the backend.
+    SeamlessCubemapsNotSupported,
+
+    /// Changing the origin or the depth mode of the clip control is not supported by the backend.
+    ClipControlNotSupported,
+
+    /// The OpenGL context has been lost and needs to be recreated. Nothing has been drawn.
+    ContextLost,
+
+    /// A texture used by the program is also attached to the framebuffer being drawn upon.
+    ///
+    /// This is only checked in debug builds.
+    FeedbackLoop {
+        /// Name of the uniform that contains the texture.
+        name: String,
+    },
 }
 
 impl Error for DrawError {
@@ -939,6 +1197,8 @@ impl Error for DrawError {
                 "The type of a vertex attribute in the vertices source doesn't match what the program requires",
             AttributeMissing =>
                 "One of the attributes required by the program is missing from the vertex format",
+            AttributeInMultipleSources =>
+                "One of the attributes required by the program is provided by multiple vertex formats",
             ViewportTooLarge =>
                 "The viewport's dimensions are not supported by the backend",
             InvalidDepthRange =>
@@ -949,6 +1209,8 @@ impl Error for DrawError {
                 "Tried to bind a uniform buffer to a single uniform value",
             UniformValueToBlock { .. } =>
                 "Tried to bind a single uniform value to a uniform block",
+            UniformBufferOffsetNotAligned { .. } =>
+                "The offset of the uniform buffer is not correctly aligned",
             UniformBlockLayoutMismatch { .. } =>
                 "The layout of the content of the uniform buffer does not match the layout of the block",
             SubroutineUniformToValue { .. } =>
@@ -971,6 +1233,12 @@ impl Error for DrawError {
                 "If you don't use indices, then all vertices sources must have the same size",
             TransformFeedbackNotSupported =>
                 "Requested not to draw primitves, but this is not supported by the backend",
+            TransformFeedbackDrawNotSupported =>
+                "Drawing the vertices captured by transform feedback is not supported by the \
+                 backend",
+            TransformFeedbackSessionInUse =>
+                "Tried to draw the vertices captured by a transform feedback session while \
+                 capturing with this session",
             WrongQueryOperation =>
                 "Wrong query operation",
             SmoothingNotSupported =>
@@ -983,6 +1251,28 @@ impl Error for DrawError {
                 "The depth clamp mode is not supported by the backend",
             BlendingParameterNotSupported =>
                 "One the blending parameters is not supported by the backend",
+            ClipPlaneIndexOutOfBounds =>
+                "One of the requested clip planes is not supported by the backend",
+            LineWidthNotSupported =>
+                "The requested line width is not supported by the backend",
+            ViewportArrayNotSupported =>
+                "Using several viewports is not supported by the backend",
+            TooManyViewports =>
+                "The number of viewports is above the maximum supported by the backend",
+            ViewportArrayWithoutGeometryShader =>
+                "Using several viewports requires a geometry shader",
+            ScissorsCountMismatch =>
+                "The number of scissor boxes is different from the number of viewports",
+            AlphaToOneNotSupported =>
+                "Replacing the alpha value by 1.0 is not supported by the backend",
+            SeamlessCubemapsNotSupported =>
+                "Seamless cubemap filtering is not supported by the backend",
+            ClipControlNotSupported =>
+                "Changing the clip control parameters is not supported by the backend",
+            ContextLost =>
+                "The OpenGL context has been lost and needs to be recreated",
+            FeedbackLoop { .. } =>
+                "A texture used by the program is also attached to the framebuffer",
         }
     }
 
@@ -1022,6 +1312,13 @@ impl fmt::Display for DrawError {
                     self.description(),
                     name,
                 ),
+            UniformBufferOffsetNotAligned { ref name } =>
+                write!(
+                    fmt,
+                    "{}: {}",
+                    self.description(),
+                    name,
+                ),
             UniformBlockLayoutMismatch { ref name, ref err } =>
                 write!(
                     fmt,
@@ -1030,6 +1327,13 @@ impl fmt::Display for DrawError {
                     name,
                     err,
                 ),
+            FeedbackLoop { ref name } =>
+                write!(
+                    fmt,
+                    "{}: {}",
+                    self.description(),
+                    name,
+                ),
             _ =>
                 write!(fmt, "{}", self.description()),
         }
@@ -1100,6 +1404,9 @@ impl Frame {
     /// Stop drawing, swap the buffers, and consume the Frame.
     ///
     /// See the documentation of `SwapBuffersError` about what is being returned.
+    ///
+    /// This function doesn't call `glGetError`, but may query the reset status of the context.
+    /// See `Context::swap_buffers` and `Context::set_error_checking_mode`.
     #[inline]
     pub fn finish(mut self) -> Result<(), SwapBuffersError> {
         self.set_finish()
@@ -1140,33 +1447,30 @@ impl Surface for Frame {
         self.context.capabilities().stencil_bits
     }
 
+    fn read_depth_rect(&self, rect: &Rect) -> Result<Vec<f32>, ReadError> {
+        ops::read_depth(&self.context, None, rect)
+    }
+
     fn draw<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
                          index_buffer: I, program: &Program, uniforms: &U,
                          draw_parameters: &DrawParameters) -> Result<(), DrawError>
                          where I: Into<index::IndicesSource<'a>>, U: uniforms::Uniforms,
                          V: vertex::MultiVerticesSource<'b>
     {
-        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
-                draw_parameters.depth.write)
-        {
-            return Err(DrawError::NoDepthBuffer);
-        }
-
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-        }
+        ops::draw_on_surface(self, &self.context, None,
+                             vertex_buffer, index_buffer, program, uniforms, draw_parameters, false)
+    }
 
-        ops::draw(&self.context, None, vertex_buffer, index_buffer.into(), program,
-                  uniforms, draw_parameters, (self.dimensions.0 as u32, self.dimensions.1 as u32))
+    fn draw_with_pipeline<'i, 'v, V, I, U>(&mut self, pipeline: &::draw_parameters::Pipeline,
+                                           vertices: V, indices: I, uniforms: &U)
+                                           -> Result<(), DrawError>
+                                           where V: ::vertex::MultiVerticesSource<'v>,
+                                                 I: Into<::index::IndicesSource<'i>>,
+                                                 U: ::uniforms::Uniforms
+    {
+        ops::draw_on_surface(self, &self.context, None,
+                             vertices, indices, pipeline.get_program(), uniforms,
+                             pipeline.get_draw_parameters(), true)
     }
 
     #[inline]
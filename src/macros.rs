@@ -501,6 +501,11 @@ macro_rules! program {
                 transform_feedback_varyings: None,
                 outputs_srgb: _outputs_srgb,
                 uses_point_size: _uses_point_size,
+                attribute_bindings: &[],
+                frag_data_bindings: &[],
+                uniform_block_bindings: &[],
+                uses_shader_includes: false,
+                defines: &[],
             };
 
             $crate::program::Program::new($context, input)
@@ -0,0 +1,157 @@
+use fbo::{self, ValidatedAttachments};
+
+use context::Context;
+use ContextExt;
+
+use version::Api;
+use version::Version;
+use gl;
+
+/// Attachment point of a framebuffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Attachment {
+    /// The color attachment with the given index.
+    Color(u32),
+
+    /// The depth attachment. Also designates the depth part of a depth-stencil attachment.
+    Depth,
+
+    /// The stencil attachment. Also designates the stencil part of a depth-stencil attachment.
+    Stencil,
+}
+
+/// Kind of object that is attached to a framebuffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AttachmentKind {
+    /// A layer or a mipmap of a texture.
+    Texture,
+
+    /// A render buffer.
+    RenderBuffer,
+}
+
+/// How the components of an attachment are stored.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AttachmentComponentType {
+    /// Floating-point values.
+    Float,
+
+    /// Signed integers.
+    Int,
+
+    /// Unsigned integers.
+    UnsignedInt,
+
+    /// Signed normalized values, between `-1.0` and `1.0`.
+    SignedNormalized,
+
+    /// Unsigned normalized values, between `0.0` and `1.0`.
+    UnsignedNormalized,
+}
+
+/// Informations about an attachment of a framebuffer, as reported by the backend.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AttachmentInfo {
+    /// Kind of object that is attached.
+    pub kind: AttachmentKind,
+
+    /// Number of bits of the red component.
+    pub red_bits: u16,
+
+    /// Number of bits of the green component.
+    pub green_bits: u16,
+
+    /// Number of bits of the blue component.
+    pub blue_bits: u16,
+
+    /// Number of bits of the alpha component.
+    pub alpha_bits: u16,
+
+    /// Number of bits of the depth component.
+    pub depth_bits: u16,
+
+    /// Number of bits of the stencil component.
+    pub stencil_bits: u16,
+
+    /// How the components are stored, or `None` if the backend didn't report it.
+    pub component_type: Option<AttachmentComponentType>,
+
+    /// True if the attachment is in the sRGB color space.
+    pub srgb: bool,
+
+    /// Number of samples of the framebuffer, or 0 if it isn't multisampled.
+    pub samples: u32,
+}
+
+/// Returns informations about an attachment of a framebuffer.
+///
+/// Returns `None` if nothing is attached at this point, if the index of a color attachment is
+/// above the maximum supported by the backend, or if the backend doesn't support
+/// `glGetFramebufferAttachmentParameteriv`.
+pub fn get_attachment_info(context: &Context, framebuffer: &ValidatedAttachments,
+                           attachment: Attachment) -> Option<AttachmentInfo>
+{
+    unsafe {
+        let mut ctxt = context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 3, 0)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 3, 0)) &&
+           !ctxt.extensions.gl_arb_framebuffer_object
+        {
+            return None;
+        }
+
+        let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt,
+                                                                             Some(framebuffer));
+        fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false);
+
+        let attachment = match attachment {
+            Attachment::Color(index) if index >= ctxt.capabilities.max_color_attachments as u32 => {
+                return None;
+            },
+            Attachment::Color(index) => gl::COLOR_ATTACHMENT0 + index,
+            Attachment::Depth => gl::DEPTH_ATTACHMENT,
+            Attachment::Stencil => gl::STENCIL_ATTACHMENT,
+        };
+
+        let get = |pname| {
+            let mut value = 0;
+            ctxt.gl.GetFramebufferAttachmentParameteriv(gl::DRAW_FRAMEBUFFER, attachment,
+                                                        pname, &mut value);
+            value
+        };
+
+        let kind = match get(gl::FRAMEBUFFER_ATTACHMENT_OBJECT_TYPE) as gl::types::GLenum {
+            gl::TEXTURE => AttachmentKind::Texture,
+            gl::RENDERBUFFER => AttachmentKind::RenderBuffer,
+            _ => return None,
+        };
+
+        let component_type = match get(gl::FRAMEBUFFER_ATTACHMENT_COMPONENT_TYPE)
+                                   as gl::types::GLenum
+        {
+            gl::FLOAT => Some(AttachmentComponentType::Float),
+            gl::INT => Some(AttachmentComponentType::Int),
+            gl::UNSIGNED_INT => Some(AttachmentComponentType::UnsignedInt),
+            gl::SIGNED_NORMALIZED => Some(AttachmentComponentType::SignedNormalized),
+            gl::UNSIGNED_NORMALIZED => Some(AttachmentComponentType::UnsignedNormalized),
+            _ => None,
+        };
+
+        let mut samples = 0;
+        ctxt.gl.GetIntegerv(gl::SAMPLES, &mut samples);
+
+        Some(AttachmentInfo {
+            kind: kind,
+            red_bits: get(gl::FRAMEBUFFER_ATTACHMENT_RED_SIZE) as u16,
+            green_bits: get(gl::FRAMEBUFFER_ATTACHMENT_GREEN_SIZE) as u16,
+            blue_bits: get(gl::FRAMEBUFFER_ATTACHMENT_BLUE_SIZE) as u16,
+            alpha_bits: get(gl::FRAMEBUFFER_ATTACHMENT_ALPHA_SIZE) as u16,
+            depth_bits: get(gl::FRAMEBUFFER_ATTACHMENT_DEPTH_SIZE) as u16,
+            stencil_bits: get(gl::FRAMEBUFFER_ATTACHMENT_STENCIL_SIZE) as u16,
+            component_type: component_type,
+            srgb: get(gl::FRAMEBUFFER_ATTACHMENT_COLOR_ENCODING) as gl::types::GLenum == gl::SRGB,
+            samples: samples as u32,
+        })
+    }
+}
@@ -18,8 +18,7 @@ pub fn blit(context: &Context, source: Option<&ValidatedAttachments>,
     unsafe {
         let mut ctxt = context.make_current();
 
-        // FIXME: we don't draw on it
-        let source = FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, source);
+        let source = FramebuffersContainer::get_framebuffer_for_blitting(&mut ctxt, source);
         let target = FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, target);
 
         // scissor testing influences blitting
@@ -29,7 +28,7 @@ pub fn blit(context: &Context, source: Option<&ValidatedAttachments>,
         }
 
         // trying to do a named blit if possible
-        if ctxt.version >= &Version(Api::Gl, 4, 5) {
+        if ctxt.capabilities.direct_state_access {
             ctxt.gl.BlitNamedFramebuffer(source, target,
                 src_rect.left as gl::types::GLint,
                 src_rect.bottom as gl::types::GLint,
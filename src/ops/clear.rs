@@ -56,6 +56,7 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments>,
             if ctxt.state.scissor != Some(rect) {
                 ctxt.gl.Scissor(rect.0, rect.1, rect.2, rect.3);
                 ctxt.state.scissor = Some(rect);
+                ctxt.state.scissor_array.clear();
             }
 
             if !ctxt.state.enabled_scissor_test {
@@ -116,6 +117,15 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments>,
                 ctxt.gl.ClearStencil(stencil);
                 ctxt.state.clear_stencil = stencil;
             }
+
+            // the stencil write mask also applies to `glClear`
+            if ctxt.state.stencil_mask_front != 0xffffffff ||
+               ctxt.state.stencil_mask_back != 0xffffffff
+            {
+                ctxt.gl.StencilMask(0xffffffff);
+                ctxt.state.stencil_mask_front = 0xffffffff;
+                ctxt.state.stencil_mask_back = 0xffffffff;
+            }
         }
 
         ctxt.gl.Clear(flags);
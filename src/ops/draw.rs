@@ -3,11 +3,14 @@ use std::ptr;
 use BufferExt;
 use BufferSliceExt;
 use ProgramExt;
+use QueryExt;
 use DrawError;
+use Surface;
 use UniformsExt;
 
 use context::Context;
 use ContextExt;
+use GlObject;
 use TransformFeedbackSessionExt;
 
 use fbo::{self, ValidatedAttachments};
@@ -18,17 +21,45 @@ use index::{self, IndicesSource};
 use vertex::{MultiVerticesSource, VerticesSource, TransformFeedbackSession};
 use vertex_array_object::VertexAttributesSystem;
 
-use draw_parameters::DrawParameters;
+use draw_parameters::{DrawParameters, SamplesQueryParam};
 
 use {gl, context, draw_parameters};
 use version::Version;
 use version::Api;
 
+/// Draws on a surface, after checking that it has a depth buffer if the draw parameters
+/// require one.
+///
+/// This is the implementation of `Surface::draw` and `Surface::draw_with_pipeline` for all the
+/// surfaces of glium.
+pub fn draw_on_surface<'a, 'b, S: ?Sized, V, I, U>(surface: &S, context: &Context,
+                                                   framebuffer: Option<&ValidatedAttachments>,
+                                                   vertex_buffers: V, indices: I,
+                                                   program: &Program, uniforms: &U,
+                                                   draw_parameters: &DrawParameters,
+                                                   prevalidated: bool) -> Result<(), DrawError>
+                                                   where S: Surface, U: Uniforms,
+                                                         V: MultiVerticesSource<'b>,
+                                                         I: Into<IndicesSource<'a>>
+{
+    if !surface.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
+                                       draw_parameters.depth.write)
+    {
+        return Err(DrawError::NoDepthBuffer);
+    }
+
+    draw(context, framebuffer, vertex_buffers, indices.into(), program, uniforms,
+         draw_parameters, surface.get_dimensions(), prevalidated)
+}
+
 /// Draws everything.
+///
+/// If `prevalidated` is true, the checks that are performed when building a `Pipeline` are
+/// skipped.
 pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachments>,
                       vertex_buffers: V, indices: IndicesSource,
                       program: &Program, uniforms: &U, draw_parameters: &DrawParameters,
-                      dimensions: (u32, u32)) -> Result<(), DrawError>
+                      dimensions: (u32, u32), prevalidated: bool) -> Result<(), DrawError>
                       where U: Uniforms, V: MultiVerticesSource<'a>
 {
     // this contains the list of fences that will need to be fulfilled after the draw command
@@ -65,9 +96,69 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
         },
     };
 
+    // drawing the vertices captured by transform feedback requires transform feedback objects
+    if let IndicesSource::TransformFeedback { session, stream, .. } = indices {
+        if session.get_id() == 0 {
+            return Err(DrawError::TransformFeedbackDrawNotSupported);
+        }
+
+        if stream != 0 && !(context.get_version() >= &Version(Api::Gl, 4, 0)) &&
+           !context.get_extensions().gl_arb_transform_feedback3
+        {
+            return Err(DrawError::TransformFeedbackDrawNotSupported);
+        }
+
+        if stream >= context.capabilities().max_vertex_streams as u32 {
+            return Err(DrawError::TransformFeedbackDrawNotSupported);
+        }
+
+        if let Some(tf) = draw_parameters.transform_feedback {
+            if tf.get_id() == session.get_id() {
+                return Err(DrawError::TransformFeedbackSessionInUse);
+            }
+        }
+    }
+
+    if !prevalidated {
+        try!(draw_parameters::validate(context, draw_parameters));
+
+        // `gl_ViewportIndex` can only be written by a geometry shader
+        if !draw_parameters.viewports.is_empty() && !program.has_geometry_shader() {
+            return Err(DrawError::ViewportArrayWithoutGeometryShader);
+        }
+    }
+
+    // sampling a texture that is attached to the framebuffer is undefined behavior ; since
+    // this requires going through all the uniforms, it is only checked in debug builds
+    if cfg!(debug_assertions) {
+        if let Some(framebuffer) = framebuffer {
+            if let Some(name) = uniforms.find_feedback_loop(program, framebuffer) {
+                return Err(DrawError::FeedbackLoop { name: name });
+            }
+        }
+    }
+
+    // a query that has never been used contains 0, but passing it to `glBeginConditionalRender`
+    // is an error ; the GPU wouldn't draw anything anyway, so we can return immediately
+    if let Some(ref condition) = draw_parameters.condition {
+        let unused = match condition.query {
+            SamplesQueryParam::SamplesPassedQuery(q) => q.is_unused(),
+            SamplesQueryParam::AnySamplesPassedQuery(q) => q.is_unused(),
+        };
+
+        if unused {
+            return Ok(());
+        }
+    }
+
     // starting the state changes
     let mut ctxt = context.make_current();
 
+    // after a context loss, the draw command would have no effect
+    if ctxt.state.lost_context {
+        return Err(DrawError::ContextLost);
+    }
+
     // handling vertices source
     let (vertices_count, instances_count, base_vertex) = {
         let index_buffer = match indices {
@@ -75,6 +166,7 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
             IndicesSource::MultidrawArray { .. } => None,
             IndicesSource::MultidrawElement { indices, .. } => Some(indices),
             IndicesSource::NoIndices { .. } => None,
+            IndicesSource::TransformFeedback { .. } => None,
         };
 
         // determining whether we can use the `base_vertex` variants for drawing
@@ -82,6 +174,7 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
             IndicesSource::MultidrawArray { .. } => false,
             IndicesSource::MultidrawElement { .. } => false,
             IndicesSource::NoIndices { .. } => true,
+            IndicesSource::TransformFeedback { .. } => false,
             _ => ctxt.version >= &Version(Api::Gl, 3, 2) ||
                  ctxt.version >= &Version(Api::GlEs, 3, 2) ||
                  ctxt.extensions.gl_arb_draw_elements_base_vertex ||
@@ -170,13 +263,25 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
     unsafe {
         try!(draw_parameters::sync(&mut ctxt, draw_parameters, dimensions, indices.get_primitives_type()));
         sync_vertices_per_patch(&mut ctxt, vertices_per_patch);
+        if vertices_per_patch.is_some() {
+            sync_default_tessellation_levels(&mut ctxt,
+                                             &draw_parameters.default_tessellation_levels);
+        }
 
         // TODO: make sure that the program is the right one
         // TODO: changing the current transform feedback requires pausing/unbinding before changing the program
         if let Some(ref tf) = draw_parameters.transform_feedback {
             tf.bind(&mut ctxt, indices.get_primitives_type());
         } else {
-            TransformFeedbackSession::unbind(&mut ctxt);
+            // a paused session stays paused, unless the vertices it captured are being drawn
+            let draws_captured_vertices = match indices {
+                IndicesSource::TransformFeedback { .. } => true,
+                _ => false,
+            };
+
+            if !ctxt.state.transform_feedback_paused || draws_captured_vertices {
+                TransformFeedbackSession::unbind(&mut ctxt);
+            }
         }
     }
 
@@ -321,6 +426,38 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                     }
                 }
             },
+
+            &IndicesSource::TransformFeedback { session, stream, primitives } => {
+                // the number of vertices is only known by the GPU once the capture has ended,
+                // which `TransformFeedbackSession::unbind` has done when syncing the parameters
+                let id = session.get_id();
+
+                unsafe {
+                    if let Some(instances_count) = instances_count {
+                        if !(ctxt.version >= &Version(Api::Gl, 4, 2)) &&
+                           !ctxt.extensions.gl_arb_transform_feedback_instanced
+                        {
+                            return Err(DrawError::TransformFeedbackDrawNotSupported);
+                        }
+
+                        if stream == 0 {
+                            ctxt.gl.DrawTransformFeedbackInstanced(primitives.to_glenum(), id,
+                                                                   instances_count as
+                                                                   gl::types::GLsizei);
+                        } else {
+                            ctxt.gl.DrawTransformFeedbackStreamInstanced(primitives.to_glenum(),
+                                                                         id, stream,
+                                                                         instances_count as
+                                                                         gl::types::GLsizei);
+                        }
+
+                    } else if stream == 0 {
+                        ctxt.gl.DrawTransformFeedback(primitives.to_glenum(), id);
+                    } else {
+                        ctxt.gl.DrawTransformFeedbackStream(primitives.to_glenum(), id, stream);
+                    }
+                }
+            },
         };
     };
 
@@ -334,6 +471,25 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
     Ok(())
 }
 
+unsafe fn sync_default_tessellation_levels(ctxt: &mut context::CommandContext,
+                                           levels: &([f32; 4], [f32; 2]))
+{
+    // OpenGL ES requires a tessellation control shader, so there is no default level
+    if !(ctxt.version >= &Version(Api::Gl, 4, 0)) && !ctxt.extensions.gl_arb_tessellation_shader {
+        return;
+    }
+
+    if ctxt.state.patch_default_outer_level != levels.0 {
+        ctxt.gl.PatchParameterfv(gl::PATCH_DEFAULT_OUTER_LEVEL, levels.0.as_ptr());
+        ctxt.state.patch_default_outer_level = levels.0;
+    }
+
+    if ctxt.state.patch_default_inner_level != levels.1 {
+        ctxt.gl.PatchParameterfv(gl::PATCH_DEFAULT_INNER_LEVEL, levels.1.as_ptr());
+        ctxt.state.patch_default_inner_level = levels.1;
+    }
+}
+
 unsafe fn sync_vertices_per_patch(ctxt: &mut context::CommandContext, vertices_per_patch: Option<u16>) {
     if let Some(vertices_per_patch) = vertices_per_patch {
         let vertices_per_patch = vertices_per_patch as gl::types::GLint;
@@ -1,9 +1,15 @@
+pub use self::attachment::{get_attachment_info, Attachment, AttachmentInfo, AttachmentKind};
+pub use self::attachment::AttachmentComponentType;
 pub use self::blit::blit;
 pub use self::clear::clear;
-pub use self::draw::draw;
-pub use self::read::{read, ReadError, Source, Destination};
+pub use self::draw::draw_on_surface;
+pub use self::read::{read, read_depth, ReadError, Source, Destination};
+pub use self::samples::{get_samples_count, get_sample_positions, set_sample_locations};
+pub use self::samples::SampleLocationsError;
 
+mod attachment;
 mod blit;
 mod clear;
 mod draw;
 mod read;
+mod samples;
@@ -9,11 +9,14 @@ use image_format::{TextureFormatRequest, TextureFormat};
 
 use fbo;
 use fbo::FramebuffersContainer;
+use ops::samples;
 
 use buffer::BufferAny;
 use BufferExt;
 use Rect;
 use context::CommandContext;
+use context::Context;
+use ContextExt;
 use gl;
 
 use version::Version;
@@ -23,6 +26,12 @@ use version::Api;
 pub enum Source<'a> {
     /// A regular framebuffer attachment.
     Attachment(&'a fbo::RegularAttachment<'a>),
+    /// A color attachment of a framebuffer, selected with `glReadBuffer`. The index is the
+    /// number of the attachment within the framebuffer, and the last member is the attachment
+    /// itself.
+    ColorAttachment(&'a fbo::ValidatedAttachments<'a>, u32, &'a fbo::RegularAttachment<'a>),
+    /// The depth buffer of a framebuffer, or of the default framebuffer if `None`.
+    DepthBuffer(Option<&'a fbo::ValidatedAttachments<'a>>),
     // TODO: use a Rust enum
     DefaultFramebuffer(gl::types::GLenum),
 }
@@ -73,6 +82,17 @@ pub enum ReadError {
     /// Clamping the values is not supported by the implementation.
     ClampingNotSupported,
 
+    /// Reading to a destination whose rows are larger than the rectangle is not supported by
+    /// the implementation, or the destination is a pixel buffer.
+    RowLengthNotSupported,
+
+    /// The surface doesn't have a depth buffer.
+    NoDepthBuffer,
+
+    /// The surface is multisampled and can't be read directly. Blit it to a surface that isn't
+    /// multisampled first.
+    MultisampledSource,
+
     // TODO: context lost
 }
 
@@ -92,19 +112,29 @@ impl Error for ReadError {
                 "The implementation doesn't support reading a depth, depth-stencil or stencil attachment",
             ClampingNotSupported =>
                 "Clamping the values is not supported by the implementation",
+            RowLengthNotSupported =>
+                "Reading with a custom row length is not supported by the implementation",
+            NoDepthBuffer =>
+                "The surface doesn't have a depth buffer",
+            MultisampledSource =>
+                "The surface is multisampled and can't be read directly",
         }
     }
 }
 
 /// Reads pixels from the source into the destination.
 ///
-/// Panicks if the destination is not large enough.
+/// If `row_length` is `Some`, each row of the destination is `row_length` pixels long
+/// and only the first `rect.width` pixels of each row are written.
+///
+/// Panicks if the destination is not large enough, or if `row_length` is smaller than the width
+/// of the rectangle.
 ///
 /// The `(u8, u8, u8, u8)` format is guaranteed to be supported.
 // TODO: differentiate between GL_* and GL_*_INTEGER
 #[inline]
 pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext, source: S, rect: &Rect, dest: D,
-                         clamp: bool) -> Result<(), ReadError>
+                         row_length: Option<u32>, clamp: bool) -> Result<(), ReadError>
                          where S: Into<Source<'a>>, D: Into<Destination<'a, T>>,
                                T: PixelValue
 {
@@ -112,12 +142,37 @@ pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext, source: S, rect: &Rect,
     let dest = dest.into();
     let output_pixel_format = <T as PixelValue>::get_format();
 
-    let pixels_to_read = rect.width * rect.height;
+    let row_length = row_length.unwrap_or(rect.width);
+    assert!(row_length >= rect.width);
+
+    let pixels_to_read = if rect.height == 0 {
+        0
+    } else {
+        row_length * (rect.height - 1) + rect.width
+    };
+
+    // checking that the row length is supported
+    if row_length != rect.width && !(ctxt.version >= &Version(Api::Gl, 1, 0) ||
+                                     ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+                                     ctxt.extensions.gl_nv_pack_subimage)
+    {
+        return Err(ReadError::RowLengthNotSupported);
+    }
+
+    // pixel buffers only store the dimensions of the image, not the length of its rows
+    if let Destination::PixelBuffer(_) = dest {
+        if row_length != rect.width {
+            return Err(ReadError::RowLengthNotSupported);
+        }
+    }
 
     // checking that the output format is supported
     // OpenGL supported everything, while OpenGL ES only supports U8U8U8U8 plus an additional
     // implementation-defined format
-    if ctxt.version >= &Version(Api::GlEs, 2, 0) && output_pixel_format != ClientFormat::U8U8U8U8 {
+    let depth_source = match source { Source::DepthBuffer(_) => true, _ => false };
+    if ctxt.version >= &Version(Api::GlEs, 2, 0) && !depth_source &&
+       output_pixel_format != ClientFormat::U8U8U8U8
+    {
         // TODO: GLES is guaranteed to support GL_RGBA and an implementation-defined format
         //       queried with GL_IMPLEMENTATION_COLOR_READ_FORMAT. We only handle GL_RGBA.
         return Err(ReadError::OutputFormatNotSupported);
@@ -148,17 +203,29 @@ pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext, source: S, rect: &Rect,
         Source::Attachment(attachment) => {
             unsafe { FramebuffersContainer::bind_framebuffer_for_reading(&mut ctxt, attachment) };
         },
+        Source::ColorAttachment(attachments, index, _) => {
+            unsafe {
+                FramebuffersContainer::bind_framebuffer_color_for_reading(&mut ctxt, attachments,
+                                                                          index)
+            };
+        },
         Source::DefaultFramebuffer(read_buffer) => {
             FramebuffersContainer::bind_default_framebuffer_for_reading(&mut ctxt, read_buffer);
         },
+        Source::DepthBuffer(attachments) => {
+            unsafe {
+                FramebuffersContainer::bind_framebuffer_depth_for_reading(&mut ctxt, attachments)
+            };
+        },
     };
 
     // determining what kind of data we are reading
     enum ReadSourceType { Color, Depth, Stencil, DepthStencil }
     let (integer, read_src_type) = match source {
-        Source::Attachment(attachment) => {
+        Source::Attachment(attachment) | Source::ColorAttachment(_, _, attachment) => {
             match attachment {
-                &fbo::RegularAttachment::Texture(ref tex) => {
+                &fbo::RegularAttachment::Texture(ref tex) |
+                &fbo::RegularAttachment::MultisampledTexture(ref tex, _) => {
                     let integer = match tex.get_texture().get_requested_format() {
                         TextureFormatRequest::Specific(TextureFormat::UncompressedIntegral(_)) => true,
                         TextureFormatRequest::Specific(TextureFormat::UncompressedUnsigned(_)) => true,
@@ -177,6 +244,7 @@ pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext, source: S, rect: &Rect,
         Source::DefaultFramebuffer(read_buffer) => {
             (false, ReadSourceType::Color)       // FIXME: wrong
         },
+        Source::DepthBuffer(_) => (false, ReadSourceType::Depth),
     };
 
     // OpenGL ES doesn't support reading from depth, stencil or depth-stencil attachments by default
@@ -201,9 +269,11 @@ pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext, source: S, rect: &Rect,
             client_format_to_gl_enum(&output_pixel_format, integer)
         },
         ReadSourceType::Depth => {
-            unimplemented!()        // TODO:
-            // TODO: NV_depth_buffer_float2
-            //(gl::DEPTH_COMPONENT, )
+            // TODO: other output formats
+            match output_pixel_format {
+                ClientFormat::F32 => (gl::DEPTH_COMPONENT, gl::FLOAT),
+                _ => return Err(ReadError::OutputFormatNotSupported),
+            }
         },
         ReadSourceType::DepthStencil => unimplemented!(),        // FIXME: only 24_8 is possible and there's no client format in the enum that corresponds to 24_8
         ReadSourceType::Stencil => {
@@ -214,31 +284,63 @@ pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext, source: S, rect: &Rect,
 
     // reading
     unsafe {
+        // the rows of the output are tightly packed, so the alignment of the rows must be
+        // adjusted to their size in bytes or OpenGL would add padding at the end of each row
+        let row_size = row_length as usize * output_pixel_format.get_size();
+        let alignment = if (row_size % 8) == 0 { 8 }
+                        else if (row_size % 4) == 0 { 4 }
+                        else if (row_size % 2) == 0 { 2 }
+                        else { 1 };
+        if ctxt.state.pixel_store_pack_alignment != alignment {
+            ctxt.state.pixel_store_pack_alignment = alignment;
+            ctxt.gl.PixelStorei(gl::PACK_ALIGNMENT, alignment);
+        }
+
+        let row_length = if row_length == rect.width { 0 } else { row_length as gl::types::GLint };
+        if ctxt.state.pixel_store_pack_row_length != row_length {
+            ctxt.state.pixel_store_pack_row_length = row_length;
+            ctxt.gl.PixelStorei(gl::PACK_ROW_LENGTH, row_length);
+        }
+
         match dest {
             Destination::Memory(dest) => {
                 let mut buf = Vec::with_capacity(pixels_to_read as usize);
 
                 BufferAny::unbind_pixel_pack(ctxt);
 
-                // adjusting data alignement
-                let ptr = buf.as_mut_ptr() as *mut D;
-                let ptr = ptr as usize;
-                if (ptr % 8) == 0 {
-                } else if (ptr % 4) == 0 && ctxt.state.pixel_store_pack_alignment != 4 {
-                    ctxt.state.pixel_store_pack_alignment = 4;
-                    ctxt.gl.PixelStorei(gl::PACK_ALIGNMENT, 4);
-                } else if (ptr % 2) == 0 && ctxt.state.pixel_store_pack_alignment > 2 {
-                    ctxt.state.pixel_store_pack_alignment = 2;
-                    ctxt.gl.PixelStorei(gl::PACK_ALIGNMENT, 2);
-                } else if ctxt.state.pixel_store_pack_alignment != 1 {
-                    ctxt.state.pixel_store_pack_alignment = 1;
-                    ctxt.gl.PixelStorei(gl::PACK_ALIGNMENT, 1);
+                // the robust versions of `glReadPixels` never write more than `buf_size` bytes
+                let buf_size = pixels_to_read as usize * output_pixel_format.get_size();
+                let buf_size = buf_size as gl::types::GLsizei;
+
+                if ctxt.version >= &Version(Api::Gl, 4, 5) ||
+                   ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+                   ctxt.extensions.gl_khr_robustness
+                {
+                    ctxt.gl.ReadnPixels(rect.left as gl::types::GLint,
+                                        rect.bottom as gl::types::GLint,
+                                        rect.width as gl::types::GLsizei,
+                                        rect.height as gl::types::GLsizei, format, gltype,
+                                        buf_size, buf.as_mut_ptr() as *mut _);
+                } else if ctxt.extensions.gl_arb_robustness {
+                    ctxt.gl.ReadnPixelsARB(rect.left as gl::types::GLint,
+                                           rect.bottom as gl::types::GLint,
+                                           rect.width as gl::types::GLsizei,
+                                           rect.height as gl::types::GLsizei, format, gltype,
+                                           buf_size, buf.as_mut_ptr() as *mut _);
+                } else if ctxt.extensions.gl_ext_robustness {
+                    ctxt.gl.ReadnPixelsEXT(rect.left as gl::types::GLint,
+                                           rect.bottom as gl::types::GLint,
+                                           rect.width as gl::types::GLsizei,
+                                           rect.height as gl::types::GLsizei, format, gltype,
+                                           buf_size, buf.as_mut_ptr() as *mut _);
+                } else {
+                    ctxt.gl.ReadPixels(rect.left as gl::types::GLint,
+                                       rect.bottom as gl::types::GLint,
+                                       rect.width as gl::types::GLsizei,
+                                       rect.height as gl::types::GLsizei, format, gltype,
+                                       buf.as_mut_ptr() as *mut _);
                 }
 
-                ctxt.gl.ReadPixels(rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
-                                   rect.width as gl::types::GLsizei,
-                                   rect.height as gl::types::GLsizei, format, gltype,
-                                   buf.as_mut_ptr() as *mut _);
                 buf.set_len(pixels_to_read as usize);
 
                 *dest = buf;
@@ -261,6 +363,38 @@ pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext, source: S, rect: &Rect,
     Ok(())
 }
 
+/// Reads the content of the depth buffer of a framebuffer, or of the default framebuffer if
+/// `None`, as values between `0.0` and `1.0`.
+///
+/// # Panic
+///
+/// Panics if the rectangle is outside of the framebuffer.
+pub fn read_depth(context: &Context, framebuffer: Option<&fbo::ValidatedAttachments>, rect: &Rect)
+                  -> Result<Vec<f32>, ReadError>
+{
+    let (depth_bits, (width, height)) = match framebuffer {
+        Some(framebuffer) => (framebuffer.get_depth_buffer_bits(), framebuffer.get_dimensions()),
+        None => (context.capabilities().depth_bits, context.get_framebuffer_dimensions()),
+    };
+
+    assert!(rect.left + rect.width <= width && rect.bottom + rect.height <= height);
+
+    if depth_bits.is_none() {
+        return Err(ReadError::NoDepthBuffer);
+    }
+
+    let mut ctxt = context.make_current();
+
+    // `glReadPixels` doesn't resolve multisampled framebuffers
+    if samples::get_samples_count(&mut ctxt, framebuffer) != 0 {
+        return Err(ReadError::MultisampledSource);
+    }
+
+    let mut data = Vec::new();
+    try!(read(&mut ctxt, Source::DepthBuffer(framebuffer), rect, &mut data, None, false));
+    Ok(data)
+}
+
 fn client_format_to_gl_enum(format: &ClientFormat, integer: bool)
                             -> (gl::types::GLenum, gl::types::GLenum)
 {
@@ -302,6 +436,8 @@ fn client_format_to_gl_enum(format: &ClientFormat, integer: bool)
         ClientFormat::F32F32 => (gl::RG, gl::FLOAT),
         ClientFormat::F32F32F32 => (gl::RGB, gl::FLOAT),
         ClientFormat::F32F32F32F32 => (gl::RGBA, gl::FLOAT),
+        ClientFormat::B8G8R8 => (gl::BGR, gl::UNSIGNED_BYTE),
+        ClientFormat::B8G8R8A8 => (gl::BGRA, gl::UNSIGNED_BYTE),
     };
 
     let format = if integer {
@@ -310,6 +446,8 @@ fn client_format_to_gl_enum(format: &ClientFormat, integer: bool)
             gl::RG => gl::RG_INTEGER,
             gl::RGB => gl::RGB_INTEGER,
             gl::RGBA => gl::RGBA_INTEGER,
+            gl::BGR => gl::BGR_INTEGER,
+            gl::BGRA => gl::BGRA_INTEGER,
             _ => unreachable!()
         }
     } else {
@@ -0,0 +1,134 @@
+use std::fmt;
+use std::error::Error;
+
+use fbo::{self, ValidatedAttachments};
+
+use context::{Context, CommandContext};
+use ContextExt;
+
+use version::Api;
+use version::Version;
+use gl;
+
+/// Returns the number of samples of the framebuffer, or `0` if it isn't multisampled.
+pub fn get_samples_count(ctxt: &mut CommandContext, framebuffer: Option<&ValidatedAttachments>)
+                         -> u32
+{
+    unsafe {
+        let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(ctxt, framebuffer);
+        fbo::bind_framebuffer(ctxt, fbo_id, true, false);
+
+        let mut samples = 0;
+        ctxt.gl.GetIntegerv(gl::SAMPLES, &mut samples);
+        samples as u32
+    }
+}
+
+/// Returns the position of each sample of the framebuffer, or `None` if querying them is not
+/// supported by the backend.
+pub fn get_sample_positions(context: &Context, framebuffer: Option<&ValidatedAttachments>)
+                            -> Option<Vec<[f32; 2]>>
+{
+    unsafe {
+        let mut ctxt = context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 3, 2)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 3, 1)) &&
+           !ctxt.extensions.gl_arb_texture_multisample
+        {
+            return None;
+        }
+
+        let samples = get_samples_count(&mut ctxt, framebuffer);
+
+        // a framebuffer without multisampling has a single sample at the center of the pixel
+        if samples == 0 {
+            return Some(vec![[0.5, 0.5]]);
+        }
+
+        Some((0 .. samples).map(|index| {
+            let mut value = [0.0, 0.0];
+            ctxt.gl.GetMultisamplefv(gl::SAMPLE_POSITION, index, value.as_mut_ptr());
+            value
+        }).collect())
+    }
+}
+
+/// Error that can happen when setting the position of the samples of a framebuffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SampleLocationsError {
+    /// The backend doesn't support programmable sample locations.
+    NotSupported,
+
+    /// The number of locations is not the same as the number of samples of the framebuffer.
+    SamplesCountMismatch {
+        /// Number of samples of the framebuffer.
+        expected: u32,
+        /// Number of locations that were passed.
+        obtained: usize,
+    },
+}
+
+impl fmt::Display for SampleLocationsError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        use self::SampleLocationsError::*;
+        match *self {
+            SamplesCountMismatch { expected, obtained } =>
+                write!(fmt, "{}: expected {}, obtained {}", self.description(), expected,
+                       obtained),
+            _ =>
+                write!(fmt, "{}", self.description()),
+        }
+    }
+}
+
+impl Error for SampleLocationsError {
+    fn description(&self) -> &str {
+        use self::SampleLocationsError::*;
+        match *self {
+            NotSupported =>
+                "The backend doesn't support programmable sample locations",
+            SamplesCountMismatch { .. } =>
+                "The number of locations is not the same as the number of samples",
+        }
+    }
+}
+
+/// Sets the position of the samples of the framebuffer with `GL_ARB_sample_locations`.
+///
+/// Passing an empty list restores the default locations. Otherwise there must be exactly one
+/// location per sample.
+pub fn set_sample_locations(context: &Context, framebuffer: Option<&ValidatedAttachments>,
+                            locations: &[[f32; 2]]) -> Result<(), SampleLocationsError>
+{
+    unsafe {
+        let mut ctxt = context.make_current();
+
+        if !ctxt.extensions.gl_arb_sample_locations {
+            return Err(SampleLocationsError::NotSupported);
+        }
+
+        // also binds the framebuffer
+        let samples = get_samples_count(&mut ctxt, framebuffer);
+
+        if locations.is_empty() {
+            ctxt.gl.FramebufferParameteri(gl::DRAW_FRAMEBUFFER,
+                                          gl::FRAMEBUFFER_PROGRAMMABLE_SAMPLE_LOCATIONS_ARB, 0);
+            return Ok(());
+        }
+
+        if locations.len() != samples as usize {
+            return Err(SampleLocationsError::SamplesCountMismatch {
+                expected: samples,
+                obtained: locations.len(),
+            });
+        }
+
+        ctxt.gl.FramebufferParameteri(gl::DRAW_FRAMEBUFFER,
+                                      gl::FRAMEBUFFER_PROGRAMMABLE_SAMPLE_LOCATIONS_ARB, 1);
+        ctxt.gl.FramebufferSampleLocationsfvARB(gl::DRAW_FRAMEBUFFER, 0,
+                                                locations.len() as gl::types::GLsizei,
+                                                locations.as_ptr() as *const _);
+        Ok(())
+    }
+}
@@ -12,11 +12,13 @@ use GlObject;
 use ProgramExt;
 use Handle;
 use RawUniformValue;
+use RawUniformArrayValue;
 
 use program::{COMPILER_GLOBAL_LOCK, ProgramCreationError, Binary, GetBinaryError};
 
 use program::reflection::{Uniform, UniformBlock};
 use program::reflection::{ShaderStage, SubroutineData};
+use program::uniform_value::ReadableUniform;
 use program::shader::{build_shader, check_shader_type_compatibility};
 
 use program::raw::RawProgram;
@@ -43,10 +45,11 @@ impl ComputeShader {
     {
         let _lock = COMPILER_GLOBAL_LOCK.lock();
 
-        let shader = try!(build_shader(facade, gl::COMPUTE_SHADER, src));
+        let shader = try!(build_shader(facade, gl::COMPUTE_SHADER, src, false, &[]));
 
         Ok(ComputeShader {
-            raw: try!(RawProgram::from_shaders(facade, &[shader], false, false, false, None))
+            raw: try!(RawProgram::from_shaders(facade, &[shader], false, false, false, None, &[],
+                                               &[]))
         })
     }
 
@@ -75,6 +78,10 @@ impl ComputeShader {
     /// Executes the compute shader.
     ///
     /// This is similar to `execute`, except that the parameters are stored in a buffer.
+    ///
+    /// The buffer can be written by a previous shader, for example to dispatch a number of work
+    /// groups computed on the GPU. In this situation glium automatically inserts the required
+    /// memory barrier before the dispatch.
     #[inline]
     pub fn execute_indirect<U>(&self, uniforms: U, buffer: BufferSlice<ComputeCommand>)
                                where U: Uniforms
@@ -97,6 +104,21 @@ impl ComputeShader {
         self.raw.get_uniform(name)
     }
 
+    /// Reads the current value of a uniform from the program.
+    ///
+    /// Returns `None` if the uniform doesn't exist or if its type doesn't match `T`.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # let program: glium::program::ComputeShader = unsafe { std::mem::uninitialized() };
+    /// let matrix: Option<[[f32; 4]; 4]> = program.get_uniform_value("matrix");
+    /// ```
+    #[inline]
+    pub fn get_uniform_value<T>(&self, name: &str) -> Option<T> where T: ReadableUniform {
+        self.raw.get_uniform_value(name)
+    }
+
     /// Returns an iterator to the list of uniforms.
     ///
     /// ## Example
@@ -141,6 +163,22 @@ impl ComputeShader {
     pub fn get_shader_storage_blocks(&self) -> &HashMap<String, UniformBlock> {
         self.raw.get_shader_storage_blocks()
     }
+
+    /// Returns the info log produced by the driver when compiling the shader.
+    ///
+    /// See `Program::get_compile_log`.
+    #[inline]
+    pub fn get_compile_log(&self) -> &str {
+        self.raw.get_compile_log()
+    }
+
+    /// Returns the info log produced by the driver when linking the shader.
+    ///
+    /// See `Program::get_link_log`.
+    #[inline]
+    pub fn get_link_log(&self) -> &str {
+        self.raw.get_link_log()
+    }
 }
 
 impl fmt::Debug for ComputeShader {
@@ -172,6 +210,13 @@ impl ProgramExt for ComputeShader {
         self.raw.set_uniform(ctxt, uniform_location, value)
     }
 
+    #[inline]
+    fn set_uniform_array(&self, ctxt: &mut CommandContext, uniform_location: gl::types::GLint,
+                         value: &RawUniformArrayValue)
+    {
+        self.raw.set_uniform_array(ctxt, uniform_location, value)
+    }
+
     #[inline]
     fn set_uniform_block_binding(&self, ctxt: &mut CommandContext, block_location: gl::types::GLuint,
                                  value: gl::types::GLuint)
@@ -9,9 +9,10 @@ use version::Version;
 
 pub use self::compute::{ComputeShader, ComputeCommand};
 pub use self::program::Program;
-pub use self::reflection::{Uniform, UniformBlock, BlockLayout, OutputPrimitives};
+pub use self::reflection::{Uniform, UniformBlock, BlockLayout, BlockMember, OutputPrimitives};
 pub use self::reflection::{Attribute, TransformFeedbackVarying, TransformFeedbackBuffer, TransformFeedbackMode};
 pub use self::reflection::{ShaderStage, SubroutineData, SubroutineUniform};
+pub use self::uniform_value::ReadableUniform;
 
 mod compute;
 mod program;
@@ -19,8 +20,37 @@ mod raw;
 mod reflection;
 mod shader;
 mod uniforms_storage;
+mod uniform_value;
 mod binary_header;
 
+/// Source code of a GLSL 1.40 vertex shader that can be used with `Surface::fullscreen_pass`.
+///
+/// The shader generates a triangle that covers the whole viewport from `gl_VertexID` and
+/// passes texture coordinates between `0.0` and `1.0` to the fragment shader in a
+/// `vec2 v_tex_coords` varying.
+pub const FULLSCREEN_VERTEX_SHADER_140: &'static str = "
+    #version 140
+
+    out vec2 v_tex_coords;
+
+    void main() {
+        v_tex_coords = vec2((gl_VertexID << 1) & 2, gl_VertexID & 2);
+        gl_Position = vec4(v_tex_coords * 2.0 - 1.0, 0.0, 1.0);
+    }
+";
+
+/// Same as `FULLSCREEN_VERTEX_SHADER_140`, but for GLSL ES 3.00.
+pub const FULLSCREEN_VERTEX_SHADER_300_ES: &'static str = "
+    #version 300 es
+
+    out vec2 v_tex_coords;
+
+    void main() {
+        v_tex_coords = vec2((gl_VertexID << 1) & 2, gl_VertexID & 2);
+        gl_Position = vec4(v_tex_coords * 2.0 - 1.0, 0.0, 1.0);
+    }
+";
+
 /// Returns true if the backend supports geometry shaders.
 #[inline]
 pub fn is_geometry_shader_supported<C>(ctxt: &C) -> bool where C: CapabilitiesSource {
@@ -78,8 +108,21 @@ pub enum ProgramCreationError {
     /// supported by the backend.
     PointSizeNotSupported,
 
+    /// You have requested explicit locations for the outputs of the fragment shader, but this
+    /// is not supported by the backend.
+    FragDataBindingsNotSupported,
+
     /// The glium-specific binary header was not found or is corrupt.
     BinaryHeaderError,
+
+    /// One of the binding points of `uniform_block_bindings` is above
+    /// `GL_MAX_UNIFORM_BUFFER_BINDINGS`, or uniform buffers are not supported by the backend.
+    UniformBlockBindingOutOfRange,
+
+    /// One of the names passed when creating the program, for example in `attribute_bindings`
+    /// or `frag_data_bindings`, contains a nul character. For `defines`, this is also returned
+    /// if the value contains a nul character, and the `String` is the name of the define.
+    NulCharacterInName(String),
 }
 
 impl fmt::Display for ProgramCreationError {
@@ -90,6 +133,8 @@ impl fmt::Display for ProgramCreationError {
                 write!(fmt, "{}: {}", self.description(), s),
             LinkingError(ref s) =>
                 write!(fmt, "{}: {}", self.description(), s),
+            NulCharacterInName(ref s) =>
+                write!(fmt, "{}: {:?}", self.description(), s),
             _ =>
                 write!(fmt, "{}", self.description()),
         }
@@ -112,8 +157,14 @@ impl Error for ProgramCreationError {
                 "Transform feedback is not supported by the backend.",
             PointSizeNotSupported =>
                 "Point size is not supported by the backend.",
+            FragDataBindingsNotSupported =>
+                "Binding the outputs of the fragment shader is not supported by the backend.",
             BinaryHeaderError =>
                 "The glium-specific binary header was not found or is corrupt.",
+            UniformBlockBindingOutOfRange =>
+                "A uniform block binding point is not supported by the backend.",
+            NulCharacterInName(_) =>
+                "A name contains a nul character.",
         }
     }
 }
@@ -209,6 +260,11 @@ pub enum ProgramCreationInput<'a> {
         ///
         /// The information specified here will be passed to the OpenGL linker. If you pass
         /// `None`, then you won't be able to use transform feedback.
+        ///
+        /// In `Interleaved` mode, the special names `gl_NextBuffer` and `gl_SkipComponents1` to
+        /// `gl_SkipComponents4` can be used to write the next varyings to the next buffer or to
+        /// leave a gap in the current buffer. This requires OpenGL 4.0 or
+        /// `GL_ARB_transform_feedback3`.
         transform_feedback_varyings: Option<(Vec<String>, TransformFeedbackMode)>,
 
         /// Whether the fragment shader outputs colors in `sRGB` or `RGB`. This is false by default,
@@ -219,7 +275,50 @@ pub enum ProgramCreationInput<'a> {
         outputs_srgb: bool,
 
         /// Whether the shader uses point size.
+        ///
+        /// If this is true, then `GL_PROGRAM_POINT_SIZE` will be enabled when this program is
+        /// used and the size of the points is the value written to `gl_PointSize`. Otherwise
+        /// the size of the points is the `point_size` of the draw parameters.
         uses_point_size: bool,
+
+        /// Locations to give to the vertex attributes of the program before linking it, with
+        /// `glBindAttribLocation`.
+        ///
+        /// This allows several programs to use the same attribute locations, and thus the same
+        /// vertex array objects, without `layout(location = ...)` in the source code. Names that
+        /// don't match an attribute of the program are ignored. An explicit location in the
+        /// source code takes precedence.
+        attribute_bindings: &'a [(&'a str, u32)],
+
+        /// Draw buffers to give to the outputs of the fragment shader before linking the
+        /// program, with `glBindFragDataLocation`.
+        ///
+        /// This allows choosing which output is written to which color attachment without
+        /// `layout(location = ...)` in the source code. Names that don't match an output of the
+        /// program are ignored. An explicit location in the source code takes precedence.
+        frag_data_bindings: &'a [(&'a str, u32)],
+
+        /// Binding points to give to the uniform blocks of the program after linking it, with
+        /// `glUniformBlockBinding`.
+        ///
+        /// When drawing, the buffer of a block is bound to this binding point if it is not
+        /// already used by another block. This allows several programs to share the same
+        /// binding points without `layout(binding = ...)` in the source code. Names that don't
+        /// match a uniform block of the program are ignored.
+        uniform_block_bindings: &'a [(&'a str, u32)],
+
+        /// Whether the shaders contain `#include` directives.
+        ///
+        /// If this is true, the directives are resolved with the strings registered with
+        /// `Context::add_shader_include`.
+        uses_shader_includes: bool,
+
+        /// Macros to define in each shader before compiling it, as `(name, value)` pairs.
+        ///
+        /// Each pair is turned into a `#define name value` line that is inserted after the
+        /// `#version` and `#extension` directives at the start of the source code. This allows
+        /// building several variants of the same program without modifying its source code.
+        defines: &'a [(&'a str, &'a str)],
     },
 
     /// Use a precompiled binary.
@@ -268,6 +367,11 @@ impl<'a> From<SourceCode<'a>> for ProgramCreationInput<'a> {
             transform_feedback_varyings: None,
             outputs_srgb: false,
             uses_point_size: false,
+            attribute_bindings: &[],
+            frag_data_bindings: &[],
+            uniform_block_bindings: &[],
+            uses_shader_includes: false,
+            defines: &[],
         }
     }
 }
@@ -14,11 +14,13 @@ use GlObject;
 use ProgramExt;
 use Handle;
 use RawUniformValue;
+use RawUniformArrayValue;
 
 use program::{COMPILER_GLOBAL_LOCK, ProgramCreationInput, ProgramCreationError, Binary};
 use program::GetBinaryError;
 
-use program::reflection::{Uniform, UniformBlock, OutputPrimitives};
+use program::reflection::{Uniform, UniformBlock, BlockMember, OutputPrimitives};
+use program::uniform_value::ReadableUniform;
 use program::reflection::{Attribute, TransformFeedbackBuffer};
 use program::reflection::{SubroutineData, ShaderStage, SubroutineUniform};
 use program::shader::build_shader;
@@ -45,7 +47,10 @@ impl Program {
             ProgramCreationInput::SourceCode { vertex_shader, tessellation_control_shader,
                                                tessellation_evaluation_shader, geometry_shader,
                                                fragment_shader, transform_feedback_varyings,
-                                               outputs_srgb, uses_point_size } =>
+                                               outputs_srgb, uses_point_size,
+                                               attribute_bindings, frag_data_bindings,
+                                               uniform_block_bindings, uses_shader_includes,
+                                               defines } =>
             {
                 let mut has_geometry_shader = false;
                 let mut has_tessellation_control_shader = false;
@@ -73,8 +78,8 @@ impl Program {
 
                 // TODO: move somewhere else
                 if transform_feedback_varyings.is_some() &&
-                    (facade.get_context().get_version() >= &Version(Api::Gl, 3, 0) ||
-                        !facade.get_context().get_extensions().gl_ext_transform_feedback)
+                    !(facade.get_context().get_version() >= &Version(Api::Gl, 3, 0)) &&
+                    !facade.get_context().get_extensions().gl_ext_transform_feedback
                 {
                     return Err(ProgramCreationError::TransformFeedbackNotSupported);
                 }
@@ -83,19 +88,45 @@ impl Program {
                     return Err(ProgramCreationError::PointSizeNotSupported);
                 }
 
+                if let Some(&(name, _)) = attribute_bindings.iter().chain(frag_data_bindings.iter())
+                                                            .find(|&&(n, _)| n.contains('\0'))
+                {
+                    return Err(ProgramCreationError::NulCharacterInName(name.to_owned()));
+                }
+
+                if let Some(&(name, _)) = defines.iter().find(|&&(n, v)| {
+                    n.contains('\0') || v.contains('\0')
+                }) {
+                    return Err(ProgramCreationError::NulCharacterInName(name.to_owned()));
+                }
+
+                if !frag_data_bindings.is_empty() &&
+                   !(facade.get_context().get_version() >= &Version(Api::Gl, 3, 0)) &&
+                   !facade.get_context().get_extensions().gl_ext_gpu_shader4
+                {
+                    return Err(ProgramCreationError::FragDataBindingsNotSupported);
+                }
+
+                let max_bindings = facade.get_capabilities().max_indexed_uniform_buffer as u32;
+                if uniform_block_bindings.iter().any(|&(_, binding)| binding >= max_bindings) {
+                    return Err(ProgramCreationError::UniformBlockBindingOutOfRange);
+                }
+
                 let _lock = COMPILER_GLOBAL_LOCK.lock();
 
                 let shaders_store = {
                     let mut shaders_store = Vec::new();
                     for (src, ty) in shaders.into_iter() {
-                        shaders_store.push(try!(build_shader(facade, ty, src)));
+                        shaders_store.push(try!(build_shader(facade, ty, src,
+                                                             uses_shader_includes, defines)));
                     }
                     shaders_store
                 };
 
                 (try!(RawProgram::from_shaders(facade, &shaders_store, has_geometry_shader,
                                                has_tessellation_control_shader, has_tessellation_evaluation_shader,
-                                               transform_feedback_varyings)),
+                                               transform_feedback_varyings, attribute_bindings,
+                                               frag_data_bindings, uniform_block_bindings)),
                  outputs_srgb, uses_point_size)
             },
 
@@ -147,6 +178,11 @@ impl Program {
             transform_feedback_varyings: None,
             outputs_srgb: false,
             uses_point_size: false,
+            attribute_bindings: &[],
+            frag_data_bindings: &[],
+            uniform_block_bindings: &[],
+            uses_shader_includes: false,
+            defines: &[],
         })
     }
 
@@ -181,6 +217,21 @@ impl Program {
         self.raw.get_uniform(name)
     }
 
+    /// Reads the current value of a uniform from the program.
+    ///
+    /// Returns `None` if the uniform doesn't exist or if its type doesn't match `T`.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # let program: glium::Program = unsafe { std::mem::uninitialized() };
+    /// let matrix: Option<[[f32; 4]; 4]> = program.get_uniform_value("matrix");
+    /// ```
+    #[inline]
+    pub fn get_uniform_value<T>(&self, name: &str) -> Option<T> where T: ReadableUniform {
+        self.raw.get_uniform_value(name)
+    }
+
     /// Returns an iterator to the list of uniforms.
     ///
     /// ## Example
@@ -226,6 +277,17 @@ impl Program {
         self.raw.transform_feedback_matches(format, stride)
     }
 
+    /// True if the transform feedback buffer of index `index` of this program matches the
+    /// specified `VertexFormat` and `stride`.
+    ///
+    /// The `stride` is the number of bytes between two vertices.
+    #[inline]
+    pub fn transform_feedback_buffer_matches(&self, index: usize, format: &VertexFormat,
+                                             stride: usize) -> bool
+    {
+        self.raw.transform_feedback_buffer_matches(index, format, stride)
+    }
+
     /// Returns the type of geometry that transform feedback would generate, or `None` if it
     /// depends on the vertex/index data passed when drawing.
     ///
@@ -236,6 +298,22 @@ impl Program {
         self.raw.get_output_primitives()
     }
 
+    /// Returns the maximum number of vertices that the geometry shader outputs per invocation,
+    /// as declared with `layout(max_vertices = ...)`, or `None` if the program doesn't contain
+    /// a geometry shader.
+    #[inline]
+    pub fn get_geometry_shader_max_output_vertices(&self) -> Option<u32> {
+        self.raw.get_geometry_shader_max_output_vertices()
+    }
+
+    /// Returns the number of times the geometry shader is invoked for each primitive, as
+    /// declared with `layout(invocations = ...)`, or `None` if the program doesn't contain
+    /// a geometry shader.
+    #[inline]
+    pub fn get_geometry_shader_invocations(&self) -> Option<u32> {
+        self.raw.get_geometry_shader_invocations()
+    }
+
     /// Returns true if the program contains a tessellation stage.
     #[inline]
     pub fn has_tessellation_shaders(&self) -> bool {
@@ -260,6 +338,25 @@ impl Program {
         self.raw.has_geometry_shader()
     }
 
+    /// Returns the info logs produced by the driver when compiling the shaders of the program.
+    ///
+    /// The compilation succeeded, but the logs can contain warnings. The logs of the different
+    /// shaders are separated by a line break. This is empty if the program has been created
+    /// from a binary, or if the driver had nothing to say.
+    #[inline]
+    pub fn get_compile_log(&self) -> &str {
+        self.raw.get_compile_log()
+    }
+
+    /// Returns the info log produced by the driver when linking the program.
+    ///
+    /// The linking succeeded, but the log can contain warnings. This is empty if the driver had
+    /// nothing to say.
+    #[inline]
+    pub fn get_link_log(&self) -> &str {
+        self.raw.get_link_log()
+    }
+
     /// Returns informations about an attribute, if it exists.
     #[inline]
     pub fn get_attribute(&self, name: &str) -> Option<&Attribute> {
@@ -302,6 +399,28 @@ impl Program {
         self.raw.get_shader_storage_blocks()
     }
 
+    /// Returns the list of active members of the uniform block or shader storage block with
+    /// the given name, with their offsets and strides.
+    ///
+    /// This allows you to write data in a buffer that matches the layout of the block without
+    /// hardcoding the offsets. Returns `None` if the block doesn't exist.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # let program: glium::Program = unsafe { std::mem::uninitialized() };
+    /// for member in program.get_block_members("MyBlock").unwrap() {
+    ///     println!("{} at offset {} with array stride {}", member.name, member.offset,
+    ///              member.array_stride);
+    /// }
+    /// ```
+    #[inline]
+    pub fn get_block_members(&self, name: &str) -> Option<&[BlockMember]> {
+        self.raw.get_uniform_blocks().get(name)
+                .or_else(|| self.raw.get_shader_storage_blocks().get(name))
+                .map(|block| &block.members[..])
+    }
+
     /// Returns the subroutine uniforms of this program.
     ///
     /// Since subroutine uniforms are unique per shader and *not* per program,
@@ -350,8 +469,10 @@ impl ProgramExt for Program {
         // compatibility was checked at program creation
         if self.uses_point_size && !ctxt.state.enabled_program_point_size {
             unsafe { ctxt.gl.Enable(gl::PROGRAM_POINT_SIZE); }
+            ctxt.state.enabled_program_point_size = true;
         } else if !self.uses_point_size && ctxt.state.enabled_program_point_size {
             unsafe { ctxt.gl.Disable(gl::PROGRAM_POINT_SIZE); }
+            ctxt.state.enabled_program_point_size = false;
         }
 
         if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_framebuffer_srgb ||
@@ -378,6 +499,13 @@ impl ProgramExt for Program {
         self.raw.set_uniform(ctxt, uniform_location, value)
     }
 
+    #[inline]
+    fn set_uniform_array(&self, ctxt: &mut CommandContext, uniform_location: gl::types::GLint,
+                         value: &RawUniformArrayValue)
+    {
+        self.raw.set_uniform_array(ctxt, uniform_location, value)
+    }
+
     #[inline]
     fn set_uniform_block_binding(&self, ctxt: &mut CommandContext, block_location: gl::types::GLuint,
                                  value: gl::types::GLuint)
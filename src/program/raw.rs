@@ -20,6 +20,7 @@ use GlObject;
 use ProgramExt;
 use Handle;
 use RawUniformValue;
+use RawUniformArrayValue;
 
 use QueryExt;
 use draw_parameters::TimeElapsedQuery;
@@ -30,6 +31,7 @@ use BufferSliceExt;
 
 use program::{ProgramCreationError, Binary, GetBinaryError};
 use program::uniforms_storage::UniformsStorage;
+use program::uniform_value::{ReadableUniform, UniformComponentsKind, get_components_kind};
 
 use program::compute::ComputeCommand;
 use program::reflection::{Uniform, UniformBlock, OutputPrimitives};
@@ -38,7 +40,7 @@ use program::reflection::{SubroutineData, ShaderStage};
 use program::reflection::{reflect_uniforms, reflect_attributes, reflect_uniform_blocks};
 use program::reflection::{reflect_transform_feedback, reflect_geometry_output_type};
 use program::reflection::{reflect_tess_eval_output_type, reflect_shader_storage_blocks};
-use program::reflection::{reflect_subroutine_data};
+use program::reflection::{reflect_subroutine_data, reflect_geometry_shader_layout};
 use program::shader::Shader;
 use program::binary_header::{attach_glium_header, process_glium_header};
 
@@ -60,9 +62,12 @@ pub struct RawProgram {
     tf_buffers: Vec<TransformFeedbackBuffer>,
     ssbos: HashMap<String, UniformBlock>,
     output_primitives: Option<OutputPrimitives>,
+    geometry_shader_layout: Option<(u32, u32)>,
     has_geometry_shader: bool,
     has_tessellation_control_shader: bool,
     has_tessellation_evaluation_shader: bool,
+    compile_log: String,
+    link_log: String,
 }
 
 impl RawProgram {
@@ -71,13 +76,20 @@ impl RawProgram {
     pub fn from_shaders<'a, F, I>(facade: &'a F, shaders: I, has_geometry_shader: bool,
                                   has_tessellation_control_shader: bool,
                                   has_tessellation_evaluation_shader: bool,
-                                  transform_feedback: Option<(Vec<String>, TransformFeedbackMode)>)
+                                  transform_feedback: Option<(Vec<String>, TransformFeedbackMode)>,
+                                  attribute_bindings: &[(&str, u32)],
+                                  frag_data_bindings: &[(&str, u32)],
+                                  uniform_block_bindings: &[(&str, u32)])
                                   -> Result<RawProgram, ProgramCreationError>
                                   where F: Facade, I: IntoIterator<Item = &'a Shader>
     {
         let mut ctxt = facade.get_context().make_current();
 
-        let shaders_ids = shaders.into_iter().map(|s| s.get_id()).collect::<Vec<_>>();
+        let shaders = shaders.into_iter().collect::<Vec<_>>();
+        let shaders_ids = shaders.iter().map(|s| s.get_id()).collect::<Vec<_>>();
+
+        let compile_log = shaders.iter().map(|s| s.get_log()).filter(|log| !log.is_empty())
+                                 .collect::<Vec<_>>().join("\n");
 
         let id = unsafe {
             let id = create_program(&mut ctxt);
@@ -135,6 +147,41 @@ impl RawProgram {
                 }
             }
 
+            // explicit attribute locations
+            for &(name, location) in attribute_bindings {
+                // the names have been checked for nul characters in the frontend
+                let name = ffi::CString::new(name.as_bytes()).unwrap();
+
+                match id {
+                    Handle::Id(id) => {
+                        ctxt.gl.BindAttribLocation(id, location, name.as_ptr());
+                    },
+                    Handle::Handle(id) => {
+                        ctxt.gl.BindAttribLocationARB(id, location, name.as_ptr());
+                    },
+                }
+            }
+
+            // explicit fragment outputs locations
+            for &(name, location) in frag_data_bindings {
+                // the names have been checked for nul characters in the frontend
+                let name = ffi::CString::new(name.as_bytes()).unwrap();
+
+                let id = match id {
+                    Handle::Id(id) => id,
+                    Handle::Handle(_) => unreachable!()     // frag data locations shouldn't be
+                                                            // available with handles
+                };
+
+                if ctxt.version >= &Version(Api::Gl, 3, 0) {
+                    ctxt.gl.BindFragDataLocation(id, location, name.as_ptr());
+                } else if ctxt.extensions.gl_ext_gpu_shader4 {
+                    ctxt.gl.BindFragDataLocationEXT(id, location, name.as_ptr());
+                } else {
+                    unreachable!();     // has been checked in the frontend
+                }
+            }
+
             // linking
             {
                 ctxt.report_debug_output_errors.set(false);
@@ -160,9 +207,11 @@ impl RawProgram {
             id
         };
 
+        let link_log = unsafe { get_program_info_log(&ctxt, id) };
+
         let uniforms = unsafe { reflect_uniforms(&mut ctxt, id) };
         let attributes = unsafe { reflect_attributes(&mut ctxt, id) };
-        let blocks = unsafe { reflect_uniform_blocks(&mut ctxt, id) };
+        let mut blocks = unsafe { reflect_uniform_blocks(&mut ctxt, id) };
         let tf_buffers = unsafe { reflect_transform_feedback(&mut ctxt, id) };
         let ssbos = unsafe { reflect_shader_storage_blocks(&mut ctxt, id) };
         let subroutine_data = unsafe {
@@ -179,11 +228,27 @@ impl RawProgram {
             None
         };
 
+        let geometry_shader_layout = if has_geometry_shader {
+            Some(unsafe { reflect_geometry_shader_layout(&mut ctxt, id) })
+        } else {
+            None
+        };
+
+        // explicit uniform block binding points
+        let uniform_values = UniformsStorage::new();
+        for &(name, binding) in uniform_block_bindings {
+            if let Some(block) = blocks.get_mut(name) {
+                uniform_values.set_uniform_block_binding(&mut ctxt, id,
+                                                         block.id as gl::types::GLuint, binding);
+                block.initial_binding = binding as i32;
+            }
+        }
+
         Ok(RawProgram {
             context: facade.get_context().clone(),
             id: id,
             uniforms: uniforms,
-            uniform_values: UniformsStorage::new(),
+            uniform_values: uniform_values,
             uniform_blocks: blocks,
             subroutine_data: subroutine_data,
             attributes: attributes,
@@ -191,9 +256,12 @@ impl RawProgram {
             tf_buffers: tf_buffers,
             ssbos: ssbos,
             output_primitives: output_primitives,
+            geometry_shader_layout: geometry_shader_layout,
             has_geometry_shader: has_geometry_shader,
             has_tessellation_control_shader: has_tessellation_control_shader,
             has_tessellation_evaluation_shader: has_tessellation_evaluation_shader,
+            compile_log: compile_log,
+            link_log: link_log,
         })
     }
 
@@ -229,6 +297,8 @@ impl RawProgram {
             id
         };
 
+        let link_log = unsafe { get_program_info_log(&ctxt, id) };
+
         let (uniforms, attributes, blocks, tf_buffers, ssbos, subroutine_data) = unsafe {
             (
                 reflect_uniforms(&mut ctxt, id),
@@ -250,6 +320,12 @@ impl RawProgram {
             None
         };
 
+        let geometry_shader_layout = if has_geometry_shader {
+            Some(unsafe { reflect_geometry_shader_layout(&mut ctxt, id) })
+        } else {
+            None
+        };
+
         Ok(RawProgram {
             context: facade.get_context().clone(),
             id: id,
@@ -262,9 +338,12 @@ impl RawProgram {
             tf_buffers: tf_buffers,
             ssbos: ssbos,
             output_primitives: output_primitives,
+            geometry_shader_layout: geometry_shader_layout,
             has_geometry_shader: has_geometry_shader,
             has_tessellation_control_shader: has_tessellation_control_shader,
             has_tessellation_evaluation_shader: has_tessellation_evaluation_shader,
+            compile_log: String::new(),
+            link_log: link_log,
         })
     }
 
@@ -327,8 +406,11 @@ impl RawProgram {
             return result.clone();
         }
 
-        // querying opengl
-        let name_c = ffi::CString::new(name.as_bytes()).unwrap();
+        // querying opengl ; a name with a nul character can't be the name of an output
+        let name_c = match ffi::CString::new(name.as_bytes()) {
+            Ok(n) => n,
+            Err(_) => return None,
+        };
 
         let ctxt = self.context.make_current();
 
@@ -361,6 +443,87 @@ impl RawProgram {
         self.uniforms.get(name)
     }
 
+    /// Reads the current value of a uniform from the program.
+    ///
+    /// Returns `None` if the uniform doesn't exist or if its type doesn't match `T`.
+    pub fn get_uniform_value<T>(&self, name: &str) -> Option<T> where T: ReadableUniform {
+        let uniform = match self.uniforms.get(name) {
+            Some(u) => u,
+            None => return None
+        };
+
+        if !T::is_readable_from(&uniform.ty) {
+            return None;
+        }
+
+        let kind = match get_components_kind(&uniform.ty) {
+            Some(k) => k,
+            None => return None
+        };
+
+        let ctxt = self.context.make_current();
+        let mut data: [u32; 16] = [0; 16];
+
+        // the robust versions of `glGetUniform` never write more than `buf_size` bytes
+        let buf_size = mem::size_of_val(&data) as gl::types::GLsizei;
+        let robust = ctxt.version >= &Version(Api::Gl, 4, 5) ||
+                     ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+                     ctxt.extensions.gl_khr_robustness;
+
+        unsafe {
+            match (self.id, kind) {
+                (Handle::Id(id), UniformComponentsKind::Float) => {
+                    if robust {
+                        ctxt.gl.GetnUniformfv(id, uniform.location, buf_size,
+                                              data.as_mut_ptr() as *mut _);
+                    } else if ctxt.extensions.gl_arb_robustness {
+                        ctxt.gl.GetnUniformfvARB(id, uniform.location, buf_size,
+                                                 data.as_mut_ptr() as *mut _);
+                    } else if ctxt.extensions.gl_ext_robustness {
+                        ctxt.gl.GetnUniformfvEXT(id, uniform.location, buf_size,
+                                                 data.as_mut_ptr() as *mut _);
+                    } else {
+                        ctxt.gl.GetUniformfv(id, uniform.location, data.as_mut_ptr() as *mut _);
+                    }
+                },
+                (Handle::Id(id), UniformComponentsKind::Int) => {
+                    if robust {
+                        ctxt.gl.GetnUniformiv(id, uniform.location, buf_size,
+                                              data.as_mut_ptr() as *mut _);
+                    } else if ctxt.extensions.gl_arb_robustness {
+                        ctxt.gl.GetnUniformivARB(id, uniform.location, buf_size,
+                                                 data.as_mut_ptr() as *mut _);
+                    } else if ctxt.extensions.gl_ext_robustness {
+                        ctxt.gl.GetnUniformivEXT(id, uniform.location, buf_size,
+                                                 data.as_mut_ptr() as *mut _);
+                    } else {
+                        ctxt.gl.GetUniformiv(id, uniform.location, data.as_mut_ptr() as *mut _);
+                    }
+                },
+                (Handle::Id(id), UniformComponentsKind::UnsignedInt) => {
+                    // GetnUniformuivEXT doesn't exist
+                    if robust {
+                        ctxt.gl.GetnUniformuiv(id, uniform.location, buf_size, data.as_mut_ptr());
+                    } else if ctxt.extensions.gl_arb_robustness {
+                        ctxt.gl.GetnUniformuivARB(id, uniform.location, buf_size,
+                                                  data.as_mut_ptr());
+                    } else {
+                        ctxt.gl.GetUniformuiv(id, uniform.location, data.as_mut_ptr());
+                    }
+                },
+                (Handle::Handle(id), UniformComponentsKind::Float) => {
+                    ctxt.gl.GetUniformfvARB(id, uniform.location, data.as_mut_ptr() as *mut _);
+                },
+                (Handle::Handle(id), _) => {
+                    // GetUniformuivARB doesn't exist
+                    ctxt.gl.GetUniformivARB(id, uniform.location, data.as_mut_ptr() as *mut _);
+                },
+            }
+
+            Some(T::from_raw(&data))
+        }
+    }
+
     /// Returns an iterator to the list of uniforms.
     ///
     /// ## Example
@@ -402,13 +565,24 @@ impl RawProgram {
     ///
     /// The `stride` is the number of bytes between two vertices.
     pub fn transform_feedback_matches(&self, format: &VertexFormat, stride: usize) -> bool {
-        // TODO: doesn't support multiple buffers
-
         if self.get_transform_feedback_buffers().len() != 1 {
             return false;
         }
 
-        let buf = &self.get_transform_feedback_buffers()[0];
+        self.transform_feedback_buffer_matches(0, format, stride)
+    }
+
+    /// True if the transform feedback buffer of index `index` of this program matches the
+    /// specified `VertexFormat` and `stride`.
+    ///
+    /// The `stride` is the number of bytes between two vertices.
+    pub fn transform_feedback_buffer_matches(&self, index: usize, format: &VertexFormat,
+                                             stride: usize) -> bool
+    {
+        let buf = match self.get_transform_feedback_buffers().get(index) {
+            Some(buf) => buf,
+            None => return false,
+        };
 
         if buf.stride != stride {
             return false;
@@ -435,6 +609,22 @@ impl RawProgram {
         self.output_primitives
     }
 
+    /// Returns the maximum number of vertices that the geometry shader outputs per invocation,
+    /// as declared with `layout(max_vertices = ...)`, or `None` if the program doesn't contain
+    /// a geometry shader.
+    #[inline]
+    pub fn get_geometry_shader_max_output_vertices(&self) -> Option<u32> {
+        self.geometry_shader_layout.map(|(vertices, _)| vertices)
+    }
+
+    /// Returns the number of times the geometry shader is invoked for each primitive, as
+    /// declared with `layout(invocations = ...)`, or `None` if the program doesn't contain
+    /// a geometry shader.
+    #[inline]
+    pub fn get_geometry_shader_invocations(&self) -> Option<u32> {
+        self.geometry_shader_layout.map(|(_, invocations)| invocations)
+    }
+
     /// Returns true if the program contains a tessellation stage.
     #[inline]
     pub fn has_tessellation_shaders(&self) -> bool {
@@ -459,6 +649,18 @@ impl RawProgram {
         self.has_geometry_shader
     }
 
+    /// Returns the info logs produced when compiling the shaders of the program.
+    #[inline]
+    pub fn get_compile_log(&self) -> &str {
+        &self.compile_log
+    }
+
+    /// Returns the info log produced when linking the program.
+    #[inline]
+    pub fn get_link_log(&self) -> &str {
+        &self.link_log
+    }
+
     /// Returns informations about an attribute, if it exists.
     #[inline]
     pub fn get_attribute(&self, name: &str) -> Option<&Attribute> {
@@ -619,6 +821,13 @@ impl ProgramExt for RawProgram {
         self.uniform_values.set_uniform_value(ctxt, self.id, uniform_location, value);
     }
 
+    #[inline]
+    fn set_uniform_array(&self, ctxt: &mut CommandContext, uniform_location: gl::types::GLint,
+                         value: &RawUniformArrayValue)
+    {
+        self.uniform_values.set_uniform_array_value(ctxt, self.id, uniform_location, value);
+    }
+
     #[inline]
     fn set_uniform_block_binding(&self, ctxt: &mut CommandContext, block_location: gl::types::GLuint,
                                  value: gl::types::GLuint)
@@ -696,6 +905,30 @@ impl Drop for RawProgram {
                 }
             }
         }
+
+        drop(ctxt);
+
+        // the contexts that share this program must stop using it, otherwise it would not be
+        // destroyed until they use another program
+        let program = self.id;
+        self.context.purge_shared_contexts(move |ctxt| {
+            VertexAttributesSystem::purge_program(ctxt, program);
+
+            if ctxt.state.program == program {
+                unsafe {
+                    match program {
+                        Handle::Id(_) => {
+                            ctxt.gl.UseProgram(0);
+                            ctxt.state.program = Handle::Id(0);
+                        },
+                        Handle::Handle(_) => {
+                            ctxt.gl.UseProgramObjectARB(0 as gl::types::GLhandleARB);
+                            ctxt.state.program = Handle::Handle(0 as gl::types::GLhandleARB);
+                        },
+                    }
+                }
+            }
+        });
     }
 }
 
@@ -755,40 +988,45 @@ unsafe fn check_program_link_errors(ctxt: &mut CommandContext, id: Handle)
             }
         };
 
-        let mut error_log_size: gl::types::GLint = mem::uninitialized();
+        let msg = get_program_info_log(ctxt, id);
+        return Err(LinkingError(msg));
+    }
 
-        match id {
-            Handle::Id(id) => {
-                assert!(ctxt.version >= &Version(Api::Gl, 2, 0));
-                ctxt.gl.GetProgramiv(id, gl::INFO_LOG_LENGTH, &mut error_log_size);
-            },
-            Handle::Handle(id) => {
-                assert!(ctxt.extensions.gl_arb_shader_objects);
-                ctxt.gl.GetObjectParameterivARB(id, gl::OBJECT_INFO_LOG_LENGTH_ARB,
-                                                &mut error_log_size);
-            }
-        }
+    Ok(())
+}
 
-        let mut error_log: Vec<u8> = Vec::with_capacity(error_log_size as usize);
+/// Returns the info log of a program, which contains the errors and warnings of the linking.
+unsafe fn get_program_info_log(ctxt: &CommandContext, id: Handle) -> String {
+    let mut log_size: gl::types::GLint = mem::uninitialized();
 
-        match id {
-            Handle::Id(id) => {
-                assert!(ctxt.version >= &Version(Api::Gl, 2, 0));
-                ctxt.gl.GetProgramInfoLog(id, error_log_size, &mut error_log_size,
-                                          error_log.as_mut_ptr() as *mut gl::types::GLchar);
-            },
-            Handle::Handle(id) => {
-                assert!(ctxt.extensions.gl_arb_shader_objects);
-                ctxt.gl.GetInfoLogARB(id, error_log_size, &mut error_log_size,
-                                      error_log.as_mut_ptr() as *mut gl::types::GLchar);
-            }
+    match id {
+        Handle::Id(id) => {
+            assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                    ctxt.version >= &Version(Api::GlEs, 2, 0));
+            ctxt.gl.GetProgramiv(id, gl::INFO_LOG_LENGTH, &mut log_size);
+        },
+        Handle::Handle(id) => {
+            assert!(ctxt.extensions.gl_arb_shader_objects);
+            ctxt.gl.GetObjectParameterivARB(id, gl::OBJECT_INFO_LOG_LENGTH_ARB, &mut log_size);
         }
+    }
 
-        error_log.set_len(error_log_size as usize);
+    let mut log: Vec<u8> = Vec::with_capacity(log_size as usize);
 
-        let msg = String::from_utf8(error_log).unwrap();
-        return Err(LinkingError(msg));
+    match id {
+        Handle::Id(id) => {
+            assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                    ctxt.version >= &Version(Api::GlEs, 2, 0));
+            ctxt.gl.GetProgramInfoLog(id, log_size, &mut log_size,
+                                      log.as_mut_ptr() as *mut gl::types::GLchar);
+        },
+        Handle::Handle(id) => {
+            assert!(ctxt.extensions.gl_arb_shader_objects);
+            ctxt.gl.GetInfoLogARB(id, log_size, &mut log_size,
+                                  log.as_mut_ptr() as *mut gl::types::GLchar);
+        }
     }
 
-    Ok(())
+    log.set_len(log_size as usize);
+    String::from_utf8_lossy(&log).into_owned()
 }
@@ -49,6 +49,39 @@ pub struct UniformBlock {
 
     /// Layout of the block.
     pub layout: BlockLayout,
+
+    /// Flat list of the active members of the block, as reported by OpenGL.
+    ///
+    /// Contrary to `layout`, this contains the strides that are necessary to write arrays and
+    /// matrices in a buffer.
+    pub members: Vec<BlockMember>,
+}
+
+/// Information about an active member of a uniform block or of a shader storage block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockMember {
+    /// Full name of the member, as reported by OpenGL. For example `lights[0].position`.
+    pub name: String,
+
+    /// Type of data.
+    pub ty: UniformType,
+
+    /// Offset of this member in bytes from the start of the buffer.
+    pub offset: usize,
+
+    /// Number of elements if this member is an array, or 1 otherwise. Is 0 for arrays whose
+    /// size isn't known at compile-time.
+    pub array_size: usize,
+
+    /// Number of bytes between two elements of the array, or 0 if this member is not an array.
+    pub array_stride: usize,
+
+    /// Number of bytes between two columns (or two rows if `row_major` is true) of the matrix,
+    /// or 0 if this member is not a matrix.
+    pub matrix_stride: usize,
+
+    /// True if this member is a matrix stored in row-major order.
+    pub row_major: bool,
 }
 
 /// Layout of a shader storage buffer or a uniform buffer.
@@ -226,6 +259,16 @@ pub unsafe fn reflect_uniforms(ctxt: &mut CommandContext, program: Handle)
         uniform_name_tmp.set_len(uniform_name_tmp_len as usize);
 
         let uniform_name = String::from_utf8(uniform_name_tmp).unwrap();
+
+        // arrays are reported with the name of their first element, for example `bones[0]`,
+        // including arrays of a single element
+        let is_array = uniform_name.ends_with("[0]");
+        let uniform_name = if is_array {
+            uniform_name[.. uniform_name.len() - 3].to_owned()
+        } else {
+            uniform_name
+        };
+
         let location = match program {
             Handle::Id(program) => {
                 assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
@@ -245,7 +288,7 @@ pub unsafe fn reflect_uniforms(ctxt: &mut CommandContext, program: Handle)
         uniforms.insert(uniform_name, Uniform {
             location: location as i32,
             ty: glenum_to_uniform_type(data_type),
-            size: if data_size == 1 { None } else { Some(data_size as usize) },
+            size: if is_array { Some(data_size as usize) } else { None },
         });
     }
 
@@ -417,6 +460,24 @@ pub unsafe fn reflect_uniform_blocks(ctxt: &mut CommandContext, program: Handle)
         ctxt.gl.GetActiveUniformsiv(program, num_members, members_indices.as_ptr(),
                                     gl::UNIFORM_SIZE, member_size.as_mut_ptr());
 
+        // getting the array strides of the members
+        let mut member_array_stride = ::std::iter::repeat(0).take(num_members as usize)
+                                                            .collect::<Vec<gl::types::GLint>>();
+        ctxt.gl.GetActiveUniformsiv(program, num_members, members_indices.as_ptr(),
+                                    gl::UNIFORM_ARRAY_STRIDE, member_array_stride.as_mut_ptr());
+
+        // getting the matrix strides of the members
+        let mut member_matrix_stride = ::std::iter::repeat(0).take(num_members as usize)
+                                                             .collect::<Vec<gl::types::GLint>>();
+        ctxt.gl.GetActiveUniformsiv(program, num_members, members_indices.as_ptr(),
+                                    gl::UNIFORM_MATRIX_STRIDE, member_matrix_stride.as_mut_ptr());
+
+        // getting whether the members are row-major
+        let mut member_row_major = ::std::iter::repeat(0).take(num_members as usize)
+                                                         .collect::<Vec<gl::types::GLint>>();
+        ctxt.gl.GetActiveUniformsiv(program, num_members, members_indices.as_ptr(),
+                                    gl::UNIFORM_IS_ROW_MAJOR, member_row_major.as_mut_ptr());
+
         // getting the length of the names of the members
         let mut member_name_len = ::std::iter::repeat(0).take(num_members as usize)
                                                          .collect::<Vec<gl::types::GLint>>();
@@ -438,17 +499,28 @@ pub unsafe fn reflect_uniform_blocks(ctxt: &mut CommandContext, program: Handle)
 
         // now computing the list of members
         let members = member_names.into_iter().enumerate().map(|(index, name)| {
-            (name, member_offsets[index] as usize,
-             glenum_to_uniform_type(member_types[index] as gl::types::GLenum),
-             member_size[index] as usize, None)
-        });
+            BlockMember {
+                name: name,
+                ty: glenum_to_uniform_type(member_types[index] as gl::types::GLenum),
+                offset: member_offsets[index] as usize,
+                array_size: member_size[index] as usize,
+                array_stride: member_array_stride[index] as usize,
+                matrix_stride: member_matrix_stride[index] as usize,
+                row_major: member_row_major[index] != 0,
+            }
+        }).collect::<Vec<_>>();
+
+        let layout = introspection_output_to_layout(members.iter().map(|m| {
+            (m.name.clone(), m.offset, m.ty, m.array_size, None)
+        }));
 
         // finally inserting into the blocks list
         blocks.insert(name, UniformBlock {
             id: block_id as i32,
             initial_binding: binding as i32,
             size: block_size as usize,
-            layout: introspection_output_to_layout(members),
+            layout: layout,
+            members: members,
         });
     }
 
@@ -550,11 +622,30 @@ pub unsafe fn reflect_transform_feedback(ctxt: &mut CommandContext, program: Han
                 });
             }
 
+            // `gl_NextBuffer` moves the next varyings to the next buffer
+            if name == "gl_NextBuffer" {
+                let id = result.len();
+                result.push(TransformFeedbackBuffer {
+                    id: id as i32,
+                    elements: vec![],
+                    stride: 0,
+                });
+                continue;
+            }
+
+            let current = result.len() - 1;
+
+            // `gl_SkipComponentsN` leaves a gap of N floats
+            if name.starts_with("gl_SkipComponents") {
+                result[current].stride += size as usize * mem::size_of::<f32>();
+                continue;
+            }
+
             let ty = glenum_to_attribute_type(ty as gl::types::GLenum);
 
-            let prev_size = result[0].stride;
-            result[0].stride += size as usize * ty.get_size_bytes();
-            result[0].elements.push(TransformFeedbackVarying {        // TODO: handle arrays
+            let prev_size = result[current].stride;
+            result[current].stride += size as usize * ty.get_size_bytes();
+            result[current].elements.push(TransformFeedbackVarying {  // TODO: handle arrays
                 name: name,
                 size: size as usize * ty.get_size_bytes(),
                 offset: prev_size,
@@ -616,6 +707,49 @@ pub unsafe fn reflect_geometry_output_type(ctxt: &mut CommandContext, program: H
     }
 }
 
+/// Obtains the maximum number of vertices that the geometry shader stage outputs and the number
+/// of times it is invoked per primitive, as declared with `layout(max_vertices = ...)` and
+/// `layout(invocations = ...)`.
+///
+/// # Unsafety
+///
+/// - `program` must be a valid handle to a program.
+/// - The program **must** contain a geometry shader.
+pub unsafe fn reflect_geometry_shader_layout(ctxt: &mut CommandContext, program: Handle)
+                                             -> (u32, u32)
+{
+    let invocations_supported = ctxt.version >= &Version(Api::Gl, 4, 0) ||
+                                ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+                                ctxt.extensions.gl_arb_gpu_shader5 ||
+                                ctxt.extensions.gl_ext_geometry_shader ||
+                                ctxt.extensions.gl_oes_geometry_shader;
+
+    let mut vertices_out = mem::uninitialized();
+    let mut invocations = 1;
+
+    match program {
+        Handle::Id(program) => {
+            assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                    ctxt.version >= &Version(Api::GlEs, 2, 0));
+            ctxt.gl.GetProgramiv(program, gl::GEOMETRY_VERTICES_OUT, &mut vertices_out);
+            if invocations_supported {
+                ctxt.gl.GetProgramiv(program, gl::GEOMETRY_SHADER_INVOCATIONS, &mut invocations);
+            }
+        },
+        Handle::Handle(program) => {
+            assert!(ctxt.extensions.gl_arb_vertex_shader);
+            ctxt.gl.GetObjectParameterivARB(program, gl::GEOMETRY_VERTICES_OUT,
+                                            &mut vertices_out);
+            if invocations_supported {
+                ctxt.gl.GetObjectParameterivARB(program, gl::GEOMETRY_SHADER_INVOCATIONS,
+                                                &mut invocations);
+            }
+        }
+    };
+
+    (vertices_out as u32, invocations as u32)
+}
+
 /// Obtains the type of data that the tessellation evaluation shader stage outputs.
 ///
 /// # Unsafety
@@ -708,18 +842,22 @@ pub unsafe fn reflect_shader_storage_blocks(ctxt: &mut CommandContext, program:
             variables
         };
 
-        // iterator over variables
+        // list of variables, with their top-level array size
         let members = active_variables.into_iter().map(|variable| {
-            let (ty, array_size, offset, _array_stride, name_len, top_level_array_size) = {
-                let mut output: [gl::types::GLint; 6] = mem::uninitialized();
+            let (ty, array_size, offset, array_stride, name_len, top_level_array_size,
+                 matrix_stride, row_major) =
+            {
+                let mut output: [gl::types::GLint; 8] = mem::uninitialized();
                 ctxt.gl.GetProgramResourceiv(program, gl::BUFFER_VARIABLE,
-                                             variable as gl::types::GLuint, 6,
+                                             variable as gl::types::GLuint, 8,
                                              [gl::TYPE, gl::ARRAY_SIZE, gl::OFFSET,
                                               gl::ARRAY_STRIDE, gl::NAME_LENGTH,
-                                              gl::TOP_LEVEL_ARRAY_SIZE].as_ptr(), 6,
+                                              gl::TOP_LEVEL_ARRAY_SIZE, gl::MATRIX_STRIDE,
+                                              gl::IS_ROW_MAJOR].as_ptr(), 8,
                                              ptr::null_mut(), output.as_mut_ptr() as *mut _);
                 (glenum_to_uniform_type(output[0] as gl::types::GLenum), output[1] as usize,
-                 output[2] as usize, output[3] as usize, output[4] as usize, output[5] as usize)
+                 output[2] as usize, output[3] as usize, output[4] as usize, output[5] as usize,
+                 output[6] as usize, output[7] != 0)
             };
 
             let name = {
@@ -734,15 +872,30 @@ pub unsafe fn reflect_shader_storage_blocks(ctxt: &mut CommandContext, program:
                 String::from_utf8(name_tmp).unwrap()
             };
 
-            (name, offset, ty, array_size, Some(top_level_array_size))
-        });
+            let member = BlockMember {
+                name: name,
+                ty: ty,
+                offset: offset,
+                array_size: array_size,
+                array_stride: array_stride,
+                matrix_stride: matrix_stride,
+                row_major: row_major,
+            };
+
+            (member, top_level_array_size)
+        }).collect::<Vec<_>>();
+
+        let layout = introspection_output_to_layout(members.iter().map(|&(ref m, top_level)| {
+            (m.name.clone(), m.offset, m.ty, m.array_size, Some(top_level))
+        }));
 
         // finally inserting into the blocks list
         blocks.insert(name, UniformBlock {
             id: block_id as i32,
             initial_binding: binding as i32,
             size: total_size,
-            layout: introspection_output_to_layout(members),
+            layout: layout,
+            members: members.into_iter().map(|(m, _)| m).collect(),
         });
     }
 
@@ -4,10 +4,11 @@ use version::Api;
 
 use CapabilitiesSource;
 use backend::Facade;
-use context::Context;
+use context::{Context, CommandContext};
 use ContextExt;
 
 use std::{ffi, mem, ptr};
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use GlObject;
@@ -19,6 +20,15 @@ use program::ProgramCreationError;
 pub struct Shader {
     context: Rc<Context>,
     id: Handle,
+    log: String,
+}
+
+impl Shader {
+    /// Returns the info log produced when compiling the shader.
+    #[inline]
+    pub fn get_log(&self) -> &str {
+        &self.log
+    }
 }
 
 impl GlObject for Shader {
@@ -51,7 +61,8 @@ impl Drop for Shader {
 }
 
 /// Builds an individual shader.
-pub fn build_shader<F>(facade: &F, shader_type: gl::types::GLenum, source_code: &str)
+pub fn build_shader<F>(facade: &F, shader_type: gl::types::GLenum, source_code: &str,
+                       uses_includes: bool, defines: &[(&str, &str)])
                        -> Result<Shader, ProgramCreationError> where F: Facade
 {
     unsafe {
@@ -65,7 +76,30 @@ pub fn build_shader<F>(facade: &F, shader_type: gl::types::GLenum, source_code:
             return Err(ProgramCreationError::ShaderTypeNotSupported);
         }
 
-        let source_code = ffi::CString::new(source_code.as_bytes()).unwrap();
+        // the includes are resolved by glium if the backend can't do it
+        let native_includes = uses_includes && ctxt.extensions.gl_arb_shading_language_include &&
+                              ctxt.version >= &Version(Api::Gl, 2, 0);
+
+        let source_code = if defines.is_empty() {
+            source_code.to_owned()
+        } else {
+            inject_defines(source_code, defines)
+        };
+
+        let source_code = if uses_includes && !native_includes {
+            let includes = facade.get_context().shader_includes().borrow();
+            try!(resolve_includes(&source_code, &includes, 0))
+        } else {
+            source_code
+        };
+
+        // the included strings come from the user and may contain nul characters
+        let source_code = match ffi::CString::new(source_code.into_bytes()) {
+            Ok(s) => s,
+            Err(_) => return Err(ProgramCreationError::CompilationError(
+                "The source code contains a nul character".to_owned()
+            )),
+        };
 
         let id = if ctxt.version >= &Version(Api::Gl, 2, 0) ||
                     ctxt.version >= &Version(Api::GlEs, 2, 0)
@@ -101,7 +135,12 @@ pub fn build_shader<F>(facade: &F, shader_type: gl::types::GLenum, source_code:
                 Handle::Id(id) => {
                     assert!(ctxt.version >= &Version(Api::Gl, 2, 0)||
                             ctxt.version >= &Version(Api::GlEs, 2, 0));
-                    ctxt.gl.CompileShader(id);
+
+                    if native_includes {
+                        ctxt.gl.CompileShaderIncludeARB(id, 0, ptr::null(), ptr::null());
+                    } else {
+                        ctxt.gl.CompileShader(id);
+                    }
                 },
                 Handle::Handle(id) => {
                     assert!(ctxt.extensions.gl_arb_shader_objects);
@@ -130,56 +169,210 @@ pub fn build_shader<F>(facade: &F, shader_type: gl::types::GLenum, source_code:
             compilation_success
         };
 
+        let log = get_shader_info_log(&ctxt, id);
+
         if compilation_success == 1 {
             Ok(Shader {
                 context: facade.get_context().clone(),
-                id: id
+                id: id,
+                log: log,
             })
 
         } else {
-            // compilation error
-            let mut error_log_size: gl::types::GLint = mem::uninitialized();
+            Err(ProgramCreationError::CompilationError(log))
+        }
+    }
+}
 
-            match id {
-                Handle::Id(id) => {
-                    assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
-                            ctxt.version >= &Version(Api::GlEs, 2, 0));
-                    ctxt.gl.GetShaderiv(id, gl::INFO_LOG_LENGTH, &mut error_log_size);
-                },
-                Handle::Handle(id) => {
-                    assert!(ctxt.extensions.gl_arb_shader_objects);
-                    ctxt.gl.GetObjectParameterivARB(id, gl::OBJECT_INFO_LOG_LENGTH_ARB,
-                                                    &mut error_log_size);
-                }
+/// Returns the info log of a shader, which contains the errors and warnings of the compilation.
+unsafe fn get_shader_info_log(ctxt: &CommandContext, id: Handle) -> String {
+    let mut log_size: gl::types::GLint = mem::uninitialized();
+
+    match id {
+        Handle::Id(id) => {
+            assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                    ctxt.version >= &Version(Api::GlEs, 2, 0));
+            ctxt.gl.GetShaderiv(id, gl::INFO_LOG_LENGTH, &mut log_size);
+        },
+        Handle::Handle(id) => {
+            assert!(ctxt.extensions.gl_arb_shader_objects);
+            ctxt.gl.GetObjectParameterivARB(id, gl::OBJECT_INFO_LOG_LENGTH_ARB, &mut log_size);
+        }
+    }
+
+    let mut log: Vec<u8> = Vec::with_capacity(log_size as usize);
+
+    match id {
+        Handle::Id(id) => {
+            assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                    ctxt.version >= &Version(Api::GlEs, 2, 0));
+            ctxt.gl.GetShaderInfoLog(id, log_size, &mut log_size,
+                                     log.as_mut_ptr() as *mut gl::types::GLchar);
+        },
+        Handle::Handle(id) => {
+            assert!(ctxt.extensions.gl_arb_shader_objects);
+            ctxt.gl.GetInfoLogARB(id, log_size, &mut log_size,
+                                  log.as_mut_ptr() as *mut gl::types::GLchar);
+        }
+    }
+
+    log.set_len(log_size as usize);
+
+    match String::from_utf8(log) {
+        Ok(msg) => msg,
+        Err(_) => "Could not convert the log message to UTF-8".to_owned(),
+    }
+}
+
+/// Inserts a `#define` directive for each macro after the `#version` and `#extension`
+/// directives that are at the start of the source code.
+///
+/// The defines are followed by a `#line` directive, so that the line numbers in the compilation
+/// errors match the original source code.
+fn inject_defines(source_code: &str, defines: &[(&str, &str)]) -> String {
+    // the defines are inserted after the last directive that must come before them ; only
+    // whitespaces and comments can be between these directives
+    let mut insertion_point = 0;
+    let mut version = None;
+    let mut pos = 0;
+
+    while pos < source_code.len() {
+        let rest = &source_code[pos ..];
+
+        if rest.starts_with("//") {
+            pos += rest.find('\n').unwrap_or(rest.len());
+
+        } else if rest.starts_with("/*") {
+            pos += rest[2 ..].find("*/").map(|end| end + 4).unwrap_or(rest.len());
+
+        } else if rest.starts_with('#') {
+            let line_end = rest.find('\n').map(|end| end + 1).unwrap_or(rest.len());
+            let directive = rest[1 .. line_end].trim_left();
+
+            if directive.starts_with("version") {
+                version = Some(directive);
+            } else if !directive.starts_with("extension") {
+                break;
             }
 
-            let mut error_log: Vec<u8> = Vec::with_capacity(error_log_size as usize);
+            pos += line_end;
+            insertion_point = pos;
 
-            match id {
-                Handle::Id(id) => {
-                    assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
-                            ctxt.version >= &Version(Api::GlEs, 2, 0));
-                    ctxt.gl.GetShaderInfoLog(id, error_log_size, &mut error_log_size,
-                                             error_log.as_mut_ptr() as *mut gl::types::GLchar);
-                },
-                Handle::Handle(id) => {
-                    assert!(ctxt.extensions.gl_arb_shader_objects);
-                    ctxt.gl.GetInfoLogARB(id, error_log_size, &mut error_log_size,
-                                          error_log.as_mut_ptr() as *mut gl::types::GLchar);
-                }
+        } else {
+            // the byte order mark is treated like a whitespace
+            match rest.chars().next() {
+                Some(c) if c.is_whitespace() || c == '\u{feff}' => pos += c.len_utf8(),
+                _ => break,
             }
+        }
+    }
 
-            error_log.set_len(error_log_size as usize);
+    // `#version` must be the first directive, so if some text that isn't a comment comes before
+    // it the defines are still inserted after it
+    if version.is_none() {
+        let mut offset = pos;
+        for line in source_code[pos ..].split('\n') {
+            offset += line.len() + 1;
 
-            match String::from_utf8(error_log) {
-                Ok(msg) => Err(ProgramCreationError::CompilationError(msg)),
-                Err(_) => Err(
-                    ProgramCreationError::CompilationError("Could not convert the log \
-                                                            message to UTF-8".to_owned())
-                ),
+            let trimmed = line.trim_left();
+            if trimmed.starts_with('#') && trimmed[1 ..].trim_left().starts_with("version") {
+                insertion_point = if offset > source_code.len() { source_code.len() }
+                                  else { offset };
+                version = Some(trimmed[1 ..].trim_left());
+                break;
             }
         }
     }
+
+    // number of the line that follows the defines in the original source code
+    let next_line = source_code[.. insertion_point].matches('\n').count() + 1;
+
+    // before GLSL 3.30 and GLSL ES 3.00, the line that follows `#line n` is numbered `n + 1`
+    let line_directive_offset = {
+        let mut words = version.map(|v| v["version".len() ..].split_whitespace())
+                               .into_iter().flat_map(|w| w);
+        let number = words.next().and_then(|n| n.parse::<u32>().ok()).unwrap_or(110);
+        let es = words.next() == Some("es") || number == 100;
+
+        if (es && number >= 300) || (!es && number >= 330) { 0 } else { 1 }
+    };
+
+    let mut output = String::with_capacity(source_code.len() + defines.len() * 32 + 16);
+    output.push_str(&source_code[.. insertion_point]);
+    if insertion_point != 0 && !output.ends_with('\n') {
+        output.push('\n');
+    }
+
+    for &(name, value) in defines {
+        output.push_str("#define ");
+        output.push_str(name);
+        output.push(' ');
+        output.push_str(value);
+        output.push('\n');
+    }
+
+    output.push_str(&format!("#line {}\n", next_line - line_directive_offset));
+    output.push_str(&source_code[insertion_point ..]);
+    output
+}
+
+/// Replaces the `#include` directives of a shader with the strings registered in the context.
+///
+/// The `#extension GL_ARB_shading_language_include` directives are removed, as the extension
+/// may not be supported.
+fn resolve_includes(source_code: &str, includes: &HashMap<String, String>, depth: u32)
+                    -> Result<String, ProgramCreationError>
+{
+    // protection against includes that include themselves
+    if depth >= 32 {
+        return Err(ProgramCreationError::CompilationError("Too many nested #include \
+                                                           directives".to_owned()));
+    }
+
+    let mut output = String::with_capacity(source_code.len());
+
+    for line in source_code.lines() {
+        let directive = line.trim_left();
+        let directive = if directive.starts_with('#') {
+            directive[1..].trim_left()
+        } else {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        };
+
+        if directive.starts_with("extension") &&
+           directive.contains("GL_ARB_shading_language_include")
+        {
+            output.push('\n');
+            continue;
+        }
+
+        if !directive.starts_with("include") {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        }
+
+        let path = directive["include".len() ..].trim();
+        if path.len() < 2 || !path.starts_with('"') || !path.ends_with('"') {
+            return Err(ProgramCreationError::CompilationError(format!("Invalid #include \
+                                                                       directive: {}", line)));
+        }
+
+        let path = &path[1 .. path.len() - 1];
+        let included = match includes.get(path) {
+            Some(s) => s,
+            None => return Err(ProgramCreationError::CompilationError(
+                format!("No string has been registered for #include \"{}\"", path)
+            )),
+        };
+
+        output.push_str(&try!(resolve_includes(included, includes, depth + 1)));
+        output.push('\n');
+    }
+
+    Ok(output)
 }
 
 pub fn check_shader_type_compatibility<C>(ctxt: &C, shader_type: gl::types::GLenum)
@@ -220,3 +413,44 @@ pub fn check_shader_type_compatibility<C>(ctxt: &C, shader_type: gl::types::GLen
 
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::inject_defines;
+
+    #[test]
+    fn defines_after_version() {
+        let output = inject_defines("#version 110\nvoid main() {}\n", &[("A", "1")]);
+        assert_eq!(output, "#version 110\n#define A 1\n#line 1\nvoid main() {}\n");
+    }
+
+    #[test]
+    fn defines_after_extensions_and_comments() {
+        let source = "/* license\n * header\n */\n#version 330 core\n// comment\n\
+                      #extension GL_ARB_foo : enable /* trailing */\nvoid main() {}\n";
+        let output = inject_defines(source, &[("A", "1")]);
+        assert_eq!(output, "/* license\n * header\n */\n#version 330 core\n// comment\n\
+                            #extension GL_ARB_foo : enable /* trailing */\n#define A 1\n\
+                            #line 7\nvoid main() {}\n");
+    }
+
+    #[test]
+    fn defines_after_version_preceded_by_text() {
+        let output = inject_defines("precision mediump float;\n#version 100\nvoid main() {}",
+                                    &[("A", "1")]);
+        assert_eq!(output, "precision mediump float;\n#version 100\n#define A 1\n#line 2\n\
+                            void main() {}");
+    }
+
+    #[test]
+    fn defines_without_version() {
+        let output = inject_defines("void main() {}", &[("A", "1")]);
+        assert_eq!(output, "#define A 1\n#line 0\nvoid main() {}");
+    }
+
+    #[test]
+    fn line_numbering_of_glsl_es_3() {
+        let output = inject_defines("\n#version 300 es\nvoid main() {}", &[("A", "1")]);
+        assert_eq!(output, "\n#version 300 es\n#define A 1\n#line 3\nvoid main() {}");
+    }
+}
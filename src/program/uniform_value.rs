@@ -0,0 +1,89 @@
+use std::ptr;
+
+use uniforms::UniformType;
+
+/// A type whose value can be read back from a uniform of a program.
+///
+/// This is implemented on the scalar, vector and matrix types that can be used as uniforms.
+pub trait ReadableUniform: Sized {
+    /// Returns true if a uniform of the given type can be read as this type.
+    fn is_readable_from(ty: &UniformType) -> bool;
+
+    /// Builds the value from the raw components returned by `glGetUniform*`.
+    #[doc(hidden)]
+    unsafe fn from_raw(data: &[u32; 16]) -> Self;
+}
+
+/// The kind of `glGetUniform*` function that must be called to read a uniform.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UniformComponentsKind {
+    Float,
+    Int,
+    UnsignedInt,
+}
+
+/// Returns the kind of components of a uniform type, or `None` if it can't be read.
+pub fn get_components_kind(ty: &UniformType) -> Option<UniformComponentsKind> {
+    match *ty {
+        UniformType::Float | UniformType::FloatVec2 | UniformType::FloatVec3 |
+        UniformType::FloatVec4 | UniformType::FloatMat2 | UniformType::FloatMat3 |
+        UniformType::FloatMat4 => Some(UniformComponentsKind::Float),
+
+        UniformType::Int | UniformType::IntVec2 | UniformType::IntVec3 | UniformType::IntVec4 |
+        UniformType::Bool | UniformType::BoolVec2 | UniformType::BoolVec3 |
+        UniformType::BoolVec4 => Some(UniformComponentsKind::Int),
+
+        UniformType::UnsignedInt | UniformType::UnsignedIntVec2 |
+        UniformType::UnsignedIntVec3 |
+        UniformType::UnsignedIntVec4 => Some(UniformComponentsKind::UnsignedInt),
+
+        _ => None,
+    }
+}
+
+macro_rules! impl_readable_uniform {
+    ($t:ty, $($ty:ident)|+) => (
+        impl ReadableUniform for $t {
+            #[inline]
+            fn is_readable_from(ty: &UniformType) -> bool {
+                match *ty {
+                    $(UniformType::$ty)|+ => true,
+                    _ => false
+                }
+            }
+
+            #[inline]
+            unsafe fn from_raw(data: &[u32; 16]) -> $t {
+                ptr::read(data.as_ptr() as *const $t)
+            }
+        }
+    );
+}
+
+impl_readable_uniform!(f32, Float);
+impl_readable_uniform!([f32; 2], FloatVec2);
+impl_readable_uniform!([f32; 3], FloatVec3);
+impl_readable_uniform!([f32; 4], FloatVec4);
+impl_readable_uniform!([[f32; 2]; 2], FloatMat2);
+impl_readable_uniform!([[f32; 3]; 3], FloatMat3);
+impl_readable_uniform!([[f32; 4]; 4], FloatMat4);
+impl_readable_uniform!(i32, Int | Bool);
+impl_readable_uniform!([i32; 2], IntVec2 | BoolVec2);
+impl_readable_uniform!([i32; 3], IntVec3 | BoolVec3);
+impl_readable_uniform!([i32; 4], IntVec4 | BoolVec4);
+impl_readable_uniform!(u32, UnsignedInt);
+impl_readable_uniform!([u32; 2], UnsignedIntVec2);
+impl_readable_uniform!([u32; 3], UnsignedIntVec3);
+impl_readable_uniform!([u32; 4], UnsignedIntVec4);
+
+impl ReadableUniform for bool {
+    #[inline]
+    fn is_readable_from(ty: &UniformType) -> bool {
+        *ty == UniformType::Bool
+    }
+
+    #[inline]
+    unsafe fn from_raw(data: &[u32; 16]) -> bool {
+        data[0] != 0
+    }
+}
@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 use RawUniformValue;
+use RawUniformArrayValue;
 
 use smallvec::SmallVec;
 
@@ -37,8 +38,7 @@ impl UniformsStorage {
     {
         let mut values = self.values.borrow_mut();
 
-        // TODO: don't assume that, instead use DSA if the program is not current
-        assert!(ctxt.state.program == program);
+        let dsa = prepare_program(ctxt, program);
 
         // TODO: more optimized
         if values.get(&location).is_none() {
@@ -46,10 +46,13 @@ impl UniformsStorage {
         }
 
         macro_rules! uniform(
-            ($ctxt:expr, $uniform:ident, $uniform_arb:ident, $($params:expr),+) => (
+            ($ctxt:expr, $dsa:expr, $uniform:ident, $uniform_arb:ident, $program_uniform:ident,
+             $($params:expr),+) => (
                 unsafe {
-                    if $ctxt.version >= &Version(Api::Gl, 1, 5) ||
-                       $ctxt.version >= &Version(Api::GlEs, 2, 0)
+                    if let Some(id) = $dsa {
+                        $ctxt.gl.$program_uniform(id, $($params),+)
+                    } else if $ctxt.version >= &Version(Api::Gl, 1, 5) ||
+                              $ctxt.version >= &Version(Api::GlEs, 2, 0)
                     {
                         $ctxt.gl.$uniform($($params),+)
                     } else {
@@ -61,24 +64,28 @@ impl UniformsStorage {
         );
 
         macro_rules! uniform_f64(
-            ($ctxt:expr, $uniform:ident, $($params:expr),+) => (
+            ($ctxt:expr, $dsa:expr, $uniform:ident, $program_uniform:ident, $($params:expr),+) => (
                 unsafe {
-                    if $ctxt.extensions.gl_arb_gpu_shader_fp64 {
-                        $ctxt.gl.$uniform($($params),+)
-                    } else {
+                    if !$ctxt.extensions.gl_arb_gpu_shader_fp64 {
                         panic!("Double precision floats are not supported on this system.")
+                    } else if let Some(id) = $dsa {
+                        $ctxt.gl.$program_uniform(id, $($params),+)
+                    } else {
+                        $ctxt.gl.$uniform($($params),+)
                     }
                 }
             )
         );
 
         macro_rules! uniform_i64(
-            ($ctxt:expr, $uniform:ident, $($params:expr),+) => (
+            ($ctxt:expr, $dsa:expr, $uniform:ident, $program_uniform:ident, $($params:expr),+) => (
                 unsafe {
-                    if $ctxt.extensions.gl_arb_gpu_shader_int64 {
-                        $ctxt.gl.$uniform($($params),+)
-                    } else {
+                    if !$ctxt.extensions.gl_arb_gpu_shader_int64 {
                         panic!("64 bit integers are not supported on this system.")
+                    } else if let Some(id) = $dsa {
+                        $ctxt.gl.$program_uniform(id, $($params),+)
+                    } else {
+                        $ctxt.gl.$uniform($($params),+)
                     }
                 }
             )
@@ -118,7 +125,7 @@ impl UniformsStorage {
 
             (&RawUniformValue::SignedInt(v), target) => {
                 *target = Some(RawUniformValue::SignedInt(v));
-                uniform!(ctxt, Uniform1i, Uniform1iARB, location, v);
+                uniform!(ctxt, dsa, Uniform1i, Uniform1iARB, ProgramUniform1i, location, v);
             },
 
             (&RawUniformValue::UnsignedInt(v), target) => {
@@ -126,8 +133,10 @@ impl UniformsStorage {
 
                 // Uniform1uiARB doesn't exist
                 unsafe {
-                    if ctxt.version >= &Version(Api::Gl, 1, 5) ||
-                       ctxt.version >= &Version(Api::GlEs, 2, 0)
+                    if let Some(id) = dsa {
+                        ctxt.gl.ProgramUniform1ui(id, location, v)
+                    } else if ctxt.version >= &Version(Api::Gl, 1, 5) ||
+                              ctxt.version >= &Version(Api::GlEs, 2, 0)
                     {
                         ctxt.gl.Uniform1ui(location, v)
                     } else {
@@ -139,55 +148,55 @@ impl UniformsStorage {
 
             (&RawUniformValue::Float(v), target) => {
                 *target = Some(RawUniformValue::Float(v));
-                uniform!(ctxt, Uniform1f, Uniform1fARB, location, v);
+                uniform!(ctxt, dsa, Uniform1f, Uniform1fARB, ProgramUniform1f, location, v);
             },
 
             (&RawUniformValue::Mat2(v), target) => {
                 *target = Some(RawUniformValue::Mat2(v));
-                uniform!(ctxt, UniformMatrix2fv, UniformMatrix2fvARB,
+                uniform!(ctxt, dsa, UniformMatrix2fv, UniformMatrix2fvARB, ProgramUniformMatrix2fv,
                          location, 1, gl::FALSE, v.as_ptr() as *const f32);
             },
 
             (&RawUniformValue::Mat3(v), target) => {
                 *target = Some(RawUniformValue::Mat3(v));
-                uniform!(ctxt, UniformMatrix3fv, UniformMatrix3fvARB,
+                uniform!(ctxt, dsa, UniformMatrix3fv, UniformMatrix3fvARB, ProgramUniformMatrix3fv,
                          location, 1, gl::FALSE, v.as_ptr() as *const f32);
             },
 
             (&RawUniformValue::Mat4(v), target) => {
                 *target = Some(RawUniformValue::Mat4(v));
-                uniform!(ctxt, UniformMatrix4fv, UniformMatrix4fvARB,
+                uniform!(ctxt, dsa, UniformMatrix4fv, UniformMatrix4fvARB, ProgramUniformMatrix4fv,
                          location, 1, gl::FALSE, v.as_ptr() as *const f32);
             },
 
             (&RawUniformValue::Vec2(v), target) => {
                 *target = Some(RawUniformValue::Vec2(v));
-                uniform!(ctxt, Uniform2fv, Uniform2fvARB, location, 1, v.as_ptr() as *const f32);
+                uniform!(ctxt, dsa, Uniform2fv, Uniform2fvARB, ProgramUniform2fv, location, 1, v.as_ptr() as *const f32);
             },
 
             (&RawUniformValue::Vec3(v), target) => {
                 *target = Some(RawUniformValue::Vec3(v));
-                uniform!(ctxt, Uniform3fv, Uniform3fvARB, location, 1, v.as_ptr() as *const f32);
+                uniform!(ctxt, dsa, Uniform3fv, Uniform3fvARB, ProgramUniform3fv, location, 1, v.as_ptr() as *const f32);
             },
 
             (&RawUniformValue::Vec4(v), target) => {
                 *target = Some(RawUniformValue::Vec4(v));
-                uniform!(ctxt, Uniform4fv, Uniform4fvARB, location, 1, v.as_ptr() as *const f32);
+                uniform!(ctxt, dsa, Uniform4fv, Uniform4fvARB, ProgramUniform4fv, location, 1, v.as_ptr() as *const f32);
             },
 
             (&RawUniformValue::IntVec2(v), target) => {
                 *target = Some(RawUniformValue::IntVec2(v));
-                uniform!(ctxt, Uniform2iv, Uniform2ivARB, location, 1, v.as_ptr() as *const gl::types::GLint);
+                uniform!(ctxt, dsa, Uniform2iv, Uniform2ivARB, ProgramUniform2iv, location, 1, v.as_ptr() as *const gl::types::GLint);
             },
 
             (&RawUniformValue::IntVec3(v), target) => {
                 *target = Some(RawUniformValue::IntVec3(v));
-                uniform!(ctxt, Uniform3iv, Uniform3ivARB, location, 1, v.as_ptr() as *const gl::types::GLint);
+                uniform!(ctxt, dsa, Uniform3iv, Uniform3ivARB, ProgramUniform3iv, location, 1, v.as_ptr() as *const gl::types::GLint);
             },
 
             (&RawUniformValue::IntVec4(v), target) => {
                 *target = Some(RawUniformValue::IntVec4(v));
-                uniform!(ctxt, Uniform4iv, Uniform4ivARB, location, 1, v.as_ptr() as *const gl::types::GLint);
+                uniform!(ctxt, dsa, Uniform4iv, Uniform4ivARB, ProgramUniform4iv, location, 1, v.as_ptr() as *const gl::types::GLint);
             },
 
             (&RawUniformValue::UnsignedIntVec2(v), target) => {
@@ -195,8 +204,10 @@ impl UniformsStorage {
 
                 // Uniform2uivARB doesn't exist
                 unsafe {
-                    if ctxt.version >= &Version(Api::Gl, 1, 5) ||
-                       ctxt.version >= &Version(Api::GlEs, 2, 0)
+                    if let Some(id) = dsa {
+                        ctxt.gl.ProgramUniform2uiv(id, location, 1, v.as_ptr() as *const gl::types::GLuint)
+                    } else if ctxt.version >= &Version(Api::Gl, 1, 5) ||
+                              ctxt.version >= &Version(Api::GlEs, 2, 0)
                     {
                         ctxt.gl.Uniform2uiv(location, 1, v.as_ptr() as *const gl::types::GLuint)
                     } else {
@@ -211,8 +222,10 @@ impl UniformsStorage {
 
                 // Uniform3uivARB doesn't exist
                 unsafe {
-                    if ctxt.version >= &Version(Api::Gl, 1, 5) ||
-                       ctxt.version >= &Version(Api::GlEs, 2, 0)
+                    if let Some(id) = dsa {
+                        ctxt.gl.ProgramUniform3uiv(id, location, 1, v.as_ptr() as *const gl::types::GLuint)
+                    } else if ctxt.version >= &Version(Api::Gl, 1, 5) ||
+                              ctxt.version >= &Version(Api::GlEs, 2, 0)
                     {
                         ctxt.gl.Uniform3uiv(location, 1, v.as_ptr() as *const gl::types::GLuint)
                     } else {
@@ -227,8 +240,10 @@ impl UniformsStorage {
 
                 // Uniform4uivARB doesn't exist
                 unsafe {
-                    if ctxt.version >= &Version(Api::Gl, 1, 5) ||
-                       ctxt.version >= &Version(Api::GlEs, 2, 0)
+                    if let Some(id) = dsa {
+                        ctxt.gl.ProgramUniform4uiv(id, location, 1, v.as_ptr() as *const gl::types::GLuint)
+                    } else if ctxt.version >= &Version(Api::Gl, 1, 5) ||
+                              ctxt.version >= &Version(Api::GlEs, 2, 0)
                     {
                         ctxt.gl.Uniform4uiv(location, 1, v.as_ptr() as *const gl::types::GLuint)
                     } else {
@@ -239,76 +254,143 @@ impl UniformsStorage {
             },
             (&RawUniformValue::Double(v), target) => {
                 *target = Some(RawUniformValue::Double(v));
-                uniform_f64!(ctxt, Uniform1d, location, v);
+                uniform_f64!(ctxt, dsa, Uniform1d, ProgramUniform1d, location, v);
             },
 
             (&RawUniformValue::DoubleMat2(v), target) => {
                 *target = Some(RawUniformValue::DoubleMat2(v));
-                uniform_f64!(ctxt, UniformMatrix2dv,
+                uniform_f64!(ctxt, dsa, UniformMatrix2dv, ProgramUniformMatrix2dv,
                          location, 1, gl::FALSE, v.as_ptr() as *const gl::types::GLdouble);
             },
 
             (&RawUniformValue::DoubleMat3(v), target) => {
                 *target = Some(RawUniformValue::DoubleMat3(v));
-                uniform_f64!(ctxt, UniformMatrix3dv,
+                uniform_f64!(ctxt, dsa, UniformMatrix3dv, ProgramUniformMatrix3dv,
                          location, 1, gl::FALSE, v.as_ptr() as *const gl::types::GLdouble);
             },
 
             (&RawUniformValue::DoubleMat4(v), target) => {
                 *target = Some(RawUniformValue::DoubleMat4(v));
-                uniform_f64!(ctxt, UniformMatrix4dv,
+                uniform_f64!(ctxt, dsa, UniformMatrix4dv, ProgramUniformMatrix4dv,
                          location, 1, gl::FALSE, v.as_ptr() as *const gl::types::GLdouble);
             },
 
             (&RawUniformValue::DoubleVec2(v), target) => {
                 *target = Some(RawUniformValue::DoubleVec2(v));
-                uniform_f64!(ctxt, Uniform2dv, location, 1, v.as_ptr() as *const gl::types::GLdouble);
+                uniform_f64!(ctxt, dsa, Uniform2dv, ProgramUniform2dv, location, 1, v.as_ptr() as *const gl::types::GLdouble);
             },
 
             (&RawUniformValue::DoubleVec3(v), target) => {
                 *target = Some(RawUniformValue::DoubleVec3(v));
-                uniform_f64!(ctxt, Uniform3dv, location, 1, v.as_ptr() as *const gl::types::GLdouble);
+                uniform_f64!(ctxt, dsa, Uniform3dv, ProgramUniform3dv, location, 1, v.as_ptr() as *const gl::types::GLdouble);
             },
 
             (&RawUniformValue::DoubleVec4(v), target) => {
                 *target = Some(RawUniformValue::DoubleVec4(v));
-                uniform_f64!(ctxt, Uniform4dv, location, 1, v.as_ptr() as *const gl::types::GLdouble);
+                uniform_f64!(ctxt, dsa, Uniform4dv, ProgramUniform4dv, location, 1, v.as_ptr() as *const gl::types::GLdouble);
             },
             (&RawUniformValue::Int64(v), target) => {
                 *target = Some(RawUniformValue::Int64(v));
-                uniform_i64!(ctxt, Uniform1i64ARB, location, v);
+                uniform_i64!(ctxt, dsa, Uniform1i64ARB, ProgramUniform1i64ARB, location, v);
             },
             (&RawUniformValue::Int64Vec2(v), target) => {
                 *target = Some(RawUniformValue::Int64Vec2(v));
-                uniform_i64!(ctxt, Uniform2i64vARB, location, 1, v.as_ptr() as *const gl::types::GLint64);
+                uniform_i64!(ctxt, dsa, Uniform2i64vARB, ProgramUniform2i64vARB, location, 1, v.as_ptr() as *const gl::types::GLint64);
             },
 
             (&RawUniformValue::Int64Vec3(v), target) => {
                 *target = Some(RawUniformValue::Int64Vec3(v));
-                uniform_i64!(ctxt, Uniform3i64vARB, location, 1, v.as_ptr() as *const gl::types::GLint64);
+                uniform_i64!(ctxt, dsa, Uniform3i64vARB, ProgramUniform3i64vARB, location, 1, v.as_ptr() as *const gl::types::GLint64);
             },
 
             (&RawUniformValue::Int64Vec4(v), target) => {
                 *target = Some(RawUniformValue::Int64Vec4(v));
-                uniform_i64!(ctxt, Uniform4i64vARB, location, 1, v.as_ptr() as *const gl::types::GLint64);
+                uniform_i64!(ctxt, dsa, Uniform4i64vARB, ProgramUniform4i64vARB, location, 1, v.as_ptr() as *const gl::types::GLint64);
             },
             (&RawUniformValue::UnsignedInt64(v), target) => {
                 *target = Some(RawUniformValue::UnsignedInt64(v));
-                uniform_i64!(ctxt, Uniform1ui64ARB, location, v);
+                uniform_i64!(ctxt, dsa, Uniform1ui64ARB, ProgramUniform1ui64ARB, location, v);
             },
             (&RawUniformValue::UnsignedInt64Vec2(v), target) => {
                 *target = Some(RawUniformValue::UnsignedInt64Vec2(v));
-                uniform_i64!(ctxt, Uniform2ui64vARB, location, 1, v.as_ptr() as *const gl::types::GLuint64);
+                uniform_i64!(ctxt, dsa, Uniform2ui64vARB, ProgramUniform2ui64vARB, location, 1, v.as_ptr() as *const gl::types::GLuint64);
             },
 
             (&RawUniformValue::UnsignedInt64Vec3(v), target) => {
                 *target = Some(RawUniformValue::UnsignedInt64Vec3(v));
-                uniform_i64!(ctxt, Uniform3ui64vARB, location, 1, v.as_ptr() as *const gl::types::GLuint64);
+                uniform_i64!(ctxt, dsa, Uniform3ui64vARB, ProgramUniform3ui64vARB, location, 1, v.as_ptr() as *const gl::types::GLuint64);
             },
 
             (&RawUniformValue::UnsignedInt64Vec4(v), target) => {
                 *target = Some(RawUniformValue::UnsignedInt64Vec4(v));
-                uniform_i64!(ctxt, Uniform4ui64vARB, location, 1, v.as_ptr() as *const gl::types::GLuint64);
+                uniform_i64!(ctxt, dsa, Uniform4ui64vARB, ProgramUniform4ui64vARB, location, 1, v.as_ptr() as *const gl::types::GLuint64);
+            },
+        }
+    }
+
+    /// Calls `glUniform` with all the elements of `value`, starting at `location`.
+    ///
+    /// Arrays are not cached, so this always calls `glUniform`.
+    pub fn set_uniform_array_value(&self, ctxt: &mut CommandContext, program: Handle,
+                                   location: gl::types::GLint, value: &RawUniformArrayValue)
+    {
+        let dsa = prepare_program(ctxt, program);
+
+        // the first element may have been cached by `set_uniform_value`
+        self.values.borrow_mut().insert(location, None);
+
+        macro_rules! uniform(
+            ($ctxt:expr, $dsa:expr, $uniform:ident, $uniform_arb:ident, $program_uniform:ident,
+             $($params:expr),+) => (
+                unsafe {
+                    if let Some(id) = $dsa {
+                        $ctxt.gl.$program_uniform(id, $($params),+)
+                    } else if $ctxt.version >= &Version(Api::Gl, 1, 5) ||
+                              $ctxt.version >= &Version(Api::GlEs, 2, 0)
+                    {
+                        $ctxt.gl.$uniform($($params),+)
+                    } else {
+                        assert!($ctxt.extensions.gl_arb_shader_objects);
+                        $ctxt.gl.$uniform_arb($($params),+)
+                    }
+                }
+            )
+        );
+
+        match *value {
+            RawUniformArrayValue::Float(v) => {
+                uniform!(ctxt, dsa, Uniform1fv, Uniform1fvARB, ProgramUniform1fv, location,
+                         v.len() as gl::types::GLsizei, v.as_ptr() as *const f32);
+            },
+
+            RawUniformArrayValue::Mat2(v) => {
+                uniform!(ctxt, dsa, UniformMatrix2fv, UniformMatrix2fvARB, ProgramUniformMatrix2fv, location,
+                         v.len() as gl::types::GLsizei, gl::FALSE, v.as_ptr() as *const f32);
+            },
+
+            RawUniformArrayValue::Mat3(v) => {
+                uniform!(ctxt, dsa, UniformMatrix3fv, UniformMatrix3fvARB, ProgramUniformMatrix3fv, location,
+                         v.len() as gl::types::GLsizei, gl::FALSE, v.as_ptr() as *const f32);
+            },
+
+            RawUniformArrayValue::Mat4(v) => {
+                uniform!(ctxt, dsa, UniformMatrix4fv, UniformMatrix4fvARB, ProgramUniformMatrix4fv, location,
+                         v.len() as gl::types::GLsizei, gl::FALSE, v.as_ptr() as *const f32);
+            },
+
+            RawUniformArrayValue::Vec2(v) => {
+                uniform!(ctxt, dsa, Uniform2fv, Uniform2fvARB, ProgramUniform2fv, location,
+                         v.len() as gl::types::GLsizei, v.as_ptr() as *const f32);
+            },
+
+            RawUniformArrayValue::Vec3(v) => {
+                uniform!(ctxt, dsa, Uniform3fv, Uniform3fvARB, ProgramUniform3fv, location,
+                         v.len() as gl::types::GLsizei, v.as_ptr() as *const f32);
+            },
+
+            RawUniformArrayValue::Vec4(v) => {
+                uniform!(ctxt, dsa, Uniform4fv, Uniform4fvARB, ProgramUniform4fv, location,
+                         v.len() as gl::types::GLsizei, v.as_ptr() as *const f32);
             },
         }
     }
@@ -326,9 +408,6 @@ impl UniformsStorage {
             }
         }
 
-        // TODO: don't assume that, instead use DSA if the program is not current
-        assert!(ctxt.state.program == program);
-
         match (value, &mut blocks[location as usize]) {
             (a, &mut Some(b)) if a == b => (),
 
@@ -357,9 +436,6 @@ impl UniformsStorage {
             }
         }
 
-        // TODO: don't assume that, instead use DSA if the program is not current
-        assert!(ctxt.state.program == program);
-
         match (value, &mut blocks[location as usize]) {
             (a, &mut Some(b)) if a == b => (),
 
@@ -388,11 +464,49 @@ impl UniformsStorage {
                 return
             }
         }
-        // TODO: don't assume that, instead use DSA if the program is not current
-        assert!(ctxt.state.program == program);
+        // there is no DSA version of `glUniformSubroutinesuiv`
+        bind_program(ctxt, program);
         subroutine_uniforms.insert(stage, indices.iter().cloned().collect());
         unsafe {
             ctxt.gl.UniformSubroutinesuiv(stage.to_gl_enum(), indices.len() as gl::types::GLsizei, indices.as_ptr() as *const _);
         }
     }
 }
+
+/// Returns the id of the program if `glProgramUniform*` can be used to modify its uniforms.
+/// Otherwise makes sure that the program is current and returns `None`.
+fn prepare_program(ctxt: &mut CommandContext, program: Handle) -> Option<gl::types::GLuint> {
+    if ctxt.state.program == program {
+        return None;
+    }
+
+    match program {
+        Handle::Id(id) if ctxt.version >= &Version(Api::Gl, 4, 1) ||
+                          ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+                          ctxt.extensions.gl_arb_separate_shader_objects =>
+        {
+            Some(id)
+        },
+
+        _ => {
+            bind_program(ctxt, program);
+            None
+        },
+    }
+}
+
+/// Binds the program if it isn't current.
+fn bind_program(ctxt: &mut CommandContext, program: Handle) {
+    if ctxt.state.program == program {
+        return;
+    }
+
+    unsafe {
+        match program {
+            Handle::Id(id) => ctxt.gl.UseProgram(id),
+            Handle::Handle(id) => ctxt.gl.UseProgramObjectARB(id),
+        }
+    }
+
+    ctxt.state.program = program;
+}
@@ -6,6 +6,8 @@ use gl;
 use backend::Facade;
 use context::Context;
 use ContextExt;
+use CapabilitiesSource;
+use std::collections::VecDeque;
 use std::rc::Rc;
 
 use std::thread;
@@ -41,6 +43,61 @@ impl SyncFence {
         unsafe { new_linear_sync_fence(&mut ctxt) }.map(|f| f.into_sync_fence(facade))
     }
 
+    /// Gives a name to the fence. OpenGL debuggers will show this name instead of the identifier
+    /// of the sync object.
+    ///
+    /// Returns `Err` if the backend doesn't support labelling objects.
+    pub fn set_label(&self, label: &str) -> Result<(), ()> {
+        let sync = self.id.unwrap();
+        let label = label.as_bytes();
+
+        let ctxt = self.context.make_current();
+
+        unsafe {
+            if ctxt.version >= &Version(Api::Gl, 4, 3) ||
+               ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+               (ctxt.version >= &Version(Api::Gl, 1, 0) && ctxt.extensions.gl_khr_debug)
+            {
+                ctxt.gl.ObjectPtrLabel(sync as *const _, label.len() as gl::types::GLsizei,
+                                       label.as_ptr() as *const _);
+                Ok(())
+
+            } else if ctxt.version >= &Version(Api::GlEs, 2, 0) &&
+                      ctxt.extensions.gl_khr_debug
+            {
+                ctxt.gl.ObjectPtrLabelKHR(sync as *const _, label.len() as gl::types::GLsizei,
+                                          label.as_ptr() as *const _);
+                Ok(())
+
+            } else {
+                Err(())
+            }
+        }
+    }
+
+    /// Returns true if the operation has finished on the server. Doesn't block.
+    pub fn is_signaled(&self) -> bool {
+        let sync = self.id.unwrap();
+        let ctxt = self.context.make_current();
+
+        let result = unsafe {
+            if ctxt.version >= &Version(Api::Gl, 3, 2) ||
+               ctxt.version >= &Version(Api::GlEs, 3, 0) || ctxt.extensions.gl_arb_sync
+            {
+                ctxt.gl.ClientWaitSync(sync, gl::SYNC_FLUSH_COMMANDS_BIT, 0)
+            } else if ctxt.extensions.gl_apple_sync {
+                ctxt.gl.ClientWaitSyncAPPLE(sync, gl::SYNC_FLUSH_COMMANDS_BIT_APPLE, 0)
+            } else {
+                unreachable!();
+            }
+        };
+
+        match result {
+            gl::ALREADY_SIGNALED | gl::CONDITION_SATISFIED => true,
+            _ => false
+        }
+    }
+
     /// Blocks until the operation has finished on the server.
     pub fn wait(mut self) {
         let sync = self.id.take().unwrap();
@@ -54,6 +111,47 @@ impl SyncFence {
             _ => panic!("Could not wait for the fence")
         };
     }
+
+    /// Makes the commands that are submitted to another context wait until the operation has
+    /// finished on the server. Doesn't block.
+    ///
+    /// This is how objects are handed over between contexts that share them. Create a fence
+    /// with the first context after modifying an object, then call `wait_in` with the second
+    /// context before using the object.
+    ///
+    /// ```no_run
+    /// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+    /// # let other_display: glium::Display = unsafe { std::mem::uninitialized() };
+    /// # fn upload_something<T>(_: &T) {}
+    /// upload_something(&display);
+    /// let fence = glium::SyncFence::new(&display).unwrap();
+    /// fence.wait_in(&other_display);
+    /// // the commands of `other_display` now see the uploaded data
+    /// ```
+    ///
+    /// # Panic
+    ///
+    /// Panics if the context of `facade` doesn't share its objects with the context of the
+    /// fence. See `Context::new_shared`.
+    pub fn wait_in<F>(mut self, facade: &F) where F: Facade {
+        let context = facade.get_context();
+        assert!(self.context.is_shared_with(context),
+                "The fence can't be used with a context that doesn't share its objects");
+
+        let sync = self.id.take().unwrap();
+
+        // the other context can't wait for a fence that hasn't been submitted yet
+        {
+            let ctxt = self.context.make_current();
+            unsafe { ctxt.gl.Flush(); }
+        }
+
+        let mut ctxt = context.make_current();
+        unsafe {
+            server_wait(&mut ctxt, sync);
+            delete_fence(&mut ctxt, sync);
+        }
+    }
 }
 
 impl Drop for SyncFence {
@@ -69,6 +167,92 @@ impl Drop for SyncFence {
     }
 }
 
+/// Limits the number of frames that the GPU can be late compared to the CPU.
+///
+/// Drivers usually let the CPU submit several frames ahead of the GPU, which increases the
+/// latency between the user's input and what is displayed on the screen. A `FrameLimiter`
+/// inserts a fence at the end of each frame, and `begin_frame` blocks until the GPU has
+/// finished the frame that is `max_frames_in_flight` frames behind.
+///
+/// ## Example
+///
+/// ```no_run
+/// # let display: glium::Display = unsafe { std::mem::uninitialized() };
+/// let mut limiter = glium::FrameLimiter::new(&display, 2).unwrap();
+///
+/// loop {
+///     limiter.begin_frame();      // blocks until frame N-2 has been processed by the GPU
+///     let target = display.draw();
+///     // ...
+///     target.finish().unwrap();
+///     limiter.end_frame();
+/// }
+/// ```
+pub struct FrameLimiter {
+    context: Rc<Context>,
+    fences: VecDeque<SyncFence>,
+    max_frames_in_flight: usize,
+}
+
+impl FrameLimiter {
+    /// Builds a new `FrameLimiter` that allows at most `max_frames_in_flight` frames to be
+    /// processed by the GPU at the same time.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `max_frames_in_flight` is 0.
+    pub fn new<F>(facade: &F, max_frames_in_flight: usize)
+                  -> Result<FrameLimiter, SyncNotSupportedError> where F: Facade
+    {
+        assert!(max_frames_in_flight >= 1);
+
+        let context = facade.get_context();
+
+        if !(context.get_version() >= &Version(Api::Gl, 3, 2) ||
+             context.get_version() >= &Version(Api::GlEs, 3, 0) ||
+             context.get_extensions().gl_arb_sync || context.get_extensions().gl_apple_sync)
+        {
+            return Err(SyncNotSupportedError);
+        }
+
+        Ok(FrameLimiter {
+            context: context.clone(),
+            fences: VecDeque::with_capacity(max_frames_in_flight),
+            max_frames_in_flight: max_frames_in_flight,
+        })
+    }
+
+    /// Returns the maximum number of frames that can be processed by the GPU at the same time.
+    #[inline]
+    pub fn get_max_frames_in_flight(&self) -> usize {
+        self.max_frames_in_flight
+    }
+
+    /// Blocks until there is less than `max_frames_in_flight` frames being processed by
+    /// the GPU.
+    ///
+    /// Call this before starting to draw a frame.
+    pub fn begin_frame(&mut self) {
+        // the fences of a lost context will never be signaled
+        if self.context.is_context_lost() {
+            self.fences.clear();
+            return;
+        }
+
+        while self.fences.len() >= self.max_frames_in_flight {
+            self.fences.pop_front().unwrap().wait();
+        }
+    }
+
+    /// Inserts a fence that marks the end of the current frame.
+    ///
+    /// Call this after the frame has been submitted, for example after `Frame::finish`.
+    pub fn end_frame(&mut self) {
+        let fence = SyncFence::new(&self.context).unwrap();
+        self.fences.push_back(fence);
+    }
+}
+
 /// Prototype for a `SyncFence`.
 ///
 /// The fence must be consumed with either `into_sync_fence`, otherwise
@@ -130,6 +314,20 @@ pub unsafe fn wait_linear_sync_fence_and_drop(mut fence: LinearSyncFence,
     delete_fence(ctxt, fence);
 }
 
+/// Makes the commands of the context wait for this fence and destroys it, from within the
+/// commands context. Doesn't block.
+///
+/// The fence can have been created by another context, as long as it shares its objects with
+/// this one.
+#[inline]
+pub unsafe fn server_wait_linear_sync_fence_and_drop(mut fence: LinearSyncFence,
+                                                     ctxt: &mut CommandContext)
+{
+    let fence = fence.id.take().unwrap();
+    server_wait(ctxt, fence);
+    delete_fence(ctxt, fence);
+}
+
 /// Destroys a fence, from within the commands context.
 #[inline]
 pub unsafe fn destroy_linear_sync_fence(ctxt: &mut CommandContext, mut fence: LinearSyncFence) {
@@ -137,6 +335,24 @@ pub unsafe fn destroy_linear_sync_fence(ctxt: &mut CommandContext, mut fence: Li
     delete_fence(ctxt, fence);
 }
 
+/// Calls `glWaitSync`.
+///
+/// # Unsafety
+///
+/// The fence object must exist.
+///
+unsafe fn server_wait(ctxt: &mut CommandContext, fence: gl::types::GLsync) {
+    if ctxt.version >= &Version(Api::Gl, 3, 2) ||
+       ctxt.version >= &Version(Api::GlEs, 3, 0) || ctxt.extensions.gl_arb_sync
+    {
+        ctxt.gl.WaitSync(fence, 0, gl::TIMEOUT_IGNORED);
+    } else if ctxt.extensions.gl_apple_sync {
+        ctxt.gl.WaitSyncAPPLE(fence, 0, gl::TIMEOUT_IGNORED_APPLE);
+    } else {
+        unreachable!();
+    }
+}
+
 /// Calls `glClientWaitSync` and returns the result.
 ///
 /// Tries without flushing first, then with flushing.
@@ -9,18 +9,30 @@ use CapabilitiesSource;
 use ContextExt;
 use TextureExt;
 use TextureMipmapExt;
+use ToGlEnum;
 use version::Api;
 use Rect;
+use Frame;
+use Surface;
+use FboAttachments;
 
 use image_format::{self, TextureFormatRequest, ClientFormatAny};
 use texture::Texture2dDataSink;
 use texture::TextureKind;
 use texture::{MipmapsOption, TextureFormat, TextureCreationError, CubeLayer};
+use texture::SwizzleComponent;
+use texture::CopyFromFramebufferError;
+use texture::ClearError;
 use texture::{get_format, InternalFormat, GetFormatError};
+use texture::{is_texture_1d_supported, is_texture_1d_array_supported};
 use texture::pixel::PixelValue;
 use texture::pixel_buffer::PixelBuffer;
 
 use fbo::ClearBufferData;
+use ops;
+use framebuffer::SimpleFrameBuffer;
+
+use sync::{self, SyncFence, SyncNotSupportedError, LinearSyncFence};
 
 use buffer::BufferSlice;
 use buffer::BufferAny;
@@ -33,10 +45,12 @@ use std::mem;
 use std::ptr;
 use std::borrow::Cow;
 use std::cell::Cell;
+use std::marker::PhantomData;
 use std::rc::Rc;
 use std::ops::Range;
 
 use ops;
+use ops::ReadError;
 use fbo;
 
 /// Type of a texture.
@@ -73,9 +87,23 @@ pub struct TextureAny {
     generate_mipmaps: bool,
 
     /// Is this texture owned by us? If not, we won't clean it up on drop.
-    owned: bool
+    owned: bool,
+
+    /// Estimated size in bytes of the texture, as registered in the context's memory usage.
+    memory_size: usize,
+
+    /// First and last mipmap levels that can be sampled, as set with `GL_TEXTURE_BASE_LEVEL`
+    /// and `GL_TEXTURE_MAX_LEVEL`.
+    sampled_levels: Cell<(u32, u32)>,
+
+    /// Swizzle mask of the texture, as set with `GL_TEXTURE_SWIZZLE_R/G/B/A`.
+    swizzle: Cell<[SwizzleComponent; 4]>,
 }
 
+/// Swizzle mask of a newly-created texture.
+const DEFAULT_SWIZZLE: [SwizzleComponent; 4] = [SwizzleComponent::Red, SwizzleComponent::Green,
+                                                SwizzleComponent::Blue, SwizzleComponent::Alpha];
+
 fn extract_dimensions(ty: Dimensions)
                       -> (u32, Option<u32>, Option<u32>, Option<u32>, Option<u32>)
 {
@@ -131,6 +159,17 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
                              -> Result<TextureAny, TextureCreationError>
                              where P: Send + Clone + 'a, F: Facade
 {
+    // one-dimensional textures are emulated with two-dimensional textures if necessary
+    let ty = match ty {
+        Dimensions::Texture1d { width } if !is_texture_1d_supported(facade) => {
+            Dimensions::Texture2d { width: width, height: 1 }
+        },
+        Dimensions::Texture1dArray { .. } if !is_texture_1d_array_supported(facade) => {
+            return Err(TextureCreationError::TypeNotSupported);
+        },
+        ty => ty
+    };
+
     // getting the width, height, depth, array_size, samples from the type
     let (width, height, depth, array_size, samples) = extract_dimensions(ty);
     let (is_client_compressed, data_bufsize) = match data {
@@ -165,6 +204,35 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
         }
     }
 
+    // checking the number of samples
+    if let Some(samples) = samples {
+        let capabilities = facade.get_context().get_capabilities();
+
+        let max_samples = match format {
+            TextureFormatRequest::AnyDepth | TextureFormatRequest::AnyStencil |
+            TextureFormatRequest::AnyDepthStencil |
+            TextureFormatRequest::Specific(TextureFormat::DepthFormat(_)) |
+            TextureFormatRequest::Specific(TextureFormat::StencilFormat(_)) |
+            TextureFormatRequest::Specific(TextureFormat::DepthStencilFormat(_)) => {
+                capabilities.max_depth_texture_samples
+            },
+            TextureFormatRequest::AnyIntegral | TextureFormatRequest::AnyUnsigned |
+            TextureFormatRequest::Specific(TextureFormat::UncompressedIntegral(_)) |
+            TextureFormatRequest::Specific(TextureFormat::UncompressedUnsigned(_)) => {
+                capabilities.max_integer_samples
+            },
+            _ => capabilities.max_color_texture_samples,
+        };
+
+        match max_samples {
+            None => return Err(TextureCreationError::TypeNotSupported),
+            Some(max) if samples == 0 || samples > max as u32 => {
+                return Err(TextureCreationError::SamplesCountNotSupported);
+            },
+            _ => ()
+        }
+    }
+
     let should_generate_mipmaps = mipmaps.should_generate();
     let texture_levels = mipmaps.num_levels(width, height, depth) as gl::types::GLsizei;
 
@@ -206,6 +274,9 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
             ctxt.gl.PixelStorei(gl::UNPACK_ALIGNMENT, 1);
         }
 
+        set_unpack_row_length(&mut ctxt, 0);
+        set_unpack_image_height(&mut ctxt, 0);
+
         BufferAny::unbind_pixel_unpack(&mut ctxt);
 
         let id: gl::types::GLuint = mem::uninitialized();
@@ -445,7 +516,7 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
         id
     };
 
-    Ok(TextureAny {
+    let mut texture = TextureAny {
         context: facade.get_context().clone(),
         id: id,
         requested_format: format,
@@ -453,8 +524,16 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
         ty: ty,
         levels: texture_levels as u32,
         generate_mipmaps: should_generate_mipmaps,
-        owned: true
-    })
+        owned: true,
+        memory_size: 0,
+        sampled_levels: Cell::new((0, texture_levels as u32 - 1)),
+        swizzle: Cell::new(DEFAULT_SWIZZLE),
+    };
+
+    // the sized format is a better estimate than the format passed to `glTexImage`
+    let internal_format = storage_internal_format.unwrap_or(teximg_internal_format);
+    register_memory_size(&mut ctxt, &mut texture, internal_format);
+    Ok(texture)
 }
 
 /// Builds a new texture reference from an existing, externally created OpenGL texture.
@@ -472,10 +551,18 @@ pub unsafe fn from_id<F: Facade>(facade: &F,
     let mipmap_levels = mipmaps.num_levels(width, height, depth);
     let should_generate_mipmaps = mipmaps.should_generate();
     if should_generate_mipmaps {
-        let ctxt = facade.get_context().make_current();
-        generate_mipmaps(&ctxt, get_bind_point(ty));
+        let mut ctxt = facade.get_context().make_current();
+        let bind_point = get_bind_point(ty);
+
+        // the texture must be bound before its mipmaps can be generated
+        ctxt.gl.BindTexture(bind_point, id);
+        let act = ctxt.state.active_texture as usize;
+        ctxt.state.texture_units[act].texture = id;
+
+        generate_mipmaps(&ctxt, bind_point);
     }
-    TextureAny {
+
+    let mut texture = TextureAny {
         context: facade.get_context().clone(),
         id: id,
         requested_format: format,
@@ -483,8 +570,173 @@ pub unsafe fn from_id<F: Facade>(facade: &F,
         ty: ty,
         levels: mipmap_levels,
         generate_mipmaps: should_generate_mipmaps,
-        owned: owned
+        owned: owned,
+        memory_size: 0,
+        sampled_levels: Cell::new((0, mipmap_levels - 1)),
+        swizzle: Cell::new(DEFAULT_SWIZZLE),
+    };
+
+    // textures that we don't own are not counted in the memory usage of the context
+    if owned {
+        let internal_format = image_format::format_request_to_glenum(facade.get_context(), format,
+                                                image_format::RequestType::TexStorage);
+
+        if let Ok(internal_format) = internal_format {
+            let mut ctxt = facade.get_context().make_current();
+            register_memory_size(&mut ctxt, &mut texture, internal_format);
+        }
+    }
+
+    texture
+}
+
+/// A texture that has been detached from its context with `export`, in order to be adopted by
+/// another context whose objects are shared with the first one.
+///
+/// Contrary to textures, this object can be sent to another thread. This allows uploading
+/// textures with a context that is current on a worker thread, while another context draws
+/// on the main thread.
+///
+/// `T` is the type of the texture that was exported. The texture is leaked if this object is
+/// destroyed without being imported.
+pub struct ExportedTexture<T> {
+    id: gl::types::GLuint,
+    requested_format: TextureFormatRequest,
+    ty: Dimensions,
+    levels: u32,
+    generate_mipmaps: bool,
+    owned: bool,
+    sampled_levels: (u32, u32),
+    swizzle: [SwizzleComponent; 4],
+    memory_size: usize,
+
+    /// Fence that is signaled when the commands that modified the texture are finished. `None`
+    /// if the backend doesn't support fences, in which case `glFinish` has been called instead.
+    fence: Option<LinearSyncFence>,
+
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Drop for ExportedTexture<T> {
+    #[inline]
+    fn drop(&mut self) {
+        // the fence can't be destroyed without a context, so it is leaked along with the texture
+        if let Some(fence) = self.fence.take() {
+            mem::forget(fence);
+        }
+    }
+}
+
+/// Detaches a texture from its context. See `ExportedTexture`.
+///
+/// A fence is inserted after the commands that modified the texture and the commands are
+/// flushed, so that the context that imports the texture can wait for them.
+pub fn export<T>(mut texture: TextureAny) -> ExportedTexture<T> {
+    let fence = {
+        let mut ctxt = texture.context.make_current();
+
+        unsafe {
+            match sync::new_linear_sync_fence(&mut ctxt) {
+                Ok(fence) => {
+                    ctxt.gl.Flush();
+                    Some(fence)
+                },
+                Err(_) => {
+                    ctxt.gl.Finish();
+                    None
+                },
+            }
+        }
+    };
+
+    let exported = ExportedTexture {
+        id: texture.id,
+        requested_format: texture.requested_format,
+        ty: texture.ty,
+        levels: texture.levels,
+        generate_mipmaps: texture.generate_mipmaps,
+        owned: texture.owned,
+        sampled_levels: texture.sampled_levels.get(),
+        swizzle: texture.swizzle.get(),
+        memory_size: texture.memory_size,
+        fence: fence,
+        marker: PhantomData,
+    };
+
+    // the destructor purges the caches of the context without deleting the texture
+    texture.owned = false;
+    exported
+}
+
+/// Adopts a texture that has been detached from another context with `export`.
+///
+/// The commands of the context of `facade` wait on the server for the commands that modified
+/// the texture to be finished. This doesn't block the current thread.
+///
+/// # Unsafety
+///
+/// The context of `facade` must share its objects with the context that exported the texture.
+pub unsafe fn import<F: Facade, T>(facade: &F, mut texture: ExportedTexture<T>) -> TextureAny {
+    let context = facade.get_context();
+    let mut ctxt = context.make_current();
+
+    if let Some(fence) = texture.fence.take() {
+        sync::server_wait_linear_sync_fence_and_drop(fence, &mut ctxt);
+    }
+
+    let mut imported = TextureAny {
+        context: context.clone(),
+        id: texture.id,
+        requested_format: texture.requested_format,
+        actual_format: Cell::new(None),
+        ty: texture.ty,
+        levels: texture.levels,
+        generate_mipmaps: texture.generate_mipmaps,
+        owned: texture.owned,
+        memory_size: 0,
+        sampled_levels: Cell::new(texture.sampled_levels),
+        swizzle: Cell::new(texture.swizzle),
+    };
+
+    if imported.owned {
+        imported.memory_size = texture.memory_size;
+        let allocated = ctxt.allocated_memory.textures.get();
+        ctxt.allocated_memory.textures.set(allocated + imported.memory_size);
     }
+
+    imported
+}
+
+/// Estimates the size in bytes of the texture from its internal format and its dimensions,
+/// and adds it to the memory usage of the context.
+///
+/// The size is 0 if the internal format is unknown.
+fn register_memory_size(ctxt: &mut CommandContext, texture: &mut TextureAny,
+                        internal_format: gl::types::GLenum)
+{
+    let bits = match image_format::get_internal_format_bits(internal_format) {
+        Some(bits) => bits,
+        None => return
+    };
+
+    let (width, height, depth, array_size, samples) = extract_dimensions(texture.ty);
+    let layers = match texture.ty {
+        Dimensions::Cubemap { .. } => 6,
+        _ => array_size.unwrap_or(1),
+    };
+
+    let mut pixels = 0;
+    for level in 0 .. texture.levels {
+        let width = cmp::max(1, width >> level) as usize;
+        let height = cmp::max(1, height.unwrap_or(1) >> level) as usize;
+        let depth = cmp::max(1, depth.unwrap_or(1) >> level) as usize;
+        pixels += width * height * depth;
+    }
+
+    texture.memory_size = pixels * layers as usize * samples.unwrap_or(1) as usize * bits / 8;
+
+    let allocated = ctxt.allocated_memory.textures.get();
+    ctxt.allocated_memory.textures.set(allocated + texture.memory_size);
 }
 
 impl TextureAny {
@@ -624,6 +876,14 @@ impl TextureAny {
         self.ty
     }
 
+    /// Returns the OpenGL target of the texture, for example `GL_TEXTURE_2D`.
+    ///
+    /// Use this alongside `GlObject::get_id` when passing the texture to another library.
+    #[inline]
+    pub fn get_target(&self) -> gl::types::GLenum {
+        get_bind_point(self.ty)
+    }
+
     /// Determines the internal format of this texture.
     #[inline]
     pub fn get_internal_format(&self) -> Result<InternalFormat, GetFormatError> {
@@ -644,6 +904,95 @@ impl TextureAny {
         self.levels
     }
 
+    /// Generates the mipmap levels `base + 1` to `max` from the level `base`, leaving the
+    /// other levels untouched.
+    ///
+    /// This allows building mipmap chains where some levels are provided by the user and the
+    /// others are generated. The levels that can be sampled are restored once the mipmaps
+    /// have been generated.
+    ///
+    /// Returns `Err` if the backend doesn't support restricting the generated levels.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `base` is greater than `max`, or if `max` is not a mipmap level of the texture.
+    pub fn generate_mipmaps_range(&self, base: u32, max: u32) -> Result<(), ()> {
+        assert!(base <= max);
+        assert!(max < self.levels);
+
+        let mut ctxt = self.context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 3, 0) ||
+             ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+             (ctxt.version >= &Version(Api::Gl, 1, 2) && ctxt.extensions.gl_ext_framebuffer_object))
+        {
+            return Err(());
+        }
+
+        let (previous_base, previous_max) = self.sampled_levels.get();
+        self.set_sampled_levels(&mut ctxt, base, max);
+
+        unsafe {
+            let bind_point = self.bind_to_current(&mut ctxt);
+            generate_mipmaps(&ctxt, bind_point);
+        }
+
+        self.set_sampled_levels(&mut ctxt, previous_base, previous_max);
+        Ok(())
+    }
+
+    /// Returns the swizzle mask of the texture.
+    ///
+    /// See `set_swizzle`.
+    #[inline]
+    pub fn get_swizzle(&self) -> [SwizzleComponent; 4] {
+        self.swizzle.get()
+    }
+
+    /// Sets the components that are returned when sampling the red, green, blue and alpha
+    /// channels of the texture.
+    ///
+    /// For example `[Red, Red, Red, One]` makes a single-channel texture be sampled as an
+    /// opaque grayscale texture, without having to modify the shader.
+    ///
+    /// Returns `Err` if the backend doesn't support texture swizzling.
+    pub fn set_swizzle(&self, swizzle: [SwizzleComponent; 4]) -> Result<(), ()> {
+        let mut ctxt = self.context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 3, 3) ||
+             ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+             ctxt.extensions.gl_arb_texture_swizzle || ctxt.extensions.gl_ext_texture_swizzle)
+        {
+            return Err(());
+        }
+
+        if self.swizzle.get() == swizzle {
+            return Ok(());
+        }
+
+        let params = [gl::TEXTURE_SWIZZLE_R, gl::TEXTURE_SWIZZLE_G, gl::TEXTURE_SWIZZLE_B,
+                      gl::TEXTURE_SWIZZLE_A];
+
+        unsafe {
+            if ctxt.capabilities.direct_state_access {
+                for (&param, component) in params.iter().zip(swizzle.iter()) {
+                    ctxt.gl.TextureParameteri(self.id, param,
+                                              component.to_glenum() as gl::types::GLint);
+                }
+
+            } else {
+                let bind_point = self.bind_to_current(&mut ctxt);
+                for (&param, component) in params.iter().zip(swizzle.iter()) {
+                    ctxt.gl.TexParameteri(bind_point, param,
+                                          component.to_glenum() as gl::types::GLint);
+                }
+            }
+        }
+
+        self.swizzle.set(swizzle);
+        Ok(())
+    }
+
     /// Returns a structure that represents the main mipmap level of the texture.
     #[inline]
     pub fn main_level(&self) -> TextureAnyMipmap {
@@ -697,6 +1046,30 @@ impl TextureExt for TextureAny {
 
         bind_point
     }
+
+    #[inline]
+    fn get_sampled_levels(&self) -> (u32, u32) {
+        self.sampled_levels.get()
+    }
+
+    fn set_sampled_levels(&self, ctxt: &mut CommandContext, base: u32, max: u32) {
+        assert!(base <= max && max < self.levels);
+        assert!(ctxt.version >= &Version(Api::Gl, 1, 2) ||
+                ctxt.version >= &Version(Api::GlEs, 3, 0));
+
+        if self.sampled_levels.get() == (base, max) {
+            return;
+        }
+
+        let bind_point = self.bind_to_current(ctxt);
+
+        unsafe {
+            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_BASE_LEVEL, base as gl::types::GLint);
+            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MAX_LEVEL, max as gl::types::GLint);
+        }
+
+        self.sampled_levels.set((base, max));
+    }
 }
 
 impl GlObject for TextureAny {
@@ -734,6 +1107,22 @@ impl Drop for TextureAny {
         if self.owned {
             unsafe { ctxt.gl.DeleteTextures(1, [ self.id ].as_ptr()); }
         }
+
+        let allocated = ctxt.allocated_memory.textures.get();
+        ctxt.allocated_memory.textures.set(allocated - self.memory_size);
+        drop(ctxt);
+
+        // the contexts that share this texture must forget about it too
+        let id = self.id;
+        self.context.purge_shared_contexts(move |ctxt| {
+            fbo::FramebuffersContainer::purge_texture(ctxt, id);
+
+            for tex_unit in ctxt.state.texture_units.iter_mut() {
+                if tex_unit.texture == id {
+                    tex_unit.texture = 0;
+                }
+            }
+        });
     }
 }
 
@@ -906,6 +1295,30 @@ impl<'a> TextureAnyMipmap<'a> {
         self.raw_upload_from_pixel_buffer_impl(source, x, y, z, false);
     }
 
+    /// Same as `raw_upload_from_pixel_buffer`, but also returns a fence that is signaled once
+    /// the backend has finished reading from the buffer.
+    ///
+    /// Glium already waits for the upload to be finished if you write to the buffer through
+    /// glium's API, so this is only useful if you want to know when the upload is finished
+    /// without blocking, for example to recycle the buffer. The data of uploads from the
+    /// client's memory (with `write`) is copied before the function returns.
+    ///
+    /// The upload happens even if fences are not supported by the backend.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the offsets and dimensions are outside the boundaries of the texture. Panics
+    /// if the buffer is not big enough to hold the data.
+    pub fn raw_upload_from_pixel_buffer_with_fence<P>(&self, source: BufferSlice<[P]>,
+                                                      x: Range<u32>, y: Range<u32>,
+                                                      z: Range<u32>)
+                                                      -> Result<SyncFence, SyncNotSupportedError>
+                                                      where P: PixelValue
+    {
+        self.raw_upload_from_pixel_buffer_impl(source, x, y, z, false);
+        SyncFence::new(&self.texture.context)
+    }
+
     /// Uploads data to the texture from a buffer. The R, G and B components are flipped.
     ///
     /// # Panic
@@ -920,6 +1333,118 @@ impl<'a> TextureAnyMipmap<'a> {
         self.raw_upload_from_pixel_buffer_impl(source, x, y, z, true);
     }
 
+    /// Uploads data to a box of the texture from the client's memory.
+    ///
+    /// Each row of `data` is `row_length` pixels long and each image of `data` is `image_height`
+    /// rows high, which allows uploading a part of a larger volume without copying it to a
+    /// temporary buffer first. If they are `None`, the width and the height of the box are used.
+    ///
+    /// For texture arrays, `z` is the range of layers to upload to.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the box is outside the boundaries of the texture, if `data` is not big enough,
+    /// if `row_length` or `image_height` are smaller than the box, or if the backend doesn't
+    /// support them.
+    pub fn raw_upload_from_memory<P>(&self, data: &[P], x: Range<u32>, y: Range<u32>,
+                                     z: Range<u32>, row_length: Option<u32>,
+                                     image_height: Option<u32>)
+                                     where P: PixelValue
+    {
+        assert!(x.start <= x.end && y.start <= y.end && z.start <= z.end);
+
+        let height = self.height.map(|_| y.end - y.start);
+        let depth = self.depth.or(self.texture.get_array_size()).map(|_| z.end - z.start);
+
+        self.upload_texture(x.start, y.start, z.start,
+                            (ClientFormatAny::ClientFormat(P::get_format()), Cow::Borrowed(data)),
+                            x.end - x.start, height, depth, row_length, image_height,
+                            self.level == 0).unwrap()
+    }
+
+    /// Fills the whole mipmap level with a single value.
+    ///
+    /// This uses `glClearTexImage` if the backend supports it (OpenGL 4.4 or
+    /// `GL_ARB_clear_texture`), which doesn't require the format to be renderable. Otherwise
+    /// the value is repeated in a buffer which is uploaded to the texture. This fallback is
+    /// only available for one-dimensional arrays, two-dimensional textures and arrays and
+    /// three-dimensional textures.
+    ///
+    /// For texture arrays, all the layers are filled.
+    ///
+    /// Returns `ClearError::FormatMismatch` if `value` can't be used with this kind of texture.
+    /// For example passing a `f32` when using an integral texture.
+    pub fn raw_clear<P>(&self, value: P) -> Result<(), ClearError> where P: PixelValue {
+        let height = self.height.unwrap_or(1);
+        let depth = self.depth.or(self.texture.get_array_size()).unwrap_or(1);
+        self.raw_clear_region(0 .. self.width, 0 .. height, 0 .. depth, value)
+    }
+
+    /// Fills a box of the mipmap level with a single value.
+    ///
+    /// For texture arrays, `z` is the range of layers to fill. See `raw_clear` for more infos.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the box is outside the boundaries of the texture.
+    pub fn raw_clear_region<P>(&self, x: Range<u32>, y: Range<u32>, z: Range<u32>, value: P)
+                               -> Result<(), ClearError> where P: PixelValue
+    {
+        assert!(x.start <= x.end && x.end <= self.width);
+        assert!(y.start <= y.end && y.end <= self.height.unwrap_or(1));
+        assert!(z.start <= z.end &&
+                z.end <= self.depth.or(self.texture.get_array_size()).unwrap_or(1));
+
+        // checking that the value matches the kind of texture
+        let client_format = ClientFormatAny::ClientFormat(P::get_format());
+        let (client_format, client_type) = match self.texture.kind() {
+            TextureKind::DepthStencil => return Err(ClearError::NotSupported),
+            _ => try!(image_format::client_format_to_glenum(&self.texture.context,
+                                                            client_format,
+                                                            self.texture.requested_format, false)
+                                    .map_err(|_| ClearError::FormatMismatch))
+        };
+
+        let supported = {
+            let ctxt = self.texture.context.make_current();
+            ctxt.version >= &Version(Api::Gl, 4, 4) || ctxt.extensions.gl_arb_clear_texture
+        };
+
+        if !supported {
+            // `upload_texture` doesn't handle the other types of textures
+            match self.texture.get_texture_type() {
+                Dimensions::Texture1dArray { .. } | Dimensions::Texture2d { .. } |
+                Dimensions::Texture2dArray { .. } | Dimensions::Texture3d { .. } => (),
+                _ => return Err(ClearError::NotSupported)
+            };
+
+            let len = (x.end - x.start) * (y.end - y.start) * (z.end - z.start);
+            let data = vec![value; len as usize];
+
+            let height = self.height.map(|_| y.end - y.start);
+            let depth = self.depth.or(self.texture.get_array_size()).map(|_| z.end - z.start);
+
+            return self.upload_texture(x.start, y.start, z.start,
+                                       (ClientFormatAny::ClientFormat(P::get_format()),
+                                        Cow::Owned(data)),
+                                       x.end - x.start, height, depth, None, None,
+                                       self.level == 0).map_err(|_| ClearError::NotSupported);
+        }
+
+        let mut ctxt = self.texture.context.make_current();
+        unsafe {
+            ctxt.gl.ClearTexSubImage(self.texture.id, self.level as gl::types::GLint,
+                                     x.start as gl::types::GLint, y.start as gl::types::GLint,
+                                     z.start as gl::types::GLint,
+                                     (x.end - x.start) as gl::types::GLsizei,
+                                     (y.end - y.start) as gl::types::GLsizei,
+                                     (z.end - z.start) as gl::types::GLsizei,
+                                     client_format, client_type, &value as *const P as *const _);
+        }
+
+        Ok(())
+    }
+
     fn raw_upload_from_pixel_buffer_impl<P>(&self, source: BufferSlice<[P]>, x: Range<u32>,
                                             y: Range<u32>, z: Range<u32>, inverted: bool)
                                             where P: PixelValue
@@ -948,12 +1473,14 @@ impl<'a> TextureAnyMipmap<'a> {
 
         // binds the pixel buffer
         source.prepare_and_bind_for_pixel_unpack(&mut ctxt);
+        unsafe {
+            set_unpack_row_length(&mut ctxt, 0);
+            set_unpack_image_height(&mut ctxt, 0);
+        }
 
         match self.texture.ty {
             Dimensions::Texture1d { .. } => {
-                if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-                   ctxt.extensions.gl_arb_direct_state_access
-                {
+                if ctxt.capabilities.direct_state_access {
                     unsafe {
                         ctxt.gl.TextureSubImage1D(self.texture.id,
                                                   self.level as gl::types::GLint,
@@ -989,9 +1516,7 @@ impl<'a> TextureAnyMipmap<'a> {
             Dimensions::Texture1dArray { .. } | Dimensions::Texture2d { .. } |
             Dimensions::Texture2dMultisample { .. } |
             Dimensions::Texture2dMultisampleArray { .. } => {
-                if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-                   ctxt.extensions.gl_arb_direct_state_access
-                {
+                if ctxt.capabilities.direct_state_access {
                     unsafe {
                         ctxt.gl.TextureSubImage2D(self.texture.id,
                                                   self.level as gl::types::GLint,
@@ -1031,9 +1556,7 @@ impl<'a> TextureAnyMipmap<'a> {
             },
 
             Dimensions::Texture2dArray { .. } | Dimensions::Texture3d { .. } => {
-                if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-                   ctxt.extensions.gl_arb_direct_state_access
-                {
+                if ctxt.capabilities.direct_state_access {
                     unsafe {
                         ctxt.gl.TextureSubImage3D(self.texture.id,
                                                   self.level as gl::types::GLint,
@@ -1088,12 +1611,139 @@ impl<'a> TextureAnyMipmap<'a> {
             fence.insert(&mut ctxt);
         }
     }
+
+    /// Copies a rectangle of the color buffer of a frame to this mipmap.
+    ///
+    /// `source_rect` is the area of the frame to copy, and `offset` is the position in the
+    /// texture where the bottom-left corner of this area is copied. This is lighter than a blit
+    /// when the destination is a texture that isn't attached to a framebuffer, for example for
+    /// feedback effects.
+    ///
+    /// Returns an error if the texture is not a two-dimensional texture, if its format is not
+    /// a floating-point or normalized uncompressed format, or if the source is multisampled.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the rectangles are outside the boundaries of the frame or of the texture.
+    #[inline]
+    pub fn copy_from_frame(&self, source: &Frame, source_rect: &Rect, offset: (u32, u32))
+                           -> Result<(), CopyFromFramebufferError>
+    {
+        let dimensions = source.get_dimensions();
+        self.copy_from_framebuffer_impl(source.get_attachments(), dimensions, source_rect, offset)
+    }
+
+    /// Copies a rectangle of the color attachment of a framebuffer to this mipmap.
+    ///
+    /// See `copy_from_frame`. Also returns an error if the framebuffer doesn't have a color
+    /// attachment.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the rectangles are outside the boundaries of the framebuffer or of the texture.
+    #[inline]
+    pub fn copy_from_simple_framebuffer(&self, source: &SimpleFrameBuffer, source_rect: &Rect,
+                                        offset: (u32, u32))
+                                        -> Result<(), CopyFromFramebufferError>
+    {
+        let dimensions = source.get_dimensions();
+        self.copy_from_framebuffer_impl(source.get_attachments(), dimensions, source_rect, offset)
+    }
+
+    fn copy_from_framebuffer_impl(&self, source: Option<&fbo::ValidatedAttachments>,
+                                  source_dimensions: (u32, u32), source_rect: &Rect,
+                                  offset: (u32, u32)) -> Result<(), CopyFromFramebufferError>
+    {
+        match self.texture.ty {
+            Dimensions::Texture2d { .. } => (),
+            _ => return Err(CopyFromFramebufferError::TextureTypeNotSupported)
+        };
+
+        match self.texture.requested_format {
+            TextureFormatRequest::Specific(TextureFormat::CompressedFormat(_)) |
+            TextureFormatRequest::Specific(TextureFormat::CompressedSrgbFormat(_)) |
+            TextureFormatRequest::AnyCompressed | TextureFormatRequest::AnyCompressedSrgb => {
+                return Err(CopyFromFramebufferError::IncompatibleFormat);
+            },
+            _ => ()
+        };
+
+        if self.texture.kind() != TextureKind::Float {
+            return Err(CopyFromFramebufferError::IncompatibleFormat);
+        }
+
+        // the color buffer of the default framebuffer is always normalized
+        if let Some(source) = source {
+            match source.get_first_color_attachment_kind() {
+                None => return Err(CopyFromFramebufferError::NoColorAttachment),
+                Some(TextureKind::Float) => (),
+                Some(_) => return Err(CopyFromFramebufferError::IncompatibleFormat),
+            }
+        }
+
+        assert!(source_rect.left + source_rect.width <= source_dimensions.0);
+        assert!(source_rect.bottom + source_rect.height <= source_dimensions.1);
+        assert!(offset.0 + source_rect.width <= self.width);
+        assert!(offset.1 + source_rect.height <= self.height.unwrap_or(1));
+
+        let mut ctxt = self.texture.context.make_current();
+
+        if ops::get_samples_count(&mut ctxt, source) != 0 {
+            return Err(CopyFromFramebufferError::MultisampledSource);
+        }
+
+        unsafe {
+            match source {
+                Some(source) => {
+                    fbo::FramebuffersContainer::bind_framebuffer_color_for_reading(&mut ctxt,
+                                                                                   source, 0);
+                },
+                None => {
+                    fbo::FramebuffersContainer::bind_default_framebuffer_for_reading(&mut ctxt,
+                                                                                     gl::BACK_LEFT);
+                },
+            }
+
+            if ctxt.capabilities.direct_state_access {
+                ctxt.gl.CopyTextureSubImage2D(self.texture.id, self.level as gl::types::GLint,
+                                              offset.0 as gl::types::GLint,
+                                              offset.1 as gl::types::GLint,
+                                              source_rect.left as gl::types::GLint,
+                                              source_rect.bottom as gl::types::GLint,
+                                              source_rect.width as gl::types::GLsizei,
+                                              source_rect.height as gl::types::GLsizei);
+
+            } else if ctxt.extensions.gl_ext_direct_state_access {
+                ctxt.gl.CopyTextureSubImage2DEXT(self.texture.id, self.texture.get_bind_point(),
+                                                 self.level as gl::types::GLint,
+                                                 offset.0 as gl::types::GLint,
+                                                 offset.1 as gl::types::GLint,
+                                                 source_rect.left as gl::types::GLint,
+                                                 source_rect.bottom as gl::types::GLint,
+                                                 source_rect.width as gl::types::GLsizei,
+                                                 source_rect.height as gl::types::GLsizei);
+
+            } else {
+                let bind_point = self.texture.bind_to_current(&mut ctxt);
+                ctxt.gl.CopyTexSubImage2D(bind_point, self.level as gl::types::GLint,
+                                          offset.0 as gl::types::GLint,
+                                          offset.1 as gl::types::GLint,
+                                          source_rect.left as gl::types::GLint,
+                                          source_rect.bottom as gl::types::GLint,
+                                          source_rect.width as gl::types::GLsizei,
+                                          source_rect.height as gl::types::GLsizei);
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
     fn upload_texture<'d, P>(&self, x_offset: u32, y_offset: u32, z_offset: u32,
                              (format, data): (ClientFormatAny, Cow<'d, [P]>), width: u32,
                              height: Option<u32>, depth: Option<u32>,
+                             row_length: Option<u32>, image_height: Option<u32>,
                              regen_mipmaps: bool)
                              -> Result<(), ()>   // TODO return a better Result!?
                              where P: Send + Copy + Clone + 'd
@@ -1103,19 +1753,76 @@ impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
 
         let (is_client_compressed, data_bufsize) = (format.is_compressed(),
                                                     format.get_buffer_size(width, height, depth, None));
+
+        // the rows of the data may be larger than the uploaded rectangle
+        let row_length = match row_length {
+            Some(row_length) if row_length != width => {
+                assert!(row_length > width);
+                assert!(!is_client_compressed);
+
+                let ctxt = self.texture.context.make_current();
+                if !(ctxt.version >= &Version(Api::Gl, 1, 0) ||
+                     ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+                     ctxt.extensions.gl_ext_unpack_subimage)
+                {
+                    return Err(());
+                }
+
+                Some(row_length)
+            },
+            _ => None,
+        };
+
+        // the images of the data may be larger than the uploaded box
+        let image_height = match image_height {
+            Some(image_height) if image_height != height.unwrap_or(1) => {
+                assert!(image_height > height.unwrap_or(1));
+                assert!(depth.is_some());
+                assert!(!is_client_compressed);
+
+                let ctxt = self.texture.context.make_current();
+                if !(ctxt.version >= &Version(Api::Gl, 1, 2) ||
+                     ctxt.version >= &Version(Api::GlEs, 3, 0))
+                {
+                    return Err(());
+                }
+
+                Some(image_height)
+            },
+            _ => None,
+        };
+
         let regen_mipmaps = regen_mipmaps && self.texture.levels >= 2 &&
                             self.texture.generate_mipmaps && !is_client_compressed;
 
+        // for arrays, the z offset is the index of the layer
+        let max_depth = self.depth.or(self.texture.get_array_size()).unwrap_or(1);
+
         assert!(!regen_mipmaps || level == 0);  // when regen_mipmaps is true, level must be 0!
         assert!(x_offset <= self.width);
         assert!(y_offset <= self.height.unwrap_or(1));
-        assert!(z_offset <= self.depth.unwrap_or(1));
+        assert!(z_offset <= max_depth);
         assert!(x_offset + width <= self.width);
         assert!(y_offset + height.unwrap_or(1) <= self.height.unwrap_or(1));
-        assert!(z_offset + depth.unwrap_or(1) <= self.depth.unwrap_or(1));
+        assert!(z_offset + depth.unwrap_or(1) <= max_depth);
+
+        if row_length.is_some() || image_height.is_some() {
+            // only the last row of the last image doesn't need to be complete
+            let row_length = row_length.unwrap_or(width);
+            let image_height = image_height.unwrap_or(height.unwrap_or(1));
+            // an empty box doesn't read anything
+            let pixels = if width == 0 || height == Some(0) || depth == Some(0) {
+                0
+            } else {
+                row_length * image_height * (depth.unwrap_or(1) - 1) +
+                row_length * (height.unwrap_or(1) - 1) + width
+            };
 
-        if data.len() * mem::size_of::<P>() != data_bufsize
-        {
+            if data.len() * mem::size_of::<P>() < format.get_buffer_size(pixels, None, None, None) {
+                panic!("Texture data size mismatch");
+            }
+
+        } else if data.len() * mem::size_of::<P>() != data_bufsize {
             panic!("Texture data size mismatch");
         }
 
@@ -1132,11 +1839,35 @@ impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
                 ctxt.gl.PixelStorei(gl::UNPACK_ALIGNMENT, 1);
             }
 
+            set_unpack_row_length(&mut ctxt, row_length.unwrap_or(0) as gl::types::GLint);
+            set_unpack_image_height(&mut ctxt, image_height.unwrap_or(0) as gl::types::GLint);
+
             BufferAny::unbind_pixel_unpack(&mut ctxt);
             let bind_point = self.texture.bind_to_current(&mut ctxt);
 
             if bind_point == gl::TEXTURE_3D || bind_point == gl::TEXTURE_2D_ARRAY {
-                unimplemented!();
+                if is_client_compressed {
+                    ctxt.gl.CompressedTexSubImage3D(bind_point, level as gl::types::GLint,
+                                                    x_offset as gl::types::GLint,
+                                                    y_offset as gl::types::GLint,
+                                                    z_offset as gl::types::GLint,
+                                                    width as gl::types::GLsizei,
+                                                    height.unwrap_or(1) as gl::types::GLsizei,
+                                                    depth.unwrap_or(1) as gl::types::GLsizei,
+                                                    client_format,
+                                                    data_bufsize  as gl::types::GLsizei,
+                                                    data.as_ptr() as *const _);
+                } else {
+                    ctxt.gl.TexSubImage3D(bind_point, level as gl::types::GLint,
+                                          x_offset as gl::types::GLint,
+                                          y_offset as gl::types::GLint,
+                                          z_offset as gl::types::GLint,
+                                          width as gl::types::GLsizei,
+                                          height.unwrap_or(1) as gl::types::GLsizei,
+                                          depth.unwrap_or(1) as gl::types::GLsizei,
+                                          client_format, client_type,
+                                          data.as_ptr() as *const _);
+                }
 
             } else if bind_point == gl::TEXTURE_2D || bind_point == gl::TEXTURE_1D_ARRAY {
                 assert!(z_offset == 0);
@@ -1186,17 +1917,32 @@ impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
 
         let mut ctxt = texture.context.make_current();
 
+        // OpenGL ES doesn't provide any way to read back compressed data
+        if !(ctxt.version >= &Version(Api::Gl, 1, 3)) {
+            return None;
+        }
+
+        let dsa = ctxt.capabilities.direct_state_access;
+
         unsafe {
-            let bind_point = texture.bind_to_current(&mut ctxt);
+            let bind_point = if dsa { texture.get_bind_point() }
+                             else { texture.bind_to_current(&mut ctxt) };
+
+            let get_level_parameter = |ctxt: &mut CommandContext, name| {
+                let mut value = mem::uninitialized();
+                if dsa {
+                    ctxt.gl.GetTextureLevelParameteriv(texture.id, level, name, &mut value);
+                } else {
+                    ctxt.gl.GetTexLevelParameteriv(bind_point, level, name, &mut value);
+                }
+                value
+            };
 
-            let mut is_compressed = mem::uninitialized();
-            ctxt.gl.GetTexLevelParameteriv(bind_point, level, gl::TEXTURE_COMPRESSED, &mut is_compressed);
+            let is_compressed = get_level_parameter(&mut ctxt, gl::TEXTURE_COMPRESSED);
             if is_compressed != 0 {
 
-                let mut buffer_size = mem::uninitialized();
-                ctxt.gl.GetTexLevelParameteriv(bind_point, level, gl::TEXTURE_COMPRESSED_IMAGE_SIZE, &mut buffer_size);
-                let mut internal_format = mem::uninitialized();
-                ctxt.gl.GetTexLevelParameteriv(bind_point, level, gl::TEXTURE_INTERNAL_FORMAT, &mut internal_format);
+                let buffer_size = get_level_parameter(&mut ctxt, gl::TEXTURE_COMPRESSED_IMAGE_SIZE);
+                let internal_format = get_level_parameter(&mut ctxt, gl::TEXTURE_INTERNAL_FORMAT);
 
                 match ClientFormatAny::from_internal_compressed_format(internal_format as gl::types::GLenum) {
                     Some(known_format) => {
@@ -1220,7 +1966,14 @@ impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
                             ctxt.gl.PixelStorei(gl::PACK_ALIGNMENT, 1);
                         }
 
-                        ctxt.gl.GetCompressedTexImage(bind_point, level, buf.as_mut_ptr() as *mut _);
+                        if dsa {
+                            ctxt.gl.GetCompressedTextureImage(texture.id, level, buffer_size,
+                                                              buf.as_mut_ptr() as *mut _);
+                        } else {
+                            ctxt.gl.GetCompressedTexImage(bind_point, level,
+                                                          buf.as_mut_ptr() as *mut _);
+                        }
+
                         Some((known_format, buf))
                     },
                     None => None,
@@ -1389,10 +2142,37 @@ impl<'a> TextureAnyImage<'a> {
         let mut ctxt = self.texture.context.make_current();
 
         let mut data = Vec::new();
-        ops::read(&mut ctxt, &fbo::RegularAttachment::Texture(*self), &rect, &mut data, false);
+        ops::read(&mut ctxt, &fbo::RegularAttachment::Texture(*self), &rect, &mut data, None,
+                  false);
         T::from_raw(Cow::Owned(data), self.width, self.height.unwrap_or(1))
     }
 
+    /// Reads the content of the image to a buffer whose rows are `row_length` pixels long.
+    ///
+    /// Only the first `rect.width` pixels of each row are written. The returned buffer contains
+    /// `row_length * (rect.height - 1) + rect.width` pixels.
+    ///
+    /// Returns an error if the backend doesn't support reading with a custom row length.
+    ///
+    /// # Panic
+    ///
+    /// - Panicks if the rect is out of range.
+    /// - Panicks if `row_length` is smaller than the width of the rect.
+    ///
+    pub fn raw_read_with_row_length<P>(&self, rect: &Rect, row_length: u32)
+                                       -> Result<Vec<P>, ReadError> where P: PixelValue
+    {
+        assert!(rect.left + rect.width <= self.width);
+        assert!(rect.bottom + rect.height <= self.height.unwrap_or(1));
+
+        let mut ctxt = self.texture.context.make_current();
+
+        let mut data = Vec::new();
+        try!(ops::read(&mut ctxt, &fbo::RegularAttachment::Texture(*self), &rect, &mut data,
+                       Some(row_length), false));
+        Ok(data)
+    }
+
     /// Reads the content of the image to a pixel buffer.
     ///
     /// # Panic
@@ -1409,7 +2189,7 @@ impl<'a> TextureAnyImage<'a> {
 
         let size = rect.width as usize * rect.height as usize * 4;
         let mut ctxt = self.texture.context.make_current();
-        ops::read(&mut ctxt, &fbo::RegularAttachment::Texture(*self), &rect, dest, false);
+        ops::read(&mut ctxt, &fbo::RegularAttachment::Texture(*self), &rect, dest, None, false);
     }
 
     /// Clears the content of the texture to a specific value.
@@ -1429,3 +2209,18 @@ impl<'a> TextureAnyImage<'a> {
         }
     }
 }
+
+/// Changes the value of `GL_UNPACK_ROW_LENGTH` if necessary.
+unsafe fn set_unpack_row_length(ctxt: &mut CommandContext, row_length: gl::types::GLint) {
+    if ctxt.state.pixel_store_unpack_row_length != row_length {
+        ctxt.state.pixel_store_unpack_row_length = row_length;
+        ctxt.gl.PixelStorei(gl::UNPACK_ROW_LENGTH, row_length);
+    }
+}
+
+unsafe fn set_unpack_image_height(ctxt: &mut CommandContext, image_height: gl::types::GLint) {
+    if ctxt.state.pixel_store_unpack_image_height != image_height {
+        ctxt.state.pixel_store_unpack_image_height = image_height;
+        ctxt.gl.PixelStorei(gl::UNPACK_IMAGE_HEIGHT, image_height);
+    }
+}
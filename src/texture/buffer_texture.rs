@@ -393,9 +393,7 @@ impl<T> BufferTexture<T> where [T]: BufferContent, T: TextureBufferContent + Cop
 
         // now the texture creation
         debug_assert_eq!(buffer.get_offset_bytes(), 0);
-        let id = if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-                    ctxt.extensions.gl_arb_direct_state_access
-        {
+        let id = if ctxt.capabilities.direct_state_access {
             unsafe {
                 let mut id = mem::uninitialized();
                 ctxt.gl.CreateTextures(gl::TEXTURE_BUFFER, 1, &mut id);
@@ -556,6 +554,16 @@ impl<'a> TextureExt for BufferTextureRef<'a> {
         unsafe { ctxt.gl.BindTexture(gl::TEXTURE_BUFFER, self.texture); }
         gl::TEXTURE_BUFFER
     }
+
+    #[inline]
+    fn get_sampled_levels(&self) -> (u32, u32) {
+        (0, 0)
+    }
+
+    #[inline]
+    fn set_sampled_levels(&self, _: &mut CommandContext, _: u32, _: u32) {
+        // buffer textures only have one level and don't have any level parameter
+    }
 }
 
 ///
@@ -163,9 +163,7 @@ pub fn get_format(ctxt: &mut CommandContext, texture: &TextureAny)
             let mut depth_sz = mem::uninitialized();
             let mut depth_ty = mem::uninitialized();
 
-            if ctxt.version >= &Version(Api::Gl, 4, 5) ||
-               ctxt.extensions.gl_arb_direct_state_access
-            {
+            if ctxt.capabilities.direct_state_access {
                 let id = texture.get_id();
                 ctxt.gl.GetTextureLevelParameteriv(id, 0, gl::TEXTURE_RED_SIZE, &mut red_sz);
                 ctxt.gl.GetTextureLevelParameteriv(id, 0, gl::TEXTURE_RED_TYPE, &mut red_ty);
@@ -0,0 +1,167 @@
+use std::error::Error;
+use std::fmt;
+
+use buffer::BufferCreationError;
+use index;
+use texture::Texture2d;
+use uniforms::{Uniforms, UniformValue};
+
+use version::Api;
+use version::Version;
+
+use ContextExt;
+use DrawError;
+use Program;
+use Surface;
+use TextureExt;
+
+/// Fills the mipmaps of a texture by rendering each level from the previous one.
+///
+/// For each level from `1` to the last one, the level is attached to a framebuffer and a
+/// triangle covering it is drawn with `program`. The vertices of the triangle have a
+/// `position` and a `tex_coords` attribute, see `index::fullscreen_triangle`. The texture is
+/// passed to the program as the uniform named `source`, in addition to `uniforms`, and only
+/// the previous level can be sampled from it. This is done by changing `GL_TEXTURE_BASE_LEVEL`
+/// and `GL_TEXTURE_MAX_LEVEL`, so the level being sampled is the level `0` from the point of
+/// view of the shader, for example when calling `textureSize(source, 0)`.
+///
+/// This is how depth pyramids for hierarchical Z culling or the average luminance of an image
+/// are usually computed. The writes of each level are visible when drawing the next level
+/// without any barrier.
+///
+/// All the levels can be sampled again once this function returns, even in case of an error.
+///
+/// ## Example
+///
+/// ```no_run
+/// # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+/// # let texture: glium::texture::Texture2d = unsafe { ::std::mem::uninitialized() };
+/// let program = glium::Program::from_source(&display, "
+///     #version 110
+///
+///     attribute vec2 position;
+///     attribute vec2 tex_coords;
+///     varying vec2 v_tex_coords;
+///
+///     void main() {
+///         v_tex_coords = tex_coords;
+///         gl_Position = vec4(position, 0.0, 1.0);
+///     }
+/// ", "
+///     #version 110
+///
+///     uniform sampler2D source;
+///     varying vec2 v_tex_coords;
+///
+///     void main() {
+///         gl_FragColor = texture2D(source, v_tex_coords);
+///     }
+/// ", None).unwrap();
+///
+/// glium::texture::build_mip_chain(&texture, &program, "source",
+///                                 &glium::uniforms::EmptyUniforms).unwrap();
+/// ```
+pub fn build_mip_chain<U>(texture: &Texture2d, program: &Program, source: &str, uniforms: &U)
+                          -> Result<(), MipChainError> where U: Uniforms
+{
+    let context = texture.get_context();
+
+    {
+        let ctxt = context.make_current();
+        if !(ctxt.version >= &Version(Api::Gl, 1, 2)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 3, 0))
+        {
+            return Err(MipChainError::NotSupported);
+        }
+    }
+
+    let (vertices, indices) = try!(index::fullscreen_triangle(context));
+    let levels = texture.get_mipmap_levels();
+
+    let mut result = Ok(());
+
+    for level in 1 .. levels {
+        texture.set_sampled_levels(&mut context.make_current(), level - 1, level - 1);
+
+        let uniforms = MipChainUniforms {
+            source: source,
+            texture: texture,
+            uniforms: uniforms,
+        };
+
+        let mut target = texture.mipmap(level).unwrap().as_surface();
+        result = target.draw(&*vertices, &indices, program, &uniforms, &Default::default());
+
+        if result.is_err() {
+            break;
+        }
+    }
+
+    texture.set_sampled_levels(&mut context.make_current(), 0, levels - 1);
+    result.map_err(|err| MipChainError::DrawError(err))
+}
+
+/// Error that can happen when building the mipmaps of a texture with `build_mip_chain`.
+#[derive(Clone, Debug)]
+pub enum MipChainError {
+    /// Restricting the levels that can be sampled is not supported by the backend.
+    NotSupported,
+
+    /// Error while creating the vertices of the triangle.
+    BufferCreationError(BufferCreationError),
+
+    /// Error while drawing one of the levels.
+    DrawError(DrawError),
+}
+
+impl From<BufferCreationError> for MipChainError {
+    #[inline]
+    fn from(err: BufferCreationError) -> MipChainError {
+        MipChainError::BufferCreationError(err)
+    }
+}
+
+impl fmt::Display for MipChainError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            MipChainError::NotSupported => write!(fmt, "{}", self.description()),
+            MipChainError::BufferCreationError(ref err) => write!(fmt, "{}", err),
+            MipChainError::DrawError(ref err) => write!(fmt, "{}", err),
+        }
+    }
+}
+
+impl Error for MipChainError {
+    fn description(&self) -> &str {
+        match *self {
+            MipChainError::NotSupported =>
+                "Restricting the levels that can be sampled is not supported by the backend",
+            MipChainError::BufferCreationError(ref err) => err.description(),
+            MipChainError::DrawError(ref err) => err.description(),
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            MipChainError::NotSupported => None,
+            MipChainError::BufferCreationError(ref err) => Some(err),
+            MipChainError::DrawError(ref err) => Some(err),
+        }
+    }
+}
+
+/// The uniforms passed to the program, with the texture as an additional uniform.
+struct MipChainUniforms<'a, U: 'a> {
+    source: &'a str,
+    texture: &'a Texture2d,
+    uniforms: &'a U,
+}
+
+impl<'u, U> Uniforms for MipChainUniforms<'u, U> where U: Uniforms {
+    #[inline]
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+        output(self.source, UniformValue::Texture2d(self.texture, None));
+        self.uniforms.visit_values(output);
+    }
+}
@@ -47,6 +47,12 @@ textures array) is that texture arrays can only be accessed by individual layers
 only access layer 0, or layer 1, or layer 2, and so on. Whereas if you use 3D textures you can
 access layer `0.5` for example.
 
+OpenGL ES doesn't support one-dimensional textures. On OpenGL ES, a `Texture1d` (and the other
+non-array one-dimensional texture types) is emulated with a two-dimensional texture whose height
+is `1`. Your shaders must then use a `sampler2D` instead of a `sampler1D`, and sample with a
+texture coordinate of the form `vec2(x, 0.5)`. One-dimensional texture arrays are not emulated,
+and creating them returns `TypeNotSupported` if `is_texture_1d_array_supported` returns false.
+
 All textures except depth, stencil and depth-stencil textures have **mipmaps**. A mipmap is a
 smaller version of the texture whose purpose is to be used during rendering when the texture will
 be small on the screen.
@@ -116,6 +122,11 @@ use std::borrow::Cow;
 use std::fmt;
 use std::error::Error;
 
+#[cfg(feature = "image")]
+use image;
+
+use gl;
+use ToGlEnum;
 use image_format::FormatNotSupportedError;
 
 pub use image_format::{ClientFormat, TextureFormat};
@@ -123,9 +134,10 @@ pub use image_format::{UncompressedFloatFormat, UncompressedIntFormat, Uncompres
 pub use image_format::{CompressedFormat, DepthFormat, DepthStencilFormat, StencilFormat};
 pub use image_format::{CompressedSrgbFormat, SrgbFormat};
 pub use self::any::{TextureAny, TextureAnyMipmap, TextureAnyLayer, TextureAnyLayerMipmap};
-pub use self::any::{TextureAnyImage, Dimensions};
+pub use self::any::{TextureAnyImage, Dimensions, ExportedTexture};
 pub use self::bindless::{ResidentTexture, TextureHandle, BindlessTexturesNotSupportedError};
 pub use self::get_format::{InternalFormat, InternalFormatType, GetFormatError};
+pub use self::mip_chain::{build_mip_chain, MipChainError};
 pub use self::pixel::PixelValue;
 pub use self::ty_support::{is_texture_1d_supported, is_texture_2d_supported};
 pub use self::ty_support::{is_texture_3d_supported, is_texture_1d_array_supported};
@@ -139,6 +151,7 @@ pub mod pixel_buffer;
 
 mod any;
 mod get_format;
+mod mip_chain;
 mod pixel;
 mod ty_support;
 
@@ -174,6 +187,38 @@ impl CubeLayer {
     }
 }
 
+/// Source of a component when sampling a texture whose swizzle mask has been changed with
+/// `set_swizzle`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SwizzleComponent {
+    /// The red component of the texture.
+    Red,
+    /// The green component of the texture.
+    Green,
+    /// The blue component of the texture.
+    Blue,
+    /// The alpha component of the texture.
+    Alpha,
+    /// Always `0`.
+    Zero,
+    /// Always `1`.
+    One,
+}
+
+impl ToGlEnum for SwizzleComponent {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            SwizzleComponent::Red => gl::RED,
+            SwizzleComponent::Green => gl::GREEN,
+            SwizzleComponent::Blue => gl::BLUE,
+            SwizzleComponent::Alpha => gl::ALPHA,
+            SwizzleComponent::Zero => gl::ZERO,
+            SwizzleComponent::One => gl::ONE,
+        }
+    }
+}
+
 /// Represents a kind of texture.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[allow(missing_docs)]      // TODO:
@@ -476,6 +521,70 @@ impl<'a, T: Clone + 'a> RawImage2d<'a, T> {
     }
 }
 
+#[cfg(feature = "image")]
+impl<'a> RawImage2d<'a, u8> {
+    /// Builds a raw image from an image of the `image` crate, flipping it vertically.
+    ///
+    /// RGB images keep their three components, while other images are converted to RGBA.
+    pub fn from_image(image: &image::DynamicImage) -> RawImage2d<'a, u8> {
+        use image::GenericImage;
+
+        let dimensions = image.dimensions();
+
+        match *image {
+            image::DynamicImage::ImageRgb8(_) | image::DynamicImage::ImageLuma8(_) => {
+                RawImage2d::from_raw_rgb_reversed(image.to_rgb().into_raw(), dimensions)
+            },
+            _ => {
+                RawImage2d::from_raw_rgba_reversed(image.to_rgba().into_raw(), dimensions)
+            },
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl Texture2d {
+    /// Builds a new texture from an image of the `image` crate.
+    ///
+    /// The image is flipped so that its first row is at the top of the texture, and the texture
+    /// is created with an 8-bit RGB or RGBA format depending on the image. Mipmaps are
+    /// automatically generated.
+    ///
+    /// The content of the image is considered linear. Use `SrgbTexture2d::from_image` for images
+    /// that are encoded in sRGB, which is the case of most PNG and JPEG files.
+    pub fn from_image<F>(facade: &F, image: &image::DynamicImage)
+                         -> Result<Texture2d, TextureCreationError>
+                         where F: ::backend::Facade
+    {
+        let image = RawImage2d::from_image(image);
+        let format = match image.format {
+            ClientFormat::U8U8U8 => UncompressedFloatFormat::U8U8U8,
+            _ => UncompressedFloatFormat::U8U8U8U8,
+        };
+
+        Texture2d::with_format(facade, image, format, MipmapsOption::AutoGeneratedMipmaps)
+    }
+}
+
+#[cfg(feature = "image")]
+impl SrgbTexture2d {
+    /// Builds a new texture from an image of the `image` crate whose content is encoded in sRGB.
+    ///
+    /// See `Texture2d::from_image`.
+    pub fn from_image<F>(facade: &F, image: &image::DynamicImage)
+                         -> Result<SrgbTexture2d, TextureCreationError>
+                         where F: ::backend::Facade
+    {
+        let image = RawImage2d::from_image(image);
+        let format = match image.format {
+            ClientFormat::U8U8U8 => SrgbFormat::U8U8U8,
+            _ => SrgbFormat::U8U8U8U8,
+        };
+
+        SrgbTexture2d::with_format(facade, image, format, MipmapsOption::AutoGeneratedMipmaps)
+    }
+}
+
 impl<'a, P: PixelValue + Clone> Texture2dDataSource<'a> for Vec<Vec<P>> {
     type Data = P;
 
@@ -640,6 +749,9 @@ pub enum TextureCreationError {
 
     /// The texture format is not supported by the backend.
     TypeNotSupported,
+
+    /// The requested number of samples is not supported for this format.
+    SamplesCountNotSupported,
 }
 
 impl fmt::Display for TextureCreationError {
@@ -658,6 +770,84 @@ impl Error for TextureCreationError {
                 "The requested texture dimensions are not supported",
             TypeNotSupported =>
                 "The texture format is not supported by the backend",
+            SamplesCountNotSupported =>
+                "The requested number of samples is not supported for this format",
+        }
+    }
+}
+
+/// Error that can happen when copying the content of a framebuffer to a texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFromFramebufferError {
+    /// The framebuffer doesn't have any color attachment.
+    NoColorAttachment,
+
+    /// The format of the texture is not compatible with the color buffer of the framebuffer.
+    ///
+    /// Only textures with floating-point or normalized formats can be copied to.
+    IncompatibleFormat,
+
+    /// Copying is only supported for two-dimensional textures.
+    TextureTypeNotSupported,
+
+    /// The framebuffer is multisampled and can't be copied from directly. Blit it to a
+    /// framebuffer that isn't multisampled first.
+    MultisampledSource,
+}
+
+impl fmt::Display for CopyFromFramebufferError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.description())
+    }
+}
+
+impl Error for CopyFromFramebufferError {
+    fn description(&self) -> &str {
+        use self::CopyFromFramebufferError::*;
+        match *self {
+            NoColorAttachment =>
+                "The framebuffer doesn't have any color attachment",
+            IncompatibleFormat =>
+                "The format of the texture is not compatible with the color buffer of the \
+                 framebuffer",
+            TextureTypeNotSupported =>
+                "Copying is only supported for two-dimensional textures",
+            MultisampledSource =>
+                "The framebuffer is multisampled and can't be copied from directly",
+        }
+    }
+}
+
+/// Error that can happen when filling a texture with a single value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClearError {
+    /// The type of the value doesn't match the kind of texture. For example a floating-point
+    /// value was used with an integral texture, or a value with more than one component was
+    /// used with a depth or stencil texture.
+    FormatMismatch,
+
+    /// The backend doesn't support clearing this type of texture.
+    ///
+    /// Without `glClearTexImage`, only one-dimensional arrays, two-dimensional textures and
+    /// arrays and three-dimensional textures can be cleared. Depth-stencil textures can't be
+    /// cleared at all.
+    NotSupported,
+}
+
+impl fmt::Display for ClearError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.description())
+    }
+}
+
+impl Error for ClearError {
+    fn description(&self) -> &str {
+        use self::ClearError::*;
+        match *self {
+            FormatMismatch =>
+                "The type of the value doesn't match the kind of texture",
+            NotSupported =>
+                "The backend doesn't support clearing this type of texture",
         }
     }
 }
@@ -5,6 +5,9 @@ use version::Api;
 use version::Version;
 
 /// Returns true is one-dimensional textures are supported.
+///
+/// If this returns false, one-dimensional textures are emulated with two-dimensional textures
+/// whose height is `1`.
 #[inline]
 pub fn is_texture_1d_supported<C>(context: &C) -> bool where C: CapabilitiesSource {
     context.get_version() >= &Version(Api::Gl, 1, 1)
@@ -29,6 +32,8 @@ pub fn is_texture_3d_supported<C>(context: &C) -> bool where C: CapabilitiesSour
 }
 
 /// Returns true is one-dimensional texture arrays are supported.
+///
+/// Contrary to one-dimensional textures, one-dimensional texture arrays are not emulated.
 #[inline]
 pub fn is_texture_1d_array_supported<C>(context: &C) -> bool where C: CapabilitiesSource {
     context.get_version() >= &Version(Api::Gl, 3, 0) ||
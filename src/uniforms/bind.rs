@@ -12,13 +12,17 @@ use DrawError;
 use ProgramExt;
 use UniformsExt;
 use RawUniformValue;
+use RawUniformArrayValue;
 use TextureExt;
 
 use uniforms::Uniforms;
 use uniforms::UniformValue;
 use uniforms::SamplerBehavior;
 
+use texture::TextureAny;
+
 use context::CommandContext;
+use fbo::ValidatedAttachments;
 use buffer::Inserter;
 use ContextExt;
 
@@ -50,9 +54,25 @@ impl<U> UniformsExt for U where U: Uniforms {
             if visiting_result.is_err() { return; }
 
             if let Some(uniform) = program.get_uniform(name) {
-                assert!(uniform.size.is_none(), "Uniform arrays not supported yet");
+                // arrays of values can only be binded to arrays of uniforms that are large enough
+                let array_len = match value {
+                    UniformValue::FloatArray(v) => Some(v.len()),
+                    UniformValue::Mat2Array(v) => Some(v.len()),
+                    UniformValue::Mat3Array(v) => Some(v.len()),
+                    UniformValue::Mat4Array(v) => Some(v.len()),
+                    UniformValue::Vec2Array(v) => Some(v.len()),
+                    UniformValue::Vec3Array(v) => Some(v.len()),
+                    UniformValue::Vec4Array(v) => Some(v.len()),
+                    _ => None,
+                };
 
-                if !value.is_usable_with(&uniform.ty) {
+                let is_array_mismatch = match (array_len, uniform.size) {
+                    (Some(len), None) => len != 1,
+                    (Some(len), Some(size)) => len > size,
+                    _ => false,
+                };
+
+                if is_array_mismatch || !value.is_usable_with(&uniform.ty) {
                     visiting_result = Err(DrawError::UniformTypeMismatch {
                         name: name.to_owned(),
                         expected: uniform.ty,
@@ -121,6 +141,94 @@ impl<U> UniformsExt for U where U: Uniforms {
 
         visiting_result
     }
+
+    fn find_feedback_loop<P>(&self, program: &P, framebuffer: &ValidatedAttachments)
+                             -> Option<String> where P: ProgramExt
+    {
+        let mut result = None;
+
+        self.visit_values(|name, value| {
+            if result.is_some() || program.get_uniform(name).is_none() {
+                return;
+            }
+
+            if let Some(texture) = get_texture(&value) {
+                if framebuffer.has_texture_attachment(texture.get_texture_id(),
+                                                      texture.get_sampled_levels())
+                {
+                    result = Some(name.to_owned());
+                }
+            }
+        });
+
+        result
+    }
+}
+
+/// Returns the texture of a uniform value, or `None` if it isn't a texture.
+fn get_texture<'a>(value: &UniformValue<'a>) -> Option<&'a TextureAny> {
+    match *value {
+        UniformValue::Texture1d(texture, _) => Some(&**texture),
+        UniformValue::CompressedTexture1d(texture, _) => Some(&**texture),
+        UniformValue::SrgbTexture1d(texture, _) => Some(&**texture),
+        UniformValue::CompressedSrgbTexture1d(texture, _) => Some(&**texture),
+        UniformValue::IntegralTexture1d(texture, _) => Some(&**texture),
+        UniformValue::UnsignedTexture1d(texture, _) => Some(&**texture),
+        UniformValue::DepthTexture1d(texture, _) => Some(&**texture),
+        UniformValue::Texture2d(texture, _) => Some(&**texture),
+        UniformValue::CompressedTexture2d(texture, _) => Some(&**texture),
+        UniformValue::SrgbTexture2d(texture, _) => Some(&**texture),
+        UniformValue::CompressedSrgbTexture2d(texture, _) => Some(&**texture),
+        UniformValue::IntegralTexture2d(texture, _) => Some(&**texture),
+        UniformValue::UnsignedTexture2d(texture, _) => Some(&**texture),
+        UniformValue::DepthTexture2d(texture, _) => Some(&**texture),
+        UniformValue::Texture2dMultisample(texture, _) => Some(&**texture),
+        UniformValue::SrgbTexture2dMultisample(texture, _) => Some(&**texture),
+        UniformValue::IntegralTexture2dMultisample(texture, _) => Some(&**texture),
+        UniformValue::UnsignedTexture2dMultisample(texture, _) => Some(&**texture),
+        UniformValue::DepthTexture2dMultisample(texture, _) => Some(&**texture),
+        UniformValue::Texture3d(texture, _) => Some(&**texture),
+        UniformValue::CompressedTexture3d(texture, _) => Some(&**texture),
+        UniformValue::SrgbTexture3d(texture, _) => Some(&**texture),
+        UniformValue::CompressedSrgbTexture3d(texture, _) => Some(&**texture),
+        UniformValue::IntegralTexture3d(texture, _) => Some(&**texture),
+        UniformValue::UnsignedTexture3d(texture, _) => Some(&**texture),
+        UniformValue::DepthTexture3d(texture, _) => Some(&**texture),
+        UniformValue::Texture1dArray(texture, _) => Some(&**texture),
+        UniformValue::CompressedTexture1dArray(texture, _) => Some(&**texture),
+        UniformValue::SrgbTexture1dArray(texture, _) => Some(&**texture),
+        UniformValue::CompressedSrgbTexture1dArray(texture, _) => Some(&**texture),
+        UniformValue::IntegralTexture1dArray(texture, _) => Some(&**texture),
+        UniformValue::UnsignedTexture1dArray(texture, _) => Some(&**texture),
+        UniformValue::DepthTexture1dArray(texture, _) => Some(&**texture),
+        UniformValue::Texture2dArray(texture, _) => Some(&**texture),
+        UniformValue::CompressedTexture2dArray(texture, _) => Some(&**texture),
+        UniformValue::SrgbTexture2dArray(texture, _) => Some(&**texture),
+        UniformValue::CompressedSrgbTexture2dArray(texture, _) => Some(&**texture),
+        UniformValue::IntegralTexture2dArray(texture, _) => Some(&**texture),
+        UniformValue::UnsignedTexture2dArray(texture, _) => Some(&**texture),
+        UniformValue::DepthTexture2dArray(texture, _) => Some(&**texture),
+        UniformValue::Texture2dMultisampleArray(texture, _) => Some(&**texture),
+        UniformValue::SrgbTexture2dMultisampleArray(texture, _) => Some(&**texture),
+        UniformValue::IntegralTexture2dMultisampleArray(texture, _) => Some(&**texture),
+        UniformValue::UnsignedTexture2dMultisampleArray(texture, _) => Some(&**texture),
+        UniformValue::DepthTexture2dMultisampleArray(texture, _) => Some(&**texture),
+        UniformValue::Cubemap(texture, _) => Some(&**texture),
+        UniformValue::CompressedCubemap(texture, _) => Some(&**texture),
+        UniformValue::SrgbCubemap(texture, _) => Some(&**texture),
+        UniformValue::CompressedSrgbCubemap(texture, _) => Some(&**texture),
+        UniformValue::IntegralCubemap(texture, _) => Some(&**texture),
+        UniformValue::UnsignedCubemap(texture, _) => Some(&**texture),
+        UniformValue::DepthCubemap(texture, _) => Some(&**texture),
+        UniformValue::CubemapArray(texture, _) => Some(&**texture),
+        UniformValue::CompressedCubemapArray(texture, _) => Some(&**texture),
+        UniformValue::SrgbCubemapArray(texture, _) => Some(&**texture),
+        UniformValue::CompressedSrgbCubemapArray(texture, _) => Some(&**texture),
+        UniformValue::IntegralCubemapArray(texture, _) => Some(&**texture),
+        UniformValue::UnsignedCubemapArray(texture, _) => Some(&**texture),
+        UniformValue::DepthCubemapArray(texture, _) => Some(&**texture),
+        _ => None,
+    }
 }
 
 fn bind_subroutine_uniforms<P>(ctxt: &mut context::CommandContext, program: &P,
@@ -182,10 +290,23 @@ fn bind_uniform_block<'a, P>(ctxt: &mut context::CommandContext, value: &Uniform
                 }
             }
 
-            let bind_point = buffer_bind_points.get_unused().expect("Not enough buffer units");
+            let alignment = ctxt.capabilities.uniform_buffer_offset_alignment as usize;
+            if buffer.get_offset_bytes() % alignment != 0 {
+                return Err(DrawError::UniformBufferOffsetNotAligned { name: name.to_owned() });
+            }
+
+            // using the binding point of the block if possible, in order to avoid calling
+            // `glUniformBlockBinding`
+            let bind_point = if block.initial_binding >= 0 &&
+                                block.initial_binding < ctxt.capabilities.max_indexed_uniform_buffer &&
+                                !buffer_bind_points.is_used(block.initial_binding as u16)
+            {
+                block.initial_binding as u16
+            } else {
+                buffer_bind_points.get_unused().expect("Not enough buffer units")
+            };
             buffer_bind_points.set_used(bind_point);
 
-            assert!(buffer.get_offset_bytes() == 0);     // TODO: not implemented
             let fence = buffer.add_fence();
             let block_id = block.id as gl::types::GLuint;
 
@@ -394,6 +515,34 @@ fn bind_uniform<P>(ctxt: &mut context::CommandContext,
             program.set_uniform(ctxt, location, &RawUniformValue::UnsignedInt64Vec4(val));
             Ok(())
         },
+        UniformValue::FloatArray(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArrayValue::Float(val));
+            Ok(())
+        },
+        UniformValue::Mat2Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArrayValue::Mat2(val));
+            Ok(())
+        },
+        UniformValue::Mat3Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArrayValue::Mat3(val));
+            Ok(())
+        },
+        UniformValue::Mat4Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArrayValue::Mat4(val));
+            Ok(())
+        },
+        UniformValue::Vec2Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArrayValue::Vec2(val));
+            Ok(())
+        },
+        UniformValue::Vec3Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArrayValue::Vec3(val));
+            Ok(())
+        },
+        UniformValue::Vec4Array(val) => {
+            program.set_uniform_array(ctxt, location, &RawUniformArrayValue::Vec4(val));
+            Ok(())
+        },
         UniformValue::Texture1d(texture, sampler) => {
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
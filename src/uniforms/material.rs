@@ -0,0 +1,113 @@
+use uniforms::{Uniforms, UniformValue, DynamicUniforms};
+
+use Program;
+
+/// A program associated with the values of some of its uniforms.
+///
+/// Several materials can share the same program and differ by the values of their uniforms.
+/// Drawing with a material passes both its program and its uniforms. Each program remembers
+/// the values of its uniforms, so switching between two materials that share a program only
+/// sends to OpenGL the uniforms whose values differ.
+///
+/// Values that change with each draw, like a model matrix, can be passed with `with` instead
+/// of being stored in the material.
+///
+/// ## Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate glium;
+/// # fn main() {
+/// # use glium::Surface;
+/// # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+/// # let program: glium::Program = unsafe { ::std::mem::uninitialized() };
+/// # let vertex_buffer: glium::VertexBuffer<u8> = unsafe { ::std::mem::uninitialized() };
+/// # let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+/// use glium::uniforms::{MaterialInstance, UniformValue};
+///
+/// let mut red = MaterialInstance::new(&program);
+/// red.insert("color", UniformValue::Vec3([1.0, 0.0, 0.0]));
+/// red.insert("shininess", UniformValue::Float(8.0));
+///
+/// let mut target = display.draw();
+/// let matrix = [[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0],
+///               [0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 0.0, 1.0f32]];
+/// target.draw(&vertex_buffer, &indices, red.get_program(), &red.with(uniform!{ matrix: matrix }),
+///             &Default::default()).unwrap();
+/// target.finish().unwrap();
+/// # }
+/// ```
+pub struct MaterialInstance<'a> {
+    program: &'a Program,
+    uniforms: DynamicUniforms<'a>,
+}
+
+impl<'a> MaterialInstance<'a> {
+    /// Builds a new material without any uniform value.
+    #[inline]
+    pub fn new(program: &'a Program) -> MaterialInstance<'a> {
+        MaterialInstance {
+            program: program,
+            uniforms: DynamicUniforms::new(),
+        }
+    }
+
+    /// Returns the program of this material.
+    #[inline]
+    pub fn get_program(&self) -> &'a Program {
+        self.program
+    }
+
+    /// Sets the value of a uniform, replacing the previous value with the same name if any.
+    #[inline]
+    pub fn insert(&mut self, name: &str, value: UniformValue<'a>) {
+        self.uniforms.insert(name, value);
+    }
+
+    /// Removes the uniform with the given name. Returns true if it was present.
+    #[inline]
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.uniforms.remove(name)
+    }
+
+    /// Returns the values of the uniforms of this material, combined with other values.
+    ///
+    /// The values of `overrides` take precedence over the values of the material with the
+    /// same name.
+    #[inline]
+    pub fn with<'b, U>(&'b self, overrides: U) -> MaterialUniforms<'a, 'b, U> where U: Uniforms {
+        MaterialUniforms {
+            material: self,
+            overrides: overrides,
+        }
+    }
+}
+
+impl<'u> Uniforms for MaterialInstance<'u> {
+    #[inline]
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, output: F) {
+        self.uniforms.visit_values(output);
+    }
+}
+
+/// The uniforms of a material combined with other values. Returned by `MaterialInstance::with`.
+pub struct MaterialUniforms<'a: 'b, 'b, U> where U: Uniforms {
+    material: &'b MaterialInstance<'a>,
+    overrides: U,
+}
+
+impl<'u, 'm, U> Uniforms for MaterialUniforms<'u, 'm, U> where U: Uniforms {
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+        let mut overridden = Vec::new();
+
+        self.overrides.visit_values(|name, value| {
+            overridden.push(name.to_owned());
+            output(name, value);
+        });
+
+        self.material.visit_values(|name, value| {
+            if !overridden.iter().any(|n| n == name) {
+                output(name, value);
+            }
+        });
+    }
+}
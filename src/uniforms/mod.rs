@@ -141,9 +141,11 @@ let program = glium::Program::from_source(&display,
 ```
 */
 pub use self::buffer::UniformBuffer;
+pub use self::material::{MaterialInstance, MaterialUniforms};
+pub use self::ring::{UniformRing, UniformRingSlot};
 pub use self::sampler::{SamplerWrapFunction, MagnifySamplerFilter, MinifySamplerFilter};
 pub use self::sampler::{Sampler, SamplerBehavior};
-pub use self::uniforms::{EmptyUniforms, UniformsStorage};
+pub use self::uniforms::{EmptyUniforms, UniformsStorage, DynamicUniforms};
 pub use self::value::{UniformValue, UniformType};
 
 use std::error::Error;
@@ -156,6 +158,8 @@ use program::BlockLayout;
 
 mod bind;
 mod buffer;
+mod material;
+mod ring;
 mod sampler;
 mod uniforms;
 mod value;
@@ -0,0 +1,169 @@
+use buffer::{Buffer, BufferSlice, BufferType, BufferMode, BufferCreationError};
+use uniforms::{AsUniformValue, UniformBlock, UniformValue, LayoutMismatchError};
+use program;
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::mem;
+use std::slice;
+
+use backend::Facade;
+use CapabilitiesSource;
+
+/// A persistent-mapped uniform buffer split into slots, each slot containing the data of one
+/// object.
+///
+/// The buffer is divided into several regions, one for each frame that can be in flight at the
+/// same time. Call `push` once per object to write its data in the next free slot of the current
+/// region, then pass the returned slot to `draw` like a `UniformBuffer`. Call `next_frame` once
+/// per frame to move to the next region.
+///
+/// The offset of each slot respects the `uniform_buffer_offset_alignment` of the backend.
+///
+/// ## Synchronization
+///
+/// Each slot is protected by a fence that is inserted when the slot is used in a draw command.
+/// If a slot is still in use by the GPU when it is written again, `push` blocks until the GPU
+/// is done. This should never happen if the number of frames passed at creation is larger than
+/// the number of frames that are in flight.
+///
+/// ## Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate glium;
+/// # fn main() {
+/// # use glium::Surface;
+/// # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+/// # let program: glium::Program = unsafe { ::std::mem::uninitialized() };
+/// # let vertex_buffer: glium::VertexBuffer<u8> = unsafe { ::std::mem::uninitialized() };
+/// # let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+/// #[derive(Copy, Clone)]
+/// struct Object {
+///     matrix: [[f32; 4]; 4],
+/// }
+///
+/// implement_uniform_block!(Object, matrix);
+///
+/// let ring = glium::uniforms::UniformRing::<Object>::new(&display, 1024, 3).unwrap();
+///
+/// let mut target = display.draw();
+/// for _ in 0 .. 1024 {
+///     let slot = ring.push(Object { matrix: [[0.0; 4]; 4] }).unwrap();
+///     target.draw(&vertex_buffer, &indices, &program, &uniform!{ Object: slot },
+///                 &Default::default()).unwrap();
+/// }
+/// target.finish().unwrap();
+/// ring.next_frame();
+/// # }
+/// ```
+pub struct UniformRing<T> where T: Copy {
+    buffer: Buffer<[u8]>,
+    // number of bytes between two slots
+    stride: usize,
+    slots_per_frame: usize,
+    frames: usize,
+    // current region of the buffer
+    current_frame: Cell<usize>,
+    // next free slot of the current region
+    next_slot: Cell<usize>,
+    marker: PhantomData<T>,
+}
+
+impl<T> UniformRing<T> where T: Copy {
+    /// Builds a new ring that can hold `slots_per_frame` objects for each of the `frames`
+    /// frames that can be in flight.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `slots_per_frame` or `frames` is 0.
+    ///
+    pub fn new<F>(facade: &F, slots_per_frame: usize, frames: usize)
+                  -> Result<UniformRing<T>, BufferCreationError>
+                  where F: Facade
+    {
+        assert!(slots_per_frame >= 1);
+        assert!(frames >= 1);
+
+        let alignment = facade.get_capabilities().uniform_buffer_offset_alignment as usize;
+        let alignment = if alignment == 0 { 1 } else { alignment };
+        let stride = (mem::size_of::<T>() + alignment - 1) / alignment * alignment;
+
+        let buffer = try!(Buffer::empty_unsized(facade, BufferType::UniformBuffer,
+                                                stride * slots_per_frame * frames,
+                                                BufferMode::Persistent));
+
+        Ok(UniformRing {
+            buffer: buffer,
+            stride: stride,
+            slots_per_frame: slots_per_frame,
+            frames: frames,
+            current_frame: Cell::new(0),
+            next_slot: Cell::new(0),
+            marker: PhantomData,
+        })
+    }
+
+    /// Writes the data of an object in the next free slot of the current frame and returns it.
+    ///
+    /// Returns `None` if all the slots of the current frame have been used.
+    pub fn push(&self, data: T) -> Option<UniformRingSlot<T>> {
+        let slot = self.next_slot.get();
+        if slot >= self.slots_per_frame {
+            return None;
+        }
+        self.next_slot.set(slot + 1);
+
+        let start = (self.current_frame.get() * self.slots_per_frame + slot) * self.stride;
+        let slice = self.buffer.slice(start .. start + mem::size_of::<T>()).unwrap();
+
+        let bytes = unsafe {
+            slice::from_raw_parts(&data as *const T as *const u8, mem::size_of::<T>())
+        };
+        slice.write(bytes);
+
+        Some(UniformRingSlot {
+            slice: slice,
+            marker: PhantomData,
+        })
+    }
+
+    /// Moves to the next region of the buffer. Should be called once per frame.
+    #[inline]
+    pub fn next_frame(&self) {
+        self.current_frame.set((self.current_frame.get() + 1) % self.frames);
+        self.next_slot.set(0);
+    }
+
+    /// Returns the number of objects that can be pushed during each frame.
+    #[inline]
+    pub fn get_slots_per_frame(&self) -> usize {
+        self.slots_per_frame
+    }
+
+    /// Returns the number of regions of the buffer.
+    #[inline]
+    pub fn get_frames(&self) -> usize {
+        self.frames
+    }
+}
+
+/// A slot of a `UniformRing` containing the data of one object.
+#[derive(Copy, Clone)]
+pub struct UniformRingSlot<'a, T: 'a> where T: Copy {
+    slice: BufferSlice<'a, [u8]>,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T> AsUniformValue for UniformRingSlot<'a, T> where T: UniformBlock + Copy {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue {
+        #[inline]
+        fn f<T>(block: &program::UniformBlock)
+                -> Result<(), LayoutMismatchError> where T: UniformBlock + Copy
+        {
+            T::matches(&block.layout, 0)
+        }
+
+        UniformValue::Block(self.slice.as_slice_any(), f::<T>)
+    }
+}
@@ -53,3 +53,78 @@ impl<'n, T, R> Uniforms for UniformsStorage<'n, T, R> where T: AsUniformValue, R
         self.rest.visit_values(output);
     }
 }
+
+/// Stores uniforms whose names and values are only known at runtime.
+///
+/// Contrary to `UniformsStorage` and the `uniform!` macro, the list of uniforms can be built
+/// dynamically, for example from a script or from a user interface. The types of the values
+/// are checked against the program when drawing, like with any other `Uniforms` object, and a
+/// `DrawError::UniformTypeMismatch` is returned in case of mismatch.
+///
+/// ## Example
+///
+/// ```
+/// use glium::uniforms::{DynamicUniforms, UniformValue};
+///
+/// let mut uniforms = DynamicUniforms::new();
+/// uniforms.insert("color", UniformValue::Vec3([1.0, 0.0, 0.0]));
+/// uniforms.insert("intensity", UniformValue::Float(0.5));
+/// ```
+pub struct DynamicUniforms<'a> {
+    values: Vec<(String, UniformValue<'a>)>,
+}
+
+impl<'a> DynamicUniforms<'a> {
+    /// Builds a new empty list of uniforms.
+    #[inline]
+    pub fn new() -> DynamicUniforms<'a> {
+        DynamicUniforms {
+            values: Vec::new(),
+        }
+    }
+
+    /// Sets the value of a uniform, replacing the previous value with the same name if any.
+    pub fn insert(&mut self, name: &str, value: UniformValue<'a>) {
+        if let Some(entry) = self.values.iter_mut().find(|&&mut (ref n, _)| n == name) {
+            entry.1 = value;
+            return;
+        }
+
+        self.values.push((name.to_owned(), value));
+    }
+
+    /// Removes the uniform with the given name. Returns true if it was present.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.values.len();
+        self.values.retain(|&(ref n, _)| n != name);
+        self.values.len() != len
+    }
+
+    /// Returns the number of uniforms.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if there is no uniform.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<'a> Default for DynamicUniforms<'a> {
+    #[inline]
+    fn default() -> DynamicUniforms<'a> {
+        DynamicUniforms::new()
+    }
+}
+
+impl<'u> Uniforms for DynamicUniforms<'u> {
+    #[inline]
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+        for &(ref name, value) in self.values.iter() {
+            output(name, value);
+        }
+    }
+}
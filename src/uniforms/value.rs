@@ -180,6 +180,16 @@ pub enum UniformValue<'a> {
     UnsignedInt64Vec2([u64; 2]),
     UnsignedInt64Vec3([u64; 3]),
     UnsignedInt64Vec4([u64; 4]),
+    FloatArray(&'a [f32]),
+    /// Array of 2x2 column-major matrices.
+    Mat2Array(&'a [[[f32; 2]; 2]]),
+    /// Array of 3x3 column-major matrices.
+    Mat3Array(&'a [[[f32; 3]; 3]]),
+    /// Array of 4x4 column-major matrices.
+    Mat4Array(&'a [[[f32; 4]; 4]]),
+    Vec2Array(&'a [[f32; 2]]),
+    Vec3Array(&'a [[f32; 3]]),
+    Vec4Array(&'a [[f32; 4]]),
     Texture1d(&'a texture::Texture1d, Option<SamplerBehavior>),
     CompressedTexture1d(&'a texture::CompressedTexture1d, Option<SamplerBehavior>),
     SrgbTexture1d(&'a texture::SrgbTexture1d, Option<SamplerBehavior>),
@@ -279,6 +289,13 @@ impl<'a> UniformValue<'a> {
             (&UniformValue::DoubleVec2(_), UniformType::DoubleVec2) => true,
             (&UniformValue::DoubleVec3(_), UniformType::DoubleVec3) => true,
             (&UniformValue::DoubleVec4(_), UniformType::DoubleVec4) => true,
+            (&UniformValue::FloatArray(_), UniformType::Float) => true,
+            (&UniformValue::Mat2Array(_), UniformType::FloatMat2) => true,
+            (&UniformValue::Mat3Array(_), UniformType::FloatMat3) => true,
+            (&UniformValue::Mat4Array(_), UniformType::FloatMat4) => true,
+            (&UniformValue::Vec2Array(_), UniformType::FloatVec2) => true,
+            (&UniformValue::Vec3Array(_), UniformType::FloatVec3) => true,
+            (&UniformValue::Vec4Array(_), UniformType::FloatVec4) => true,
             (&UniformValue::Texture1d(_, _), UniformType::Sampler1d) => true,
             (&UniformValue::CompressedTexture1d(_, _), UniformType::Sampler1d) => true,
             (&UniformValue::SrgbTexture1d(_, _), UniformType::Sampler1d) => true,
@@ -286,6 +303,14 @@ impl<'a> UniformValue<'a> {
             (&UniformValue::IntegralTexture1d(_, _), UniformType::ISampler1d) => true,
             (&UniformValue::UnsignedTexture1d(_, _), UniformType::USampler1d) => true,
             (&UniformValue::DepthTexture1d(_, _), UniformType::Sampler1d) => true,
+            // one-dimensional textures are emulated with two-dimensional textures on OpenGL ES
+            (&UniformValue::Texture1d(t, _), UniformType::Sampler2d) => t.get_height().is_some(),
+            (&UniformValue::CompressedTexture1d(t, _), UniformType::Sampler2d) => t.get_height().is_some(),
+            (&UniformValue::SrgbTexture1d(t, _), UniformType::Sampler2d) => t.get_height().is_some(),
+            (&UniformValue::CompressedSrgbTexture1d(t, _), UniformType::Sampler2d) => t.get_height().is_some(),
+            (&UniformValue::IntegralTexture1d(t, _), UniformType::ISampler2d) => t.get_height().is_some(),
+            (&UniformValue::UnsignedTexture1d(t, _), UniformType::USampler2d) => t.get_height().is_some(),
+            (&UniformValue::DepthTexture1d(t, _), UniformType::Sampler2d) => t.get_height().is_some(),
             (&UniformValue::Texture2d(_, _), UniformType::Sampler2d) => true,
             (&UniformValue::CompressedTexture2d(_, _), UniformType::Sampler2d) => true,
             (&UniformValue::SrgbTexture2d(_, _), UniformType::Sampler2d) => true,
@@ -693,6 +718,55 @@ impl AsUniformValue for [[f32; 4]; 4] {
 
 impl_uniform_block_basic!([[f32; 4]; 4], UniformType::FloatMat4);
 
+impl<'a> AsUniformValue for &'a [f32] {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::FloatArray(*self)
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[[f32; 2]; 2]] {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Mat2Array(*self)
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[[f32; 3]; 3]] {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Mat3Array(*self)
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[[f32; 4]; 4]] {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Mat4Array(*self)
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[f32; 2]] {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Vec2Array(*self)
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[f32; 3]] {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Vec3Array(*self)
+    }
+}
+
+impl<'a> AsUniformValue for &'a [[f32; 4]] {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Vec4Array(*self)
+    }
+}
+
 impl AsUniformValue for (f32, f32) {
     #[inline]
     fn as_uniform_value(&self) -> UniformValue {
@@ -54,13 +54,14 @@ impl Bitsfield {
         }
     }
 
+    /// Bits that are outside of the bitsfield are considered used, so that they are never picked.
     #[inline]
     pub fn is_used(&self, mut bit: u16) -> bool {
         let mut offset = 0;
 
         loop {
             if offset >= NUM_DWORDS {
-                unimplemented!();
+                return true;
             }
 
             if bit < 32 {
@@ -127,4 +128,11 @@ mod tests {
         assert!(bitsfield.is_used(37));
         assert!(!bitsfield.is_used(38));
     }
+
+    #[test]
+    fn is_used_out_of_range() {
+        let bitsfield = Bitsfield::new();
+        assert!(bitsfield.is_used(256));
+        assert!(bitsfield.is_used(1000));
+    }
 }
@@ -1,2 +1,3 @@
 pub mod bitsfield;
+pub mod layout;
 pub mod range;
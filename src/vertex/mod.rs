@@ -120,6 +120,12 @@ be the same, or a `DrawError::VerticesSourcesLengthMismatch` will be produced.
 In all situation, the length of all per-instance sources must match, or
 `DrawError::InstancesCountMismatch` will be retured.
 
+When you pass multiple sources, each attribute of the program is read from the source that
+contains an attribute with the same name. This means that you can for example put the positions
+of your vertices in one buffer and the other attributes in another, and only pass the first
+buffer when the program only uses the positions (for a depth pre-pass for example). Each
+attribute used by the program must be provided by exactly one source, otherwise drawing panics.
+
 # Transform feedback
 
 Transform feedback allows you to write in a buffer the list of primitives that are generated by
@@ -140,6 +146,7 @@ pub use self::buffer::VertexBufferSlice;
 pub use self::buffer::CreationError as BufferCreationError;
 pub use self::format::{AttributeType, VertexFormat};
 pub use self::transform_feedback::{is_transform_feedback_supported, TransformFeedbackSession};
+pub use self::transform_feedback::{TransformFeedbackOutput, TransformFeedbackSessionCreationError};
 
 use buffer::BufferAnySlice;
 use CapabilitiesSource;
@@ -183,6 +190,11 @@ impl<'a> IntoVerticesSource<'a> for VerticesSource<'a> {
 }
 
 /// Marker that can be passed instead of a buffer to indicate an empty list of buffers.
+///
+/// This allows attributeless rendering: the program must not have any vertex attribute and
+/// generates its vertices itself, usually from `gl_VertexID`. A vertex array object without
+/// any enabled attribute is bound, and `len` vertices are drawn.
+#[derive(Debug, Copy, Clone)]
 pub struct EmptyVertexAttributes {
     /// Number of phantom vertices.
     pub len: usize,
@@ -196,8 +208,12 @@ impl<'a> IntoVerticesSource<'a> for EmptyVertexAttributes {
 }
 
 /// Marker that can be passed instead of a buffer to indicate an empty list of buffers.
+///
+/// This allows instancing without any per-instance attribute, in which case the shader can use
+/// `gl_InstanceID` to distinguish between instances.
+#[derive(Debug, Copy, Clone)]
 pub struct EmptyInstanceAttributes {
-    /// Number of phantom vertices.
+    /// Number of phantom instances.
     pub len: usize,
 }
 
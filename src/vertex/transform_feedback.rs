@@ -1,5 +1,7 @@
 use std::{ mem, fmt };
+use std::cell::Cell;
 use std::error::Error;
+use std::vec;
 
 use version::Api;
 use version::Version;
@@ -9,12 +11,13 @@ use BufferExt;
 use GlObject;
 use ContextExt;
 use CapabilitiesSource;
+use ProgramExt;
 use TransformFeedbackSessionExt;
 use buffer::{Buffer, BufferAnySlice};
 use index::PrimitiveType;
 use program::OutputPrimitives;
 use program::Program;
-use vertex::Vertex;
+use vertex::{MultiVerticesSource, Vertex, VertexFormat, VerticesSource};
 
 use gl;
 
@@ -54,6 +57,18 @@ use gl;
 ///   to be pushed in the buffer after the existing data. However you must always use the same type
 ///   of primitives and the same program.
 ///
+/// - If the program writes to multiple buffers, for example in `Separate` mode, with
+///   `gl_NextBuffer`, or with a geometry shader that emits vertices to multiple vertex streams,
+///   use `TransformFeedbackSession::with_outputs` to pass one buffer for each of them.
+///
+/// - The vertices captured by the latest draw command can be drawn again without reading back
+///   their number with `Surface::draw_transform_feedback`. This requires OpenGL 4.0 or
+///   `GL_ARB_transform_feedback2`.
+///
+/// - Drawing without the session ends the capture, and the next draw command that uses the
+///   session starts writing again at the beginning of the buffers. To interleave draw commands
+///   without transform feedback, call `pause` before them and `resume` afterwards.
+///
 /// # Example
 ///
 /// ```no_run
@@ -92,8 +107,37 @@ use gl;
 /// ```
 #[derive(Debug)]
 pub struct TransformFeedbackSession<'a> {
-    buffer: BufferAnySlice<'a>,
+    outputs: Vec<TransformFeedbackOutput<'a>>,
     program: &'a Program,
+
+    // the transform feedback object, or 0 if they are not supported by the backend
+    id: gl::types::GLuint,
+
+    // type of the primitives captured by the latest draw command
+    captured_primitives: Cell<Option<PrimitiveType>>,
+}
+
+/// A buffer that receives the vertices written to one of the transform feedback buffers of a
+/// program.
+#[derive(Debug)]
+pub struct TransformFeedbackOutput<'a> {
+    buffer: BufferAnySlice<'a>,
+    format: VertexFormat,
+    stride: usize,
+}
+
+impl<'a> TransformFeedbackOutput<'a> {
+    /// Builds a new output that writes to the given buffer.
+    #[inline]
+    pub fn new<V>(buffer: &'a mut Buffer<[V]>) -> TransformFeedbackOutput<'a>
+                  where V: Vertex + Copy + Send + 'static
+    {
+        TransformFeedbackOutput {
+            buffer: buffer.as_slice_any(),
+            format: <V as Vertex>::build_bindings(),
+            stride: mem::size_of::<V>(),
+        }
+    }
 }
 
 /// Error that can happen when creating a `TransformFeedbackSession`.
@@ -104,6 +148,10 @@ pub enum TransformFeedbackSessionCreationError {
 
     /// The format of the output doesn't match what the program is expected to output.
     WrongVertexFormat,
+
+    /// The number of buffers doesn't match the number of transform feedback buffers of the
+    /// program.
+    WrongBuffersCount,
 }
 
 impl fmt::Display for TransformFeedbackSessionCreationError {
@@ -120,6 +168,9 @@ impl Error for TransformFeedbackSessionCreationError {
                 "Transform feedback is not supported by the OpenGL implementation",
             WrongVertexFormat =>
                 "The format of the output doesn't match what the program is expected to output",
+            WrongBuffersCount =>
+                "The number of buffers doesn't match the number of transform feedback buffers \
+                 of the program",
         }
     }
 }
@@ -135,40 +186,190 @@ pub fn is_transform_feedback_supported<F>(facade: &F) -> bool where F: Facade {
 }
 
 impl<'a> TransformFeedbackSession<'a> {
-    /// Builds a new transform feedback session.
-    ///
-    /// TODO: this constructor should ultimately support passing multiple buffers of different
-    ///       types
+    /// Builds a new transform feedback session that writes to a single buffer.
+    #[inline]
     pub fn new<F, V>(facade: &F, program: &'a Program, buffer: &'a mut Buffer<[V]>)
                      -> Result<TransformFeedbackSession<'a>, TransformFeedbackSessionCreationError>
                      where F: Facade, V: Vertex + Copy + Send + 'static
+    {
+        TransformFeedbackSession::with_outputs(facade, program,
+                                               vec![TransformFeedbackOutput::new(buffer)])
+    }
+
+    /// Builds a new transform feedback session that writes to multiple buffers.
+    ///
+    /// The outputs must be passed in the same order as the buffers returned by
+    /// `Program::get_transform_feedback_buffers`. The buffer of index `n` is bound to the
+    /// transform feedback binding point `n`.
+    pub fn with_outputs<F>(facade: &F, program: &'a Program,
+                           outputs: Vec<TransformFeedbackOutput<'a>>)
+                           -> Result<TransformFeedbackSession<'a>,
+                                     TransformFeedbackSessionCreationError>
+                           where F: Facade
     {
         if !is_transform_feedback_supported(facade) {
             return Err(TransformFeedbackSessionCreationError::NotSupported);
         }
 
-        if !program.transform_feedback_matches(&<V as Vertex>::build_bindings(),
-                                               mem::size_of::<V>())
-        {
-            return Err(TransformFeedbackSessionCreationError::WrongVertexFormat);
+        if outputs.is_empty() || program.get_transform_feedback_buffers().len() != outputs.len() {
+            return Err(TransformFeedbackSessionCreationError::WrongBuffersCount);
+        }
+
+        for (index, output) in outputs.iter().enumerate() {
+            if !program.transform_feedback_buffer_matches(index, &output.format, output.stride) {
+                return Err(TransformFeedbackSessionCreationError::WrongVertexFormat);
+            }
         }
 
+        let id = {
+            let ctxt = facade.get_context().make_current();
+
+            if ctxt.version >= &Version(Api::Gl, 4, 0) ||
+               ctxt.extensions.gl_arb_transform_feedback2
+            {
+                unsafe {
+                    let mut id = mem::uninitialized();
+                    ctxt.gl.GenTransformFeedbacks(1, &mut id);
+                    id
+                }
+            } else {
+                0
+            }
+        };
+
         Ok(TransformFeedbackSession {
-            buffer: buffer.as_slice_any(),
+            outputs: outputs,
             program: program,
+            id: id,
+            captured_primitives: Cell::new(None),
         })
     }
+
+    /// Returns the type of the primitives captured by the latest draw command that used this
+    /// session, or `None` if the session hasn't been used yet.
+    #[inline]
+    pub fn get_captured_primitives(&self) -> Option<PrimitiveType> {
+        self.captured_primitives.get()
+    }
+
+    /// Pauses the capture with `glPauseTransformFeedback`.
+    ///
+    /// While the session is paused, you can draw without transform feedback and with other
+    /// programs without ending the capture. The next draw command that uses the session, or a
+    /// call to `resume`, continues writing after the data that has already been written.
+    ///
+    /// Has no effect if the session isn't currently capturing. Drawing with another session
+    /// or with the vertices captured by this session ends the capture, and in this situation
+    /// resuming has no effect either.
+    ///
+    /// This requires OpenGL 4.0 or `GL_ARB_transform_feedback2`. Returns `Err` if it is not
+    /// supported.
+    pub fn pause(&self) -> Result<(), ()> {
+        let mut ctxt = self.outputs[0].buffer.get_context().make_current();
+
+        if self.id == 0 {
+            return Err(());
+        }
+
+        if ctxt.state.transform_feedback_object != self.id ||
+           ctxt.state.transform_feedback_enabled.is_none() ||
+           ctxt.state.transform_feedback_paused
+        {
+            return Ok(());
+        }
+
+        unsafe { ctxt.gl.PauseTransformFeedback(); }
+        ctxt.state.transform_feedback_paused = true;
+        Ok(())
+    }
+
+    /// Resumes the capture after a call to `pause` with `glResumeTransformFeedback`.
+    ///
+    /// OpenGL requires the program that was used when the capture started to be current when
+    /// resuming, therefore this function also binds the program of the session.
+    ///
+    /// Has no effect if the session isn't paused. Returns `Err` if pausing and resuming is not
+    /// supported.
+    pub fn resume(&self) -> Result<(), ()> {
+        let mut ctxt = self.outputs[0].buffer.get_context().make_current();
+
+        if self.id == 0 {
+            return Err(());
+        }
+
+        if ctxt.state.transform_feedback_object != self.id ||
+           ctxt.state.transform_feedback_enabled.is_none() ||
+           !ctxt.state.transform_feedback_paused
+        {
+            return Ok(());
+        }
+
+        self.program.use_program(&mut ctxt);
+        unsafe { ctxt.gl.ResumeTransformFeedback(); }
+        ctxt.state.transform_feedback_paused = false;
+        Ok(())
+    }
+}
+
+impl<'a> GlObject for TransformFeedbackSession<'a> {
+    type Id = gl::types::GLuint;
+
+    /// Returns the transform feedback object of this session, or `0` if transform feedback
+    /// objects are not supported by the backend.
+    #[inline]
+    fn get_id(&self) -> gl::types::GLuint {
+        self.id
+    }
+}
+
+impl<'a, 'b> MultiVerticesSource<'b> for &'b TransformFeedbackSession<'a> where 'a: 'b {
+    type Iterator = vec::IntoIter<VerticesSource<'b>>;
+
+    fn iter(self) -> vec::IntoIter<VerticesSource<'b>> {
+        self.outputs.iter().map(|output| {
+            VerticesSource::VertexBuffer(output.buffer, &output.format, false)
+        }).collect::<Vec<_>>().into_iter()
+    }
+}
+
+/// Binds a transform feedback object if it's not already bound.
+fn bind_transform_feedback_object(ctxt: &mut CommandContext, id: gl::types::GLuint) {
+    if ctxt.state.transform_feedback_object == id {
+        return;
+    }
+
+    unsafe { ctxt.gl.BindTransformFeedback(gl::TRANSFORM_FEEDBACK, id); }
+    ctxt.state.transform_feedback_object = id;
+
+    // the buffer bindings are part of the transform feedback object
+    for elem in ctxt.state.indexed_transform_feedback_buffer_bindings.iter_mut() {
+        *elem = Default::default();
+    }
 }
 
 impl<'a> TransformFeedbackSessionExt for TransformFeedbackSession<'a> {
     fn bind(&self, mut ctxt: &mut CommandContext, draw_primitives: PrimitiveType) {
         // TODO: check that the state matches what is required
         if ctxt.state.transform_feedback_enabled.is_some() {
-            unimplemented!();
+            // continuing the capture of this session, which may have been paused
+            if self.id != 0 && ctxt.state.transform_feedback_object == self.id {
+                if ctxt.state.transform_feedback_paused {
+                    unsafe { ctxt.gl.ResumeTransformFeedback(); }
+                    ctxt.state.transform_feedback_paused = false;
+                }
+
+                return;
+            }
+
+            Self::unbind(ctxt);
         }
 
+        bind_transform_feedback_object(ctxt, self.id);
+
         // FIXME: use the memory barrier system
-        self.buffer.bind_to_transform_feedback(ctxt, 0);
+        for (index, output) in self.outputs.iter().enumerate() {
+            output.buffer.bind_to_transform_feedback(ctxt, index as gl::types::GLuint);
+        }
 
         unsafe {
             let primitives = match (self.program.get_output_primitives(), draw_primitives) {
@@ -190,6 +391,12 @@ impl<'a> TransformFeedbackSessionExt for TransformFeedbackSession<'a> {
                 (None, PrimitiveType::Patches { .. }) => unreachable!(),
             };
 
+            self.captured_primitives.set(Some(match primitives {
+                gl::POINTS => PrimitiveType::Points,
+                gl::LINES => PrimitiveType::LinesList,
+                _ => PrimitiveType::TrianglesList,
+            }));
+
             ctxt.gl.BeginTransformFeedback(primitives);
             ctxt.state.transform_feedback_enabled = Some(primitives);
             ctxt.state.transform_feedback_paused = false;
@@ -234,7 +441,18 @@ impl<'a> Drop for TransformFeedbackSession<'a> {
         // Since the session can be mem::forget'ed, the code in buffer/alloc.rs ensures that the
         // buffer isn't used by transform feedback.
         // However we end the session now anyway.
-        let mut ctxt = self.buffer.get_context().make_current();
-        Self::ensure_buffer_out_of_transform_feedback(&mut ctxt, self.buffer.get_id());
+        let mut ctxt = self.outputs[0].buffer.get_context().make_current();
+        for output in self.outputs.iter() {
+            Self::ensure_buffer_out_of_transform_feedback(&mut ctxt, output.buffer.get_id());
+        }
+
+        if self.id != 0 {
+            if ctxt.state.transform_feedback_object == self.id {
+                Self::unbind(&mut ctxt);
+                bind_transform_feedback_object(&mut ctxt, 0);
+            }
+
+            unsafe { ctxt.gl.DeleteTransformFeedbacks(1, [self.id].as_ptr()); }
+        }
     }
 }
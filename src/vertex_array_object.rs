@@ -1,6 +1,7 @@
 use std::borrow::Borrow;
 use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::mem;
 
 use smallvec::SmallVec;
@@ -23,6 +24,11 @@ pub struct VertexAttributesSystem {
     // we maintain a list of VAOs for each vertexbuffer-indexbuffer-program association
     // the key is a (buffers-list-with-offset, program) ; the buffers list must be sorted
     vaos: RefCell<HashMap<(Vec<(gl::types::GLuint, usize)>, Handle), VertexArrayObject>>,
+
+    // if the backend supports separate vertex formats and bindings, we instead maintain a list
+    // of VAOs for each layout-program association, and only change the buffers binded to them
+    // the key is a (list-of-formats-and-divisors, program)
+    layout_vaos: RefCell<HashMap<(Vec<(VertexFormat, Option<u32>)>, Handle), VertexArrayObject>>,
 }
 
 /// Object allowing one to bind vertex attributes to the current context.
@@ -40,6 +46,7 @@ impl VertexAttributesSystem {
     pub fn new() -> VertexAttributesSystem {
         VertexAttributesSystem {
             vaos: RefCell::new(HashMap::new()),
+            layout_vaos: RefCell::new(HashMap::new()),
         }
     }
 
@@ -71,14 +78,26 @@ impl VertexAttributesSystem {
     pub fn purge_buffer(ctxt: &mut CommandContext, id: gl::types::GLuint) {
         VertexAttributesSystem::purge_if(ctxt, |&(ref buffers, _)| {
             buffers.iter().find(|&&(b, _)| b == id).is_some()
-        })
+        });
+
+        // the VAOs that don't depend on buffers may still have this buffer binded, and the ID
+        // may be reused by a future buffer
+        for (_, vao) in ctxt.vertex_array_objects.layout_vaos.borrow().iter() {
+            vao.forget_buffer(id);
+        }
     }
 
     /// This function *must* be called whenever you destroy a program so that the system can
     /// purge its VAOs cache.
     #[inline]
     pub fn purge_program(ctxt: &mut CommandContext, program: Handle) {
-        VertexAttributesSystem::purge_if(ctxt, |&(_, p)| p == program)
+        VertexAttributesSystem::purge_if(ctxt, |&(_, p)| p == program);
+
+        let mut vaos = ctxt.vertex_array_objects.layout_vaos.borrow_mut();
+        let keys: Vec<_> = vaos.keys().filter(|&&(_, p)| p == program).cloned().collect();
+        for key in keys {
+            vaos.remove(&key).unwrap().destroy(ctxt);
+        }
     }
 
     /// Purges the VAOs cache.
@@ -89,6 +108,13 @@ impl VertexAttributesSystem {
         for (_, vao) in vaos {
             vao.destroy(ctxt);
         }
+
+        let vaos = mem::replace(&mut *ctxt.vertex_array_objects.layout_vaos.borrow_mut(),
+                                HashMap::new());
+
+        for (_, vao) in vaos {
+            vao.destroy(ctxt);
+        }
     }
 
     /// Purges the VAOs cache. Contrary to `purge_all`, this function expects the system to be
@@ -100,13 +126,21 @@ impl VertexAttributesSystem {
         for (_, vao) in vaos {
             vao.destroy(ctxt);
         }
+
+        let vaos = mem::replace(&mut *ctxt.vertex_array_objects.layout_vaos.borrow_mut(),
+                                HashMap::with_capacity(0));
+
+        for (_, vao) in vaos {
+            vao.destroy(ctxt);
+        }
     }
 
     /// Tells the VAOs system that the currently binded element array buffer will change.
     pub fn hijack_current_element_array_buffer(ctxt: &mut CommandContext) {
         let vaos = ctxt.vertex_array_objects.vaos.borrow_mut();
+        let layout_vaos = ctxt.vertex_array_objects.layout_vaos.borrow_mut();
 
-        for (_, vao) in vaos.iter() {
+        for (_, vao) in vaos.iter().chain(layout_vaos.iter()) {
             if vao.id == ctxt.state.vertex_array {
                 vao.element_array_buffer_hijacked.set(true);
                 return;
@@ -185,6 +219,33 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
                 }
             }
 
+            // using separate vertex formats and bindings if possible, in which case a VAO can
+            // be reused with different buffers
+            if is_separate_format_supported(ctxt) &&
+               self.vertex_buffers.iter().all(|&(_, ref f, _, _, _)| is_relative_offset_valid(f))
+            {
+                let layout: Vec<_> = self.vertex_buffers.iter()
+                                                        .map(|&(_, ref f, _, _, d)| (f.clone(), d))
+                                                        .collect();
+                let buffers: SmallVec<[_; 2]> = self.vertex_buffers.iter()
+                                                    .map(|&(b, _, o, s, _)| (b, o, s))
+                                                    .collect();
+                let program_id = self.program.get_id();
+
+                let mut vaos = ctxt.vertex_array_objects.layout_vaos.borrow_mut();
+                let vao = match vaos.entry((layout, program_id)) {
+                    Entry::Occupied(e) => e.into_mut(),
+                    Entry::Vacant(e) => e.insert(unsafe {
+                        VertexArrayObject::with_separate_format(ctxt, &self.vertex_buffers,
+                                                                self.program)
+                    }),
+                };
+
+                vao.bind_with_buffers(ctxt, &buffers,
+                                      self.element_array_buffer.map(|b| b.get_id()).unwrap_or(0));
+                return base_vertex.map(|v| v as gl::types::GLint);
+            }
+
             let mut buffers_list: Vec<_> = self.vertex_buffers.iter()
                                                               .map(|&(v, _, o, s, _)| (v, o))
                                                               .collect();
@@ -244,8 +305,12 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
 struct VertexArrayObject {
     id: gl::types::GLuint,
     destroyed: bool,
-    element_array_buffer: gl::types::GLuint,
+    element_array_buffer: Cell<gl::types::GLuint>,
     element_array_buffer_hijacked: Cell<bool>,
+
+    // for VAOs that use separate vertex formats, the buffer, offset and stride currently binded
+    // to each binding index
+    binded_vertex_buffers: RefCell<SmallVec<[(gl::types::GLuint, usize, usize); 2]>>,
 }
 
 impl VertexArrayObject {
@@ -257,56 +322,10 @@ impl VertexArrayObject {
                   vertex_buffers: &[(gl::types::GLuint, VertexFormat, usize, usize, Option<u32>)],
                   index_buffer: Option<BufferAnySlice>, program: &Program) -> VertexArrayObject
     {
-        // checking the attributes types
-        for &(_, ref bindings, _, _, _) in vertex_buffers {
-            for &(ref name, _, ty) in bindings.iter() {
-                let attribute = match program.get_attribute(Borrow::<str>::borrow(name)) {
-                    Some(a) => a,
-                    None => continue
-                };
-
-                if ty.get_num_components() != attribute.ty.get_num_components() ||
-                    attribute.size != 1
-                {
-                    panic!("The program attribute `{}` does not match the vertex format. \
-                            Program expected {:?}, got {:?}.", name, attribute.ty, ty);
-                }
-            }
-        }
-
-        // checking for missing attributes
-        for (&ref name, _) in program.attributes() {
-            let mut found = false;
-            for &(_, ref bindings, _, _, _) in vertex_buffers {
-                if bindings.iter().find(|&&(ref n, _, _)| n == name).is_some() {
-                    found = true;
-                    break;
-                }
-            }
-            if !found {
-                panic!("The program attribute `{}` is missing in the vertex bindings", name);
-            }
-        };
-
-        // TODO: check for collisions between the vertices sources
+        check_attributes(vertex_buffers, program);
 
         // building the VAO
-        let id = {
-            let mut id = mem::uninitialized();
-            if ctxt.version >= &Version(Api::Gl, 3, 0) ||
-                ctxt.version >= &Version(Api::GlEs, 3, 0) ||
-                ctxt.extensions.gl_arb_vertex_array_object
-            {
-                ctxt.gl.GenVertexArrays(1, &mut id);
-            } else if ctxt.extensions.gl_oes_vertex_array_object {
-                ctxt.gl.GenVertexArraysOES(1, &mut id);
-            } else if ctxt.extensions.gl_apple_vertex_array_object {
-                ctxt.gl.GenVertexArraysAPPLE(1, &mut id);
-            } else {
-                unreachable!();
-            };
-            id
-        };
+        let id = gen_vao(ctxt);
 
         // we don't use DSA as we're going to make multiple calls for this VAO
         // and we're likely going to use the VAO right after it's been created
@@ -324,8 +343,90 @@ impl VertexArrayObject {
         VertexArrayObject {
             id: id,
             destroyed: false,
-            element_array_buffer: index_buffer.map(|b| b.get_id()).unwrap_or(0),
+            element_array_buffer: Cell::new(index_buffer.map(|b| b.get_id()).unwrap_or(0)),
             element_array_buffer_hijacked: Cell::new(false),
+            binded_vertex_buffers: RefCell::new(SmallVec::new()),
+        }
+    }
+
+    /// Builds a new `VertexArrayObject` that uses separate vertex formats and bindings.
+    ///
+    /// Only the layout of the vertex buffers is used. The buffers themselves must be binded
+    /// with `bind_with_buffers`.
+    unsafe fn with_separate_format(mut ctxt: &mut CommandContext,
+                                   vertex_buffers: &[(gl::types::GLuint, VertexFormat, usize,
+                                                      usize, Option<u32>)],
+                                   program: &Program) -> VertexArrayObject
+    {
+        check_attributes(vertex_buffers, program);
+
+        let id = gen_vao(ctxt);
+        bind_vao(&mut ctxt, id);
+
+        for (binding_index, &(_, ref bindings, _, _, divisor)) in vertex_buffers.iter().enumerate() {
+            set_attributes_format(ctxt, program, binding_index as gl::types::GLuint, bindings);
+            ctxt.gl.VertexBindingDivisor(binding_index as gl::types::GLuint,
+                                         divisor.unwrap_or(0));
+        }
+
+        VertexArrayObject {
+            id: id,
+            destroyed: false,
+            element_array_buffer: Cell::new(0),
+            element_array_buffer_hijacked: Cell::new(false),
+            binded_vertex_buffers: RefCell::new(SmallVec::new()),
+        }
+    }
+
+    /// Sets this VAO as the current VAO, and binds the given buffers to it.
+    ///
+    /// Must only be used with VAOs created with `with_separate_format`. `vertex_buffers`
+    /// contains the buffer, offset and stride for each binding index.
+    fn bind_with_buffers(&self, ctxt: &mut CommandContext,
+                         vertex_buffers: &[(gl::types::GLuint, usize, usize)],
+                         element_array_buffer: gl::types::GLuint)
+    {
+        bind_vao(ctxt, self.id);
+
+        if self.element_array_buffer_hijacked.get() ||
+           self.element_array_buffer.get() != element_array_buffer
+        {
+            unsafe { ctxt.gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, element_array_buffer) };
+            self.element_array_buffer.set(element_array_buffer);
+            self.element_array_buffer_hijacked.set(false);
+        }
+
+        let mut binded = self.binded_vertex_buffers.borrow_mut();
+        for (binding_index, &(buffer, offset, stride)) in vertex_buffers.iter().enumerate() {
+            if binded.get(binding_index) == Some(&(buffer, offset, stride)) {
+                continue;
+            }
+
+            unsafe {
+                ctxt.gl.BindVertexBuffer(binding_index as gl::types::GLuint, buffer,
+                                         offset as gl::types::GLintptr,
+                                         stride as gl::types::GLsizei);
+            }
+
+            if binding_index < binded.len() {
+                binded[binding_index] = (buffer, offset, stride);
+            } else {
+                binded.push((buffer, offset, stride));
+            }
+        }
+    }
+
+    /// Tells the VAO that a buffer is going to be destroyed, so that it is binded again even if
+    /// a new buffer reuses its ID.
+    fn forget_buffer(&self, id: gl::types::GLuint) {
+        for entry in self.binded_vertex_buffers.borrow_mut().iter_mut() {
+            if entry.0 == id {
+                *entry = (0, 0, 0);
+            }
+        }
+
+        if self.element_array_buffer.get() == id {
+            self.element_array_buffer_hijacked.set(true);
         }
     }
 
@@ -339,9 +440,10 @@ impl VertexArrayObject {
                 if ctxt.version >= &Version(Api::Gl, 1, 5) ||
                     ctxt.version >= &Version(Api::GlEs, 2, 0)
                 {
-                    ctxt.gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.element_array_buffer);
+                    ctxt.gl.BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.element_array_buffer.get());
                 } else if ctxt.extensions.gl_arb_vertex_buffer_object {
-                    ctxt.gl.BindBufferARB(gl::ELEMENT_ARRAY_BUFFER_ARB, self.element_array_buffer);
+                    ctxt.gl.BindBufferARB(gl::ELEMENT_ARRAY_BUFFER_ARB,
+                                          self.element_array_buffer.get());
                 } else {
                     unreachable!();
                 }
@@ -475,6 +577,83 @@ fn vertex_binding_type_to_gl(ty: AttributeType) -> (gl::types::GLenum, gl::types
     }
 }
 
+/// Returns true if the backend supports `glVertexAttribFormat` and `glBindVertexBuffer`.
+#[inline]
+fn is_separate_format_supported(ctxt: &CommandContext) -> bool {
+    ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+    ctxt.extensions.gl_arb_vertex_attrib_binding
+}
+
+/// Returns true if all the offsets of a vertex format can be passed to `glVertexAttribFormat`.
+///
+/// `GL_MAX_VERTEX_ATTRIB_RELATIVE_OFFSET` is guaranteed to be at least 2047.
+#[inline]
+fn is_relative_offset_valid(bindings: &VertexFormat) -> bool {
+    bindings.iter().all(|&(_, offset, ty)| {
+        let (data_type, elements_count, instances_count) = vertex_binding_type_to_gl(ty);
+        let component_size = match data_type {
+            gl::DOUBLE | gl::INT64_NV | gl::UNSIGNED_INT64_NV => 8,
+            _ => 4,
+        };
+        offset + ((instances_count - 1) * elements_count * component_size) as usize <= 2047
+    })
+}
+
+/// Panics if the attributes of the vertex buffers don't match the attributes of the program.
+fn check_attributes(vertex_buffers: &[(gl::types::GLuint, VertexFormat, usize, usize,
+                                       Option<u32>)], program: &Program)
+{
+    // checking the attributes types
+    for &(_, ref bindings, _, _, _) in vertex_buffers {
+        for &(ref name, _, ty) in bindings.iter() {
+            let attribute = match program.get_attribute(Borrow::<str>::borrow(name)) {
+                Some(a) => a,
+                None => continue
+            };
+
+            if ty.get_num_components() != attribute.ty.get_num_components() ||
+                attribute.size != 1
+            {
+                panic!("The program attribute `{}` does not match the vertex format. \
+                        Program expected {:?}, got {:?}.", name, attribute.ty, ty);
+            }
+        }
+    }
+
+    // checking for missing attributes and for collisions between the vertices sources
+    for (&ref name, _) in program.attributes() {
+        let sources = vertex_buffers.iter()
+                                    .filter(|&&(_, ref bindings, _, _, _)| {
+                                        bindings.iter().find(|&&(ref n, _, _)| n == name).is_some()
+                                    })
+                                    .count();
+
+        if sources == 0 {
+            panic!("The program attribute `{}` is missing in the vertex bindings", name);
+        } else if sources > 1 {
+            panic!("The program attribute `{}` is provided by multiple vertices sources", name);
+        }
+    };
+}
+
+/// Generates a new vertex array object.
+unsafe fn gen_vao(ctxt: &mut CommandContext) -> gl::types::GLuint {
+    let mut id = mem::uninitialized();
+    if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+        ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+        ctxt.extensions.gl_arb_vertex_array_object
+    {
+        ctxt.gl.GenVertexArrays(1, &mut id);
+    } else if ctxt.extensions.gl_oes_vertex_array_object {
+        ctxt.gl.GenVertexArraysOES(1, &mut id);
+    } else if ctxt.extensions.gl_apple_vertex_array_object {
+        ctxt.gl.GenVertexArraysAPPLE(1, &mut id);
+    } else {
+        unreachable!();
+    };
+    id
+}
+
 /// Binds the vertex array object as the current one. Unbinds if `0` is passed.
 ///
 /// ## Panic
@@ -568,3 +747,54 @@ unsafe fn bind_attribute(ctxt: &mut CommandContext, program: &Program,
         }
     }
 }
+
+/// Sets the format of the attributes of a vertex source in the current VAO, and associates them
+/// with a binding index.
+unsafe fn set_attributes_format(ctxt: &mut CommandContext, program: &Program,
+                                binding_index: gl::types::GLuint, bindings: &VertexFormat)
+{
+    for &(ref name, offset, ty) in bindings.iter() {
+        let (data_type, elements_count, instances_count) = vertex_binding_type_to_gl(ty);
+
+        let attribute = match program.get_attribute(Borrow::<str>::borrow(name)) {
+            Some(a) => a,
+            None => continue
+        };
+
+        if attribute.location != -1 {
+            let (attribute_ty, _, _) = vertex_binding_type_to_gl(attribute.ty);
+            match attribute_ty {
+                gl::BYTE | gl::UNSIGNED_BYTE | gl::SHORT | gl::UNSIGNED_SHORT |
+                gl::INT | gl::UNSIGNED_INT =>
+                    ctxt.gl.VertexAttribIFormat(attribute.location as u32,
+                                                elements_count as gl::types::GLint, data_type,
+                                                offset as gl::types::GLuint),
+
+                gl::FLOAT => {
+                    for i in 0..instances_count {
+                        ctxt.gl.VertexAttribFormat((attribute.location + i) as u32,
+                                                   elements_count as gl::types::GLint, data_type, 0,
+                                                   (offset + (i * elements_count * 4) as usize)
+                                                        as gl::types::GLuint)
+                    }
+                },
+
+                gl::DOUBLE | gl::INT64_NV | gl::UNSIGNED_INT64_NV => {
+                    for i in 0..instances_count {
+                        ctxt.gl.VertexAttribLFormat((attribute.location + i) as u32,
+                                                    elements_count as gl::types::GLint, data_type,
+                                                    (offset + (i * elements_count * 8) as usize)
+                                                        as gl::types::GLuint)
+                    }
+                },
+
+                _ => unreachable!()
+            }
+
+            for i in 0..instances_count {
+                ctxt.gl.VertexAttribBinding((attribute.location + i) as u32, binding_index);
+                ctxt.gl.EnableVertexAttribArray((attribute.location + i) as u32);
+            }
+        }
+    }
+}
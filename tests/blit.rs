@@ -56,3 +56,92 @@ fn blit_texture_to_window() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn copy_from_simple_framebuffer() {
+    let display = support::build_display();
+
+    let source = glium::Texture2d::empty(&display, 2, 2).unwrap();
+    source.as_surface().clear_color(0.0, 1.0, 0.0, 1.0);
+
+    let target = glium::Texture2d::empty(&display, 4, 4).unwrap();
+    target.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &source).unwrap();
+    let src_rect = Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    target.main_level().copy_from_simple_framebuffer(&framebuffer, &src_rect, (1, 1)).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = target.read();
+
+    assert_eq!(data[1][1], (0, 255, 0, 255));
+    assert_eq!(data[2][2], (0, 255, 0, 255));
+    assert_eq!(data[0][0], (0, 0, 0, 0));
+    assert_eq!(data[3][3], (0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn copy_from_framebuffer_integral_texture() {
+    let display = support::build_display();
+
+    let source = support::build_renderable_texture(&display);
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &source).unwrap();
+
+    let target = match glium::texture::IntegralTexture2d::empty(&display, 4, 4) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let src_rect = Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    let result = target.main_level().copy_from_simple_framebuffer(&framebuffer, &src_rect,
+                                                                  (0, 0));
+    assert_eq!(result, Err(glium::texture::CopyFromFramebufferError::IncompatibleFormat));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn copy_from_integral_framebuffer() {
+    let display = support::build_display();
+
+    let source = match glium::texture::UnsignedTexture2d::empty(&display, 2, 2) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let framebuffer = match glium::framebuffer::SimpleFrameBuffer::new(&display, &source) {
+        Ok(f) => f,
+        Err(_) => return
+    };
+
+    let target = glium::Texture2d::empty(&display, 4, 4).unwrap();
+
+    let src_rect = Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    let result = target.main_level().copy_from_simple_framebuffer(&framebuffer, &src_rect,
+                                                                  (0, 0));
+    assert_eq!(result, Err(glium::texture::CopyFromFramebufferError::IncompatibleFormat));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn copy_from_multisampled_framebuffer() {
+    let display = support::build_display();
+
+    let source = match glium::texture::Texture2dMultisample::empty(&display, 2, 2, 4) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &source).unwrap();
+
+    let target = glium::Texture2d::empty(&display, 4, 4).unwrap();
+
+    let src_rect = Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    let result = target.main_level().copy_from_simple_framebuffer(&framebuffer, &src_rect,
+                                                                  (0, 0));
+    assert_eq!(result, Err(glium::texture::CopyFromFramebufferError::MultisampledSource));
+
+    display.assert_no_error(None);
+}
@@ -325,6 +325,30 @@ fn buffer_immutable_read_slice() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn buffer_read_range() {
+    let display = support::build_display();
+
+    let buffer = glium::buffer::Buffer::new(&display, &[1u32, 2, 3, 4, 5, 6][..],
+                                            glium::buffer::BufferType::ArrayBuffer,
+                                            glium::buffer::BufferMode::Default).unwrap();
+
+    let data = match buffer.read_range(2 .. 5) {
+        Ok(r) => r,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => e.unwrap()
+    };
+
+    assert_eq!(data, vec![3, 4, 5]);
+
+    match buffer.read_range(4 .. 7) {
+        Err(glium::buffer::ReadError::OutOfBounds) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn buffer_dynamic_read_slice() {
     let display = support::build_display();
@@ -977,3 +1001,52 @@ fn copy_to_slice() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn reinterpret() {
+    let display = support::build_display();
+
+    let buf = glium::buffer::BufferView::<[u8]>::new(&display, &[1, 0, 2, 0, 3, 0],
+                                                     glium::buffer::BufferType::ArrayBuffer,
+                                                     BufferMode::Default).unwrap();
+
+    let buf = buf.reinterpret::<[u8; 4]>().err().unwrap();
+    let buf = buf.reinterpret::<[u8; 2]>().unwrap();
+    assert_eq!(buf.len(), 3);
+
+    let result = match buf.read() {
+        Ok(r) => r,
+        Err(_) => return
+    };
+
+    assert_eq!(result, [[1, 0], [2, 0], [3, 0]]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn mapping_coherent_flush_if_needed() {
+    let display = support::build_display();
+
+    let buf = glium::buffer::BufferView::new(&display, &[1, 2, 3],
+                                             glium::buffer::BufferType::ArrayBuffer,
+                                             BufferMode::Default).unwrap();
+    assert!(!buf.is_mapping_coherent());
+    buf.flush_if_needed(0 .. 4);
+
+    let mut buf = glium::buffer::BufferView::new(&display, &[1, 2, 3],
+                                                 glium::buffer::BufferType::ArrayBuffer,
+                                                 BufferMode::Persistent).unwrap();
+    if buf.is_persistent() {
+        // glium maps persistent buffers with `GL_MAP_FLUSH_EXPLICIT_BIT`
+        assert!(!buf.is_mapping_coherent());
+    }
+
+    {
+        let mut mapping = buf.map();
+        mapping[0] = 3;
+    }
+    buf.flush_if_needed(0 .. 4);
+
+    display.assert_no_error(None);
+}
@@ -0,0 +1,121 @@
+#[macro_use]
+extern crate glium;
+
+mod support;
+
+#[test]
+fn execute_indirect_from_shader_written_buffer() {
+    let display = support::build_display();
+
+    if !glium::program::ComputeShader::is_supported(&display) {
+        return;
+    }
+
+    // first pass: writes the dispatch command
+    let command_program = glium::program::ComputeShader::from_source(&display, r#"
+            #version 430
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(std430) buffer Command {
+                uint num_groups_x;
+                uint num_groups_y;
+                uint num_groups_z;
+            };
+
+            void main() {
+                num_groups_x = 3;
+                num_groups_y = 1;
+                num_groups_z = 1;
+            }
+        "#).unwrap();
+
+    // second pass: each work group writes its own value
+    let program = glium::program::ComputeShader::from_source(&display, r#"
+            #version 430
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(std430) buffer Output {
+                uint values[4];
+            };
+
+            void main() {
+                values[gl_WorkGroupID.x] = 1;
+            }
+        "#).unwrap();
+
+    let command = glium::uniforms::UniformBuffer::new(&display, glium::program::ComputeCommand {
+        num_groups_x: 0, num_groups_y: 0, num_groups_z: 0
+    }).unwrap();
+
+    let output = glium::uniforms::UniformBuffer::new(&display, [0u32; 4]).unwrap();
+
+    command_program.execute(uniform! { Command: &command }, 1, 1, 1);
+    program.execute_indirect(uniform! { Output: &output }, command.as_slice());
+
+    assert_eq!(output.read().unwrap(), [1, 1, 1, 0]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn buffer_reduce() {
+    let display = support::build_display();
+
+    let reducer = match glium::buffer::BufferReducer::new(&display) {
+        Ok(r) => r,
+        Err(_) => return
+    };
+
+    let data = (0 .. 1000).map(|i| i as f32).collect::<Vec<_>>();
+    let input = glium::buffer::Buffer::new(&display, &data[..],
+                                           glium::buffer::BufferType::ShaderStorageBuffer,
+                                           glium::buffer::BufferMode::Default).unwrap();
+
+    let min = reducer.reduce(input.as_slice(), glium::buffer::ReduceOp::Min).unwrap();
+    assert_eq!(min.read().unwrap(), 0.0);
+
+    let max = reducer.reduce(input.as_slice(), glium::buffer::ReduceOp::Max).unwrap();
+    assert_eq!(max.read().unwrap(), 999.0);
+
+    let sum = reducer.reduce(input.as_slice(), glium::buffer::ReduceOp::Sum).unwrap();
+    assert_eq!(sum.read().unwrap(), 499500.0);
+
+    let average = reducer.reduce(input.as_slice(), glium::buffer::ReduceOp::Average).unwrap();
+    assert_eq!(average.read().unwrap(), 499.5);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn buffer_reduce_multiple_work_groups() {
+    let display = support::build_display();
+
+    let reducer = match glium::buffer::BufferReducer::new(&display) {
+        Ok(r) => r,
+        Err(_) => return
+    };
+
+    let data = (0 .. 100000).map(|i| (i % 1000) as f32 - 500.0).collect::<Vec<_>>();
+    let input = glium::buffer::Buffer::new(&display, &data[..],
+                                           glium::buffer::BufferType::ShaderStorageBuffer,
+                                           glium::buffer::BufferMode::Default).unwrap();
+
+    let min = reducer.reduce(input.as_slice(), glium::buffer::ReduceOp::Min).unwrap();
+    assert_eq!(min.read().unwrap(), -500.0);
+
+    let max = reducer.reduce(input.as_slice(), glium::buffer::ReduceOp::Max).unwrap();
+    assert_eq!(max.read().unwrap(), 499.0);
+
+    let ones = vec![1.0f32; 100000];
+    let input = glium::buffer::Buffer::new(&display, &ones[..],
+                                           glium::buffer::BufferType::ShaderStorageBuffer,
+                                           glium::buffer::BufferMode::Default).unwrap();
+
+    let sum = reducer.reduce(input.as_slice(), glium::buffer::ReduceOp::Sum).unwrap();
+    assert_eq!(sum.read().unwrap(), 100000.0);
+
+    let average = reducer.reduce(input.as_slice(), glium::buffer::ReduceOp::Average).unwrap();
+    assert_eq!(average.read().unwrap(), 1.0);
+
+    display.assert_no_error(None);
+}
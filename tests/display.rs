@@ -64,6 +64,25 @@ fn release_shader_compiler() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn texture_format_infos() {
+    let display = support::build_display();
+
+    let format = glium::texture::TextureFormat::UncompressedFloat(
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8);
+    let infos = display.get_texture_format_infos(format).unwrap();
+
+    if let Some(renderable) = infos.color_renderable {
+        assert!(renderable);
+    }
+
+    if let Some(filterable) = infos.filterable {
+        assert!(filterable);
+    }
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn timestamp_query() {
     let display = support::build_display();
@@ -94,6 +113,50 @@ fn sync() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn sync_wait_in() {
+    let display = support::build_display();
+
+    // a context always shares its objects with itself
+    if let Ok(fence) = glium::SyncFence::new(&display) {
+        fence.wait_in(&display);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[should_panic]
+fn sync_wait_in_unshared_context() {
+    let display1 = support::build_display();
+    let display2 = support::build_display();
+
+    match glium::SyncFence::new(&display1) {
+        Ok(fence) => fence.wait_in(&display2),
+        Err(_) => panic!()      // the test expects a panic
+    };
+}
+
+#[test]
+fn frame_limiter() {
+    let display = support::build_display();
+
+    let mut limiter = match glium::FrameLimiter::new(&display, 2) {
+        Ok(l) => l,
+        Err(_) => return
+    };
+
+    for _ in 0 .. 5 {
+        limiter.begin_frame();
+        let mut target = display.draw();
+        target.clear_color(0.0, 0.0, 0.0, 1.0);
+        target.finish().unwrap();
+        limiter.end_frame();
+    }
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn multiple_displays() {
     let display1 = support::build_display();
@@ -133,6 +196,118 @@ fn multiple_displays() {
     display2.assert_no_error(None);
 }
 
+#[test]
+fn unshared_displays() {
+    let display1 = support::build_display();
+    let display2 = support::build_display();
+
+    assert!(display1.is_shared_with(&display1));
+    assert!(!display1.is_shared_with(&display2));
+    assert!(!display2.is_shared_with(&display1));
+
+    display1.assert_no_error(None);
+    display2.assert_no_error(None);
+}
+
+#[test]
+fn shared_displays() {
+    // headless contexts can't share their objects
+    if ::std::env::var("GLIUM_HEADLESS_TESTS").is_ok() {
+        return;
+    }
+
+    let display1 = support::build_display();
+    let display2 = display1.build_shared(glium::glutin::WindowBuilder::new()
+                                                            .with_visibility(false)).unwrap();
+
+    assert!(display1.is_shared_with(&display2));
+    assert!(display2.is_shared_with(&display1));
+
+    // the pipeline is created with the first display and used with the second one
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display1);
+    if let Ok(fence) = glium::SyncFence::new(&display1) {
+        fence.wait_in(&display2);
+    }
+
+    let texture = support::build_renderable_texture(&display2);
+
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in read_back.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    drop((vb, ib, program));
+
+    display1.assert_no_error(None);
+    display2.assert_no_error(None);
+}
+
+#[test]
+fn build_shared_headless() {
+    if ::std::env::var("GLIUM_HEADLESS_TESTS").is_err() {
+        return;
+    }
+
+    let display = support::build_display();
+    assert!(display.build_shared(glium::glutin::WindowBuilder::new()
+                                                      .with_visibility(false)).is_err());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_export_import() {
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8, 3u8), (4u8, 5u8, 6u8, 7u8)],
+    ]).unwrap();
+
+    // the exported texture can be sent to another thread
+    let exported = texture.export();
+    let exported = ::std::thread::spawn(move || exported).join().unwrap();
+
+    let texture = unsafe { glium::Texture2d::from_exported(&display, exported) };
+    assert_eq!(texture.get_width(), 2);
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 1, 2, 3));
+    assert_eq!(read_back[0][1], (4, 5, 6, 7));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_export_import_shared() {
+    // headless contexts can't share their objects
+    if ::std::env::var("GLIUM_HEADLESS_TESTS").is_ok() {
+        return;
+    }
+
+    let display1 = support::build_display();
+    let display2 = display1.build_shared(glium::glutin::WindowBuilder::new()
+                                                            .with_visibility(false)).unwrap();
+
+    let texture = glium::Texture2d::new(&display1, vec![
+        vec![(255u8, 0u8, 0u8, 255u8), (0u8, 255u8, 0u8, 255u8)],
+    ]).unwrap();
+
+    let texture = unsafe { glium::Texture2d::from_exported(&display2, texture.export()) };
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (255, 0, 0, 255));
+    assert_eq!(read_back[0][1], (0, 255, 0, 255));
+
+    display1.assert_no_error(None);
+    display2.assert_no_error(None);
+}
+
 #[test]
 fn debug_string() {
     // tests that `insert_debug_marker` doesn't trigger an OpenGL error
@@ -149,3 +324,158 @@ fn is_context_lost() {
     display.is_context_lost();
     display.assert_no_error(None);
 }
+
+#[test]
+fn reset_status() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let display = support::build_display();
+
+    let called = Rc::new(Cell::new(false));
+    let called2 = called.clone();
+    display.set_context_lost_callback(move |_| called2.set(true));
+
+    assert_eq!(display.reset_status(), glium::backend::ResetStatus::NoError);
+    assert!(!called.get());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn context_lost_callback() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let display = support::build_display();
+    let (vertex_buffer, index_buffer, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let called = Rc::new(Cell::new(false));
+    let called2 = called.clone();
+    display.set_context_lost_callback(move |_| called2.set(true));
+
+    // drawing and swapping buffers detect context losses, but the context isn't lost here
+    let mut target = display.draw();
+    target.draw(&vertex_buffer, &index_buffer, &program, &glium::uniforms::EmptyUniforms,
+                &Default::default()).unwrap();
+    target.finish().unwrap();
+
+    assert!(!called.get());
+    assert!(!display.is_context_lost());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn error_checking_mode() {
+    let display = support::build_display();
+
+    let mode = glium::backend::ErrorCheckingMode {
+        per_call: !display.get_error_checking_mode().per_call,
+        on_swap: false,
+    };
+    display.set_error_checking_mode(mode);
+    assert_eq!(display.get_error_checking_mode(), mode);
+
+    let mut target = display.draw();
+    target.clear_color(0.0, 0.0, 0.0, 0.0);
+    target.finish().unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn set_hint() {
+    let display = support::build_display();
+
+    display.set_hint(glium::backend::HintTarget::FragmentShaderDerivative,
+                     glium::backend::HintMode::Nicest).ok();
+    display.set_hint(glium::backend::HintTarget::GenerateMipmap,
+                     glium::backend::HintMode::Fastest).ok();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn set_color_clamp() {
+    let display = support::build_display();
+
+    display.set_color_clamp(glium::backend::ColorClampTarget::Fragment,
+                            glium::backend::ColorClampMode::NoClamp).ok();
+    display.set_color_clamp(glium::backend::ColorClampTarget::Vertex,
+                            glium::backend::ColorClampMode::FixedOnly).ok();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn max_texture_anisotropy() {
+    use glium::CapabilitiesSource;
+
+    let display = support::build_display();
+
+    if let Some(max) = display.get_capabilities().max_texture_max_anisotropy {
+        assert!(max >= 1.0);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn memory_usage_buffers() {
+    let display = support::build_display();
+
+    let before = display.memory_usage();
+
+    let buffer = glium::buffer::Buffer::<[u8]>::empty_unsized(&display,
+                                            glium::buffer::BufferType::ArrayBuffer, 1024,
+                                            glium::buffer::BufferMode::Default).unwrap();
+    assert_eq!(display.memory_usage().buffers, before.buffers + 1024);
+
+    drop(buffer);
+    assert_eq!(display.memory_usage().buffers, before.buffers);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn memory_usage_textures() {
+    let display = support::build_display();
+
+    let before = display.memory_usage();
+    let texture = glium::texture::Texture2d::empty_with_format(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            glium::texture::MipmapsOption::NoMipmap,
+                                            128, 128).unwrap();
+    assert_eq!(display.memory_usage().textures, before.textures + 128 * 128 * 4);
+
+    drop(texture);
+    assert_eq!(display.memory_usage().textures, before.textures);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn debug_group() {
+    let display = support::build_display();
+
+    {
+        let _outer = display.push_debug_group("outer").ok();
+        let _inner = display.push_debug_group("inner").ok();
+        display.insert_debug_marker("Hello world").ok();
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sync_label() {
+    let display = support::build_display();
+
+    if let Ok(fence) = glium::SyncFence::new(&display) {
+        fence.set_label("fence").ok();
+        fence.wait();
+    }
+
+    display.assert_no_error(None);
+}
@@ -81,6 +81,44 @@ fn wrong_depth_range() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn reversed_depth_range() {
+    let display = support::build_display();
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    let depth = glium::framebuffer::DepthRenderBuffer::new(&display,
+                                                           glium::texture::DepthFormat::I24,
+                                                           texture.get_width(),
+                                                           texture.get_height().unwrap()).unwrap();
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                                  &texture,
+                                                                                  &depth).unwrap();
+    framebuffer.clear_color_and_depth((0.0, 0.0, 0.0, 0.0), 0.0);
+
+    let params = glium::DrawParameters {
+        depth: glium::Depth {
+            test: glium::DepthTest::IfMore,
+            write: true,
+            range: (1.0, 0.0),
+            .. Default::default()
+        },
+        .. Default::default()
+    };
+
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn scissor() {
     let display = support::build_display();
@@ -217,6 +255,260 @@ fn viewport() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn viewport_subpixel_offset() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        viewport: Some(glium::Rect {
+            left: 0,
+            bottom: 0,
+            width: 1,
+            height: 1,
+        }),
+        viewport_subpixel_offset: (1.0, 1.0),
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 0, 0, 0));
+    assert_eq!(data[1][1], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn viewport_subpixel_offset_fraction() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        viewport: Some(glium::Rect {
+            left: 0,
+            bottom: 0,
+            width: 1,
+            height: 1,
+        }),
+        viewport_subpixel_offset: (0.25, 0.25),
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    // the center of the first pixel is still covered
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data[1][1], (0, 0, 0, 0));
+
+    // drawing without offset afterwards must reset the viewport
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[1][1], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn viewport_array() {
+    let display = support::build_display();
+    let (vb, ib, _) = support::build_fullscreen_red_pipeline(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 410
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 410
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        Some("
+            #version 410
+
+            layout(triangles) in;
+            layout(triangle_strip, max_vertices = 3) out;
+
+            void main() {
+                for (int i = 0; i < 3; ++i) {
+                    gl_Position = gl_in[i].gl_Position;
+                    gl_ViewportIndex = 1;
+                    EmitVertex();
+                }
+                EndPrimitive();
+            }
+        "))
+    {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let params = glium::DrawParameters {
+        viewports: vec![
+            glium::Rect { left: 0, bottom: 0, width: 2, height: 4 },
+            glium::Rect { left: 2, bottom: 0, width: 2, height: 4 },
+        ],
+        .. Default::default()
+    };
+
+    let texture = glium::Texture2d::empty(&display, 4, 4).unwrap();
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::ViewportArrayNotSupported) => return,
+        Err(e) => panic!("{:?}", e),
+        Ok(_) => ()
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[1][0], (0, 0, 0, 0));
+    assert_eq!(data[1][1], (0, 0, 0, 0));
+    assert_eq!(data[1][2], (255, 0, 0, 255));
+    assert_eq!(data[1][3], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn scissor_array() {
+    let display = support::build_display();
+    let (vb, ib, _) = support::build_fullscreen_red_pipeline(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 410
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 410
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        Some("
+            #version 410
+
+            layout(triangles) in;
+            layout(triangle_strip, max_vertices = 3) out;
+
+            void main() {
+                for (int i = 0; i < 3; ++i) {
+                    gl_Position = gl_in[i].gl_Position;
+                    gl_ViewportIndex = 1;
+                    EmitVertex();
+                }
+                EndPrimitive();
+            }
+        "))
+    {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let viewports = vec![
+        glium::Rect { left: 0, bottom: 0, width: 2, height: 4 },
+        glium::Rect { left: 2, bottom: 0, width: 2, height: 4 },
+    ];
+
+    let params = glium::DrawParameters {
+        viewports: viewports.clone(),
+        scissors: vec![
+            glium::Rect { left: 0, bottom: 0, width: 2, height: 4 },
+            glium::Rect { left: 2, bottom: 0, width: 1, height: 4 },
+        ],
+        .. Default::default()
+    };
+
+    let texture = glium::Texture2d::empty(&display, 4, 4).unwrap();
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::ViewportArrayNotSupported) => return,
+        Err(e) => panic!("{:?}", e),
+        Ok(_) => ()
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[1][0], (0, 0, 0, 0));
+    assert_eq!(data[1][1], (0, 0, 0, 0));
+    assert_eq!(data[1][2], (255, 0, 0, 255));
+    assert_eq!(data[1][3], (0, 0, 0, 0));
+
+    let params = glium::DrawParameters {
+        viewports: viewports,
+        scissors: vec![glium::Rect { left: 0, bottom: 0, width: 2, height: 4 }],
+        .. Default::default()
+    };
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::ScissorsCountMismatch) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn viewport_array_without_geometry_shader() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let params = glium::DrawParameters {
+        viewports: vec![
+            glium::Rect { left: 0, bottom: 0, width: 2, height: 4 },
+            glium::Rect { left: 2, bottom: 0, width: 2, height: 4 },
+        ],
+        .. Default::default()
+    };
+
+    let texture = glium::Texture2d::empty(&display, 4, 4).unwrap();
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::ViewportArrayNotSupported) => (),
+        Err(glium::DrawError::ViewportArrayWithoutGeometryShader) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn dont_draw_primitives() {
     let display = support::build_display();
@@ -668,6 +960,10 @@ fn cull_counterclockwise_trianglestrip() {
 
 macro_rules! blending_test {
     ($name:ident, $func:expr, $source:expr, $dest:expr, $result:expr) => (
+        blending_test!($name, $func, (1.0, 1.0, 1.0, 1.0), $source, $dest, $result);
+    );
+
+    ($name:ident, $func:expr, $constant:expr, $source:expr, $dest:expr, $result:expr) => (
         #[test]
         fn $name() {
             let display = support::build_display();
@@ -676,7 +972,7 @@ macro_rules! blending_test {
                 blend: glium::Blend {
                     color: $func,
                     alpha: $func,
-                    constant_value: (1.0, 1.0, 1.0, 1.0)
+                    constant_value: $constant
                 },
                 .. Default::default()
             };
@@ -771,6 +1067,13 @@ blending_test!(one_plus_one, glium::BlendingFunction::Addition {
                },
                (0.0, 1.0, 1.0, 0.0), (1.0, 0.0, 0.0, 1.0), (255, 255, 255, 255));
 
+blending_test!(constant_color_crossfade, glium::BlendingFunction::Addition {
+                   source: glium::LinearBlendingFactor::ConstantColor,
+                   destination: glium::LinearBlendingFactor::OneMinusConstantColor,
+               },
+               (1.0, 0.0, 1.0, 0.0),
+               (0.0, 1.0, 1.0, 0.0), (1.0, 0.0, 0.0, 1.0), (255, 255, 0, 0));
+
 
 #[test]
 fn provoking_vertex_last() {
@@ -914,71 +1217,33 @@ fn provoking_vertex_first() {
 }
 
 #[test]
-fn depth_clamp_all() {
+fn origin_upper_left() {
     let display = support::build_display();
 
     let vertex_buffer = {
         #[derive(Copy, Clone)]
         struct Vertex {
-            position: (f32, f32, f32),
+            position: [f32; 2],
         }
 
         implement_vertex!(Vertex, position);
 
         glium::VertexBuffer::new(&display, &[
-            Vertex { position: (-1.0, 1.0, -3.0) },
-            Vertex { position: (1.0, 1.0, 3.0) },
-            Vertex { position: (-1.0, -1.0, -3.0) },
-            Vertex { position: (1.0, -1.0, 3.0) },
+            Vertex { position: [-1.0, 1.0] },
+            Vertex { position: [1.0, -1.0] },
+            Vertex { position: [-1.0, -1.0] },
         ]).unwrap()
     };
 
     let program = program!(&display,
-        140 => {
-            vertex: "
-                #version 140
-
-                in vec3 position;
-
-                void main() {
-                    gl_Position = vec4(position, 1.0);
-                }
-            ",
-            fragment: "
-                #version 140
-
-                out vec4 color;
-                void main() {
-                    color = vec4(1.0, 0.0, 0.0, 1.0);
-                }
-            "
-        },
-        110 => {
-            vertex: "
-                #version 110
-
-                attribute vec3 position;
-
-                void main() {
-                    gl_Position = vec4(position, 1.0);
-                }
-            ",
-            fragment: "
-                #version 110
-
-                void main() {
-                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
-                }
-            "
-        },
         100 => {
             vertex: "
                 #version 100
 
-                attribute lowp vec3 position;
+                attribute lowp vec2 position;
 
                 void main() {
-                    gl_Position = vec4(position, 1.0);
+                    gl_Position = vec4(position, 0.0, 1.0);
                 }
             ",
             fragment: "
@@ -987,26 +1252,23 @@ fn depth_clamp_all() {
                 void main() {
                     gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
                 }
-            "
+            ",
         },
     ).unwrap();
 
     let texture = support::build_renderable_texture(&display);
     texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
     let res = texture.as_surface().draw(&vertex_buffer,
-        &glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip), &program,
+        &glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList), &program,
         &glium::uniforms::EmptyUniforms,
         &glium::DrawParameters {
-            depth: glium::Depth {
-                clamp: glium::draw_parameters::DepthClamp::Clamp,
-                .. Default::default()
-            },
+            origin: glium::draw_parameters::SurfaceOrigin::UpperLeft,
             .. Default::default()
         });
 
     match res {
         Ok(_) => (),
-        Err(glium::DrawError::DepthClampNotSupported) => {
+        Err(glium::DrawError::ClipControlNotSupported) => {
             display.assert_no_error(None);
             return;
         },
@@ -1015,43 +1277,354 @@ fn depth_clamp_all() {
 
     let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
 
-    for row in data.iter() {
-        for pixel in row.iter() {
-            assert_eq!(pixel, &(255, 0, 0, 255));
-        }
-    }
+    // the triangle is now in the top-left half of the texture instead of the bottom-left half
+    assert_eq!(data[900][500], (255, 0, 0, 255));
+    assert_eq!(data[100][500], (0, 0, 0, 0));
+    assert_eq!(data[100][900], (0, 0, 0, 0));
 
     display.assert_no_error(None);
 }
 
 #[test]
-fn depth_clamp_near() {
+fn depth_clip_mode_zero_to_one() {
     let display = support::build_display();
 
     let vertex_buffer = {
         #[derive(Copy, Clone)]
         struct Vertex {
-            position: (f32, f32, f32),
+            position: [f32; 3],
         }
 
         implement_vertex!(Vertex, position);
 
         glium::VertexBuffer::new(&display, &[
-            Vertex { position: (-1.0, 1.0, -3.0) },
-            Vertex { position: (1.0, 1.0, 3.0) },
-            Vertex { position: (-1.0, -1.0, -3.0) },
-            Vertex { position: (1.0, -1.0, 3.0) },
+            Vertex { position: [-1.0, 1.0, -0.5] },
+            Vertex { position: [1.0, 1.0, -0.5] },
+            Vertex { position: [-1.0, -1.0, -0.5] },
+            Vertex { position: [1.0, -1.0, -0.5] },
         ]).unwrap()
     };
 
-    let program = program!(&display,
-        140 => {
-            vertex: "
-                #version 140
+    let visible_vertex_buffer = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: [f32; 3],
+        }
 
-                in vec3 position;
+        implement_vertex!(Vertex, position);
 
-                void main() {
+        glium::VertexBuffer::new(&display, &[
+            Vertex { position: [-1.0, 1.0, 0.5] },
+            Vertex { position: [1.0, 1.0, 0.5] },
+            Vertex { position: [-1.0, -1.0, 0.5] },
+            Vertex { position: [1.0, -1.0, 0.5] },
+        ]).unwrap()
+    };
+
+    let program = program!(&display,
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec3 position;
+
+                void main() {
+                    gl_Position = vec4(position, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    let res = texture.as_surface().draw(&vertex_buffer,
+        &glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip), &program,
+        &glium::uniforms::EmptyUniforms,
+        &glium::DrawParameters {
+            depth_clip_mode: glium::draw_parameters::DepthClipMode::ZeroToOne,
+            .. Default::default()
+        });
+
+    match res {
+        Ok(_) => (),
+        Err(glium::DrawError::ClipControlNotSupported) => {
+            display.assert_no_error(None);
+            return;
+        },
+        e => e.unwrap(),
+    }
+
+    // a Z coordinate of -0.5 is outside of the [0, 1] range and is clipped
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 0, 0, 0));
+        }
+    }
+
+    // a Z coordinate of 0.5 is inside of the [0, 1] range and is drawn
+    texture.as_surface().draw(&visible_vertex_buffer,
+        &glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip), &program,
+        &glium::uniforms::EmptyUniforms,
+        &glium::DrawParameters {
+            depth_clip_mode: glium::draw_parameters::DepthClipMode::ZeroToOne,
+            .. Default::default()
+        }).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_clamp_all() {
+    let display = support::build_display();
+
+    let vertex_buffer = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: (f32, f32, f32),
+        }
+
+        implement_vertex!(Vertex, position);
+
+        glium::VertexBuffer::new(&display, &[
+            Vertex { position: (-1.0, 1.0, -3.0) },
+            Vertex { position: (1.0, 1.0, 3.0) },
+            Vertex { position: (-1.0, -1.0, -3.0) },
+            Vertex { position: (1.0, -1.0, 3.0) },
+        ]).unwrap()
+    };
+
+    let program = program!(&display,
+        140 => {
+            vertex: "
+                #version 140
+
+                in vec3 position;
+
+                void main() {
+                    gl_Position = vec4(position, 1.0);
+                }
+            ",
+            fragment: "
+                #version 140
+
+                out vec4 color;
+                void main() {
+                    color = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec3 position;
+
+                void main() {
+                    gl_Position = vec4(position, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec3 position;
+
+                void main() {
+                    gl_Position = vec4(position, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    let res = texture.as_surface().draw(&vertex_buffer,
+        &glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip), &program,
+        &glium::uniforms::EmptyUniforms,
+        &glium::DrawParameters {
+            depth: glium::Depth {
+                clamp: glium::draw_parameters::DepthClamp::Clamp,
+                .. Default::default()
+            },
+            .. Default::default()
+        });
+
+    match res {
+        Ok(_) => (),
+        Err(glium::DrawError::DepthClampNotSupported) => {
+            display.assert_no_error(None);
+            return;
+        },
+        e => e.unwrap(),
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_clamp_disabled_after_use() {
+    let display = support::build_display();
+
+    let vertex_buffer = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: (f32, f32, f32),
+        }
+
+        implement_vertex!(Vertex, position);
+
+        glium::VertexBuffer::new(&display, &[
+            Vertex { position: (-1.0, 1.0, 3.0) },
+            Vertex { position: (1.0, 1.0, 3.0) },
+            Vertex { position: (-1.0, -1.0, 3.0) },
+            Vertex { position: (1.0, -1.0, 3.0) },
+        ]).unwrap()
+    };
+
+    let program = program!(&display,
+        140 => {
+            vertex: "
+                #version 140
+
+                in vec3 position;
+
+                void main() {
+                    gl_Position = vec4(position, 1.0);
+                }
+            ",
+            fragment: "
+                #version 140
+
+                out vec4 color;
+                void main() {
+                    color = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec3 position;
+
+                void main() {
+                    gl_Position = vec4(position, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+    ).unwrap();
+
+    let indices = glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    let res = texture.as_surface().draw(&vertex_buffer, &indices, &program,
+        &glium::uniforms::EmptyUniforms,
+        &glium::DrawParameters {
+            depth: glium::Depth {
+                clamp: glium::draw_parameters::DepthClamp::Clamp,
+                .. Default::default()
+            },
+            .. Default::default()
+        });
+
+    match res {
+        Ok(_) => (),
+        Err(glium::DrawError::DepthClampNotSupported) => {
+            display.assert_no_error(None);
+            return;
+        },
+        e => e.unwrap(),
+    }
+
+    // the primitive is entirely outside of the depth range and must be clipped again
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vertex_buffer, &indices, &program,
+                              &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 0, 0, 0));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_clamp_near() {
+    let display = support::build_display();
+
+    let vertex_buffer = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: (f32, f32, f32),
+        }
+
+        implement_vertex!(Vertex, position);
+
+        glium::VertexBuffer::new(&display, &[
+            Vertex { position: (-1.0, 1.0, -3.0) },
+            Vertex { position: (1.0, 1.0, 3.0) },
+            Vertex { position: (-1.0, -1.0, -3.0) },
+            Vertex { position: (1.0, -1.0, 3.0) },
+        ]).unwrap()
+    };
+
+    let program = program!(&display,
+        140 => {
+            vertex: "
+                #version 140
+
+                in vec3 position;
+
+                void main() {
                     gl_Position = vec4(position, 1.0);
                 }
             ",
@@ -1279,3 +1852,411 @@ fn primitive_bounding_box() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn clip_planes() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 130
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+                gl_ClipDistance[0] = -1.0;
+            }
+        ",
+        "
+            #version 130
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let texture = support::build_renderable_texture(&display);
+
+    // without the clip plane, the whole texture is drawn
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    // with the clip plane, nothing is drawn
+    let params = glium::DrawParameters {
+        clip_planes_bitmask: 1,
+        .. Default::default()
+    };
+
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn clip_planes_out_of_bounds() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        clip_planes_bitmask: 1 << 31,
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::ClipPlaneIndexOutOfBounds) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn program_point_size() {
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    let display = support::build_display();
+
+    let vb = glium::VertexBuffer::new(&display, &[Vertex { position: [0.0, 0.0] }]).unwrap();
+    let ib = glium::index::NoIndices(PrimitiveType::Points);
+
+    let build_program = |uses_point_size| {
+        glium::Program::new(&display, glium::program::ProgramCreationInput::SourceCode {
+            tessellation_control_shader: None,
+            tessellation_evaluation_shader: None,
+            geometry_shader: None,
+            outputs_srgb: false,
+            uses_point_size: uses_point_size,
+            attribute_bindings: &[],
+            frag_data_bindings: &[],
+            uniform_block_bindings: &[],
+            uses_shader_includes: false,
+            defines: &[],
+            transform_feedback_varyings: None,
+
+            vertex_shader: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                    gl_PointSize = 16.0;
+                }
+            ",
+            fragment_shader: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        })
+    };
+
+    let with_size = match build_program(true) {
+        Ok(p) => p,
+        Err(_) => return
+    };
+    let without_size = build_program(false).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &with_size, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[516][516], (255, 0, 0, 255));
+
+    // `GL_PROGRAM_POINT_SIZE` must be disabled again for the second program
+    let params = glium::DrawParameters {
+        point_size: Some(1.0),
+        .. Default::default()
+    };
+
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &without_size, &glium::uniforms::EmptyUniforms,
+                              &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[516][516], (0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn line_width_not_supported() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        line_width: Some(100000.0),
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::LineWidthNotSupported) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn default_tessellation_levels() {
+    let display = support::build_display();
+    let (vb, _) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::new(&display,
+        glium::program::ProgramCreationInput::SourceCode {
+            tessellation_control_shader: None,
+            geometry_shader: None,
+            outputs_srgb: false,
+            uses_point_size: false,
+            attribute_bindings: &[],
+            frag_data_bindings: &[],
+            uniform_block_bindings: &[],
+            uses_shader_includes: false,
+            defines: &[],
+            transform_feedback_varyings: None,
+
+            vertex_shader: "
+                #version 400
+
+                in vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            tessellation_evaluation_shader: Some("
+                #version 400
+
+                layout(quads) in;
+
+                void main() {
+                    vec4 bottom = mix(gl_in[2].gl_Position, gl_in[3].gl_Position, gl_TessCoord.x);
+                    vec4 top = mix(gl_in[0].gl_Position, gl_in[1].gl_Position, gl_TessCoord.x);
+                    gl_Position = mix(bottom, top, gl_TessCoord.y);
+                }
+            "),
+            fragment_shader: "
+                #version 400
+
+                out vec4 color;
+
+                void main() {
+                    color = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        });
+
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let params = glium::DrawParameters {
+        default_tessellation_levels: ([4.0, 4.0, 4.0, 4.0], [4.0, 4.0]),
+        .. Default::default()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &glium::index::NoIndices(PrimitiveType::Patches {
+                                  vertices_per_patch: 4
+                              }), &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data[512][512], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn pipeline() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let pipeline = glium::draw_parameters::Pipeline::new(&display, &program, Default::default(),
+                                                         &[vb.get_bindings()]).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw_with_pipeline(&pipeline, &vb, &ib,
+                                            &glium::uniforms::EmptyUniforms).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn pipeline_invalid_depth_range() {
+    let display = support::build_display();
+    let (vb, _, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let params = glium::DrawParameters {
+        depth: glium::Depth {
+            range: (-0.1, 1.0),
+            .. Default::default()
+        },
+        .. Default::default()
+    };
+
+    match glium::draw_parameters::Pipeline::new(&display, &program, params,
+                                                &[vb.get_bindings()])
+    {
+        Err(glium::DrawError::InvalidDepthRange) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn pipeline_attribute_missing() {
+    let display = support::build_display();
+    let (_, _, program) = support::build_fullscreen_red_pipeline(&display);
+
+    match glium::draw_parameters::Pipeline::new(&display, &program, Default::default(), &[]) {
+        Err(glium::DrawError::AttributeMissing) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn pipeline_attribute_in_multiple_sources() {
+    let display = support::build_display();
+    let (vb, _, program) = support::build_fullscreen_red_pipeline(&display);
+
+    match glium::draw_parameters::Pipeline::new(&display, &program, Default::default(),
+                                                &[vb.get_bindings(), vb.get_bindings()])
+    {
+        Err(glium::DrawError::AttributeInMultipleSources) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sample_coverage() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let multisampled = match glium::texture::Texture2dMultisample::empty(&display, 64, 64, 4) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let params = glium::DrawParameters {
+        multisampling: glium::draw_parameters::MultisampleState {
+            sample_coverage: Some((0.5, false)),
+            .. Default::default()
+        },
+        .. Default::default()
+    };
+
+    multisampled.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    multisampled.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                   &params).unwrap();
+
+    // resolving the samples, half of which are red
+    let texture = glium::Texture2d::empty(&display, 64, 64).unwrap();
+    multisampled.as_surface().fill(&texture.as_surface(),
+                                   glium::uniforms::MagnifySamplerFilter::Nearest);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    let (r, g, b, a) = data[32][32];
+    assert!(r >= 112 && r <= 144);
+    assert_eq!(g, 0);
+    assert_eq!(b, 0);
+    assert!(a >= 112 && a <= 144);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn alpha_to_coverage() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let params = glium::DrawParameters {
+        multisampling: glium::draw_parameters::MultisampleState {
+            alpha_to_coverage: true,
+            .. Default::default()
+        },
+        .. Default::default()
+    };
+
+    // the coverage has no effect on a target without multisampling
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn seamless_cubemaps() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let params = glium::DrawParameters {
+        seamless_cubemaps: true,
+        .. Default::default()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Ok(_) => (),
+        Err(glium::DrawError::SeamlessCubemapsNotSupported) => return,
+        e => e.unwrap()
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
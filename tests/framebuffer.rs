@@ -98,6 +98,77 @@ fn simple_render_to_texture() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn multisample_render_to_texture() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              glium::texture::MipmapsOption::NoMipmap,
+                                              128, 128).unwrap();
+
+    {
+        let mut framebuffer =
+            glium::framebuffer::MultisampleFrameBuffer::new(&display, &texture, 4).unwrap();
+        assert_eq!(framebuffer.get_dimensions(), (128, 128));
+        framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                         &Default::default()).unwrap();
+        framebuffer.resolve();
+    }
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+
+    assert_eq!(read_back[0][0], (255, 0, 0, 255));
+    assert_eq!(read_back[64][64], (255, 0, 0, 255));
+    assert_eq!(read_back[127][127], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multisample_render_to_texture_too_many_samples() {
+    use glium::CapabilitiesSource;
+
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              glium::texture::MipmapsOption::NoMipmap,
+                                              128, 128).unwrap();
+
+    let max = display.get_capabilities().max_samples.unwrap_or(0) as u32;
+
+    match glium::framebuffer::MultisampleFrameBuffer::new(&display, &texture, max + 1) {
+        Err(glium::framebuffer::MultisampleFrameBufferCreationError::ValidationError(
+            glium::framebuffer::ValidationError::SamplesCountNotSupported)) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multisample_render_to_texture_default_format() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = glium::Texture2d::empty(&display, 128, 128).unwrap();
+
+    {
+        let mut framebuffer =
+            glium::framebuffer::MultisampleFrameBuffer::new(&display, &texture, 4).unwrap();
+        framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                         &Default::default()).unwrap();
+        framebuffer.resolve();
+    }
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[64][64], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn depth_texture2d() {
     use std::iter;
@@ -254,6 +325,157 @@ fn multioutput() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn multioutput_read_color_attachment() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color1;
+            out vec4 color2;
+
+            void main() {
+                color1 = vec4(1.0, 1.0, 1.0, 1.0);
+                color2 = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let color1 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+    let color2 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                               [("color1", &color1), ("color2", &color2)].iter().cloned()).unwrap();
+
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &Default::default()).unwrap();
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 128, height: 128 };
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = framebuffer.read_color_attachment(1, &rect)
+                                                           .unwrap();
+    assert_eq!(read_back.len(), 128);
+    for row in read_back.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = framebuffer.read_color_attachment(0, &rect)
+                                                           .unwrap();
+    assert_eq!(read_back[0][0], (255, 255, 255, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multioutput_blending_doesnt_leak() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color1;
+            out vec4 color2;
+
+            void main() {
+                color1 = vec4(1.0, 1.0, 1.0, 1.0);
+                color2 = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let color1 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+    let color2 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                               [("color1", &color1), ("color2", &color2)].iter().cloned()).unwrap();
+
+    // first draw with blending enabled
+    let params = glium::DrawParameters {
+        blend: glium::Blend {
+            color: glium::BlendingFunction::Max,
+            alpha: glium::BlendingFunction::Max,
+            constant_value: (0.0, 0.0, 0.0, 0.0),
+        },
+        .. Default::default()
+    };
+
+    color1.as_surface().clear_color(0.0, 0.0, 1.0, 1.0);
+    color2.as_surface().clear_color(0.0, 0.0, 1.0, 1.0);
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    let read_back2: Vec<Vec<(u8, u8, u8, u8)>> = color2.read();
+    assert_eq!(read_back2[0][0], (255, 0, 255, 255));
+
+    // second draw without blending must overwrite both attachments
+    color1.as_surface().clear_color(0.0, 0.0, 1.0, 1.0);
+    color2.as_surface().clear_color(0.0, 0.0, 1.0, 1.0);
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &Default::default()).unwrap();
+
+    let read_back1: Vec<Vec<(u8, u8, u8, u8)>> = color1.read();
+    for row in read_back1.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 255, 255, 255));
+        }
+    }
+
+    let read_back2: Vec<Vec<(u8, u8, u8, u8)>> = color2.read();
+    for row in read_back2.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn array_level() {
     let display = support::build_display();
@@ -447,3 +669,316 @@ fn empty_framebuffer_simple_draw() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn hdr_render_target() {
+    use glium::framebuffer::{HdrRenderTarget, HdrPrecision};
+
+    let display = support::build_display();
+
+    let target = match HdrRenderTarget::new(&display, HdrPrecision::F16, 128, 128,
+                                            Some(glium::texture::DepthFormat::I24))
+    {
+        Ok(t) => t,
+        Err(glium::texture::TextureCreationError::FormatNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    target.as_surface().clear_color_and_depth((2.0, 0.5, 0.0, 1.0), 1.0);
+
+    assert!(target.get_depth().is_some());
+    assert_eq!(target.get_color().get_width(), 128);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_only_clear_depth() {
+    let display = support::build_display();
+
+    let depth = match glium::framebuffer::DepthRenderBuffer::new(&display,
+                                                                 glium::texture::DepthFormat::I24,
+                                                                 128, 128)
+    {
+        Ok(d) => d,
+        Err(_) => return
+    };
+
+    let mut framebuffer = match glium::framebuffer::SimpleFrameBuffer::depth_only(&display,
+                                                                                  &depth)
+    {
+        Ok(f) => f,
+        Err(_) => return
+    };
+
+    framebuffer.clear_depth(0.5);
+
+    let data = match framebuffer.read_depth() {
+        Ok(d) => d,
+        Err(glium::ReadError::AttachmentTypeNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    for value in data {
+        assert!((value - 0.5).abs() < 0.001);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn read_depth() {
+    let display = support::build_display();
+
+    let depth = match glium::framebuffer::DepthRenderBuffer::new(&display,
+                                                                 glium::texture::DepthFormat::I24,
+                                                                 128, 128)
+    {
+        Ok(d) => d,
+        Err(_) => return
+    };
+
+    let mut framebuffer = match glium::framebuffer::SimpleFrameBuffer::depth_only(&display,
+                                                                                  &depth)
+    {
+        Ok(f) => f,
+        Err(_) => return
+    };
+
+    framebuffer.clear_depth(0.5);
+
+    let data = match framebuffer.read_depth() {
+        Ok(d) => d,
+        Err(glium::ReadError::AttachmentTypeNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    assert_eq!(data.len(), 128 * 128);
+    for value in data {
+        assert!((value - 0.5).abs() < 0.001);
+    }
+
+    let value = framebuffer.read_depth_pixel(12, 34).unwrap();
+    assert!((value - 0.5).abs() < 0.001);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn read_depth_without_depth_buffer() {
+    let display = support::build_display();
+
+    let texture = support::build_renderable_texture(&display);
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
+
+    match framebuffer.read_depth() {
+        Err(glium::ReadError::NoDepthBuffer) => (),
+        _ => panic!()
+    };
+
+    match framebuffer.read_depth_pixel(0, 0) {
+        Err(glium::ReadError::NoDepthBuffer) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn read_depth_multisampled() {
+    let display = support::build_display();
+
+    let color = match glium::framebuffer::RenderBuffer::new_multisample(&display,
+                                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            128, 128, 4)
+    {
+        Ok(c) => c,
+        Err(_) => return
+    };
+
+    let depth = match glium::framebuffer::DepthRenderBuffer::new_multisample(&display,
+                                            glium::texture::DepthFormat::I24, 128, 128, 4)
+    {
+        Ok(d) => d,
+        Err(_) => return
+    };
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                 &color, &depth).unwrap();
+    framebuffer.clear_depth(0.5);
+
+    match framebuffer.read_depth() {
+        Err(glium::ReadError::MultisampledSource) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sample_positions() {
+    let display = support::build_display();
+
+    let texture = support::build_renderable_texture(&display);
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
+
+    if let Some(positions) = framebuffer.get_sample_positions() {
+        assert!(!positions.is_empty());
+
+        for position in positions {
+            assert!(position[0] >= 0.0 && position[0] <= 1.0);
+            assert!(position[1] >= 0.0 && position[1] <= 1.0);
+        }
+    }
+
+    framebuffer.set_sample_locations(&[]).ok();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_multisample_sample_positions() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture2dMultisample::empty(&display, 16, 16, 4) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let positions = match texture.sample_positions() {
+        Some(p) => p,
+        None => return
+    };
+
+    assert_eq!(positions.len(), 4);
+    for position in positions {
+        assert!(position[0] >= 0.0 && position[0] <= 1.0);
+        assert!(position[1] >= 0.0 && position[1] <= 1.0);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn set_sample_locations_count_mismatch() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture2dMultisample::empty(&display, 16, 16, 4) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let framebuffer = texture.as_surface();
+
+    match framebuffer.set_sample_locations(&[[0.5, 0.5]]) {
+        Err(glium::framebuffer::SampleLocationsError::NotSupported) => return,
+        Err(glium::framebuffer::SampleLocationsError::SamplesCountMismatch {
+            expected: 4, obtained: 1 }) => (),
+        _ => panic!()
+    };
+
+    framebuffer.set_sample_locations(&[[0.25, 0.25], [0.75, 0.25],
+                                       [0.25, 0.75], [0.75, 0.75]]).unwrap();
+    framebuffer.set_sample_locations(&[]).unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_only_draw() {
+    let display = support::build_display();
+    let (vertex_buffer, index_buffer, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let depth = match glium::framebuffer::DepthRenderBuffer::new(&display,
+                                                                 glium::texture::DepthFormat::I24,
+                                                                 128, 128)
+    {
+        Ok(d) => d,
+        Err(_) => return
+    };
+
+    let mut framebuffer = match glium::framebuffer::SimpleFrameBuffer::depth_only(&display,
+                                                                                  &depth)
+    {
+        Ok(f) => f,
+        Err(_) => return
+    };
+
+    assert_eq!(framebuffer.get_dimensions(), (128, 128));
+
+    let params = glium::DrawParameters {
+        depth: glium::Depth {
+            test: glium::DepthTest::IfLess,
+            write: true,
+            .. Default::default()
+        },
+        .. Default::default()
+    };
+
+    framebuffer.clear_depth(1.0);
+    framebuffer.draw(&vertex_buffer, &index_buffer, &program,
+                     &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn attachment_info() {
+    use glium::framebuffer::{Attachment, AttachmentKind, AttachmentComponentType};
+
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            glium::texture::MipmapsOption::NoMipmap,
+                                              128, 128).unwrap();
+
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
+
+    let info = match framebuffer.attachment_info(Attachment::Color(0)) {
+        Some(info) => info,
+        None => return
+    };
+
+    assert_eq!(info.kind, AttachmentKind::Texture);
+    assert_eq!(info.red_bits, 8);
+    assert_eq!(info.green_bits, 8);
+    assert_eq!(info.blue_bits, 8);
+    assert_eq!(info.alpha_bits, 8);
+    assert_eq!(info.component_type, Some(AttachmentComponentType::UnsignedNormalized));
+    assert_eq!(info.samples, 0);
+
+    assert!(framebuffer.attachment_info(Attachment::Depth).is_none());
+    assert!(framebuffer.attachment_info(Attachment::Color(1024)).is_none());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn render_to_mipmap() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            glium::texture::MipmapsOption::EmptyMipmaps,
+                                              128, 128).unwrap();
+
+    let level = texture.mipmap(1).unwrap();
+    let mut framebuffer = level.as_surface();
+    assert_eq!(framebuffer.get_dimensions(), (64, 64));
+
+    framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &Default::default()).unwrap();
+
+    let image: glium::texture::TextureAnyImage = level.into();
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = image.raw_read(&glium::Rect {
+        left: 0, bottom: 0, width: 64, height: 64
+    });
+    assert_eq!(read_back.len(), 64);
+    assert_eq!(read_back[0][0], (255, 0, 0, 255));
+    assert_eq!(read_back[63][63], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
@@ -145,6 +145,31 @@ fn get_primitives_type() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn indices_source_primitives_type() {
+    let display = support::build_display();
+
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TriangleStrip,
+                                          &[0u16, 1, 2, 3]).unwrap();
+    let source: index::IndicesSource = (&indices).into();
+    assert_eq!(source.get_primitives_type(), PrimitiveType::TriangleStrip);
+
+    let source: index::IndicesSource = index::NoIndices(PrimitiveType::LinesList).into();
+    assert_eq!(source.get_primitives_type(), PrimitiveType::LinesList);
+
+    if let Ok(multidraw) = index::DrawCommandsNoIndicesBuffer::empty(&display, 1) {
+        let source = multidraw.with_primitive_type(PrimitiveType::Points);
+        assert_eq!(source.get_primitives_type(), PrimitiveType::Points);
+    }
+
+    if let Ok(multidraw) = index::DrawCommandsIndicesBuffer::empty(&display, 1) {
+        let source = multidraw.with_index_buffer(&indices);
+        assert_eq!(source.get_primitives_type(), PrimitiveType::TriangleStrip);
+    }
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn get_indices_type_u8() {
     let display = support::build_display();
@@ -412,3 +437,704 @@ fn multidraw_elements() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn index_buffer_from_shader_written_buffer() {
+    let display = support::build_display();
+
+    if !glium::program::ComputeShader::is_supported(&display) {
+        return;
+    }
+
+    let compute = glium::program::ComputeShader::from_source(&display, r#"
+            #version 430
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(std430) buffer Indices {
+                uint indices[];
+            };
+
+            void main() {
+                indices[0] = 0; indices[1] = 1; indices[2] = 2;
+                indices[3] = 1; indices[4] = 3; indices[5] = 2;
+            }
+        "#).unwrap();
+
+    let buffer: glium::buffer::Buffer<[u32]> =
+        glium::buffer::Buffer::empty_array(&display,
+                                           glium::buffer::BufferType::ShaderStorageBuffer, 6,
+                                           glium::buffer::BufferMode::Default).unwrap();
+
+    compute.execute(uniform! { Indices: &buffer }, 1, 1, 1);
+
+    let indices = glium::IndexBuffer::from_buffer(buffer, PrimitiveType::TrianglesList).unwrap();
+
+    let vb = glium::VertexBuffer::new(&display, &[
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]).unwrap();
+
+    let program = build_program(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &indices, &program, &uniform!{},
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multidraw_elements_at() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, &[
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]).unwrap();
+
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                          &[0u16, 1, 2, 1, 3, 2]).unwrap();
+
+    let multidraw = glium::index::DrawCommandsIndicesBuffer::empty(&display, 2);
+    let multidraw = match multidraw {
+        Ok(buf) => buf,
+        Err(_) => return
+    };
+
+    multidraw.write(&[
+        glium::index::DrawCommandIndices {
+            count: 3,
+            instance_count: 1,
+            first_index: 0,
+            base_vertex: 0,
+            base_instance: 0,
+        },
+        glium::index::DrawCommandIndices {
+            count: 6,
+            instance_count: 1,
+            first_index: 0,
+            base_vertex: 0,
+            base_instance: 0,
+        },
+    ]);
+
+    assert!(multidraw.with_index_buffer_at(&indices, 1, 2).is_none());
+    assert!(multidraw.with_index_buffer_at(&indices, 1, std::usize::MAX).is_none());
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, multidraw.with_index_buffer_at(&indices, 1, 1).unwrap(),
+                              &program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compact_commands() {
+    let display = support::build_display();
+
+    let compactor = match glium::index::CommandsCompactor::new(&display) {
+        Ok(c) => c,
+        Err(_) => return
+    };
+
+    let source = match glium::index::DrawCommandsIndicesBuffer::empty(&display, 3) {
+        Ok(buf) => buf,
+        Err(_) => return
+    };
+
+    let mut destination = glium::index::DrawCommandsIndicesBuffer::empty(&display, 3).unwrap();
+
+    let commands = [
+        glium::index::DrawCommandIndices {
+            count: 3, instance_count: 1, first_index: 0, base_vertex: 0, base_instance: 0,
+        },
+        glium::index::DrawCommandIndices {
+            count: 6, instance_count: 2, first_index: 3, base_vertex: 1, base_instance: 0,
+        },
+        glium::index::DrawCommandIndices {
+            count: 9, instance_count: 3, first_index: 6, base_vertex: 2, base_instance: 0,
+        },
+    ];
+
+    source.write(&commands);
+    destination.write(&commands);
+
+    let visibility = glium::buffer::Buffer::new(&display, &[1u32, 0, 1][..],
+                                                glium::buffer::BufferType::ShaderStorageBuffer,
+                                                glium::buffer::BufferMode::Default).unwrap();
+
+    let mut count = glium::buffer::Buffer::new(&display, &0u32,
+                                               glium::buffer::BufferType::ShaderStorageBuffer,
+                                               glium::buffer::BufferMode::Default).unwrap();
+
+    compactor.compact(&source, visibility.as_slice(), &mut destination, &mut count).unwrap();
+
+    assert_eq!(count.read().unwrap(), 2);
+
+    let data = destination.read().unwrap();
+    assert_eq!(data[0].count, 3);
+    assert_eq!(data[0].first_index, 0);
+    assert_eq!(data[1].count, 9);
+    assert_eq!(data[1].instance_count, 3);
+    assert_eq!(data[1].first_index, 6);
+    assert_eq!(data[1].base_vertex, 2);
+    assert_eq!(data[2].instance_count, 0);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compact_commands_multiple_work_groups() {
+    let display = support::build_display();
+
+    let compactor = match glium::index::CommandsCompactor::new(&display) {
+        Ok(c) => c,
+        Err(_) => return
+    };
+
+    let source = match glium::index::DrawCommandsIndicesBuffer::empty(&display, 20000) {
+        Ok(buf) => buf,
+        Err(_) => return
+    };
+
+    let mut destination =
+        glium::index::DrawCommandsIndicesBuffer::empty(&display, 20000).unwrap();
+
+    let commands = (0 .. 20000).map(|i| {
+        glium::index::DrawCommandIndices {
+            count: i, instance_count: 1, first_index: 0, base_vertex: 0, base_instance: 0,
+        }
+    }).collect::<Vec<_>>();
+
+    source.write(&commands);
+    destination.write(&commands);
+
+    let visibility = (0 .. 20000).map(|i| if i % 3 == 0 { 1u32 } else { 0 })
+                                 .collect::<Vec<_>>();
+    let visibility = glium::buffer::Buffer::new(&display, &visibility[..],
+                                                glium::buffer::BufferType::ShaderStorageBuffer,
+                                                glium::buffer::BufferMode::Default).unwrap();
+
+    let mut count = glium::buffer::Buffer::new(&display, &0u32,
+                                               glium::buffer::BufferType::ShaderStorageBuffer,
+                                               glium::buffer::BufferMode::Default).unwrap();
+
+    compactor.compact(&source, visibility.as_slice(), &mut destination, &mut count).unwrap();
+
+    assert_eq!(count.read().unwrap(), 6667);
+
+    let data = destination.read().unwrap();
+    for (i, command) in data.iter().enumerate() {
+        if i < 6667 {
+            assert_eq!(command.count, i as u32 * 3);
+            assert_eq!(command.instance_count, 1);
+        } else {
+            assert_eq!(command.instance_count, 0);
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multidraw_elements_base_instance() {
+    let display = support::build_display();
+
+    if !glium::index::is_base_instance_supported(&display) {
+        return;
+    }
+
+    #[derive(Copy, Clone)]
+    struct Instance {
+        offset: [f32; 2],
+    }
+
+    implement_vertex!(Instance, offset);
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+                attribute vec2 offset;
+
+                void main() {
+                    gl_Position = vec4(position + offset, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    // a quad that covers the left half of the target
+    let vb = glium::VertexBuffer::new(&display, &[
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [0.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [0.0, -1.0] },
+    ]).unwrap();
+
+    let instances = glium::VertexBuffer::new(&display, &[
+        Instance { offset: [0.0, 0.0] }, Instance { offset: [1.0, 0.0] },
+    ]).unwrap();
+
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                          &[0u16, 1, 2, 1, 3, 2]).unwrap();
+
+    let multidraw = glium::index::DrawCommandsIndicesBuffer::empty(&display, 2);
+    let multidraw = match multidraw {
+        Ok(buf) => buf,
+        Err(_) => return
+    };
+
+    multidraw.write(&[
+        glium::index::DrawCommandIndices {
+            count: 6,
+            instance_count: 1,
+            first_index: 0,
+            base_vertex: 0,
+            base_instance: 0,
+        },
+        glium::index::DrawCommandIndices {
+            count: 6,
+            instance_count: 1,
+            first_index: 0,
+            base_vertex: 0,
+            base_instance: 1,
+        },
+    ]);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw((&vb, instances.per_instance().unwrap()),
+                              multidraw.with_index_buffer(&indices),
+                              &program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multidraw_elements_draw_id() {
+    let display = support::build_display();
+
+    if !glium::index::is_shader_draw_parameters_supported(&display) {
+        return;
+    }
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 330
+            #extension GL_ARB_shader_draw_parameters : require
+
+            in vec2 position;
+            flat out int draw_id;
+
+            void main() {
+                draw_id = gl_DrawIDARB;
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            flat in int draw_id;
+            out vec4 color;
+
+            void main() {
+                color = draw_id == 0 ? vec4(1.0, 0.0, 0.0, 1.0) : vec4(0.0, 1.0, 0.0, 1.0);
+            }
+        ",
+        None);
+
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    // a quad on the left half of the target, then a quad on the right half
+    let vb = glium::VertexBuffer::new(&display, &[
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [0.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [0.0, -1.0] },
+        Vertex { position: [ 0.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [ 0.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]).unwrap();
+
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                          &[0u16, 1, 2, 1, 3, 2]).unwrap();
+
+    let multidraw = glium::index::DrawCommandsIndicesBuffer::empty(&display, 2);
+    let multidraw = match multidraw {
+        Ok(buf) => buf,
+        Err(_) => return
+    };
+
+    multidraw.write(&[
+        glium::index::DrawCommandIndices {
+            count: 6,
+            instance_count: 1,
+            first_index: 0,
+            base_vertex: 0,
+            base_instance: 0,
+        },
+        glium::index::DrawCommandIndices {
+            count: 6,
+            instance_count: 1,
+            first_index: 0,
+            base_vertex: 4,
+            base_instance: 0,
+        },
+    ]);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, multidraw.with_index_buffer(&indices),
+                              &program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        let width = row.len();
+        assert_eq!(row[0], (255, 0, 0, 255));
+        assert_eq!(row[width - 1], (0, 255, 0, 255));
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multi_mesh_builder() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let mut builder = glium::index::MultiMeshBuilder::new(PrimitiveType::TrianglesList);
+
+    // top-left and bottom-right triangles of the target
+    let first = builder.add(&[
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] },
+    ], &[0u16, 1, 2]);
+    let second = builder.add(&[
+        Vertex { position: [1.0,  1.0] }, Vertex { position: [1.0, -1.0] },
+        Vertex { position: [-1.0, -1.0] },
+    ], &[0u16, 1, 2]);
+
+    assert_eq!(first, 0);
+    assert_eq!(second, 1);
+    assert_eq!(builder.len(), 2);
+    assert!(!builder.is_empty());
+
+    let mesh = match builder.build(&display) {
+        Ok(m) => m,
+        Err(glium::index::MultiMeshCreationError::CommandsBufferCreationError(_)) => return,
+        Err(e) => panic!("{:?}", e),
+    };
+
+    assert_eq!(mesh.vertices.len(), 6);
+    assert_eq!(mesh.indices.len(), 6);
+
+    let commands = mesh.commands.read().unwrap();
+    assert_eq!(commands[1].first_index, 3);
+    assert_eq!(commands[1].base_vertex, 3);
+    assert_eq!(commands[1].count, 3);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&mesh.vertices, mesh.commands.with_index_buffer(&mesh.indices),
+                              &program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multi_mesh_builder_empty() {
+    let display = support::build_display();
+
+    let builder = glium::index::MultiMeshBuilder::<Vertex, u16>::new(PrimitiveType::TrianglesList);
+    assert!(builder.is_empty());
+
+    match builder.build(&display) {
+        Err(glium::index::MultiMeshCreationError::Empty) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multidraw_read_command() {
+    let display = support::build_display();
+
+    let multidraw = glium::index::DrawCommandsIndicesBuffer::empty(&display, 2);
+    let multidraw = match multidraw {
+        Ok(buf) => buf,
+        Err(_) => return
+    };
+
+    multidraw.write(&[
+        glium::index::DrawCommandIndices {
+            count: 6,
+            instance_count: 1,
+            first_index: 0,
+            base_vertex: 0,
+            base_instance: 0,
+        },
+        glium::index::DrawCommandIndices {
+            count: 3,
+            instance_count: 2,
+            first_index: 6,
+            base_vertex: 4,
+            base_instance: 0,
+        },
+    ]);
+
+    let command = match multidraw.read_command(1) {
+        Ok(c) => c,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e),
+    };
+
+    assert_eq!(command.count, 3);
+    assert_eq!(command.instance_count, 2);
+    assert_eq!(command.first_index, 6);
+    assert_eq!(command.base_vertex, 4);
+
+    match multidraw.read_command(2) {
+        Err(glium::buffer::ReadError::OutOfBounds) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multidraw_coalesce() {
+    let display = support::build_display();
+
+    let multidraw = glium::index::DrawCommandsIndicesBuffer::empty(&display, 4);
+    let mut multidraw = match multidraw {
+        Ok(buf) => buf,
+        Err(_) => return
+    };
+
+    multidraw.write(&[
+        glium::index::DrawCommandIndices {
+            count: 6, instance_count: 2, first_index: 0, base_vertex: 0, base_instance: 0,
+        },
+        glium::index::DrawCommandIndices {
+            count: 6, instance_count: 3, first_index: 0, base_vertex: 0, base_instance: 2,
+        },
+        glium::index::DrawCommandIndices {
+            count: 6, instance_count: 1, first_index: 0, base_vertex: 0, base_instance: 7,
+        },
+        glium::index::DrawCommandIndices {
+            count: 3, instance_count: 1, first_index: 6, base_vertex: 0, base_instance: 8,
+        },
+    ]);
+
+    let len = match multidraw.coalesce() {
+        Ok(l) => l,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        Err(e) => panic!("{:?}", e),
+    };
+
+    assert_eq!(len, 3);
+
+    let commands = multidraw.read().unwrap();
+    assert_eq!(commands[0].instance_count, 5);
+    assert_eq!(commands[0].base_instance, 0);
+    assert_eq!(commands[1].instance_count, 1);
+    assert_eq!(commands[1].base_instance, 7);
+    assert_eq!(commands[2].count, 3);
+    assert_eq!(commands[3].instance_count, 0);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multidraw_elements_zeroed() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, &[
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]).unwrap();
+
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                          &[0u16, 1, 2, 1, 3, 2]).unwrap();
+
+    let multidraw = glium::index::DrawCommandsIndicesBuffer::empty_zeroed(&display, 4);
+    let multidraw = match multidraw {
+        Ok(buf) => buf,
+        Err(_) => return
+    };
+
+    // only the first command is filled, the others must draw nothing
+    multidraw.slice(0 .. 1).unwrap().write(&[
+        glium::index::DrawCommandIndices {
+            count: 3,
+            instance_count: 1,
+            first_index: 0,
+            base_vertex: 0,
+            base_instance: 0,
+        }
+    ]);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, multidraw.with_index_buffer(&indices),
+                              &program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn build_indexed_triangle_soup() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, PartialEq, Eq, Hash)]
+    struct Vertex {
+        position: [i8; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    // two triangles that share two vertices
+    let soup = [
+        Vertex { position: [-1,  1] }, Vertex { position: [1,  1] }, Vertex { position: [-1, -1] },
+        Vertex { position: [ 1,  1] }, Vertex { position: [1, -1] }, Vertex { position: [-1, -1] },
+    ];
+
+    let (vertices, indices) = glium::index::build_indexed(&soup);
+    assert_eq!(vertices.len(), 4);
+    assert_eq!(indices, vec![0, 1, 2, 1, 3, 2]);
+
+    let vb = glium::VertexBuffer::new(&display, &vertices).unwrap();
+    let ib = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList, &indices).unwrap();
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn build_indexed_with_epsilon() {
+    let vertices = [
+        [0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0],
+        [0.9999, 0.0, 0.0], [0.0, 1.0001, 0.0], [0.5, 0.5, 0.0],
+    ];
+
+    let (unique, indices) = glium::index::build_indexed_with_epsilon(&vertices, 0.001, |v| *v);
+    assert_eq!(unique.len(), 4);
+    assert_eq!(indices, vec![0, 1, 2, 1, 2, 3]);
+}
+
+#[test]
+fn triangle_strip_to_list_winding() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, &[
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]).unwrap();
+
+    let list = glium::index::triangle_strip_to_list(&[0u16, 1, 2, 3]);
+    assert_eq!(list, vec![0, 1, 2, 2, 1, 3]);
+
+    let indices = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                          &list).unwrap();
+
+    // both triangles are clockwise, so none of them must be culled
+    let params = glium::DrawParameters {
+        backface_culling: glium::BackfaceCullingMode::CullCounterClockwise,
+        .. Default::default()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &indices, &program, &uniform!{}, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn triangle_fan_to_list_degenerate() {
+    let list = glium::index::triangle_fan_to_list(&[0u32, 1, 2, 2, 3]);
+    assert_eq!(list, vec![0, 1, 2, 0, 2, 3]);
+
+    assert!(glium::index::triangle_fan_to_list(&[0u32, 1]).is_empty());
+}
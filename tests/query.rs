@@ -192,10 +192,7 @@ fn primitives_generated() {
 
 // FIXME: add test for transform feedback query
 
-// FIXME: add more tests for conditional rendering
-
 #[test]
-#[ignore]       // FIXME: problem with query not having a type yet when passed to BeginConditionalRender
 fn conditional_render_nodraw() {
     let display = support::build_display();
 
@@ -234,6 +231,58 @@ fn conditional_render_nodraw() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn conditional_render_draw() {
+    let display = support::build_display();
+
+    let query = match glium::draw_parameters::AnySamplesPassedQuery::new(&display, false) {
+        Err(_) => return,
+        Ok(q) => q
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    // drawing the "bounding box" with the query
+    {
+        let params = glium::DrawParameters {
+            samples_passed_query: Some((&query).into()),
+            color_mask: (false, false, false, false),
+            .. Default::default()
+        };
+
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params)
+               .unwrap();
+    }
+
+    // drawing the object only if the bounding box was visible
+    {
+        let params = glium::DrawParameters {
+            condition: Some(glium::draw_parameters::ConditionalRendering {
+                query: (&query).into(),
+                wait: true,
+                per_region: false,
+            }),
+            .. Default::default()
+        };
+
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params)
+               .unwrap();
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
 #[test]
 #[ignore]       // FIXME: not implemented yet
 fn conditional_render_simultaneous_query() {
@@ -309,3 +358,25 @@ fn query_to_buffer() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn unused_query_to_buffer() {
+    let display = support::build_display();
+
+    let query = match glium::draw_parameters::SamplesPassedQuery::new(&display) {
+        Err(_) => return,
+        Ok(q) => q
+    };
+
+    let mut buffer = glium::buffer::BufferView::new(&display, &12u32,
+                                                    glium::buffer::BufferType::ArrayBuffer,
+                                                    glium::buffer::BufferMode::Default).unwrap();
+    if let Err(_) = query.to_buffer_u32(buffer.as_slice()) {
+        return;
+    }
+
+    let mapping = buffer.map();
+    assert_eq!(*mapping, 0);
+
+    display.assert_no_error(None);
+}
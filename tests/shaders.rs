@@ -230,6 +230,58 @@ fn get_uniform_blocks() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn get_block_members() {
+    let display = support::build_display();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 330
+
+            layout(std140) uniform MyBlock {
+                mat4 matrix;
+                float color[12];
+            };
+
+            void main() {
+                gl_Position = matrix * vec4(0.0, 0.0, 0.0, color[2]);
+            }
+        ",
+        "
+            #version 130
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    assert!(program.get_block_members("Unexisting").is_none());
+
+    let mut members = program.get_block_members("MyBlock").unwrap().to_vec();
+    members.sort_by(|a, b| a.offset.cmp(&b.offset));
+    assert_eq!(members.len(), 2);
+
+    assert_eq!(members[0].ty, glium::uniforms::UniformType::FloatMat4);
+    assert_eq!(members[0].offset, 0);
+    assert_eq!(members[0].matrix_stride, 16);
+
+    assert_eq!(members[1].ty, glium::uniforms::UniformType::Float);
+    assert_eq!(members[1].offset, 64);
+    assert_eq!(members[1].array_size, 12);
+    assert_eq!(members[1].array_stride, 16);
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn get_program_binary() {
     let display = support::build_display();
@@ -365,6 +417,11 @@ fn get_transform_feedback_varyings() {
         geometry_shader: None,
         outputs_srgb: false,
         uses_point_size: false,
+        attribute_bindings: &[],
+        frag_data_bindings: &[],
+        uniform_block_bindings: &[],
+        uses_shader_includes: false,
+        defines: &[],
 
         vertex_shader: "
             #version 110
@@ -856,3 +913,528 @@ fn array_layout_offsets() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn attribute_bindings() {
+    let display = support::build_display();
+
+    let program = glium::Program::new(&display, glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        attribute_bindings: &[("position", 3), ("color", 5), ("missing", 7)],
+        frag_data_bindings: &[],
+        uniform_block_bindings: &[],
+        uses_shader_includes: false,
+        defines: &[],
+        transform_feedback_varyings: None,
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+            attribute vec3 color;
+
+            varying vec3 v_color;
+
+            void main() {
+                v_color = color;
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 110
+
+            varying vec3 v_color;
+
+            void main() {
+                gl_FragColor = vec4(v_color, 1.0);
+            }
+        ",
+    }).unwrap();
+
+    assert_eq!(program.get_attribute("position").unwrap().location, 3);
+    assert_eq!(program.get_attribute("color").unwrap().location, 5);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn attribute_bindings_nul_character() {
+    let display = support::build_display();
+
+    let program = glium::Program::new(&display, glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        attribute_bindings: &[("posi\0tion", 3)],
+        frag_data_bindings: &[],
+        uniform_block_bindings: &[],
+        uses_shader_includes: false,
+        defines: &[],
+        transform_feedback_varyings: None,
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+    });
+
+    match program {
+        Err(glium::program::ProgramCreationError::NulCharacterInName(_)) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn frag_data_bindings() {
+    let display = support::build_display();
+
+    let program = glium::Program::new(&display, glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        attribute_bindings: &[],
+        frag_data_bindings: &[("normal", 2), ("albedo", 0), ("missing", 3)],
+        uniform_block_bindings: &[],
+        uses_shader_includes: false,
+        defines: &[],
+        transform_feedback_varyings: None,
+
+        vertex_shader: "
+            #version 130
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 130
+
+            out vec4 albedo;
+            out vec4 normal;
+
+            void main() {
+                albedo = vec4(1.0, 0.0, 0.0, 1.0);
+                normal = vec4(0.0, 0.0, 1.0, 1.0);
+            }
+        ",
+    });
+
+    let program = match program {
+        Err(glium::CompilationError(..)) => return,
+        Err(glium::ProgramCreationError::FragDataBindingsNotSupported) => return,
+        p => p.unwrap()
+    };
+
+    assert_eq!(program.get_frag_data_location("albedo"), Some(0));
+    assert_eq!(program.get_frag_data_location("normal"), Some(2));
+    assert_eq!(program.get_frag_data_location("alb\0edo"), None);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn frag_data_bindings_nul_character() {
+    let display = support::build_display();
+
+    let program = glium::Program::new(&display, glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        attribute_bindings: &[],
+        frag_data_bindings: &[("alb\0edo", 0)],
+        uniform_block_bindings: &[],
+        uses_shader_includes: false,
+        defines: &[],
+        transform_feedback_varyings: None,
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+    });
+
+    match program {
+        Err(glium::program::ProgramCreationError::NulCharacterInName(_)) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn shader_includes() {
+    let display = support::build_display();
+
+    display.add_shader_include("/test/color.glsl", "
+        uniform vec4 u_color;
+
+        vec4 get_color() {
+            return u_color;
+        }
+    ");
+
+    let program = glium::Program::new(&display, glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        attribute_bindings: &[],
+        frag_data_bindings: &[],
+        uniform_block_bindings: &[],
+        uses_shader_includes: true,
+        defines: &[],
+        transform_feedback_varyings: None,
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 110
+            #extension GL_ARB_shading_language_include : require
+
+            #include \"/test/color.glsl\"
+
+            void main() {
+                gl_FragColor = get_color();
+            }
+        ",
+    }).unwrap();
+
+    assert!(program.get_uniform("u_color").is_some());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn shader_include_missing() {
+    let display = support::build_display();
+
+    let program = glium::Program::new(&display, glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        attribute_bindings: &[],
+        frag_data_bindings: &[],
+        uniform_block_bindings: &[],
+        uses_shader_includes: true,
+        defines: &[],
+        transform_feedback_varyings: None,
+
+        vertex_shader: "
+            #version 110
+            #extension GL_ARB_shading_language_include : require
+
+            #include \"/test/missing.glsl\"
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+    });
+
+    match program {
+        Err(glium::CompilationError(_)) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn shader_source_nul_character() {
+    let display = support::build_display();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);\0
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+        None);
+
+    match program {
+        Err(glium::CompilationError(_)) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn shader_defines() {
+    let display = support::build_display();
+
+    let program = glium::Program::new(&display, glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        attribute_bindings: &[],
+        frag_data_bindings: &[],
+        uniform_block_bindings: &[],
+        uses_shader_includes: false,
+        defines: &[("USE_UNIFORM_COLOR", "1"), ("SCALE", "0.5")],
+        transform_feedback_varyings: None,
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position * SCALE, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 110
+
+            #if USE_UNIFORM_COLOR
+                uniform vec4 u_color;
+            #endif
+
+            void main() {
+            #if USE_UNIFORM_COLOR
+                gl_FragColor = u_color;
+            #else
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            #endif
+            }
+        ",
+    }).unwrap();
+
+    assert!(program.get_uniform("u_color").is_some());
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn shader_defines_nul_character() {
+    let display = support::build_display();
+
+    let program = glium::Program::new(&display, glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        attribute_bindings: &[],
+        frag_data_bindings: &[],
+        uniform_block_bindings: &[],
+        uses_shader_includes: false,
+        defines: &[("SCALE", "0.\05")],
+        transform_feedback_varyings: None,
+
+        vertex_shader: "
+            #version 110
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+    });
+
+    match program {
+        Err(glium::program::ProgramCreationError::NulCharacterInName(ref name))
+            if name == "SCALE" => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn program_logs() {
+    let display = support::build_display();
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                void main() {
+                    gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    // the content of the logs depends on the driver, but they must not contain errors
+    assert!(!program.get_compile_log().contains("ERROR"));
+    assert!(!program.get_link_log().contains("ERROR"));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn geometry_shader_invocations() {
+    let display = support::build_display();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 400
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 400
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+        Some("
+            #version 400
+
+            layout(triangles, invocations = 6) in;
+            layout(triangle_strip, max_vertices = 3) out;
+
+            void main() {
+                for (int i = 0; i < 3; ++i) {
+                    gl_Position = gl_in[i].gl_Position;
+                    gl_Layer = gl_InvocationID;
+                    EmitVertex();
+                }
+                EndPrimitive();
+            }
+        "));
+
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    assert!(program.has_geometry_shader());
+    assert_eq!(program.get_geometry_shader_max_output_vertices(), Some(3));
+    assert_eq!(program.get_geometry_shader_invocations(), Some(6));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn geometry_shader_too_many_output_vertices() {
+    use glium::CapabilitiesSource;
+
+    let display = support::build_display();
+
+    let max = match display.get_capabilities().max_geometry_output_vertices {
+        Some(max) => max,
+        None => return
+    };
+
+    let geometry_shader = format!("
+            #version 150
+
+            layout(points) in;
+            layout(points, max_vertices = {}) out;
+
+            void main() {{
+                gl_Position = gl_in[0].gl_Position;
+                EmitVertex();
+            }}
+        ", max + 1);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 150
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 150
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+        Some(&geometry_shader));
+
+    assert!(program.is_err());
+
+    display.assert_no_error(None);
+}
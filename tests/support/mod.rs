@@ -27,6 +27,25 @@ pub fn build_display() -> glium::Display {
     display
 }
 
+/// Builds a headless display for tests, requesting a robust context if possible.
+pub fn build_robust_display() -> glium::Display {
+    let version = parse_version();
+    let robustness = glutin::Robustness::TryRobustNoResetNotification;
+
+    let display = if env::var("GLIUM_HEADLESS_TESTS").is_ok() {
+        glutin::HeadlessRendererBuilder::new(1024, 768).with_gl_debug_flag(true)
+                                                       .with_gl(version)
+                                                       .with_gl_robustness(robustness)
+                                                       .build_glium().unwrap()
+    } else {
+        glutin::WindowBuilder::new().with_gl_debug_flag(true).with_visibility(false)
+                                    .with_gl(version).with_gl_robustness(robustness)
+                                    .build_glium().unwrap()
+    };
+
+    display
+}
+
 /// Rebuilds an existing display.
 ///
 /// In real applications this is used for things such as switching to fullscreen. Some things are
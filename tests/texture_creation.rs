@@ -1,5 +1,7 @@
 #[macro_use]
 extern crate glium;
+#[cfg(feature = "image")]
+extern crate image;
 
 use glium::Surface;
 
@@ -68,6 +70,38 @@ fn depth_texture_1d_creation() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn texture_1d_emulation() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture1d::new(&display, vec![(0u8, 0u8, 0u8, 0u8); 4]).unwrap();
+
+    assert_eq!(texture.get_width(), 4);
+    if glium::texture::is_texture_1d_supported(&display) {
+        assert_eq!(texture.get_height(), None);
+    } else {
+        assert_eq!(texture.get_height(), Some(1));
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_1d_array_not_emulated() {
+    let display = support::build_display();
+
+    if glium::texture::is_texture_1d_array_supported(&display) {
+        return;
+    }
+
+    match glium::texture::Texture1dArray::new(&display, vec![vec![(0u8, 0u8, 0u8, 0u8); 4]; 2]) {
+        Err(glium::texture::TextureCreationError::TypeNotSupported) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn texture_2d_creation() {
     let display = support::build_display();
@@ -86,6 +120,23 @@ fn texture_2d_creation() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn unsigned_texture_2d_creation_with_floats() {
+    let display = support::build_display();
+
+    // integral textures can't be filled with floating-point values
+    let texture = glium::texture::UnsignedTexture2d::new(&display, vec![
+        vec![(0.0, 0.0, 0.0, 0.0), (0.0, 0.0, 0.0, 0.0f32)],
+    ]);
+
+    match texture {
+        Err(glium::texture::TextureCreationError::FormatNotSupported) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn empty_texture2d_u8u8u8u8() {
     let display = support::build_display();
@@ -363,6 +414,31 @@ fn upload_from_pixel_buffer() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn upload_from_pixel_buffer_with_fence() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty(&display, 2, 2).unwrap();
+
+    let buffer = glium::texture::pixel_buffer::PixelBuffer::new_empty(&display, 4);
+    buffer.write(&[(0u8, 255u8, 0u8, 255u8), (255, 0, 255, 0), (255, 255, 0, 255),
+                   (0, 0, 255, 255)]);
+
+    let fence = texture.main_level().raw_upload_from_pixel_buffer_with_fence(buffer.as_slice(),
+                                                                             0 .. 2, 0 .. 2,
+                                                                             0 .. 1);
+    if let Ok(fence) = fence {
+        fence.is_signaled();
+        fence.wait();
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 255, 0, 255));
+    assert_eq!(data[1][1], (0, 0, 255, 255));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn upload_from_pixel_buffer_inverted() {
     let display = support::build_display();
@@ -384,3 +460,125 @@ fn upload_from_pixel_buffer_inverted() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn texture_target() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty(&display, 2, 2).unwrap();
+    assert_eq!(texture.get_target(), 0x0DE1);       // GL_TEXTURE_2D
+
+    let texture = glium::texture::Cubemap::empty(&display, 2).unwrap();
+    assert_eq!(texture.get_target(), 0x8513);       // GL_TEXTURE_CUBE_MAP
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_from_id() {
+    use glium::GlObject;
+
+    let display = support::build_display();
+
+    let source = glium::texture::Texture2d::new(&display, vec![vec![(0u8, 255u8, 0u8, 255u8)]])
+                                               .unwrap();
+
+    let wrapped = unsafe {
+        glium::texture::Texture2d::from_id(&display,
+                                           glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                           source.get_id(), false,
+                                           glium::texture::MipmapsOption::NoMipmap,
+                                           glium::texture::Dimensions::Texture2d {
+                                               width: 1, height: 1
+                                           })
+    };
+
+    assert_eq!(wrapped.get_id(), source.get_id());
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = wrapped.read();
+    assert_eq!(data[0][0], (0, 255, 0, 255));
+
+    // the wrapper doesn't own the texture, so `source` must still be valid afterwards
+    drop(wrapped);
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = source.read();
+    assert_eq!(data[0][0], (0, 255, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_multisample_too_many_samples() {
+    let display = support::build_display();
+
+    if !glium::texture::is_texture_2d_multisample_supported(&display) {
+        return;
+    }
+
+    match glium::texture::Texture2dMultisample::empty(&display, 16, 16, 1024) {
+        Err(glium::texture::TextureCreationError::SamplesCountNotSupported) => (),
+        a => panic!("{:?}", a.err())
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[cfg(feature = "image")]
+fn texture_2d_from_image() {
+    let display = support::build_display();
+
+    // the first row of the image is red, the second one is blue
+    let image = image::ImageBuffer::from_fn(2, 2, |_, y| {
+        if y == 0 { image::Rgb([255u8, 0, 0]) } else { image::Rgb([0u8, 0, 255]) }
+    });
+    let image = image::DynamicImage::ImageRgb8(image);
+
+    let texture = glium::texture::Texture2d::from_image(&display, &image).unwrap();
+    assert_eq!(texture.get_width(), 2);
+    assert_eq!(texture.get_height(), Some(2));
+
+    // the first row of the image is at the top of the texture
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 0, 255, 255));
+    assert_eq!(read_back[1][0], (255, 0, 0, 255));
+
+    let texture = glium::texture::SrgbTexture2d::from_image(&display, &image).unwrap();
+    assert_eq!(texture.get_width(), 2);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_generate_mipmaps_range() {
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              glium::texture::MipmapsOption::EmptyMipmaps,
+                                              4, 4).unwrap();
+    assert_eq!(texture.get_mipmap_levels(), 3);
+
+    // levels 0 and 1 are provided, level 2 is generated from level 1
+    texture.as_surface().clear_color(1.0, 0.0, 0.0, 1.0);
+    texture.mipmap(1).unwrap().as_surface().clear_color(0.0, 1.0, 0.0, 1.0);
+    texture.mipmap(2).unwrap().as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match texture.generate_mipmaps_range(1, 2) {
+        Err(_) => return,
+        Ok(_) => ()
+    };
+
+    let image: glium::texture::TextureAnyImage = texture.mipmap(2).unwrap().into();
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = image.raw_read(&glium::Rect {
+        left: 0, bottom: 0, width: 1, height: 1
+    });
+    assert_eq!(data[0][0], (0, 255, 0, 255));
+
+    let image: glium::texture::TextureAnyImage = texture.main_level().into();
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = image.raw_read(&glium::Rect {
+        left: 0, bottom: 0, width: 1, height: 1
+    });
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
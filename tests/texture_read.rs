@@ -14,6 +14,26 @@ fn empty_pixel_buffer() {
     let _: Vec<Vec<(u8, u8, u8, u8)>> = pixel_buffer.read_as_texture_2d().unwrap();
 }
 
+#[test]
+fn texture_2d_read_robust() {
+    let display = support::build_robust_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8), (4u8, 8u8, 16u8)],
+        vec![(32u8, 64u8, 128u8), (32u8, 16u8, 4u8)],
+    ]).unwrap();
+
+    // `glReadnPixels` is used if the backend supports it
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+
+    assert_eq!(read_back[0][0], (0, 1, 2, 255));
+    assert_eq!(read_back[0][1], (4, 8, 16, 255));
+    assert_eq!(read_back[1][0], (32, 64, 128, 255));
+    assert_eq!(read_back[1][1], (32, 16, 4, 255));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn texture_2d_read_pixelbuffer() {
     let display = support::build_display();
@@ -119,6 +139,11 @@ read_texture_test!(read_texture2d, Texture2d, (u8, u8, u8, u8),
         vec![(0u8, 1u8, 2u8, 3u8), (4u8, 5u8, 6u8, 7u8)],
         vec![(8u8, 9u8, 10u8, 11u8), (12u8, 13u8, 14u8, 15u8)]
     ]);
+read_texture_test!(read_texture2d_rgb_odd_width, Texture2d, (u8, u8, u8),
+    vec![
+        vec![(0u8, 1u8, 2u8), (4u8, 5u8, 6u8), (8u8, 9u8, 10u8)],
+        vec![(12u8, 13u8, 14u8), (16u8, 17u8, 18u8), (20u8, 21u8, 22u8)]
+    ]);
 /*read_texture_test!(read_texture2darray, Texture2dArray, (u8, u8, u8, u8),
     vec![vec![vec![(0, 1, 2, 3), (4, 5, 6, 7)], vec![(8, 9, 10, 11), (12, 13, 14, 15)]]]);
 read_texture_test!(read_texture3d, Texture3d, (u8, u8, u8, u8),
@@ -134,3 +159,30 @@ read_texture_test!(read_unsignedtexture2darray, UnsignedTexture2dArray, (u8, u8,
 read_texture_test!(read_unsignedtexture3d, UnsignedTexture3d, (u8, u8, u8, u8),
     vec![vec![vec![(0, 1, 2, 3), (4, 5, 6, 7)], vec![(8, 9, 10, 11), (12, 13, 14, 15)]]]);*/
 // TODO: srgb textures
+
+#[test]
+fn compressed_texture_2d_read_compressed_data() {
+    let display = support::build_display();
+
+    // a single 4x4 DXT1 block
+    let data = [0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00u8];
+
+    let texture = match glium::texture::CompressedTexture2d::with_compressed_data(&display,
+                                &data[..], 4, 4,
+                                glium::texture::CompressedFormat::S3tcDxt1NoAlpha,
+                                glium::texture::CompressedMipmapsOption::NoMipmap)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let (format, read_back) = match texture.read_compressed_data() {
+        Some(d) => d,
+        None => return
+    };
+
+    assert_eq!(format, glium::texture::CompressedFormat::S3tcDxt1NoAlpha);
+    assert_eq!(read_back, &data[..]);
+
+    display.assert_no_error(None);
+}
@@ -130,3 +130,213 @@ fn bindless_texture() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn sample_texture_2d_multisample() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    if !glium::texture::is_texture_2d_multisample_supported(&display) {
+        return;
+    }
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 150
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 150
+
+            uniform sampler2DMS texture;
+            out vec4 color;
+
+            void main() {
+                color = texelFetch(texture, ivec2(0, 0), 3);
+            }
+        ",
+        None)
+    {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let texture = match glium::texture::Texture2dMultisample::empty(&display, 16, 16, 4) {
+        Ok(t) => t,
+        Err(glium::texture::TextureCreationError::SamplesCountNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    texture.as_surface().clear_color(1.0, 0.0, 0.0, 1.0);
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: &texture },
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn feedback_loop() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D texture;
+
+            void main() {
+                gl_FragColor = texture2D(texture, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    let result = texture.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: &texture },
+                                           &Default::default());
+
+    if cfg!(debug_assertions) {
+        match result {
+            Err(glium::DrawError::FeedbackLoop { ref name }) => assert_eq!(name, "texture"),
+            _ => panic!()
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn build_mip_chain() {
+    let display = support::build_display();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+            attribute vec2 tex_coords;
+            varying vec2 v_tex_coords;
+
+            void main() {
+                v_tex_coords = tex_coords;
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D source;
+            uniform float offset;
+            varying vec2 v_tex_coords;
+
+            void main() {
+                gl_FragColor = texture2D(source, v_tex_coords) + vec4(0.0, 0.0, offset, 0.0);
+            }
+        ",
+        None).unwrap();
+
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              glium::texture::MipmapsOption::EmptyMipmaps,
+                                              4, 4).unwrap();
+    assert_eq!(texture.get_mipmap_levels(), 3);
+    texture.as_surface().clear_color(1.0, 1.0, 0.0, 1.0);
+    texture.mipmap(1).unwrap().as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.mipmap(2).unwrap().as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match glium::texture::build_mip_chain(&texture, &program, "source",
+                                          &uniform!{ offset: 0.25f32 })
+    {
+        Err(glium::texture::MipChainError::NotSupported) => return,
+        r => r.unwrap()
+    };
+
+    // each level adds some blue to the previous one
+    let image: glium::texture::TextureAnyImage = texture.mipmap(2).unwrap().into();
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = image.raw_read(&glium::Rect {
+        left: 0, bottom: 0, width: 1, height: 1
+    });
+    // 0.5 isn't exactly representable with 8 bits, so the blue component can be rounded either
+    // way
+    let (r, g, b, a) = data[0][0];
+    assert_eq!((r, g, a), (255, 255, 255));
+    assert!(b >= 127 && b <= 129);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_swizzle() {
+    use glium::texture::SwizzleComponent;
+
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(128u8, 0u8, 0u8, 0u8), (128u8, 0u8, 0u8, 0u8)],
+        vec![(128u8, 0u8, 0u8, 0u8), (128u8, 0u8, 0u8, 0u8)],
+    ]).unwrap();
+
+    let swizzle = [SwizzleComponent::Red, SwizzleComponent::Red, SwizzleComponent::Red,
+                   SwizzleComponent::One];
+    match texture.set_swizzle(swizzle) {
+        Err(_) => return,
+        Ok(_) => ()
+    };
+    assert_eq!(texture.get_swizzle(), swizzle);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D texture;
+
+            void main() {
+                gl_FragColor = texture2D(texture, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: &texture },
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(128, 128, 128, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
@@ -24,3 +24,212 @@ fn texture_2d_write() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn texture_2d_write_with_row_length() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 0u8, 0u8), (0u8, 0u8, 0u8)],
+        vec![(0u8, 0u8, 0u8), (0u8, 0u8, 0u8)],
+    ]).unwrap();
+
+    // rows of 3 pixels, of which only the first 2 are uploaded
+    let data = vec![
+        (0u8, 1u8, 2u8), (4u8, 8u8, 16u8), (255u8, 255u8, 255u8),
+        (32u8, 64u8, 128u8), (32u8, 16u8, 4u8),
+    ];
+
+    let image = glium::texture::RawImage2d {
+        data: std::borrow::Cow::Owned(data),
+        width: 2,
+        height: 2,
+        format: glium::texture::ClientFormat::U8U8U8,
+    };
+
+    texture.write_with_row_length(glium::Rect { bottom: 0, left: 0, width: 2, height: 2 },
+                                  image, 3);
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 1, 2, 255));
+    assert_eq!(read_back[0][1], (4, 8, 16, 255));
+    assert_eq!(read_back[1][0], (32, 64, 128, 255));
+    assert_eq!(read_back[1][1], (32, 16, 4, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_write_bgra() {
+    let display = support::build_display();
+
+    // OpenGL ES doesn't support BGRA client data
+    if !(display.get_opengl_version() >= &glium::Version(glium::Api::Gl, 1, 2)) {
+        return;
+    }
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 0u8, 0u8, 0u8), (0u8, 0u8, 0u8, 0u8)],
+        vec![(0u8, 0u8, 0u8, 0u8), (0u8, 0u8, 0u8, 0u8)],
+    ]).unwrap();
+
+    let data = glium::texture::RawImage2d {
+        data: std::borrow::Cow::Owned(vec![2u8, 1, 0, 255, 16, 8, 4, 128]),
+        width: 2,
+        height: 1,
+        format: glium::texture::ClientFormat::B8G8R8A8,
+    };
+
+    texture.write(glium::Rect { bottom: 0, left: 0, width: 2, height: 1 }, data);
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 1, 2, 255));
+    assert_eq!(read_back[0][1], (4, 8, 16, 128));
+    assert_eq!(read_back[1][0], (0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_creation_bgr() {
+    let display = support::build_display();
+
+    let data = glium::texture::RawImage2d {
+        data: std::borrow::Cow::Owned(vec![2u8, 1, 0, 16, 8, 4]),
+        width: 2,
+        height: 1,
+        format: glium::texture::ClientFormat::B8G8R8,
+    };
+
+    let texture = match glium::texture::Texture2d::new(&display, data) {
+        Ok(t) => t,
+        Err(glium::texture::TextureCreationError::FormatNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (0, 1, 2, 255));
+    assert_eq!(read_back[0][1], (4, 8, 16, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_clear() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0u8, 0u8, 0u8, 0u8), (0u8, 0u8, 0u8, 0u8)],
+        vec![(0u8, 0u8, 0u8, 0u8), (0u8, 0u8, 0u8, 0u8)],
+    ]).unwrap();
+
+    texture.clear(0, (255u8, 0u8, 0u8, 255u8)).unwrap();
+    texture.clear_region(0, &glium::Rect { bottom: 1, left: 1, width: 1, height: 1 },
+                         (0u8, 255u8, 0u8, 255u8)).unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (255, 0, 0, 255));
+    assert_eq!(read_back[0][1], (255, 0, 0, 255));
+    assert_eq!(read_back[1][0], (255, 0, 0, 255));
+    assert_eq!(read_back[1][1], (0, 255, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn integral_texture_2d_clear_with_float() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::IntegralTexture2d::empty(&display, 2, 2) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    match texture.main_level().raw_clear((1.0f32, 0.0f32, 0.0f32, 1.0f32)) {
+        Err(glium::texture::ClearError::FormatMismatch) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_array_write_layer() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture2dArray::empty(&display, 2, 2, 3) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    assert!(texture.main_level().layer(3).is_none());
+
+    let rect = glium::Rect { bottom: 0, left: 0, width: 2, height: 2 };
+    let layer = texture.main_level().layer(2).unwrap();
+    layer.write(rect, vec![vec![(0u8, 1u8, 2u8, 255u8), (4u8, 8u8, 16u8, 255u8)],
+                           vec![(32u8, 64u8, 128u8, 255u8), (32u8, 16u8, 4u8, 255u8)]]);
+
+    // writing another layer must not modify this one
+    texture.main_level().layer(1).unwrap().write(rect, vec![vec![(0u8, 0u8, 0u8, 0u8); 2]; 2]);
+
+    let any: &glium::texture::TextureAny = &texture;
+    let layer2: Vec<Vec<(u8, u8, u8, u8)>> = any.main_level().layer(2).unwrap()
+                                                .into_image(None).unwrap().raw_read(&rect);
+    assert_eq!(layer2, vec![vec![(0, 1, 2, 255), (4, 8, 16, 255)],
+                            vec![(32, 64, 128, 255), (32, 16, 4, 255)]]);
+
+    let layer1: Vec<Vec<(u8, u8, u8, u8)>> = any.main_level().layer(1).unwrap()
+                                                .into_image(None).unwrap().raw_read(&rect);
+    assert_eq!(layer1, vec![vec![(0, 0, 0, 0); 2]; 2]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_array_upload_from_larger_volume() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture2dArray::empty(&display, 2, 2, 2) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    // volume of 3x3x2 pixels whose value is the index of the pixel
+    let volume: Vec<(u8, u8, u8, u8)> = (0 .. 18u8).map(|i| (i, 0, 0, 255)).collect();
+
+    // uploading the 2x2x2 box that starts at the pixel (1, 1, 0) of the volume
+    let any: &glium::texture::TextureAny = &texture;
+    any.main_level().raw_upload_from_memory(&volume[4 ..], 0 .. 2, 0 .. 2, 0 .. 2, Some(3),
+                                            Some(3));
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 2, height: 2 };
+    let layer0: Vec<Vec<(u8, u8, u8, u8)>> = any.main_level().layer(0).unwrap()
+                                                .into_image(None).unwrap().raw_read(&rect);
+    assert_eq!(layer0, vec![vec![(4, 0, 0, 255), (5, 0, 0, 255)],
+                            vec![(7, 0, 0, 255), (8, 0, 0, 255)]]);
+
+    let layer1: Vec<Vec<(u8, u8, u8, u8)>> = any.main_level().layer(1).unwrap()
+                                                .into_image(None).unwrap().raw_read(&rect);
+    assert_eq!(layer1, vec![vec![(13, 0, 0, 255), (14, 0, 0, 255)],
+                            vec![(16, 0, 0, 255), (17, 0, 0, 255)]]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_array_upload_empty_box() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture2dArray::empty(&display, 2, 2, 2) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let data: Vec<(u8, u8, u8, u8)> = Vec::new();
+
+    let any: &glium::texture::TextureAny = &texture;
+    any.main_level().raw_upload_from_memory(&data, 0 .. 2, 0 .. 2, 0 .. 0, Some(3), Some(3));
+    any.main_level().raw_upload_from_memory(&data, 0 .. 2, 0 .. 0, 0 .. 2, Some(3), Some(3));
+
+    display.assert_no_error(None);
+}
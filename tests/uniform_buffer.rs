@@ -158,6 +158,144 @@ fn block() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn uniform_block_bindings() {
+    let display = support::build_display();
+
+    if !display.is_glsl_version_supported(&glium::Version(glium::Api::Gl, 3, 3)) {
+        return;
+    }
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::new(&display, glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        attribute_bindings: &[],
+        frag_data_bindings: &[],
+        uniform_block_bindings: &[("Red", 3), ("Green", 5), ("missing", 7)],
+        uses_shader_includes: false,
+        defines: &[],
+        transform_feedback_varyings: None,
+
+        vertex_shader: "
+            #version 330
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 330
+            uniform layout(std140);
+
+            uniform Red {
+                float red;
+            };
+
+            uniform Green {
+                float green;
+            };
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(red, green, 0.0, 1.0);
+            }
+        ",
+    }).unwrap();
+
+    assert_eq!(program.get_uniform_blocks().get("Red").unwrap().initial_binding, 3);
+    assert_eq!(program.get_uniform_blocks().get("Green").unwrap().initial_binding, 5);
+
+    #[derive(Copy, Clone)]
+    struct RedData {
+        red: f32,
+    }
+
+    implement_uniform_block!(RedData, red);
+
+    #[derive(Copy, Clone)]
+    struct GreenData {
+        green: f32,
+    }
+
+    implement_uniform_block!(GreenData, green);
+
+    let red = match glium::uniforms::UniformBuffer::new(&display, RedData { red: 1.0 }) {
+        Err(_) => return,
+        Ok(b) => b
+    };
+
+    let green = glium::uniforms::UniformBuffer::new(&display, GreenData { green: 1.0 }).unwrap();
+
+    let uniforms = uniform!{
+        Red: &red,
+        Green: &green,
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 255, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn uniform_block_bindings_out_of_range() {
+    let display = support::build_display();
+
+    let program = glium::Program::new(&display, glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        attribute_bindings: &[],
+        frag_data_bindings: &[],
+        uniform_block_bindings: &[("Block", 1 << 30)],
+        uses_shader_includes: false,
+        defines: &[],
+        transform_feedback_varyings: None,
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+    });
+
+    match program {
+        Err(glium::program::ProgramCreationError::UniformBlockBindingOutOfRange) => (),
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn block_wrong_type() {
     let display = support::build_display();
@@ -338,3 +476,77 @@ fn empty_uniform_buffer() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn uniform_ring() {
+    let display = support::build_display();
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+            uniform layout(std140);
+
+            uniform MyBlock {
+                vec3 color;
+            };
+
+            void main() {
+                gl_FragColor = vec4(color, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    #[derive(Copy, Clone)]
+    struct Data {
+        color: (f32, f32, f32),
+    }
+
+    implement_uniform_block!(Data, color);
+
+    let ring = match glium::uniforms::UniformRing::<Data>::new(&display, 2, 2) {
+        Err(_) => return,
+        Ok(r) => r
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    for _ in 0 .. 3 {
+        let first = ring.push(Data { color: (1.0, 0.0, 0.0) }).unwrap();
+        let second = ring.push(Data { color: (0.0, 1.0, 0.0) }).unwrap();
+        assert!(ring.push(Data { color: (0.0, 0.0, 1.0) }).is_none());
+
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{ MyBlock: first },
+                                  &Default::default()).unwrap();
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{ MyBlock: second },
+                                  &Default::default()).unwrap();
+
+        ring.next_frame();
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 255, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
@@ -172,6 +172,336 @@ fn uniform_wrong_type() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn dynamic_uniforms() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color;
+
+            void main() {
+                gl_FragColor = color;
+            }
+        ",
+        None).unwrap();
+
+    let mut uniforms = glium::uniforms::DynamicUniforms::new();
+    uniforms.insert("color", glium::uniforms::UniformValue::Vec4([0.0, 1.0, 0.0, 1.0]));
+    uniforms.insert("color", glium::uniforms::UniformValue::Vec4([1.0, 0.0, 0.0, 0.5]));
+    uniforms.insert("inactive", glium::uniforms::UniformValue::Float(2.0));
+    assert_eq!(uniforms.len(), 2);
+    assert!(uniforms.remove("inactive"));
+    assert!(!uniforms.remove("inactive"));
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    // an alpha of 0.5 can be rounded either way depending on the driver
+    for &(r, g, b, a) in [data[0][0], *data.last().unwrap().last().unwrap()].iter() {
+        assert_eq!((r, g, b), (255, 0, 0));
+        assert!(a >= 127 && a <= 129);
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn dynamic_uniforms_wrong_type() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color;
+
+            void main() {
+                gl_FragColor = color;
+            }
+        ",
+        None).unwrap();
+
+    let mut uniforms = glium::uniforms::DynamicUniforms::new();
+    uniforms.insert("color", glium::uniforms::UniformValue::Float(1.0));
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformTypeMismatch { .. }) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn uniform_array_of_vectors() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 colors[2];
+
+            void main() {
+                gl_FragColor = colors[0] + colors[1];
+            }
+        ",
+        None).unwrap();
+
+    let colors: &[[f32; 4]] = &[[1.0, 0.0, 0.0, 0.0], [0.0, 0.0, 1.0, 1.0]];
+    let uniforms = glium::uniforms::UniformsStorage::new("colors", colors);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 255, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 255, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn uniform_array_too_long() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 colors[2];
+
+            void main() {
+                gl_FragColor = colors[0] + colors[1];
+            }
+        ",
+        None).unwrap();
+
+    let colors: &[[f32; 4]] = &[[1.0, 0.0, 0.0, 0.0], [0.0, 0.0, 1.0, 1.0], [0.0, 1.0, 0.0, 0.0]];
+    let uniforms = glium::uniforms::UniformsStorage::new("colors", colors);
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::UniformTypeMismatch { .. }) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn uniform_array_of_one_element() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 colors[1];
+
+            void main() {
+                gl_FragColor = colors[0];
+            }
+        ",
+        None).unwrap();
+
+    assert_eq!(program.get_uniform("colors").map(|u| u.size), Some(Some(1)));
+
+    let colors: &[[f32; 4]] = &[[1.0, 0.0, 0.0, 1.0]];
+    let uniforms = glium::uniforms::UniformsStorage::new("colors", colors);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn uniform_array_of_matrices() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            uniform mat4 bones[2];
+
+            void main() {
+                gl_Position = bones[1] * bones[0] * vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let identity = [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0f32],
+    ];
+
+    let bones: &[[[f32; 4]; 4]] = &[identity, identity];
+    let uniforms = glium::uniforms::UniformsStorage::new("bones", bones);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn uniform_value_read_back() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color;
+
+            void main() {
+                gl_FragColor = color;
+            }
+        ",
+        None).unwrap();
+
+    let uniforms = uniform! { color: [1.0, 0.5, 0.25, 1.0f32] };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    assert_eq!(program.get_uniform_value::<[f32; 4]>("color"), Some([1.0, 0.5, 0.25, 1.0]));
+    assert_eq!(program.get_uniform_value::<f32>("color"), None);
+    assert_eq!(program.get_uniform_value::<[f32; 4]>("foo"), None);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn uniform_value_read_back_robust() {
+    let display = support::build_robust_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color;
+            uniform int index;
+
+            void main() {
+                gl_FragColor = color * float(index);
+            }
+        ",
+        None).unwrap();
+
+    let uniforms = uniform! { color: [1.0, 0.5, 0.25, 1.0f32], index: 1i32 };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    // `glGetnUniform` is used if the backend supports it
+    assert_eq!(program.get_uniform_value::<[f32; 4]>("color"), Some([1.0, 0.5, 0.25, 1.0]));
+    assert_eq!(program.get_uniform_value::<i32>("index"), Some(1));
+
+    display.assert_no_error(None);
+}
+
 macro_rules! uniform_test(
     ($name:ident, $glsl_ty:expr, $value:expr) => (
         #[test]
@@ -358,3 +688,54 @@ uniform_test!(uniform_type_boolarr_boolvec3, "bvec3", [true, true, true]);
 uniform_test!(uniform_type_booltup_boolvec3, "bvec3", (false, false, false));
 uniform_test!(uniform_type_boolarr_boolvec4, "bvec4", [true, false, false, true]);
 uniform_test!(uniform_type_booltup_boolvec4, "bvec4", (false, true, true, false));
+
+#[test]
+fn material_instances() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color;
+            uniform float alpha;
+
+            void main() {
+                gl_FragColor = vec4(color.rgb, alpha);
+            }
+        ",
+        None).unwrap();
+
+    let mut red = glium::uniforms::MaterialInstance::new(&program);
+    red.insert("color", glium::uniforms::UniformValue::Vec4([1.0, 0.0, 0.0, 1.0]));
+    red.insert("alpha", glium::uniforms::UniformValue::Float(1.0));
+
+    let mut green = glium::uniforms::MaterialInstance::new(&program);
+    green.insert("color", glium::uniforms::UniformValue::Vec4([0.0, 1.0, 0.0, 1.0]));
+    green.insert("alpha", glium::uniforms::UniformValue::Float(1.0));
+
+    let texture = support::build_renderable_texture(&display);
+
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, red.get_program(), &red, &Default::default()).unwrap();
+    assert_eq!(texture.read::<Vec<Vec<(u8, u8, u8, u8)>>>()[0][0], (255, 0, 0, 255));
+
+    texture.as_surface().draw(&vb, &ib, green.get_program(), &green.with(uniform!{ alpha: 0.0f32 }),
+                              &Default::default()).unwrap();
+    assert_eq!(texture.read::<Vec<Vec<(u8, u8, u8, u8)>>>()[0][0], (0, 255, 0, 0));
+
+    texture.as_surface().draw(&vb, &ib, red.get_program(), &red, &Default::default()).unwrap();
+    assert_eq!(texture.read::<Vec<Vec<(u8, u8, u8, u8)>>>()[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
@@ -24,6 +24,11 @@ fn transform_feedback() {
         geometry_shader: None,
         outputs_srgb: false,
         uses_point_size: false,
+        attribute_bindings: &[],
+        frag_data_bindings: &[],
+        uniform_block_bindings: &[],
+        uses_shader_includes: false,
+        defines: &[],
 
         vertex_shader: "
             #version 110
@@ -88,3 +93,356 @@ fn transform_feedback() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn transform_feedback_pause_resume() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct Vertex {
+        output_val: (f32, f32),
+    }
+
+    implement_vertex!(Vertex, output_val);
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+    let (other_vb, other_ib, other_program) = support::build_fullscreen_red_pipeline(&display);
+
+    let source = glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        attribute_bindings: &[],
+        frag_data_bindings: &[],
+        uniform_block_bindings: &[],
+        uses_shader_includes: false,
+        defines: &[],
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            varying vec2 output_val;
+
+            void main() {
+                output_val = position;
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 130
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+
+        transform_feedback_varyings: Some((
+            vec!["output_val".to_string()],
+            glium::program::TransformFeedbackMode::Separate
+        )),
+    };
+
+    let program = match glium::Program::new(&display, source) {
+        Ok(p) => p,
+        Err(glium::program::ProgramCreationError::TransformFeedbackNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let mut out_buffer: glium::VertexBuffer<Vertex> = glium::VertexBuffer::empty(&display, 12).unwrap();
+    let texture = support::build_renderable_texture(&display);
+
+    {
+        let session = glium::vertex::TransformFeedbackSession::new(&display, &program,
+                                                                   &mut out_buffer).unwrap();
+
+        let params = glium::DrawParameters {
+            transform_feedback: Some(&session),
+            .. Default::default()
+        };
+
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &params).unwrap();
+
+        if session.pause().is_err() {
+            return;
+        }
+
+        texture.as_surface().draw(&other_vb, &other_ib, &other_program, &uniform!{},
+                                  &Default::default()).unwrap();
+
+        session.resume().unwrap();
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &params).unwrap();
+    }
+
+    let result = match out_buffer.read() {
+        Ok(r) => r,
+        Err(glium::buffer::ReadError::NotSupported) => return,
+        e => e.unwrap()
+    };
+
+    // the second draw command has been appended after the first one
+    for &offset in &[0usize, 6] {
+        assert_eq!(result[offset + 0].output_val, (-1.0, 1.0));
+        assert_eq!(result[offset + 1].output_val, (1.0, 1.0));
+        assert_eq!(result[offset + 2].output_val, (-1.0, -1.0));
+        assert_eq!(result[offset + 3].output_val, (-1.0, -1.0));
+        assert_eq!(result[offset + 4].output_val, (1.0, 1.0));
+        assert_eq!(result[offset + 5].output_val, (1.0, -1.0));
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn draw_transform_feedback() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct Vertex {
+        output_val: (f32, f32),
+    }
+
+    implement_vertex!(Vertex, output_val);
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let source = glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        attribute_bindings: &[],
+        frag_data_bindings: &[],
+        uniform_block_bindings: &[],
+        uses_shader_includes: false,
+        defines: &[],
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            varying vec2 output_val;
+
+            void main() {
+                output_val = position;
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 130
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+
+        transform_feedback_varyings: Some((
+            vec!["output_val".to_string()],
+            glium::program::TransformFeedbackMode::Separate
+        )),
+    };
+
+    let capture_program = match glium::Program::new(&display, source) {
+        Ok(p) => p,
+        Err(glium::program::ProgramCreationError::TransformFeedbackNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let draw_program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 output_val;
+
+                void main() {
+                    gl_Position = vec4(output_val, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let mut out_buffer: glium::VertexBuffer<Vertex> = glium::VertexBuffer::empty(&display, 6).unwrap();
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    {
+        let session = glium::vertex::TransformFeedbackSession::new(&display, &capture_program,
+                                                                   &mut out_buffer).unwrap();
+
+        let params = glium::DrawParameters {
+            transform_feedback: Some(&session),
+            draw_primitives: false,
+            .. Default::default()
+        };
+
+        texture.as_surface().draw(&vb, &ib, &capture_program, &uniform!{}, &params).unwrap();
+
+        match texture.as_surface().draw_transform_feedback(&session, &draw_program, &uniform!{},
+                                                            &Default::default())
+        {
+            Ok(_) => (),
+            Err(glium::DrawError::TransformFeedbackDrawNotSupported) => return,
+            Err(e) => panic!("{:?}", e)
+        }
+    }
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn transform_feedback_multiple_streams() {
+    let display = support::build_display();
+
+    // also checks that multiple vertex streams are supported
+    let query0 = glium::draw_parameters::TransformFeedbackPrimitivesWrittenQuery::new_for_stream(
+                                                                              &display, 0);
+    let query1 = glium::draw_parameters::TransformFeedbackPrimitivesWrittenQuery::new_for_stream(
+                                                                              &display, 1);
+    let (query0, query1) = match (query0, query1) {
+        (Ok(q0), Ok(q1)) => (q0, q1),
+        _ => return
+    };
+
+    #[derive(Copy, Clone)]
+    struct Input {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Input, position);
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct OutputA {
+        output_a: (f32, f32),
+    }
+
+    implement_vertex!(OutputA, output_a);
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct OutputB {
+        output_b: (f32, f32),
+    }
+
+    implement_vertex!(OutputB, output_b);
+
+    let vb = glium::VertexBuffer::new(&display, &[
+        Input { position: [0.5, 0.25] }, Input { position: [-0.5, 1.0] }
+    ]).unwrap();
+
+    let source = glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        attribute_bindings: &[],
+        frag_data_bindings: &[],
+        uniform_block_bindings: &[],
+        uses_shader_includes: false,
+        defines: &[],
+
+        vertex_shader: "
+            #version 400
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        geometry_shader: Some("
+            #version 400
+
+            layout(points) in;
+            layout(points, max_vertices = 2) out;
+
+            layout(stream = 0) out vec2 output_a;
+            layout(stream = 1) out vec2 output_b;
+
+            void main() {
+                output_a = gl_in[0].gl_Position.xy;
+                EmitStreamVertex(0);
+                EndStreamPrimitive(0);
+
+                output_b = gl_in[0].gl_Position.xy * 2.0;
+                EmitStreamVertex(1);
+                EndStreamPrimitive(1);
+            }
+        "),
+        fragment_shader: "
+            #version 400
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+
+        transform_feedback_varyings: Some((
+            vec!["output_a".to_string(), "gl_NextBuffer".to_string(), "output_b".to_string()],
+            glium::program::TransformFeedbackMode::Interleaved
+        )),
+    };
+
+    let program = glium::Program::new(&display, source).unwrap();
+    assert_eq!(program.get_transform_feedback_buffers().len(), 2);
+
+    let mut out_a: glium::VertexBuffer<OutputA> = glium::VertexBuffer::empty(&display, 2).unwrap();
+    let mut out_b: glium::VertexBuffer<OutputB> = glium::VertexBuffer::empty(&display, 2).unwrap();
+
+    {
+        let session = glium::vertex::TransformFeedbackSession::with_outputs(&display, &program,
+            vec![
+                glium::vertex::TransformFeedbackOutput::new(&mut out_a),
+                glium::vertex::TransformFeedbackOutput::new(&mut out_b),
+            ]).unwrap();
+
+        let queries = [&query0, &query1];
+        let params = glium::DrawParameters {
+            transform_feedback: Some(&session),
+            transform_feedback_primitives_written_queries: &queries,
+            draw_primitives: false,
+            .. Default::default()
+        };
+
+        let texture = support::build_renderable_texture(&display);
+        texture.as_surface().draw(&vb, &glium::index::NoIndices(glium::index::PrimitiveType::Points),
+                                  &program, &uniform!{}, &params).unwrap();
+    }
+
+    assert_eq!(query0.get(), 2);
+    assert_eq!(query1.get(), 2);
+
+    let result_a = out_a.read().unwrap();
+    assert_eq!(result_a[0].output_a, (0.5, 0.25));
+    assert_eq!(result_a[1].output_a, (-0.5, 1.0));
+
+    let result_b = out_b.read().unwrap();
+    assert_eq!(result_b[0].output_b, (1.0, 0.5));
+    assert_eq!(result_b[1].output_b, (-1.0, 2.0));
+
+    display.assert_no_error(None);
+}
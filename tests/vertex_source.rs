@@ -659,3 +659,329 @@ fn per_instance_length_mismatch() {
     frame.finish().unwrap();
     display.assert_no_error(None);
 }
+
+#[test]
+fn fullscreen_pass() {
+    let display = support::build_display();
+
+    let program = match glium::Program::from_source(&display,
+        glium::program::FULLSCREEN_VERTEX_SHADER_140,
+        "
+            #version 140
+
+            in vec2 v_tex_coords;
+            out vec4 color;
+
+            void main() {
+                if (v_tex_coords.x >= 0.0 && v_tex_coords.x <= 1.0 &&
+                    v_tex_coords.y >= 0.0 && v_tex_coords.y <= 1.0)
+                {
+                    color = vec4(1.0, 0.0, 0.0, 1.0);
+                } else {
+                    color = vec4(0.0, 1.0, 0.0, 1.0);
+                }
+            }
+        ",
+        None) {
+        Ok(p) => p,
+        _ => return
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().fullscreen_pass(&program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn draw_instanced() {
+    let display = support::build_display();
+
+    // each instance covers one half of the viewport
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 140
+
+            void main() {
+                float left = gl_InstanceID == 0 ? -1.0 : 0.0;
+                float x = (gl_VertexID == 0 || gl_VertexID == 2) ? left : left + 1.0;
+                float y = (gl_VertexID == 0 || gl_VertexID == 1) ? 1.0 : -1.0;
+                gl_Position = vec4(x, y, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 140
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None) {
+        Ok(p) => p,
+        _ => return
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw_instanced(glium::vertex::EmptyVertexAttributes { len: 4 },
+                                        &glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip),
+                                        &program, &uniform!{}, &Default::default(), 2).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn fullscreen_triangle() {
+    use glium::GlObject;
+
+    let display = support::build_display();
+
+    let (vertices, indices) = glium::index::fullscreen_triangle(&display).unwrap();
+    let (vertices2, _) = glium::index::fullscreen_triangle(&display).unwrap();
+    assert_eq!(vertices.get_id(), vertices2.get_id());
+
+    let program = program!(&display,
+        140 => {
+            vertex: "
+                #version 140
+
+                in vec2 position;
+                in vec2 tex_coords;
+                out vec2 v_tex_coords;
+
+                void main() {
+                    v_tex_coords = tex_coords;
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 140
+
+                in vec2 v_tex_coords;
+                out vec4 color;
+
+                void main() {
+                    if (v_tex_coords.x >= 0.0 && v_tex_coords.x <= 1.0 &&
+                        v_tex_coords.y >= 0.0 && v_tex_coords.y <= 1.0)
+                    {
+                        color = vec4(1.0, 0.0, 0.0, 1.0);
+                    } else {
+                        color = vec4(0.0, 1.0, 0.0, 1.0);
+                    }
+                }
+            ",
+        },
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+                attribute vec2 tex_coords;
+                varying vec2 v_tex_coords;
+
+                void main() {
+                    v_tex_coords = tex_coords;
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                varying vec2 v_tex_coords;
+
+                void main() {
+                    if (v_tex_coords.x >= 0.0 && v_tex_coords.x <= 1.0 &&
+                        v_tex_coords.y >= 0.0 && v_tex_coords.y <= 1.0)
+                    {
+                        gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                    } else {
+                        gl_FragColor = vec4(0.0, 1.0, 0.0, 1.0);
+                    }
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&*vertices, &indices, &program, &uniform!{},
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn same_layout_different_buffers() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+        color: [f32; 3],
+    }
+
+    implement_vertex!(Vertex, position, color);
+
+    let build_buffer = |color: [f32; 3]| {
+        glium::VertexBuffer::new(&display,
+            &[
+                Vertex { position: [-1.0,  1.0], color: color },
+                Vertex { position: [ 1.0,  1.0], color: color },
+                Vertex { position: [-1.0, -1.0], color: color },
+                Vertex { position: [ 1.0, -1.0], color: color },
+            ]
+        ).unwrap()
+    };
+
+    let program = program!(&display,
+        140 => {
+            vertex: "
+                #version 140
+
+                in vec2 position;
+                in vec3 color;
+                out vec3 v_color;
+
+                void main() {
+                    v_color = color;
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 140
+
+                in vec3 v_color;
+                out vec4 f_color;
+
+                void main() {
+                    f_color = vec4(v_color, 1.0);
+                }
+            ",
+        },
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+                attribute vec3 color;
+                varying vec3 v_color;
+
+                void main() {
+                    v_color = color;
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                varying vec3 v_color;
+
+                void main() {
+                    gl_FragColor = vec4(v_color, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let indices = glium::index::NoIndices(PrimitiveType::TriangleStrip);
+    let texture = support::build_renderable_texture(&display);
+
+    let red = build_buffer([1.0, 0.0, 0.0]);
+    texture.as_surface().draw(&red, &indices, &program, &uniform!{},
+                              &Default::default()).unwrap();
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    {
+        let green = build_buffer([0.0, 1.0, 0.0]);
+        texture.as_surface().draw(&green, &indices, &program, &uniform!{},
+                                  &Default::default()).unwrap();
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+        assert_eq!(data[0][0], (0, 255, 0, 255));
+    }
+
+    // the new buffer may reuse the ID of the destroyed one
+    let blue = build_buffer([0.0, 0.0, 1.0]);
+    texture.as_surface().draw(&blue, &indices, &program, &uniform!{},
+                              &Default::default()).unwrap();
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 0, 255, 255));
+
+    texture.as_surface().draw(&red, &indices, &program, &uniform!{},
+                              &Default::default()).unwrap();
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[should_panic]
+fn multiple_buffers_same_attribute() {
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    let data = [
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [ 1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [ 1.0, -1.0] },
+    ];
+
+    let buffer1 = glium::VertexBuffer::new(&display, &data).unwrap();
+    let buffer2 = glium::VertexBuffer::new(&display, &data).unwrap();
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().draw((&buffer1, &buffer2),
+                              &glium::index::NoIndices(PrimitiveType::TriangleStrip),
+                              &program, &uniform!{}, &Default::default()).unwrap();
+}